@@ -0,0 +1,58 @@
+-- Create submissions table: one row per envelope sent from a template
+CREATE TABLE IF NOT EXISTS submissions (
+    id BIGSERIAL PRIMARY KEY,
+    template_id BIGINT NOT NULL REFERENCES templates(id) ON DELETE CASCADE,
+    user_id BIGINT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    account_id BIGINT REFERENCES accounts(id) ON DELETE CASCADE,
+    name VARCHAR(255),
+    status VARCHAR(50) NOT NULL DEFAULT 'pending', -- pending, completed, expired, declined
+    session_id VARCHAR(255), -- Session ID shared with the submitters of this envelope
+    expires_at TIMESTAMP WITH TIME ZONE,
+    completed_at TIMESTAMP WITH TIME ZONE,
+    archived_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Link submitters to their submission
+ALTER TABLE submitters ADD COLUMN IF NOT EXISTS submission_id BIGINT REFERENCES submissions(id) ON DELETE CASCADE;
+
+-- Create indexes for better performance
+CREATE INDEX IF NOT EXISTS idx_submissions_template_id ON submissions(template_id);
+CREATE INDEX IF NOT EXISTS idx_submissions_user_id ON submissions(user_id);
+CREATE INDEX IF NOT EXISTS idx_submissions_account_id ON submissions(account_id);
+CREATE INDEX IF NOT EXISTS idx_submissions_status ON submissions(status);
+CREATE INDEX IF NOT EXISTS idx_submissions_archived_at ON submissions(archived_at);
+CREATE INDEX IF NOT EXISTS idx_submitters_submission_id ON submitters(submission_id);
+
+-- Backfill: every existing send shared one session_id, so turn each group into a submission
+INSERT INTO submissions (template_id, user_id, account_id, status, session_id, created_at, updated_at)
+SELECT s.template_id,
+       s.user_id,
+       u.account_id,
+       CASE
+           WHEN BOOL_OR(s.status = 'declined') THEN 'declined'
+           WHEN BOOL_AND(s.status IN ('signed', 'completed')) THEN 'completed'
+           ELSE 'pending'
+       END,
+       s.session_id,
+       MIN(s.created_at),
+       MAX(s.updated_at)
+FROM submitters s
+LEFT JOIN users u ON u.id = s.user_id
+WHERE s.submission_id IS NULL
+GROUP BY s.template_id, s.user_id, u.account_id, s.session_id;
+
+UPDATE submitters s
+SET submission_id = sub.id
+FROM submissions sub
+WHERE s.submission_id IS NULL
+  AND sub.template_id = s.template_id
+  AND sub.user_id = s.user_id
+  AND sub.session_id IS NOT DISTINCT FROM s.session_id;
+
+-- Add comments for documentation
+COMMENT ON COLUMN submissions.status IS 'Envelope status: pending, completed, expired, declined';
+COMMENT ON COLUMN submissions.expires_at IS 'After this time the envelope can no longer be signed';
+COMMENT ON COLUMN submissions.archived_at IS 'Set when the envelope is archived; archived envelopes are hidden from listings';
+COMMENT ON COLUMN submitters.submission_id IS 'Submission (envelope) this submitter belongs to';
@@ -0,0 +1,15 @@
+-- Add sequential signing support to submissions and submitters
+ALTER TABLE submissions ADD COLUMN IF NOT EXISTS signing_mode VARCHAR(20) NOT NULL DEFAULT 'parallel';
+ALTER TABLE submitters ADD COLUMN IF NOT EXISTS signing_order INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE submitters ADD COLUMN IF NOT EXISTS invited_at TIMESTAMP WITH TIME ZONE;
+
+-- Existing submitters were all invited when they were created
+UPDATE submitters SET invited_at = created_at WHERE invited_at IS NULL AND status <> 'waiting';
+
+-- Create index for finding the next signing group
+CREATE INDEX IF NOT EXISTS idx_submitters_submission_order ON submitters(submission_id, signing_order);
+
+-- Add comments for documentation
+COMMENT ON COLUMN submissions.signing_mode IS 'parallel: everyone is invited at once; sequential: submitters are invited by signing_order';
+COMMENT ON COLUMN submitters.signing_order IS 'Position in the signing order; submitters with equal values sign in parallel';
+COMMENT ON COLUMN submitters.invited_at IS 'When the invitation was sent; NULL while the submitter is waiting for earlier signers';
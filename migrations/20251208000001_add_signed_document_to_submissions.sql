@@ -0,0 +1,11 @@
+-- Store the final signed PDF of a completed submission
+ALTER TABLE submissions ADD COLUMN IF NOT EXISTS signed_document_key TEXT;
+ALTER TABLE submissions ADD COLUMN IF NOT EXISTS signed_document_sha256 VARCHAR(64);
+ALTER TABLE submissions ADD COLUMN IF NOT EXISTS signed_document_size BIGINT;
+ALTER TABLE submissions ADD COLUMN IF NOT EXISTS signed_document_digitally_signed BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE submissions ADD COLUMN IF NOT EXISTS signed_document_created_at TIMESTAMP WITH TIME ZONE;
+
+-- Add comments for documentation
+COMMENT ON COLUMN submissions.signed_document_key IS 'Storage key of the final flattened (and digitally signed) PDF';
+COMMENT ON COLUMN submissions.signed_document_sha256 IS 'Hex SHA-256 of the stored PDF, checked on every download';
+COMMENT ON COLUMN submissions.signed_document_digitally_signed IS 'Whether the stored PDF carries a PKCS#7 signature (false when no auto-sign certificate was available)';
@@ -0,0 +1,40 @@
+-- Append-only audit trail of submitter lifecycle events
+-- No foreign keys: the trail must outlive deleted submitters/templates
+CREATE TABLE IF NOT EXISTS audit_events (
+    id BIGSERIAL PRIMARY KEY,
+    submitter_id BIGINT NOT NULL,
+    submission_id BIGINT,
+    template_id BIGINT NOT NULL,
+    event_type VARCHAR(50) NOT NULL,
+    actor VARCHAR(255) NOT NULL,
+    details TEXT NOT NULL,
+    data JSONB,
+    ip_address VARCHAR(255),
+    user_agent TEXT,
+    timezone VARCHAR(100),
+    prev_hash VARCHAR(64),
+    hash VARCHAR(64) NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_audit_events_submitter_id ON audit_events(submitter_id, id);
+CREATE INDEX IF NOT EXISTS idx_audit_events_submission_id ON audit_events(submission_id);
+
+-- Reject any modification of recorded events
+CREATE OR REPLACE FUNCTION prevent_audit_events_modification()
+RETURNS TRIGGER AS $$
+BEGIN
+    RAISE EXCEPTION 'audit_events is append-only';
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS trigger_prevent_audit_events_modification ON audit_events;
+CREATE TRIGGER trigger_prevent_audit_events_modification
+    BEFORE UPDATE OR DELETE ON audit_events
+    FOR EACH ROW
+    EXECUTE FUNCTION prevent_audit_events_modification();
+
+-- Add comments for documentation
+COMMENT ON COLUMN audit_events.event_type IS 'sent, email_opened, viewed, field_filled, signed, declined, reminded, resubmitted, copy_sent, downloaded';
+COMMENT ON COLUMN audit_events.prev_hash IS 'Hash of the previous event of the same submitter (NULL for the first event)';
+COMMENT ON COLUMN audit_events.hash IS 'Hex SHA-256 over prev_hash and this event''s content';
@@ -0,0 +1,7 @@
+-- RFC 3161 trusted timestamps: when enabled, signatures get a token from the configured authority
+ALTER TABLE pdf_signature_settings
+    ADD COLUMN IF NOT EXISTS tsa_enabled BOOLEAN NOT NULL DEFAULT FALSE,
+    ADD COLUMN IF NOT EXISTS tsa_url TEXT;
+
+COMMENT ON COLUMN pdf_signature_settings.tsa_enabled IS 'Request an RFC 3161 timestamp token for every PDF signature';
+COMMENT ON COLUMN pdf_signature_settings.tsa_url IS 'Timestamp authority endpoint accepting application/timestamp-query requests, or ''local'' for the development stand-in';
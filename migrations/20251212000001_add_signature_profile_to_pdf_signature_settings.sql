@@ -0,0 +1,5 @@
+-- Signature format: legacy adbe.pkcs7.detached or a PAdES baseline level (ETSI.CAdES.detached)
+ALTER TABLE pdf_signature_settings
+    ADD COLUMN IF NOT EXISTS signature_profile VARCHAR(32) NOT NULL DEFAULT 'adbe.pkcs7.detached';
+
+COMMENT ON COLUMN pdf_signature_settings.signature_profile IS 'One of adbe.pkcs7.detached, pades-b-b, pades-b-t (needs a TSA) or pades-b-lt (TSA plus DSS validation data)';
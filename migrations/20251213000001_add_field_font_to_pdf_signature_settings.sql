@@ -0,0 +1,5 @@
+-- Font used for text, date, initials and cells values drawn into signed PDFs
+ALTER TABLE pdf_signature_settings
+    ADD COLUMN IF NOT EXISTS field_font VARCHAR(64) NOT NULL DEFAULT 'noto-sans';
+
+COMMENT ON COLUMN pdf_signature_settings.field_font IS 'Primary font key (noto-sans, noto-sans-bold or a PDF_FALLBACK_FONTS file stem); characters it lacks fall back to the configured fallback fonts';
@@ -0,0 +1,5 @@
+-- Declarative show/require conditions on other fields, copied into each submission
+ALTER TABLE template_fields ADD COLUMN IF NOT EXISTS conditions JSONB;
+ALTER TABLE submission_fields ADD COLUMN IF NOT EXISTS conditions JSONB;
+
+COMMENT ON COLUMN template_fields.conditions IS 'Array of {field, action: show|require, operator, value}';
@@ -0,0 +1,5 @@
+-- Server-side validation rules for field values, copied into each submission
+ALTER TABLE template_fields ADD COLUMN IF NOT EXISTS validation JSONB;
+ALTER TABLE submission_fields ADD COLUMN IF NOT EXISTS validation JSONB;
+
+COMMENT ON COLUMN template_fields.validation IS 'Object of {pattern, min_length, max_length, min, max, date_format, format: email|phone, message}';
@@ -0,0 +1,3 @@
+-- Values filled in by the sender when the submission is created, optionally locked
+ALTER TABLE submission_fields ADD COLUMN IF NOT EXISTS prefilled_value TEXT;
+ALTER TABLE submission_fields ADD COLUMN IF NOT EXISTS readonly BOOLEAN NOT NULL DEFAULT FALSE;
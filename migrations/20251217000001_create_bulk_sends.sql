@@ -0,0 +1,24 @@
+-- Bulk sends: one background job creating a submission per spreadsheet row
+CREATE TABLE IF NOT EXISTS bulk_sends (
+    id BIGSERIAL PRIMARY KEY,
+    template_id BIGINT NOT NULL REFERENCES templates(id) ON DELETE CASCADE,
+    user_id BIGINT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    account_id BIGINT REFERENCES accounts(id) ON DELETE CASCADE,
+    filename VARCHAR(255),
+    status VARCHAR(20) NOT NULL DEFAULT 'pending',
+    total_rows INT NOT NULL DEFAULT 0,
+    processed_rows INT NOT NULL DEFAULT 0,
+    succeeded_rows INT NOT NULL DEFAULT 0,
+    failed_rows INT NOT NULL DEFAULT 0,
+    results JSONB NOT NULL DEFAULT '[]'::jsonb,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    completed_at TIMESTAMP WITH TIME ZONE
+);
+
+CREATE INDEX IF NOT EXISTS idx_bulk_sends_user_id ON bulk_sends(user_id);
+CREATE INDEX IF NOT EXISTS idx_bulk_sends_account_id ON bulk_sends(account_id);
+
+-- Add comments for documentation
+COMMENT ON COLUMN bulk_sends.status IS 'pending, processing, completed, failed';
+COMMENT ON COLUMN bulk_sends.results IS 'Per-row report: [{"row", "status", "emails", "submission_id", "error"}]';
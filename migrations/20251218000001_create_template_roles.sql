@@ -0,0 +1,49 @@
+-- Signing parties defined on a template, e.g. "Buyer" and "Seller"
+CREATE TABLE IF NOT EXISTS template_roles (
+    id BIGSERIAL PRIMARY KEY,
+    template_id BIGINT NOT NULL REFERENCES templates(id) ON DELETE CASCADE,
+    name VARCHAR(255) NOT NULL,
+    display_order INT NOT NULL DEFAULT 0,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    UNIQUE (template_id, name)
+);
+
+ALTER TABLE template_fields ADD COLUMN IF NOT EXISTS role_id BIGINT REFERENCES template_roles(id) ON DELETE SET NULL;
+ALTER TABLE submission_fields ADD COLUMN IF NOT EXISTS role_id BIGINT REFERENCES template_roles(id) ON DELETE SET NULL;
+ALTER TABLE submitters ADD COLUMN IF NOT EXISTS role_id BIGINT REFERENCES template_roles(id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_template_roles_template_id ON template_roles(template_id);
+CREATE INDEX IF NOT EXISTS idx_template_fields_role_id ON template_fields(role_id);
+CREATE INDEX IF NOT EXISTS idx_submitters_role_id ON submitters(role_id);
+
+-- Backfill: every distinct partner string of a template becomes a role, ordered by its first field
+INSERT INTO template_roles (template_id, name, display_order)
+SELECT template_id, partner, (ROW_NUMBER() OVER (PARTITION BY template_id ORDER BY MIN(display_order), partner) - 1)::INT
+FROM template_fields
+WHERE partner IS NOT NULL AND partner <> ''
+GROUP BY template_id, partner
+ON CONFLICT (template_id, name) DO NOTHING;
+
+UPDATE template_fields f
+SET role_id = r.id
+FROM template_roles r
+WHERE f.role_id IS NULL AND r.template_id = f.template_id AND r.name = f.partner;
+
+UPDATE submission_fields sf
+SET role_id = tf.role_id
+FROM template_fields tf
+WHERE sf.role_id IS NULL AND tf.id = sf.template_field_id;
+
+-- Existing submitters were matched to partners by name, email or "(partner)" in the name
+UPDATE submitters s
+SET role_id = r.id
+FROM template_roles r
+WHERE s.role_id IS NULL
+  AND r.template_id = s.template_id
+  AND (r.name = s.name OR r.name = s.email OR POSITION('(' || r.name || ')' IN s.name) > 0);
+
+-- Add comments for documentation
+COMMENT ON COLUMN template_fields.role_id IS 'Role whose submitter fills this field; NULL means any submitter';
+COMMENT ON COLUMN template_fields.partner IS 'Name of the field role, kept for older clients';
+COMMENT ON COLUMN submitters.role_id IS 'Template role this submitter signs as';
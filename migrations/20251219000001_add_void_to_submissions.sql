@@ -0,0 +1,112 @@
+-- Voiding cancels a sent submission while keeping its submitters and audit trail
+ALTER TABLE submissions ADD COLUMN IF NOT EXISTS voided_at TIMESTAMP WITH TIME ZONE;
+ALTER TABLE submissions ADD COLUMN IF NOT EXISTS void_reason TEXT;
+
+COMMENT ON COLUMN submissions.status IS 'Envelope status: pending, completed, expired, declined, voided';
+COMMENT ON COLUMN submissions.voided_at IS 'Set when the sender voids the submission; every signing link stops working';
+COMMENT ON COLUMN submissions.void_reason IS 'Reason given by the sender, shown in the cancellation email';
+
+-- Default 'cancellation' email template, sent to recipients of a voided submission
+CREATE OR REPLACE FUNCTION create_default_cancellation_email_template_for_user(new_user_id BIGINT)
+RETURNS VOID AS $$
+BEGIN
+    INSERT INTO email_templates (user_id, template_type, subject, body, body_format, is_default, attach_documents, attach_audit_log)
+    VALUES (
+        new_user_id,
+        'cancellation',
+        'Cancelled: {template.name}',
+        '<!DOCTYPE html>
+<html lang="en">
+<head>
+    <meta charset="UTF-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1.0">
+    <title>Signature Request Cancelled</title>
+    <style>
+        body {
+            font-family: ''Segoe UI'', Tahoma, Geneva, Verdana, sans-serif;
+            line-height: 1.6;
+            color: #333;
+            max-width: 600px;
+            margin: 0 auto;
+            padding: 20px;
+        }
+        .container {
+            background-color: #ffffff;
+            border-radius: 8px;
+            padding: 30px;
+            box-shadow: 0 2px 10px rgba(0,0,0,0.1);
+        }
+        .header {
+            text-align: center;
+            margin-bottom: 30px;
+        }
+        .header h1 {
+            color: #dc3545;
+            margin-bottom: 10px;
+        }
+        .reason {
+            background-color: #f8f9fa;
+            border-left: 4px solid #dc3545;
+            padding: 10px 15px;
+            margin: 20px 0;
+        }
+        .footer {
+            margin-top: 30px;
+            padding-top: 20px;
+            border-top: 1px solid #e9ecef;
+            font-size: 14px;
+            color: #6c757d;
+            text-align: center;
+        }
+    </style>
+</head>
+<body>
+    <div class="container">
+        <div class="header">
+            <h1>Signature Request Cancelled</h1>
+            <p>Hello <strong>{submitter.name}</strong>,</p>
+        </div>
+
+        <div class="content">
+            <p>The request to sign <strong>"{template.name}"</strong> has been cancelled by the sender. No further action is needed and the signing link no longer works.</p>
+
+            <div class="reason">
+                <strong>Reason:</strong> {void.reason}
+            </div>
+        </div>
+
+        <div class="footer">
+            <p>This email was sent automatically from the DocuSeal Pro system.</p>
+            <p>&copy; 2025 DocuSeal Pro. All rights reserved.</p>
+        </div>
+    </div>
+</body>
+</html>',
+        'html',
+        true,
+        false,
+        false
+    );
+END;
+$$ LANGUAGE plpgsql;
+
+SELECT create_default_cancellation_email_template_for_user(u.id)
+FROM users u
+WHERE NOT EXISTS (
+    SELECT 1 FROM email_templates et
+    WHERE et.user_id = u.id AND et.template_type = 'cancellation' AND et.is_default = true
+);
+
+CREATE OR REPLACE FUNCTION trigger_create_default_cancellation_email_template()
+RETURNS TRIGGER AS $$
+BEGIN
+    PERFORM create_default_cancellation_email_template_for_user(NEW.id);
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS trigger_create_default_cancellation_email_template_on_user_insert ON users;
+CREATE TRIGGER trigger_create_default_cancellation_email_template_on_user_insert
+    AFTER INSERT ON users
+    FOR EACH ROW
+    EXECUTE FUNCTION trigger_create_default_cancellation_email_template();
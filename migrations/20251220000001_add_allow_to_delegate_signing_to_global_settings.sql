@@ -0,0 +1,4 @@
+-- Let signers hand their part of a submission to someone else from the signing page
+ALTER TABLE global_settings ADD COLUMN IF NOT EXISTS allow_to_delegate_signing BOOLEAN NOT NULL DEFAULT FALSE;
+
+COMMENT ON COLUMN global_settings.allow_to_delegate_signing IS 'Whether signers may delegate signing to another person; senders can always reassign';
@@ -0,0 +1,41 @@
+-- Durable background jobs shared by every server instance; workers claim due rows with FOR UPDATE SKIP LOCKED
+CREATE TABLE IF NOT EXISTS jobs (
+    id BIGSERIAL PRIMARY KEY,
+    kind VARCHAR(50) NOT NULL,
+    payload JSONB NOT NULL DEFAULT '{}'::jsonb,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending',
+    attempts INT NOT NULL DEFAULT 0,
+    max_attempts INT NOT NULL DEFAULT 5,
+    run_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    locked_until TIMESTAMP WITH TIME ZONE,
+    last_error TEXT,
+    idempotency_key VARCHAR(255),
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    completed_at TIMESTAMP WITH TIME ZONE
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_jobs_idempotency_key ON jobs(idempotency_key) WHERE idempotency_key IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_jobs_due ON jobs(run_at) WHERE status = 'pending';
+CREATE INDEX IF NOT EXISTS idx_jobs_lease ON jobs(locked_until) WHERE status = 'running';
+
+COMMENT ON COLUMN jobs.kind IS 'Handler that runs the job, e.g. record_payment, send_reminder, auto_sign, completion_emails';
+COMMENT ON COLUMN jobs.status IS 'pending, running, completed, dead (retries exhausted)';
+COMMENT ON COLUMN jobs.locked_until IS 'Lease of the worker running the job; an expired lease makes the job claimable again';
+COMMENT ON COLUMN jobs.idempotency_key IS 'Enqueueing a second job with the same key is a no-op';
+
+-- Reminders used to be found by polling the submitters table; give every submitter that may
+-- still get one a job, which reschedules itself for when the next reminder is due.
+-- Keys match reminder_job_key() in src/services/reminder_queue.rs.
+INSERT INTO jobs (kind, payload, idempotency_key)
+SELECT 'send_reminder',
+       jsonb_build_object('submitter_id', s.id, 'reminder_number', s.reminder_count + 1),
+       'reminder:' || s.id || ':' || (s.reminder_count + 1)
+           || ':' || FLOOR(EXTRACT(EPOCH FROM COALESCE(s.invited_at, s.created_at)))::BIGINT
+           || ':' || COALESCE(FLOOR(EXTRACT(EPOCH FROM sub.expires_at))::BIGINT, 0)
+FROM submitters s
+LEFT JOIN submissions sub ON sub.id = s.submission_id
+WHERE s.status IN ('pending', 'sent', 'viewed')
+  AND s.reminder_config IS NOT NULL
+  AND s.reminder_count < 3
+ON CONFLICT DO NOTHING;
@@ -0,0 +1,28 @@
+-- Every email the application sends, with the rendered message so it can be resent unchanged
+CREATE TABLE IF NOT EXISTS email_outbox (
+    id BIGSERIAL PRIMARY KEY,
+    user_id BIGINT REFERENCES users(id) ON DELETE CASCADE,
+    submitter_id BIGINT REFERENCES submitters(id) ON DELETE SET NULL,
+    kind VARCHAR(50) NOT NULL,
+    transport VARCHAR(20) NOT NULL,
+    from_email VARCHAR(255) NOT NULL,
+    to_email VARCHAR(255) NOT NULL,
+    to_name VARCHAR(255),
+    subject TEXT NOT NULL,
+    raw_message BYTEA NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending',
+    attempts INT NOT NULL DEFAULT 0,
+    last_error TEXT,
+    last_attempt_at TIMESTAMP WITH TIME ZONE,
+    sent_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_email_outbox_submitter_id ON email_outbox(submitter_id);
+CREATE INDEX IF NOT EXISTS idx_email_outbox_user_id ON email_outbox(user_id, created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_email_outbox_failed ON email_outbox(created_at) WHERE status = 'failed';
+
+COMMENT ON COLUMN email_outbox.kind IS 'invitation, reminder, completion, copy, cancellation, expired, activation, team_invitation, password_reset, ...';
+COMMENT ON COLUMN email_outbox.transport IS 'smtp, file or memory';
+COMMENT ON COLUMN email_outbox.status IS 'pending (being sent), sent, failed';
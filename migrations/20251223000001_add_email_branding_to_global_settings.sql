@@ -0,0 +1,33 @@
+-- Per-account sender identity and HTML layout for template emails
+ALTER TABLE global_settings ADD COLUMN IF NOT EXISTS email_from_name TEXT;
+ALTER TABLE global_settings ADD COLUMN IF NOT EXISTS email_reply_to TEXT;
+ALTER TABLE global_settings ADD COLUMN IF NOT EXISTS email_layout TEXT;
+
+COMMENT ON COLUMN global_settings.email_from_name IS 'Sender display name of account emails; the address stays FROM_EMAIL. NULL uses company_name';
+COMMENT ON COLUMN global_settings.email_reply_to IS 'Reply-To address of account emails, NULL for none';
+COMMENT ON COLUMN global_settings.email_layout IS 'HTML layout wrapping html email templates, rendering the email with {{content}}. NULL uses the built-in branded layout';
+
+-- Default email templates were seeded with the product name; show the account name instead
+UPDATE email_templates
+SET subject = REPLACE(subject, 'DocuSeal Pro', '{account.name}'),
+    body = REPLACE(body, 'DocuSeal Pro', '{account.name}'),
+    updated_at = NOW()
+WHERE is_default = true AND (subject LIKE '%DocuSeal Pro%' OR body LIKE '%DocuSeal Pro%');
+
+-- Same for the defaults seeded for new users
+CREATE OR REPLACE FUNCTION brand_default_email_template()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF NEW.is_default THEN
+        NEW.subject := REPLACE(NEW.subject, 'DocuSeal Pro', '{account.name}');
+        NEW.body := REPLACE(NEW.body, 'DocuSeal Pro', '{account.name}');
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS brand_default_email_template ON email_templates;
+CREATE TRIGGER brand_default_email_template
+    BEFORE INSERT ON email_templates
+    FOR EACH ROW
+    EXECUTE FUNCTION brand_default_email_template();
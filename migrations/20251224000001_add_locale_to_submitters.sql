@@ -0,0 +1,4 @@
+-- Language a submitter gets their emails, signing page errors and dates in, chosen at send time
+ALTER TABLE submitters ADD COLUMN IF NOT EXISTS locale VARCHAR(10);
+
+COMMENT ON COLUMN submitters.locale IS 'Language code of the submitter (en, fr, es, pt, de, it, nl, vi). NULL uses the account locale';
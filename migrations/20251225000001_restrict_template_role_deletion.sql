@@ -0,0 +1,13 @@
+-- A role that submitters sign as must not be deleted: with SET NULL their fields would
+-- become fillable by every submitter of the envelope. Only template fields are unassigned.
+-- NO ACTION is checked at the end of the statement, so deleting a whole template still
+-- cascades through its roles and submitters.
+ALTER TABLE submission_fields DROP CONSTRAINT IF EXISTS submission_fields_role_id_fkey;
+ALTER TABLE submission_fields
+    ADD CONSTRAINT submission_fields_role_id_fkey FOREIGN KEY (role_id) REFERENCES template_roles(id) ON DELETE NO ACTION;
+
+ALTER TABLE submitters DROP CONSTRAINT IF EXISTS submitters_role_id_fkey;
+ALTER TABLE submitters
+    ADD CONSTRAINT submitters_role_id_fkey FOREIGN KEY (role_id) REFERENCES template_roles(id) ON DELETE NO ACTION;
+
+CREATE INDEX IF NOT EXISTS idx_submission_fields_role_id ON submission_fields(role_id);
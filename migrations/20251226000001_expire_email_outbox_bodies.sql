@@ -0,0 +1,10 @@
+-- Rendered messages hold signing links and personal data; only keep them for a while.
+-- The row stays as a record of the send once its body is dropped.
+ALTER TABLE email_outbox ALTER COLUMN raw_message DROP NOT NULL;
+
+COMMENT ON COLUMN email_outbox.raw_message IS 'Cleared by the purge_email_bodies job after EMAIL_BODY_RETENTION_DAYS (default 30)';
+
+-- The purge job reschedules itself; the key matches PURGE_EMAIL_BODIES_KEY in src/services/email.rs
+INSERT INTO jobs (kind, payload, idempotency_key)
+VALUES ('purge_email_bodies', '{}'::jsonb, 'purge_email_bodies')
+ON CONFLICT DO NOTHING;
@@ -0,0 +1,5 @@
+-- Lease of the worker delivering a webhook right now. Its result is only recorded while the
+-- lease is still its own, and a leased delivery cannot be queued for redelivery.
+ALTER TABLE webhook_deliveries ADD COLUMN IF NOT EXISTS locked_until TIMESTAMP WITH TIME ZONE;
+
+COMMENT ON COLUMN webhook_deliveries.locked_until IS 'Set while a worker is delivering; equals next_attempt_at until the attempt is recorded';
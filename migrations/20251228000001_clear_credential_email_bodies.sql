@@ -0,0 +1,5 @@
+-- Password reset codes and activation/invitation links are no longer stored with their email;
+-- drop the copies recorded before. Kinds match CREDENTIAL_KINDS in src/services/email.rs.
+UPDATE email_outbox
+SET raw_message = NULL, updated_at = NOW()
+WHERE kind IN ('password_reset', 'activation', 'team_invitation') AND raw_message IS NOT NULL;
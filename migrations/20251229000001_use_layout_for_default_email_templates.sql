@@ -0,0 +1,86 @@
+-- Default email templates were complete html documents with the product name in their
+-- footer, so the account layout never wrapped them. Seed them as body fragments instead,
+-- which the layout wraps with the account name, logo and footer.
+CREATE OR REPLACE FUNCTION default_email_template_body(email_template_type TEXT)
+RETURNS TEXT AS $$
+BEGIN
+    RETURN CASE email_template_type
+        WHEN 'invitation' THEN '<h2 style="margin: 0 0 16px; color: #667eea;">Document Signature Request</h2>
+<p>Hello <strong>{{submitter.name}}</strong>,</p>
+<p>You have received a request to sign a document from <strong>{{account.name}}</strong>.</p>
+<div style="background-color: #fff3cd; border: 1px solid #ffeaa7; color: #856404; padding: 15px; border-radius: 5px; margin: 20px 0;">
+    <strong>Important:</strong> This link is only valid for a limited time. Please complete your signature as soon as possible.
+</div>
+<p><strong>Document Name:</strong> {{template.name}}</p>
+<p>Please click the button below to access and sign the document:</p>
+<p style="text-align: center; margin: 24px 0;"><a href="{{submitter.link}}" style="display: inline-block; padding: 12px 24px; background-color: #667eea; color: #ffffff; text-decoration: none; border-radius: 6px; font-weight: bold;">Access and Sign Document</a></p>
+<p>If the button above doesn''t work, you can copy and paste the following link into your browser:</p>
+<p style="word-break: break-all; background-color: #f8f9fa; padding: 10px; border-radius: 5px; font-family: monospace;">{{submitter.link}}</p>
+<p style="font-size: 13px; color: #6b7280;">If you do not wish to receive this email, please ignore it.</p>'
+        WHEN 'reminder' THEN '<p style="margin: 0 0 8px;"><span style="display: inline-block; padding: 4px 12px; background-color: #ff9800; color: #ffffff; border-radius: 16px; font-size: 13px; font-weight: bold;">Reminder #{{reminder.number}}</span></p>
+<h2 style="margin: 0 0 16px; color: #ff9800;">Document Signature Reminder</h2>
+<p>Hello <strong>{{submitter.name}}</strong>,</p>
+<p>We noticed that you haven''t completed signing the document <strong>"{{template.name}}"</strong>.</p>
+<div style="background-color: #fff3cd; border: 1px solid #ffeaa7; color: #856404; padding: 15px; border-radius: 5px; margin: 20px 0;">
+    <strong>Notice:</strong> This signature link is only valid for a limited time. Please complete the signing as soon as possible.
+</div>
+<p>Please click the button below to access and complete the document signing:</p>
+<p style="text-align: center; margin: 24px 0;"><a href="{{submitter.link}}" style="display: inline-block; padding: 12px 24px; background-color: #ff9800; color: #ffffff; text-decoration: none; border-radius: 6px; font-weight: bold;">Sign Document Now</a></p>
+<p>If the button above doesn''t work, you can copy and paste the following link into your browser:</p>
+<p style="word-break: break-all; background-color: #f8f9fa; padding: 10px; border-radius: 5px; font-family: monospace;">{{submitter.link}}</p>
+<p style="font-size: 13px; color: #6b7280;">If you have already completed the signing, please ignore this email.</p>'
+        WHEN 'completion' THEN '<h2 style="margin: 0 0 16px; color: #28a745;">Document Signing Completed</h2>
+<p>Hello <strong>{{submitter.name}}</strong>,</p>
+<p>We are pleased to inform you that the document <strong>"{{template.name}}"</strong> has been successfully signed by all parties.</p>
+<p>The signed document is attached to this email.</p>
+<p>Thank you!</p>'
+        WHEN 'copy' THEN '<h2 style="margin: 0 0 16px; color: #17a2b8;">Document Copy</h2>
+<p>Hello <strong>{{submitter.name}}</strong>,</p>
+<p>Here is a copy of the completed document <strong>"{{template.name}}"</strong> that you signed.</p>
+<p>You can download the completed document from the attachment.</p>
+<p>Thank you!</p>'
+        WHEN 'cancellation' THEN '<h2 style="margin: 0 0 16px; color: #dc3545;">Signature Request Cancelled</h2>
+<p>Hello <strong>{{submitter.name}}</strong>,</p>
+<p>The request to sign <strong>"{{template.name}}"</strong> has been cancelled by the sender. No further action is needed and the signing link no longer works.</p>
+{{#if void.reason}}<div style="background-color: #f8d7da; border: 1px solid #f5c6cb; color: #721c24; padding: 15px; border-radius: 5px; margin: 20px 0;">
+    <strong>Reason:</strong> {{void.reason}}
+</div>{{/if}}'
+    END;
+END;
+$$ LANGUAGE plpgsql IMMUTABLE;
+
+CREATE OR REPLACE FUNCTION create_default_email_templates_for_user(new_user_id BIGINT)
+RETURNS VOID AS $$
+BEGIN
+    INSERT INTO email_templates (user_id, template_type, subject, body, body_format, is_default, attach_documents, attach_audit_log)
+    VALUES
+        (new_user_id, 'invitation', 'Please sign: {template.name}', default_email_template_body('invitation'), 'html', true, false, false),
+        (new_user_id, 'reminder', 'Reminder: Please sign {template.name}', default_email_template_body('reminder'), 'html', true, false, false),
+        (new_user_id, 'completion', 'Document completed: {template.name}', default_email_template_body('completion'), 'html', true, true, true),
+        (new_user_id, 'copy', 'Copy: {template.name}', default_email_template_body('copy'), 'html', true, true, true);
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION create_default_cancellation_email_template_for_user(new_user_id BIGINT)
+RETURNS VOID AS $$
+BEGIN
+    INSERT INTO email_templates (user_id, template_type, subject, body, body_format, is_default, attach_documents, attach_audit_log)
+    VALUES (new_user_id, 'cancellation', 'Cancelled: {template.name}', default_email_template_body('cancellation'), 'html', true, false, false);
+END;
+$$ LANGUAGE plpgsql;
+
+-- Existing defaults nobody edited, matched by the bodies seeded before and after the
+-- product name was replaced with {account.name}. Edited templates are left alone.
+UPDATE email_templates
+SET body = default_email_template_body(template_type),
+    updated_at = NOW()
+WHERE is_default = true
+  AND body_format = 'html'
+  AND md5(body) IN (
+      '830331ac1e5665abfad287200d4bda79', '9fdaa32d6243917f53a6150557483a0a',
+      '15a2b75114a9ad4edfdf60bf02e873ce', 'a1cc91b27bce6224ce327d44c31c982e',
+      '4ba6f9a1da1d71ce377a203abb9e843f', '39010d3136b77406121214f76a96d531',
+      'fe3b149358a364a809d16230692a1cba', '07dddd61e70d28f005a705fa7bd607e4',
+      'fa4486afad47aa74db2c50988a47d4f3', '5c3f7f126a0359e45b146ae92178db53',
+      'bb58e061d2cff89fea8da00581a3965e', '81a2e5bf644833a861adcd34eb47bc76'
+  );
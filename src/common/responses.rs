@@ -56,6 +56,20 @@ impl<T> ApiResponse<T> {
         )
     }
 
+    /// 422 Unprocessable Entity - Well-formed request with invalid values, details in data
+    pub fn unprocessable(data: T, error: String) -> (StatusCode, Json<ApiResponse<T>>) {
+        (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(ApiResponse {
+                success: false,
+                status_code: 422,
+                message: "Unprocessable Entity".to_string(),
+                data: Some(data),
+                error: Some(error),
+            }),
+        )
+    }
+
     /// 401 Unauthorized - Authentication required
     pub fn unauthorized(error: String) -> (StatusCode, Json<ApiResponse<T>>) {
         (
@@ -98,6 +112,20 @@ impl<T> ApiResponse<T> {
         )
     }
 
+    /// 410 Gone - Resource existed but is no longer available
+    pub fn gone(error: String) -> (StatusCode, Json<ApiResponse<T>>) {
+        (
+            StatusCode::GONE,
+            Json(ApiResponse {
+                success: false,
+                status_code: 410,
+                message: "Gone".to_string(),
+                data: None,
+                error: Some(error),
+            }),
+        )
+    }
+
     /// 500 Internal Server Error - Server error
     pub fn internal_error(error: String) -> (StatusCode, Json<ApiResponse<T>>) {
         (
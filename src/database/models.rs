@@ -167,6 +167,9 @@ pub struct DbTemplateField {
     pub options: Option<serde_json::Value>,
     pub metadata: Option<serde_json::Value>,
     pub partner: Option<String>, // Which partner/signer this field belongs to
+    pub conditions: Option<serde_json::Value>, // FieldCondition array
+    pub validation: Option<serde_json::Value>, // FieldValidation rules
+    pub role_id: Option<i64>, // Template role whose submitter fills this field
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub deleted_at: Option<DateTime<Utc>>,
@@ -184,6 +187,20 @@ pub struct CreateTemplateField {
     pub options: Option<serde_json::Value>,
     pub metadata: Option<serde_json::Value>,
     pub partner: Option<String>, // Which partner/signer this field belongs to
+    pub conditions: Option<serde_json::Value>, // FieldCondition array
+    pub validation: Option<serde_json::Value>, // FieldValidation rules
+    pub role_id: Option<i64>, // Template role whose submitter fills this field
+}
+
+// Database template role model (a signing party such as "Buyer" or "Seller")
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct DbTemplateRole {
+    pub id: i64,
+    pub template_id: i64,
+    pub name: String,
+    pub display_order: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
 }
 
 // Database-specific template folder model
@@ -256,6 +273,11 @@ pub struct DbSubmitter {
     pub updated_at: DateTime<Utc>,
     pub decline_reason: Option<String>,
     pub template_name: Option<String>, // Added for reminder emails
+    pub submission_id: Option<i64>, // Envelope this submitter belongs to
+    pub signing_order: i32, // Submitters with the same order sign in parallel
+    pub invited_at: Option<DateTime<Utc>>, // None while waiting for earlier signers
+    pub role_id: Option<i64>, // Template role this submitter signs as
+    pub locale: Option<String>, // Language chosen at send time; None uses the account locale
 }// Create submitter request
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateSubmitter {
@@ -267,6 +289,100 @@ pub struct CreateSubmitter {
     pub token: String,
     pub reminder_config: Option<serde_json::Value>,
     pub session_id: Option<String>,
+    pub submission_id: Option<i64>,
+    pub signing_order: i32,
+    pub role_id: Option<i64>,
+    pub locale: Option<String>,
+}
+
+// Database submission model (one envelope sent from a template)
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct DbSubmission {
+    pub id: i64,
+    pub template_id: i64,
+    pub user_id: i64,
+    pub account_id: Option<i64>,
+    pub name: Option<String>,
+    pub status: String, // pending, completed, expired, declined, voided
+    pub signing_mode: String, // parallel, sequential
+    pub session_id: Option<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub archived_at: Option<DateTime<Utc>>,
+    pub signed_document_key: Option<String>, // Storage key of the final signed PDF
+    pub signed_document_sha256: Option<String>,
+    pub signed_document_size: Option<i64>,
+    pub signed_document_digitally_signed: bool,
+    pub signed_document_created_at: Option<DateTime<Utc>>,
+    pub voided_at: Option<DateTime<Utc>>,
+    pub void_reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// Create submission request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateSubmission {
+    pub template_id: i64,
+    pub user_id: i64,
+    pub account_id: Option<i64>,
+    pub name: Option<String>,
+    pub signing_mode: String,
+    pub session_id: Option<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+// Database bulk send model (one background job sending a template to many recipients)
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct DbBulkSend {
+    pub id: i64,
+    pub template_id: i64,
+    pub user_id: i64,
+    pub account_id: Option<i64>,
+    pub filename: Option<String>,
+    pub status: String, // pending, processing, completed, failed
+    pub total_rows: i32,
+    pub processed_rows: i32,
+    pub succeeded_rows: i32,
+    pub failed_rows: i32,
+    pub results: serde_json::Value, // BulkSendRowResult array
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+// Database audit event model (append-only, hash-chained per submitter)
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct DbAuditEvent {
+    pub id: i64,
+    pub submitter_id: i64,
+    pub submission_id: Option<i64>,
+    pub template_id: i64,
+    pub event_type: String, // sent, email_opened, viewed, field_filled, signed, declined, reminded, resubmitted, copy_sent, downloaded
+    pub actor: String,
+    pub details: String,
+    pub data: Option<serde_json::Value>,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub timezone: Option<String>,
+    pub prev_hash: Option<String>,
+    pub hash: String,
+    pub created_at: DateTime<Utc>,
+}
+
+// Create audit event request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateAuditEvent {
+    pub submitter_id: i64,
+    pub submission_id: Option<i64>,
+    pub template_id: i64,
+    pub event_type: String,
+    pub actor: String,
+    pub details: String,
+    pub data: Option<serde_json::Value>,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub timezone: Option<String>,
 }
 
 // Database-specific signature data model
@@ -320,6 +436,11 @@ pub struct DbSubmissionField {
     pub options: Option<serde_json::Value>,
     pub metadata: Option<serde_json::Value>,
     pub partner: Option<String>, // Which partner/signer this field belongs to
+    pub conditions: Option<serde_json::Value>, // FieldCondition array
+    pub validation: Option<serde_json::Value>, // FieldValidation rules
+    pub role_id: Option<i64>, // Template role whose submitter fills this field
+    pub prefilled_value: Option<String>, // Value supplied by the sender at send time
+    pub readonly: bool, // Prefilled value cannot be changed by the signer
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -337,6 +458,11 @@ pub struct CreateSubmissionField {
     pub options: Option<serde_json::Value>,
     pub metadata: Option<serde_json::Value>,
     pub partner: Option<String>, // Which partner/signer this field belongs to
+    pub conditions: Option<serde_json::Value>, // FieldCondition array
+    pub validation: Option<serde_json::Value>, // FieldValidation rules
+    pub role_id: Option<i64>, // Template role whose submitter fills this field
+    pub prefilled_value: Option<String>, // Value supplied by the sender at send time
+    pub readonly: bool, // Prefilled value cannot be changed by the signer
 }
 
 // Create payment record request
@@ -375,6 +501,10 @@ pub struct DbGlobalSettings {
     pub completion_body: Option<String>,
     pub redirect_title: Option<String>,
     pub redirect_url: Option<String>,
+    pub allow_to_delegate_signing: bool,
+    pub email_from_name: Option<String>,
+    pub email_reply_to: Option<String>,
+    pub email_layout: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -401,6 +531,120 @@ pub struct UpdateGlobalSettings {
     pub completion_body: Option<String>,
     pub redirect_title: Option<String>,
     pub redirect_url: Option<String>,
+    pub allow_to_delegate_signing: Option<bool>,
+    // An empty string clears these three
+    pub email_from_name: Option<String>,
+    pub email_reply_to: Option<String>,
+    pub email_layout: Option<String>,
+}
+
+// Database webhook endpoint model
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct DbWebhookEndpoint {
+    pub id: i64,
+    pub user_id: i64,
+    pub account_id: Option<i64>,
+    pub url: String,
+    pub secret: String,
+    pub events: serde_json::Value, // JSON array of event types
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// Create webhook endpoint request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateWebhookEndpoint {
+    pub user_id: i64,
+    pub account_id: Option<i64>,
+    pub url: String,
+    pub secret: String,
+    pub events: serde_json::Value,
+}
+
+// Database webhook delivery model (persistent retry queue)
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct DbWebhookDelivery {
+    pub id: i64,
+    pub endpoint_id: i64,
+    pub event_type: String,
+    pub payload: serde_json::Value,
+    pub status: String, // pending, succeeded, failed
+    pub attempts: i32,
+    pub next_attempt_at: Option<DateTime<Utc>>,
+    pub last_attempt_at: Option<DateTime<Utc>>,
+    pub last_response_status: Option<i32>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// Database webhook delivery attempt model (delivery log)
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct DbWebhookDeliveryAttempt {
+    pub id: i64,
+    pub delivery_id: i64,
+    pub attempt: i32,
+    pub response_status: Option<i32>,
+    pub response_body: Option<String>,
+    pub error: Option<String>,
+    pub duration_ms: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+// Database background job model
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct DbJob {
+    pub id: i64,
+    pub kind: String,
+    pub payload: serde_json::Value,
+    pub status: String, // pending, running, completed, dead
+    pub attempts: i32,
+    pub max_attempts: i32,
+    pub run_at: DateTime<Utc>,
+    pub locked_until: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+    pub idempotency_key: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+// Database outbox email model (every message sent, with its delivery status)
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct DbOutboxEmail {
+    pub id: i64,
+    pub user_id: Option<i64>,
+    pub submitter_id: Option<i64>,
+    pub kind: String,
+    pub transport: String,
+    pub from_email: String,
+    pub to_email: String,
+    pub to_name: Option<String>,
+    pub subject: String,
+    /// None once the retention period has passed
+    pub raw_message: Option<Vec<u8>>,
+    pub status: String, // pending, sent, failed
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub last_attempt_at: Option<DateTime<Utc>>,
+    pub sent_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// Data for recording an outgoing email
+#[derive(Debug, Clone)]
+pub struct CreateOutboxEmail {
+    pub user_id: Option<i64>,
+    pub submitter_id: Option<i64>,
+    pub kind: String,
+    pub transport: String,
+    pub from_email: String,
+    pub to_email: String,
+    pub to_name: Option<String>,
+    pub subject: String,
+    /// None for messages that carry a credential
+    pub raw_message: Option<Vec<u8>>,
 }
 
 // Email template database model
@@ -408,7 +652,7 @@ pub struct UpdateGlobalSettings {
 pub struct DbEmailTemplate {
     pub id: i64,
     pub user_id: i64,
-    pub template_type: String, // 'invitation', 'reminder', 'completion', 'copy'
+    pub template_type: String, // 'invitation', 'reminder', 'completion', 'copy', 'cancellation'
     pub subject: String,
     pub body: String,
     pub body_format: String, // 'text' or 'html'
@@ -422,7 +666,7 @@ pub struct DbEmailTemplate {
 // Update email template request
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UpdateEmailTemplate {
-    pub template_type: Option<String>, // 'invitation', 'reminder', 'completion', 'copy'
+    pub template_type: Option<String>, // 'invitation', 'reminder', 'completion', 'copy', 'cancellation'
     pub subject: Option<String>,
     pub body: Option<String>,
     pub body_format: Option<String>, // 'text' or 'html'
@@ -1,7 +1,8 @@
 use sqlx::{PgPool, Row};
+use std::collections::HashSet;
 use chrono::{Utc, DateTime};
 
-use super::models::{DbUser, CreateUser, DbTemplate, CreateTemplate, DbTemplateField, CreateTemplateField, CreateSubmitter, DbSubmitter, DbPaymentRecord, CreatePaymentRecord, DbSignatureData, DbSubscriptionPlan, DbTemplateFolder, CreateTemplateFolder, DbSubmissionField, CreateSubmissionField, DbGlobalSettings, UpdateGlobalSettings, DbEmailTemplate, UpdateEmailTemplate, DbAccount, CreateAccount, UpdateAccount, DbAccountLinkedAccount};
+use super::models::{DbUser, CreateUser, DbTemplate, CreateTemplate, DbTemplateField, CreateTemplateField, DbTemplateRole, CreateSubmitter, DbSubmitter, CreateSubmission, DbSubmission, DbBulkSend, CreateAuditEvent, DbAuditEvent, CreateWebhookEndpoint, DbWebhookEndpoint, DbWebhookDelivery, DbWebhookDeliveryAttempt, DbJob, DbOutboxEmail, CreateOutboxEmail, DbPaymentRecord, CreatePaymentRecord, DbSignatureData, DbSubscriptionPlan, DbTemplateFolder, CreateTemplateFolder, DbSubmissionField, CreateSubmissionField, DbGlobalSettings, UpdateGlobalSettings, DbEmailTemplate, UpdateEmailTemplate, DbAccount, CreateAccount, UpdateAccount, DbAccountLinkedAccount};
 use crate::models::signature::SignatureInfo;
 
 // Structured query implementations for better organization
@@ -1472,11 +1473,11 @@ impl TemplateFieldQueries {
             r#"
             INSERT INTO template_fields (
                 template_id, name, field_type, required, display_order,
-                position, options, metadata, partner, created_at, updated_at
+                position, options, metadata, partner, conditions, validation, role_id, created_at, updated_at
             )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
             RETURNING id, template_id, name, field_type, required, display_order,
-                     position, options, metadata, partner, created_at, updated_at, deleted_at
+                     position, options, metadata, partner, conditions, validation, role_id, created_at, updated_at, deleted_at
             "#
         )
         .bind(field_data.template_id)
@@ -1488,6 +1489,9 @@ impl TemplateFieldQueries {
         .bind(&field_data.options)
         .bind(&field_data.metadata)
         .bind(&field_data.partner)
+        .bind(&field_data.conditions)
+        .bind(&field_data.validation)
+        .bind(field_data.role_id)
         .bind(now)
         .bind(now)
         .fetch_one(pool)
@@ -1504,6 +1508,9 @@ impl TemplateFieldQueries {
             options: row.try_get("options")?,
             metadata: row.try_get("metadata")?,
             partner: row.try_get("partner")?,
+            conditions: row.try_get("conditions")?,
+            validation: row.try_get("validation")?,
+            role_id: row.try_get("role_id")?,
             created_at: row.try_get("created_at")?,
             updated_at: row.try_get("updated_at")?,
             deleted_at: row.try_get("deleted_at")?,
@@ -1544,10 +1551,10 @@ impl TemplateFieldQueries {
             r#"
             UPDATE template_fields SET
                 name = $2, field_type = $3, required = $4, display_order = $5,
-                position = $6, options = $7, metadata = $8, partner = $9, updated_at = $10
+                position = $6, options = $7, metadata = $8, partner = $9, conditions = $10, validation = $11, role_id = $12, updated_at = $13
             WHERE id = $1 AND deleted_at IS NULL
             RETURNING id, template_id, name, field_type, required, display_order,
-                     position, options, metadata, partner, created_at, updated_at, deleted_at
+                     position, options, metadata, partner, conditions, validation, role_id, created_at, updated_at, deleted_at
             "#
         )
         .bind(field_id)
@@ -1559,6 +1566,9 @@ impl TemplateFieldQueries {
         .bind(&field_data.options)
         .bind(&field_data.metadata)
         .bind(&field_data.partner)
+        .bind(&field_data.conditions)
+        .bind(&field_data.validation)
+        .bind(field_data.role_id)
         .bind(now)
         .fetch_optional(pool)
         .await?;
@@ -1575,6 +1585,9 @@ impl TemplateFieldQueries {
                 options: row.try_get("options")?,
                 metadata: row.try_get("metadata")?,
                 partner: row.try_get("partner")?,
+                conditions: row.try_get("conditions")?,
+                validation: row.try_get("validation")?,
+                role_id: row.try_get("role_id")?,
                 created_at: row.try_get("created_at")?,
                 updated_at: row.try_get("updated_at")?,
                 deleted_at: row.try_get("deleted_at")?,
@@ -1594,18 +1607,35 @@ impl TemplateFieldQueries {
         Ok(result.rows_affected() > 0)
     }
 
+    // Copies the roles too; cloned fields point at the clone's role of the same name
     pub async fn clone_template_fields(pool: &PgPool, from_template_id: i64, to_template_id: i64) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO template_roles (template_id, name, display_order, created_at, updated_at)
+            SELECT $2, name, display_order, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP
+            FROM template_roles
+            WHERE template_id = $1
+            ON CONFLICT (template_id, name) DO NOTHING
+            "#
+        )
+        .bind(from_template_id)
+        .bind(to_template_id)
+        .execute(pool)
+        .await?;
+
         sqlx::query(
             r#"
             INSERT INTO template_fields (
                 template_id, name, field_type, required, display_order,
-                position, options, metadata, partner, created_at, updated_at
+                position, options, metadata, partner, conditions, validation, role_id, created_at, updated_at
             )
             SELECT
-                $2 as template_id, name, field_type, required, display_order,
-                position, options, metadata, partner, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP
-            FROM template_fields
-            WHERE template_id = $1 AND deleted_at IS NULL
+                $2 as template_id, f.name, f.field_type, f.required, f.display_order,
+                f.position, f.options, f.metadata, f.partner, f.conditions, f.validation, cloned_role.id, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP
+            FROM template_fields f
+            LEFT JOIN template_roles role ON role.id = f.role_id
+            LEFT JOIN template_roles cloned_role ON cloned_role.template_id = $2 AND cloned_role.name = role.name
+            WHERE f.template_id = $1 AND f.deleted_at IS NULL
             "#
         )
         .bind(from_template_id)
@@ -1628,15 +1658,124 @@ impl TemplateFieldQueries {
     }
 }
 
+pub struct TemplateRoleQueries;
+
+impl TemplateRoleQueries {
+    pub async fn get_roles_by_template(pool: &PgPool, template_id: i64) -> Result<Vec<DbTemplateRole>, sqlx::Error> {
+        sqlx::query_as::<_, DbTemplateRole>(
+            "SELECT id, template_id, name, display_order, created_at, updated_at
+             FROM template_roles WHERE template_id = $1 ORDER BY display_order, id"
+        )
+        .bind(template_id)
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn get_role_by_id(pool: &PgPool, id: i64) -> Result<Option<DbTemplateRole>, sqlx::Error> {
+        sqlx::query_as::<_, DbTemplateRole>(
+            "SELECT id, template_id, name, display_order, created_at, updated_at
+             FROM template_roles WHERE id = $1"
+        )
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create_role(pool: &PgPool, template_id: i64, name: &str, display_order: Option<i32>) -> Result<DbTemplateRole, sqlx::Error> {
+        // New roles go after the existing ones unless an order is given
+        sqlx::query_as::<_, DbTemplateRole>(
+            "INSERT INTO template_roles (template_id, name, display_order, created_at, updated_at)
+             VALUES ($1, $2, COALESCE($3, (SELECT COUNT(*)::INT FROM template_roles WHERE template_id = $1)), NOW(), NOW())
+             RETURNING id, template_id, name, display_order, created_at, updated_at"
+        )
+        .bind(template_id)
+        .bind(name)
+        .bind(display_order)
+        .fetch_one(pool)
+        .await
+    }
+
+    // Role with this name on the template, created if it does not exist yet
+    pub async fn find_or_create_role(pool: &PgPool, template_id: i64, name: &str) -> Result<DbTemplateRole, sqlx::Error> {
+        let existing = sqlx::query_as::<_, DbTemplateRole>(
+            "SELECT id, template_id, name, display_order, created_at, updated_at
+             FROM template_roles WHERE template_id = $1 AND name = $2"
+        )
+        .bind(template_id)
+        .bind(name)
+        .fetch_optional(pool)
+        .await?;
+
+        match existing {
+            Some(role) => Ok(role),
+            None => Self::create_role(pool, template_id, name, None).await,
+        }
+    }
+
+    // Renaming a role also renames the partner label of its fields
+    pub async fn update_role(pool: &PgPool, id: i64, name: &str, display_order: i32) -> Result<Option<DbTemplateRole>, sqlx::Error> {
+        let role = sqlx::query_as::<_, DbTemplateRole>(
+            "UPDATE template_roles SET name = $2, display_order = $3, updated_at = NOW()
+             WHERE id = $1
+             RETURNING id, template_id, name, display_order, created_at, updated_at"
+        )
+        .bind(id)
+        .bind(name)
+        .bind(display_order)
+        .fetch_optional(pool)
+        .await?;
+
+        if role.is_some() {
+            sqlx::query("UPDATE template_fields SET partner = $2, updated_at = NOW() WHERE role_id = $1")
+                .bind(id)
+                .bind(name)
+                .execute(pool)
+                .await?;
+        }
+
+        Ok(role)
+    }
+
+    // Whether any submitter signs as the role or any submission field belongs to it
+    pub async fn is_role_in_use(pool: &PgPool, id: i64) -> Result<bool, sqlx::Error> {
+        let in_use: bool = sqlx::query_scalar(
+            "SELECT EXISTS (SELECT 1 FROM submitters WHERE role_id = $1)
+                 OR EXISTS (SELECT 1 FROM submission_fields WHERE role_id = $1)"
+        )
+        .bind(id)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(in_use)
+    }
+
+    // Fields of a deleted role become fillable by any submitter
+    pub async fn delete_role(pool: &PgPool, id: i64) -> Result<bool, sqlx::Error> {
+        sqlx::query("UPDATE template_fields SET partner = NULL, updated_at = NOW() WHERE role_id = $1")
+            .bind(id)
+            .execute(pool)
+            .await?;
+
+        let result = sqlx::query("DELETE FROM template_roles WHERE id = $1")
+            .bind(id)
+            .execute(pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
 impl SubmitterQueries {
     pub async fn create_submitter(pool: &PgPool, submitter_data: CreateSubmitter) -> Result<DbSubmitter, sqlx::Error> {
         let now = Utc::now();
+        // Waiting submitters are invited later, when their turn in the signing order comes
+        let invited_at = if submitter_data.status == "waiting" { None } else { Some(now) };
         eprintln!("Creating submitter: template_id={}, user_id={}, name={}, email={}, token={}",
             submitter_data.template_id, submitter_data.user_id, submitter_data.name, submitter_data.email, submitter_data.token);
         let row = sqlx::query(
-            "INSERT INTO submitters (template_id, user_id, name, email, status, signed_at, token, bulk_signatures, ip_address, user_agent, session_id, reminder_config, reminder_count, created_at, updated_at)
-             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
-             RETURNING id, template_id, user_id, name, email, status, signed_at, token, bulk_signatures, ip_address, user_agent, reminder_config, last_reminder_sent_at, reminder_count, created_at, updated_at, decline_reason, session_id, viewed_at, timezone"
+            "INSERT INTO submitters (template_id, user_id, name, email, status, signed_at, token, bulk_signatures, ip_address, user_agent, session_id, reminder_config, reminder_count, created_at, updated_at, submission_id, signing_order, invited_at, role_id, locale)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20)
+             RETURNING id, template_id, user_id, name, email, status, signed_at, token, bulk_signatures, ip_address, user_agent, reminder_config, last_reminder_sent_at, reminder_count, created_at, updated_at, decline_reason, session_id, viewed_at, timezone, submission_id, signing_order, invited_at, role_id, locale"
         )
         .bind(submitter_data.template_id)
         .bind(submitter_data.user_id)
@@ -1653,6 +1792,11 @@ impl SubmitterQueries {
         .bind(0) // reminder_count
         .bind(now)
         .bind(now)
+        .bind(submitter_data.submission_id)
+        .bind(submitter_data.signing_order)
+        .bind(invited_at)
+        .bind(submitter_data.role_id)
+        .bind(submitter_data.locale)
         .fetch_one(pool)
         .await?;
 
@@ -1678,6 +1822,11 @@ impl SubmitterQueries {
             session_id: row.get(17),
             viewed_at: row.get(18),
             timezone: row.get(19),
+            submission_id: row.get(20),
+            signing_order: row.get(21),
+            invited_at: row.get(22),
+            role_id: row.get(23),
+            locale: row.get(24),
             template_name: None,
         })
     }
@@ -1685,7 +1834,7 @@ impl SubmitterQueries {
     pub async fn get_submitters_by_template(pool: &PgPool, template_id: i64) -> Result<Vec<DbSubmitter>, sqlx::Error> {
         eprintln!("Getting submitters for template_id: {}", template_id);
         let rows = sqlx::query(
-            "SELECT id, template_id, user_id, name, email, status, signed_at, token, bulk_signatures, ip_address, user_agent, reminder_config, last_reminder_sent_at, reminder_count, created_at, updated_at, decline_reason, session_id, viewed_at, timezone
+            "SELECT id, template_id, user_id, name, email, status, signed_at, token, bulk_signatures, ip_address, user_agent, reminder_config, last_reminder_sent_at, reminder_count, created_at, updated_at, decline_reason, session_id, viewed_at, timezone, submission_id, signing_order, invited_at, role_id, locale
              FROM submitters WHERE template_id = $1 ORDER BY created_at "
         )
         .bind(template_id)
@@ -1716,6 +1865,11 @@ impl SubmitterQueries {
                 session_id: row.get(17),
                 viewed_at: row.get(18),
                 timezone: row.get(19),
+                submission_id: row.get(20),
+                signing_order: row.get(21),
+                invited_at: row.get(22),
+                role_id: row.get(23),
+                locale: row.get(24),
             template_name: None,
             });
         }
@@ -1725,7 +1879,7 @@ impl SubmitterQueries {
     pub async fn get_submitters_by_user(pool: &PgPool, user_id: i64) -> Result<Vec<DbSubmitter>, sqlx::Error> {
         eprintln!("Getting submitters for user_id: {}", user_id);
         let rows = sqlx::query(
-            "SELECT id, template_id, user_id, name, email, status, signed_at, token, bulk_signatures, ip_address, user_agent, reminder_config, last_reminder_sent_at, reminder_count, created_at, updated_at, decline_reason, session_id, viewed_at, timezone
+            "SELECT id, template_id, user_id, name, email, status, signed_at, token, bulk_signatures, ip_address, user_agent, reminder_config, last_reminder_sent_at, reminder_count, created_at, updated_at, decline_reason, session_id, viewed_at, timezone, submission_id, signing_order, invited_at, role_id, locale
              FROM submitters WHERE user_id = $1 ORDER BY created_at "
         )
         .bind(user_id)
@@ -1756,6 +1910,11 @@ impl SubmitterQueries {
                 session_id: row.get(17),
                 viewed_at: row.get(18),
                 timezone: row.get(19),
+                submission_id: row.get(20),
+                signing_order: row.get(21),
+                invited_at: row.get(22),
+                role_id: row.get(23),
+                locale: row.get(24),
             template_name: None,
             });
         }
@@ -1764,7 +1923,7 @@ impl SubmitterQueries {
 
     pub async fn get_submitter_by_token(pool: &PgPool, token: &str) -> Result<Option<DbSubmitter>, sqlx::Error> {
         let row = sqlx::query(
-            "SELECT id, template_id, user_id, name, email, status, signed_at, token, bulk_signatures, ip_address, user_agent, reminder_config, last_reminder_sent_at, reminder_count, created_at, updated_at, decline_reason, session_id, viewed_at, timezone
+            "SELECT id, template_id, user_id, name, email, status, signed_at, token, bulk_signatures, ip_address, user_agent, reminder_config, last_reminder_sent_at, reminder_count, created_at, updated_at, decline_reason, session_id, viewed_at, timezone, submission_id, signing_order, invited_at, role_id, locale
              FROM submitters WHERE token = $1"
         )
         .bind(token)
@@ -1793,6 +1952,11 @@ impl SubmitterQueries {
                 session_id: row.get(17),
                 viewed_at: row.get(18),
                 timezone: row.get(19),
+                submission_id: row.get(20),
+                signing_order: row.get(21),
+                invited_at: row.get(22),
+                role_id: row.get(23),
+                locale: row.get(24),
             template_name: None,
             }))
         } else {
@@ -1807,7 +1971,7 @@ impl SubmitterQueries {
         let row = sqlx::query(
             "UPDATE submitters SET status = COALESCE($1, status), signed_at = COALESCE($2, signed_at), updated_at = $3 
              WHERE id = $4 
-             RETURNING id, template_id, user_id, name, email, status, signed_at, token, bulk_signatures, ip_address, user_agent, reminder_config, last_reminder_sent_at, reminder_count, created_at, updated_at, decline_reason, session_id, viewed_at, timezone"
+             RETURNING id, template_id, user_id, name, email, status, signed_at, token, bulk_signatures, ip_address, user_agent, reminder_config, last_reminder_sent_at, reminder_count, created_at, updated_at, decline_reason, session_id, viewed_at, timezone, submission_id, signing_order, invited_at, role_id, locale"
         )
         .bind(status)
         .bind(signed_at)
@@ -1838,6 +2002,11 @@ impl SubmitterQueries {
                 session_id: row.get(17),
                 viewed_at: row.get(18),
                 timezone: row.get(19),
+                submission_id: row.get(20),
+                signing_order: row.get(21),
+                invited_at: row.get(22),
+                role_id: row.get(23),
+                locale: row.get(24),
             template_name: None,
             }))
         } else {
@@ -1858,7 +2027,7 @@ impl SubmitterQueries {
         let row = sqlx::query(
             "UPDATE submitters SET bulk_signatures = $1, ip_address = $2, user_agent = $3, timezone = $4, status = 'signed', signed_at = $5, updated_at = $5 
              WHERE id = $6 
-             RETURNING id, template_id, user_id, name, email, status, signed_at, token, bulk_signatures, ip_address, user_agent, reminder_config, last_reminder_sent_at, reminder_count, created_at, updated_at, decline_reason, session_id, viewed_at, timezone"
+             RETURNING id, template_id, user_id, name, email, status, signed_at, token, bulk_signatures, ip_address, user_agent, reminder_config, last_reminder_sent_at, reminder_count, created_at, updated_at, decline_reason, session_id, viewed_at, timezone, submission_id, signing_order, invited_at, role_id, locale"
         )
         .bind(bulk_signatures)
         .bind(ip_address)
@@ -1891,6 +2060,11 @@ impl SubmitterQueries {
                 session_id: row.get(17),
                 viewed_at: row.get(18),
                 timezone: row.get(19),
+                submission_id: row.get(20),
+                signing_order: row.get(21),
+                invited_at: row.get(22),
+                role_id: row.get(23),
+                locale: row.get(24),
             template_name: None,
             }))
         } else {
@@ -1912,7 +2086,7 @@ impl SubmitterQueries {
         let row = sqlx::query(
             "UPDATE submitters SET status = 'declined', decline_reason = $1, bulk_signatures = $2, ip_address = $3, user_agent = $4, timezone = $5, updated_at = $6 
              WHERE id = $7 
-             RETURNING id, template_id, user_id, name, email, status, signed_at, token, bulk_signatures, ip_address, user_agent, reminder_config, last_reminder_sent_at, reminder_count, created_at, updated_at, decline_reason, session_id, viewed_at, timezone"
+             RETURNING id, template_id, user_id, name, email, status, signed_at, token, bulk_signatures, ip_address, user_agent, reminder_config, last_reminder_sent_at, reminder_count, created_at, updated_at, decline_reason, session_id, viewed_at, timezone, submission_id, signing_order, invited_at, role_id, locale"
         )
         .bind(decline_reason)
         .bind(bulk_signatures)
@@ -1946,6 +2120,11 @@ impl SubmitterQueries {
                 session_id: row.get(17),
                 viewed_at: row.get(18),
                 timezone: row.get(19),
+                submission_id: row.get(20),
+                signing_order: row.get(21),
+                invited_at: row.get(22),
+                role_id: row.get(23),
+                locale: row.get(24),
             template_name: None,
             }))
         } else {
@@ -1955,7 +2134,7 @@ impl SubmitterQueries {
 
     pub async fn get_submitter_by_id(pool: &PgPool, id: i64) -> Result<Option<DbSubmitter>, sqlx::Error> {
         let row = sqlx::query(
-            "SELECT id, template_id, user_id, name, email, status, signed_at, token, bulk_signatures, ip_address, user_agent, reminder_config, last_reminder_sent_at, reminder_count, created_at, updated_at, decline_reason, session_id, viewed_at, timezone
+            "SELECT id, template_id, user_id, name, email, status, signed_at, token, bulk_signatures, ip_address, user_agent, reminder_config, last_reminder_sent_at, reminder_count, created_at, updated_at, decline_reason, session_id, viewed_at, timezone, submission_id, signing_order, invited_at, role_id, locale
              FROM submitters WHERE id = $1"
         )
         .bind(id)
@@ -1984,6 +2163,11 @@ impl SubmitterQueries {
                 session_id: row.get(17),
                 viewed_at: row.get(18),
                 timezone: row.get(19),
+                submission_id: row.get(20),
+                signing_order: row.get(21),
+                invited_at: row.get(22),
+                role_id: row.get(23),
+                locale: row.get(24),
             template_name: None,
             }))
         } else {
@@ -1991,51 +2175,6 @@ impl SubmitterQueries {
         }
     }
 
-    // Get submitters that need reminder emails
-    pub async fn get_pending_reminders(pool: &PgPool) -> Result<Vec<DbSubmitter>, sqlx::Error> {
-        let rows = sqlx::query(
-            r#"
-            SELECT s.id, s.template_id, s.user_id, s.name, s.email, s.status, s.signed_at, s.token, s.bulk_signatures, s.ip_address, s.user_agent, s.reminder_config, s.last_reminder_sent_at, s.reminder_count, s.created_at, s.updated_at, s.decline_reason, s.session_id, s.viewed_at, s.timezone, t.name as template_name
-            FROM submitters s
-            LEFT JOIN templates t ON s.template_id = t.id
-            WHERE s.status IN ('pending', 'sent', 'viewed')
-              AND s.reminder_config IS NOT NULL
-              AND s.reminder_count < 3
-            ORDER BY s.created_at
-            "#
-        )
-        .fetch_all(pool)
-        .await?;
-
-        let mut submitters = Vec::new();
-        for row in rows {
-            submitters.push(DbSubmitter {
-                id: row.get(0),
-                template_id: row.get(1),
-                user_id: row.get(2),
-                name: row.get(3),
-                email: row.get(4),
-                status: row.get(5),
-                signed_at: row.get(6),
-                token: row.get(7),
-                bulk_signatures: row.get(8),
-                ip_address: row.get(9),
-                user_agent: row.get(10),
-                reminder_config: row.get(11),
-                last_reminder_sent_at: row.get(12),
-                reminder_count: row.get(13),
-                created_at: row.get(14),
-                updated_at: row.get(15),
-                decline_reason: row.get(16),
-                session_id: row.get(17),
-                viewed_at: row.get(18),
-                timezone: row.get(19),
-                template_name: row.get(20),
-            });
-        }
-        Ok(submitters)
-    }
-
     // Update reminder status after sending
     pub async fn update_reminder_sent(pool: &PgPool, submitter_id: i64) -> Result<(), sqlx::Error> {
         let now = Utc::now();
@@ -2131,7 +2270,7 @@ impl SubmitterQueries {
             .map(|i| format!("${}", i))
             .collect();
         let query_str = format!(
-            "SELECT id, template_id, user_id, name, email, status, signed_at, token, bulk_signatures, ip_address, user_agent, reminder_config, last_reminder_sent_at, reminder_count, created_at, updated_at, decline_reason, session_id, viewed_at, timezone
+            "SELECT id, template_id, user_id, name, email, status, signed_at, token, bulk_signatures, ip_address, user_agent, reminder_config, last_reminder_sent_at, reminder_count, created_at, updated_at, decline_reason, session_id, viewed_at, timezone, submission_id, signing_order, invited_at, role_id, locale
              FROM submitters 
              WHERE user_id IN ({}) 
              ORDER BY created_at DESC",
@@ -2168,12 +2307,89 @@ impl SubmitterQueries {
                 session_id: row.get(17),
                 viewed_at: row.get(18),
                 timezone: row.get(19),
+                submission_id: row.get(20),
+                signing_order: row.get(21),
+                invited_at: row.get(22),
+                role_id: row.get(23),
+                locale: row.get(24),
             template_name: None,
             });
         }
         Ok(submitters)
     }
 
+    // Move a waiting submitter to pending once it is their turn to sign. Returns false when
+    // the submitter was no longer waiting, e.g. because a concurrent completion invited them first.
+    pub async fn invite_submitter(pool: &PgPool, id: i64) -> Result<bool, sqlx::Error> {
+        let now = Utc::now();
+        let result = sqlx::query("UPDATE submitters SET status = 'pending', invited_at = $1, updated_at = $1 WHERE id = $2 AND status = 'waiting'")
+            .bind(now)
+            .bind(id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    // Hand an unsigned submitter over to another person. The fields stay with this submitter;
+    // the new token replaces the old one, so the previous signing link stops working.
+    pub async fn reassign_submitter(pool: &PgPool, id: i64, name: &str, email: &str, token: &str) -> Result<Option<DbSubmitter>, sqlx::Error> {
+        let now = Utc::now();
+        let row = sqlx::query(
+            "UPDATE submitters SET name = $1, email = $2, token = $3, viewed_at = NULL, reminder_count = 0, last_reminder_sent_at = NULL,
+                 status = CASE WHEN status = 'waiting' THEN 'waiting' ELSE 'pending' END,
+                 invited_at = CASE WHEN status = 'waiting' THEN invited_at ELSE $4 END,
+                 updated_at = $4
+             WHERE id = $5 AND status IN ('pending', 'sent', 'viewed', 'waiting')
+             RETURNING id, template_id, user_id, name, email, status, signed_at, token, bulk_signatures, ip_address, user_agent, reminder_config, last_reminder_sent_at, reminder_count, created_at, updated_at, decline_reason, session_id, viewed_at, timezone, submission_id, signing_order, invited_at, role_id, locale"
+        )
+        .bind(name)
+        .bind(email)
+        .bind(token)
+        .bind(now)
+        .bind(id)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(row.map(|row| DbSubmitter {
+            id: row.get(0),
+            template_id: row.get(1),
+            user_id: row.get(2),
+            name: row.get(3),
+            email: row.get(4),
+            status: row.get(5),
+            signed_at: row.get(6),
+            token: row.get(7),
+            bulk_signatures: row.get(8),
+            ip_address: row.get(9),
+            user_agent: row.get(10),
+            reminder_config: row.get(11),
+            last_reminder_sent_at: row.get(12),
+            reminder_count: row.get(13),
+            created_at: row.get(14),
+            updated_at: row.get(15),
+            decline_reason: row.get(16),
+            session_id: row.get(17),
+            viewed_at: row.get(18),
+            timezone: row.get(19),
+            submission_id: row.get(20),
+            signing_order: row.get(21),
+            invited_at: row.get(22),
+            role_id: row.get(23),
+            locale: row.get(24),
+            template_name: None,
+        }))
+    }
+
+    // Keep the first view time; later views are only recorded in the audit trail
+    pub async fn mark_submitter_viewed(pool: &PgPool, id: i64) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE submitters SET viewed_at = COALESCE(viewed_at, $1) WHERE id = $2")
+            .bind(Utc::now())
+            .bind(id)
+            .execute(pool)
+            .await?;
+        Ok(())
+    }
+
     pub async fn resubmit_submitter(pool: &PgPool, id: i64) -> Result<(), sqlx::Error> {
         sqlx::query("UPDATE submitters SET status = 'pending' WHERE id = $1")
             .bind(id)
@@ -2184,7 +2400,7 @@ impl SubmitterQueries {
 
     pub async fn get_submitters_by_template_id(pool: &PgPool, template_id: i64) -> Result<Vec<DbSubmitter>, sqlx::Error> {
         let rows = sqlx::query(
-            "SELECT id, template_id, user_id, name, email, status, signed_at, token, bulk_signatures, ip_address, user_agent, reminder_config, last_reminder_sent_at, reminder_count, created_at, updated_at, decline_reason, session_id, viewed_at, timezone
+            "SELECT id, template_id, user_id, name, email, status, signed_at, token, bulk_signatures, ip_address, user_agent, reminder_config, last_reminder_sent_at, reminder_count, created_at, updated_at, decline_reason, session_id, viewed_at, timezone, submission_id, signing_order, invited_at, role_id, locale
              FROM submitters WHERE template_id = $1"
         )
         .bind(template_id)
@@ -2214,20 +2430,957 @@ impl SubmitterQueries {
                 session_id: row.get(17),
                 viewed_at: row.get(18),
                 timezone: row.get(19),
+                submission_id: row.get(20),
+                signing_order: row.get(21),
+                invited_at: row.get(22),
+                role_id: row.get(23),
+                locale: row.get(24),
             template_name: None,
             });
         }
         Ok(submitters)
     }
+
+    pub async fn get_submitters_by_submission_id(pool: &PgPool, submission_id: i64) -> Result<Vec<DbSubmitter>, sqlx::Error> {
+        let rows = sqlx::query(
+            "SELECT id, template_id, user_id, name, email, status, signed_at, token, bulk_signatures, ip_address, user_agent, reminder_config, last_reminder_sent_at, reminder_count, created_at, updated_at, decline_reason, session_id, viewed_at, timezone, submission_id, signing_order, invited_at, role_id, locale
+             FROM submitters WHERE submission_id = $1 ORDER BY signing_order, id"
+        )
+        .bind(submission_id)
+        .fetch_all(pool)
+        .await?;
+
+        let mut submitters = Vec::new();
+        for row in rows {
+            submitters.push(DbSubmitter {
+                id: row.get(0),
+                template_id: row.get(1),
+                user_id: row.get(2),
+                name: row.get(3),
+                email: row.get(4),
+                status: row.get(5),
+                signed_at: row.get(6),
+                token: row.get(7),
+                bulk_signatures: row.get(8),
+                ip_address: row.get(9),
+                user_agent: row.get(10),
+                reminder_config: row.get(11),
+                last_reminder_sent_at: row.get(12),
+                reminder_count: row.get(13),
+                created_at: row.get(14),
+                updated_at: row.get(15),
+                decline_reason: row.get(16),
+                session_id: row.get(17),
+                viewed_at: row.get(18),
+                timezone: row.get(19),
+                submission_id: row.get(20),
+                signing_order: row.get(21),
+                invited_at: row.get(22),
+                role_id: row.get(23),
+                locale: row.get(24),
+                template_name: None,
+            });
+        }
+        Ok(submitters)
+    }
+}
+
+// Submission (envelope) queries
+pub struct SubmissionQueries;
+
+impl SubmissionQueries {
+    pub async fn create_submission(pool: &PgPool, submission_data: CreateSubmission) -> Result<DbSubmission, sqlx::Error> {
+        let now = Utc::now();
+
+        let row = sqlx::query_as::<_, DbSubmission>(
+            r#"
+            INSERT INTO submissions (template_id, user_id, account_id, name, status, signing_mode, session_id, expires_at, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, 'pending', $5, $6, $7, $8, $9)
+            RETURNING id, template_id, user_id, account_id, name, status, signing_mode, session_id, expires_at, completed_at, archived_at, signed_document_key, signed_document_sha256, signed_document_size, signed_document_digitally_signed, signed_document_created_at, voided_at, void_reason, created_at, updated_at
+            "#
+        )
+        .bind(submission_data.template_id)
+        .bind(submission_data.user_id)
+        .bind(submission_data.account_id)
+        .bind(submission_data.name)
+        .bind(submission_data.signing_mode)
+        .bind(submission_data.session_id)
+        .bind(submission_data.expires_at)
+        .bind(now)
+        .bind(now)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(row)
+    }
+
+    pub async fn get_submission_by_id(pool: &PgPool, id: i64) -> Result<Option<DbSubmission>, sqlx::Error> {
+        let row = sqlx::query_as::<_, DbSubmission>(
+            "SELECT id, template_id, user_id, account_id, name, status, signing_mode, session_id, expires_at, completed_at, archived_at, signed_document_key, signed_document_sha256, signed_document_size, signed_document_digitally_signed, signed_document_created_at, voided_at, void_reason, created_at, updated_at
+             FROM submissions WHERE id = $1"
+        )
+        .bind(id)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(row)
+    }
+
+    // Get submissions visible to the user: whole account if the user has one, otherwise own submissions
+    pub async fn get_team_submissions(
+        pool: &PgPool,
+        user_id: i64,
+        status: Option<&str>,
+        include_archived: bool,
+        offset: i64,
+        limit: i64,
+    ) -> Result<Vec<DbSubmission>, sqlx::Error> {
+        let account_id_result = sqlx::query("SELECT account_id FROM users WHERE id = $1")
+            .bind(user_id)
+            .fetch_one(pool)
+            .await?;
+        let account_id: Option<i64> = account_id_result.try_get("account_id")?;
+
+        let owner_filter = if account_id.is_some() { "account_id = $1" } else { "user_id = $1" };
+        let archived_filter = if include_archived { "" } else { " AND archived_at IS NULL" };
+        let query_str = format!(
+            "SELECT id, template_id, user_id, account_id, name, status, signing_mode, session_id, expires_at, completed_at, archived_at, signed_document_key, signed_document_sha256, signed_document_size, signed_document_digitally_signed, signed_document_created_at, voided_at, void_reason, created_at, updated_at
+             FROM submissions
+             WHERE {}{} AND ($2::TEXT IS NULL OR status = $2)
+             ORDER BY created_at DESC
+             LIMIT $3 OFFSET $4",
+            owner_filter, archived_filter
+        );
+
+        let rows = sqlx::query_as::<_, DbSubmission>(&query_str)
+            .bind(account_id.unwrap_or(user_id))
+            .bind(status)
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(pool)
+            .await?;
+
+        Ok(rows)
+    }
+
+    pub async fn update_submission_status(pool: &PgPool, id: i64, status: &str) -> Result<Option<DbSubmission>, sqlx::Error> {
+        let now = Utc::now();
+        let completed_at = if status == "completed" { Some(now) } else { None };
+
+        let row = sqlx::query_as::<_, DbSubmission>(
+            r#"
+            UPDATE submissions SET status = $1, completed_at = COALESCE($2, completed_at), updated_at = $3
+            WHERE id = $4
+            RETURNING id, template_id, user_id, account_id, name, status, signing_mode, session_id, expires_at, completed_at, archived_at, signed_document_key, signed_document_sha256, signed_document_size, signed_document_digitally_signed, signed_document_created_at, voided_at, void_reason, created_at, updated_at
+            "#
+        )
+        .bind(status)
+        .bind(completed_at)
+        .bind(now)
+        .bind(id)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(row)
+    }
+
+    // Recompute the envelope status from its submitters (declined wins, then completed when everyone signed).
+    // Voided and expired envelopes are closed and keep their status.
+    pub async fn refresh_submission_status(pool: &PgPool, id: i64) -> Result<Option<DbSubmission>, sqlx::Error> {
+        let submitters = SubmitterQueries::get_submitters_by_submission_id(pool, id).await?;
+        if submitters.is_empty() {
+            return Self::get_submission_by_id(pool, id).await;
+        }
+
+        let status = if submitters.iter().any(|s| s.status == "declined") {
+            "declined"
+        } else if submitters.iter().all(|s| s.status == "signed" || s.status == "completed") {
+            "completed"
+        } else {
+            "pending"
+        };
+
+        let now = Utc::now();
+        let completed_at = if status == "completed" { Some(now) } else { None };
+        let row = sqlx::query_as::<_, DbSubmission>(
+            r#"
+            UPDATE submissions SET status = $1, completed_at = COALESCE($2, completed_at), updated_at = $3
+            WHERE id = $4 AND status NOT IN ('voided', 'expired')
+            RETURNING id, template_id, user_id, account_id, name, status, signing_mode, session_id, expires_at, completed_at, archived_at, signed_document_key, signed_document_sha256, signed_document_size, signed_document_digitally_signed, signed_document_created_at, voided_at, void_reason, created_at, updated_at
+            "#
+        )
+        .bind(status)
+        .bind(completed_at)
+        .bind(now)
+        .bind(id)
+        .fetch_optional(pool)
+        .await?;
+
+        match row {
+            Some(submission) => Ok(Some(submission)),
+            None => Self::get_submission_by_id(pool, id).await,
+        }
+    }
+
+    // Record the final signed PDF of a completed submission
+    pub async fn set_signed_document(
+        pool: &PgPool,
+        id: i64,
+        key: &str,
+        sha256: &str,
+        size: i64,
+        digitally_signed: bool,
+    ) -> Result<Option<DbSubmission>, sqlx::Error> {
+        let now = Utc::now();
+
+        let row = sqlx::query_as::<_, DbSubmission>(
+            r#"
+            UPDATE submissions
+            SET signed_document_key = $1,
+                signed_document_sha256 = $2,
+                signed_document_size = $3,
+                signed_document_digitally_signed = $4,
+                signed_document_created_at = $5,
+                updated_at = $5
+            WHERE id = $6
+            RETURNING id, template_id, user_id, account_id, name, status, signing_mode, session_id, expires_at, completed_at, archived_at, signed_document_key, signed_document_sha256, signed_document_size, signed_document_digitally_signed, signed_document_created_at, voided_at, void_reason, created_at, updated_at
+            "#
+        )
+        .bind(key)
+        .bind(sha256)
+        .bind(size)
+        .bind(digitally_signed)
+        .bind(now)
+        .bind(id)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(row)
+    }
+
+    // Mark every pending submission past its expiry as expired and return them.
+    // The single UPDATE lets several app instances sweep without notifying twice.
+    pub async fn expire_due_submissions(pool: &PgPool) -> Result<Vec<DbSubmission>, sqlx::Error> {
+        let rows = sqlx::query_as::<_, DbSubmission>(
+            r#"
+            UPDATE submissions SET status = 'expired', updated_at = NOW()
+            WHERE status = 'pending' AND expires_at IS NOT NULL AND expires_at <= NOW()
+            RETURNING id, template_id, user_id, account_id, name, status, signing_mode, session_id, expires_at, completed_at, archived_at, signed_document_key, signed_document_sha256, signed_document_size, signed_document_digitally_signed, signed_document_created_at, voided_at, void_reason, created_at, updated_at
+            "#
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows)
+    }
+
+    // Move the expiry of a pending or expired submission; an expired one becomes pending again
+    pub async fn extend_submission(pool: &PgPool, id: i64, expires_at: DateTime<Utc>) -> Result<Option<DbSubmission>, sqlx::Error> {
+        let row = sqlx::query_as::<_, DbSubmission>(
+            r#"
+            UPDATE submissions SET expires_at = $1, status = 'pending', updated_at = NOW()
+            WHERE id = $2 AND status IN ('pending', 'expired')
+            RETURNING id, template_id, user_id, account_id, name, status, signing_mode, session_id, expires_at, completed_at, archived_at, signed_document_key, signed_document_sha256, signed_document_size, signed_document_digitally_signed, signed_document_created_at, voided_at, void_reason, created_at, updated_at
+            "#
+        )
+        .bind(expires_at)
+        .bind(id)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(row)
+    }
+
+    // Void a pending or expired submission and every signer who has not finished.
+    // Returns None when the submission is already completed, declined or voided.
+    pub async fn void_submission(pool: &PgPool, id: i64, reason: &str) -> Result<Option<DbSubmission>, sqlx::Error> {
+        let mut tx = pool.begin().await?;
+
+        let row = sqlx::query_as::<_, DbSubmission>(
+            r#"
+            UPDATE submissions SET status = 'voided', voided_at = NOW(), void_reason = $1, updated_at = NOW()
+            WHERE id = $2 AND status IN ('pending', 'expired')
+            RETURNING id, template_id, user_id, account_id, name, status, signing_mode, session_id, expires_at, completed_at, archived_at, signed_document_key, signed_document_sha256, signed_document_size, signed_document_digitally_signed, signed_document_created_at, voided_at, void_reason, created_at, updated_at
+            "#
+        )
+        .bind(reason)
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        if row.is_some() {
+            sqlx::query(
+                "UPDATE submitters SET status = 'voided', updated_at = NOW()
+                 WHERE submission_id = $1 AND status NOT IN ('signed', 'completed', 'declined')"
+            )
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(row)
+    }
+
+    pub async fn archive_submission(pool: &PgPool, id: i64) -> Result<Option<DbSubmission>, sqlx::Error> {
+        let now = Utc::now();
+
+        let row = sqlx::query_as::<_, DbSubmission>(
+            r#"
+            UPDATE submissions SET archived_at = $1, updated_at = $1
+            WHERE id = $2
+            RETURNING id, template_id, user_id, account_id, name, status, signing_mode, session_id, expires_at, completed_at, archived_at, signed_document_key, signed_document_sha256, signed_document_size, signed_document_digitally_signed, signed_document_created_at, voided_at, void_reason, created_at, updated_at
+            "#
+        )
+        .bind(now)
+        .bind(id)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(row)
+    }
+}
+
+pub struct BulkSendQueries;
+
+const BULK_SEND_COLUMNS: &str = "id, template_id, user_id, account_id, filename, status, total_rows, processed_rows, succeeded_rows, failed_rows, results, created_at, updated_at, completed_at";
+
+impl BulkSendQueries {
+    pub async fn create_bulk_send(pool: &PgPool, template_id: i64, user_id: i64, account_id: Option<i64>, filename: Option<String>, total_rows: i32) -> Result<DbBulkSend, sqlx::Error> {
+        let now = Utc::now();
+
+        let row = sqlx::query_as::<_, DbBulkSend>(&format!(
+            "INSERT INTO bulk_sends (template_id, user_id, account_id, filename, status, total_rows, created_at, updated_at)
+             VALUES ($1, $2, $3, $4, 'pending', $5, $6, $7)
+             RETURNING {}",
+            BULK_SEND_COLUMNS
+        ))
+        .bind(template_id)
+        .bind(user_id)
+        .bind(account_id)
+        .bind(filename)
+        .bind(total_rows)
+        .bind(now)
+        .bind(now)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(row)
+    }
+
+    pub async fn get_bulk_send_by_id(pool: &PgPool, id: i64) -> Result<Option<DbBulkSend>, sqlx::Error> {
+        let row = sqlx::query_as::<_, DbBulkSend>(&format!("SELECT {} FROM bulk_sends WHERE id = $1", BULK_SEND_COLUMNS))
+            .bind(id)
+            .fetch_optional(pool)
+            .await?;
+
+        Ok(row)
+    }
+
+    pub async fn update_status(pool: &PgPool, id: i64, status: &str) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "UPDATE bulk_sends
+             SET status = $2::VARCHAR,
+                 completed_at = CASE WHEN $2::VARCHAR IN ('completed', 'failed') THEN NOW() ELSE completed_at END,
+                 updated_at = NOW()
+             WHERE id = $1"
+        )
+        .bind(id)
+        .bind(status)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    // Append one row's outcome to the report and advance the progress counters
+    pub async fn record_row_result(pool: &PgPool, id: i64, result: serde_json::Value, succeeded: bool) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "UPDATE bulk_sends
+             SET results = results || jsonb_build_array($2::JSONB),
+                 processed_rows = processed_rows + 1,
+                 succeeded_rows = succeeded_rows + CASE WHEN $3 THEN 1 ELSE 0 END,
+                 failed_rows = failed_rows + CASE WHEN $3 THEN 0 ELSE 1 END,
+                 updated_at = NOW()
+             WHERE id = $1"
+        )
+        .bind(id)
+        .bind(result)
+        .bind(succeeded)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+}
+
+pub struct AuditEventQueries;
+
+impl AuditEventQueries {
+    /// Append an event, chaining it to the submitter's latest event.
+    /// An advisory lock per submitter keeps concurrent appends from forking the chain.
+    pub async fn append_event(pool: &PgPool, event_data: CreateAuditEvent) -> Result<DbAuditEvent, sqlx::Error> {
+        use chrono::SubsecRound;
+
+        let mut tx = pool.begin().await?;
+
+        sqlx::query("SELECT pg_advisory_xact_lock($1)")
+            .bind(event_data.submitter_id)
+            .execute(&mut *tx)
+            .await?;
+
+        let prev_hash: Option<String> = sqlx::query_scalar(
+            "SELECT hash FROM audit_events WHERE submitter_id = $1 ORDER BY id DESC LIMIT 1"
+        )
+        .bind(event_data.submitter_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        // Postgres keeps microseconds, so hash exactly what will be stored
+        let now = Utc::now().trunc_subsecs(6);
+        let hash = crate::services::audit::compute_event_hash(prev_hash.as_deref(), &event_data, now);
+
+        let row = sqlx::query_as::<_, DbAuditEvent>(
+            r#"
+            INSERT INTO audit_events (submitter_id, submission_id, template_id, event_type, actor, details, data, ip_address, user_agent, timezone, prev_hash, hash, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+            RETURNING id, submitter_id, submission_id, template_id, event_type, actor, details, data, ip_address, user_agent, timezone, prev_hash, hash, created_at
+            "#
+        )
+        .bind(event_data.submitter_id)
+        .bind(event_data.submission_id)
+        .bind(event_data.template_id)
+        .bind(event_data.event_type)
+        .bind(event_data.actor)
+        .bind(event_data.details)
+        .bind(event_data.data)
+        .bind(event_data.ip_address)
+        .bind(event_data.user_agent)
+        .bind(event_data.timezone)
+        .bind(prev_hash)
+        .bind(hash)
+        .bind(now)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(row)
+    }
+
+    pub async fn get_events_by_submitter(pool: &PgPool, submitter_id: i64) -> Result<Vec<DbAuditEvent>, sqlx::Error> {
+        let rows = sqlx::query_as::<_, DbAuditEvent>(
+            "SELECT id, submitter_id, submission_id, template_id, event_type, actor, details, data, ip_address, user_agent, timezone, prev_hash, hash, created_at
+             FROM audit_events WHERE submitter_id = $1 ORDER BY id ASC"
+        )
+        .bind(submitter_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows)
+    }
+}
+
+pub struct WebhookQueries;
+
+impl WebhookQueries {
+    pub async fn create_endpoint(pool: &PgPool, endpoint_data: CreateWebhookEndpoint) -> Result<DbWebhookEndpoint, sqlx::Error> {
+        let now = Utc::now();
+
+        let row = sqlx::query_as::<_, DbWebhookEndpoint>(
+            r#"
+            INSERT INTO webhook_endpoints (user_id, account_id, url, secret, events, is_active, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, TRUE, $6, $7)
+            RETURNING id, user_id, account_id, url, secret, events, is_active, created_at, updated_at
+            "#
+        )
+        .bind(endpoint_data.user_id)
+        .bind(endpoint_data.account_id)
+        .bind(endpoint_data.url)
+        .bind(endpoint_data.secret)
+        .bind(endpoint_data.events)
+        .bind(now)
+        .bind(now)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(row)
+    }
+
+    pub async fn get_endpoint_by_id(pool: &PgPool, id: i64) -> Result<Option<DbWebhookEndpoint>, sqlx::Error> {
+        let row = sqlx::query_as::<_, DbWebhookEndpoint>(
+            "SELECT id, user_id, account_id, url, secret, events, is_active, created_at, updated_at
+             FROM webhook_endpoints WHERE id = $1"
+        )
+        .bind(id)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(row)
+    }
+
+    // Endpoints are shared by the whole account; users without an account only see their own
+    pub async fn get_endpoints_by_owner(pool: &PgPool, user_id: i64, account_id: Option<i64>) -> Result<Vec<DbWebhookEndpoint>, sqlx::Error> {
+        let rows = sqlx::query_as::<_, DbWebhookEndpoint>(
+            "SELECT id, user_id, account_id, url, secret, events, is_active, created_at, updated_at
+             FROM webhook_endpoints
+             WHERE ($2::BIGINT IS NOT NULL AND account_id = $2) OR ($2::BIGINT IS NULL AND user_id = $1 AND account_id IS NULL)
+             ORDER BY id ASC"
+        )
+        .bind(user_id)
+        .bind(account_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows)
+    }
+
+    // Active endpoints of the owner subscribed to an event type
+    pub async fn get_subscribed_endpoints(pool: &PgPool, user_id: i64, account_id: Option<i64>, event_type: &str) -> Result<Vec<DbWebhookEndpoint>, sqlx::Error> {
+        let rows = sqlx::query_as::<_, DbWebhookEndpoint>(
+            "SELECT id, user_id, account_id, url, secret, events, is_active, created_at, updated_at
+             FROM webhook_endpoints
+             WHERE is_active = TRUE
+               AND events ? $3
+               AND (($2::BIGINT IS NOT NULL AND account_id = $2) OR ($2::BIGINT IS NULL AND user_id = $1 AND account_id IS NULL))"
+        )
+        .bind(user_id)
+        .bind(account_id)
+        .bind(event_type)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows)
+    }
+
+    pub async fn update_endpoint(
+        pool: &PgPool,
+        id: i64,
+        url: Option<&str>,
+        events: Option<serde_json::Value>,
+        is_active: Option<bool>,
+    ) -> Result<Option<DbWebhookEndpoint>, sqlx::Error> {
+        let row = sqlx::query_as::<_, DbWebhookEndpoint>(
+            r#"
+            UPDATE webhook_endpoints
+            SET url = COALESCE($2, url),
+                events = COALESCE($3, events),
+                is_active = COALESCE($4, is_active),
+                updated_at = $5
+            WHERE id = $1
+            RETURNING id, user_id, account_id, url, secret, events, is_active, created_at, updated_at
+            "#
+        )
+        .bind(id)
+        .bind(url)
+        .bind(events)
+        .bind(is_active)
+        .bind(Utc::now())
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(row)
+    }
+
+    pub async fn delete_endpoint(pool: &PgPool, id: i64) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM webhook_endpoints WHERE id = $1")
+            .bind(id)
+            .execute(pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn create_delivery(pool: &PgPool, endpoint_id: i64, event_type: &str, payload: &serde_json::Value) -> Result<DbWebhookDelivery, sqlx::Error> {
+        let now = Utc::now();
+
+        let row = sqlx::query_as::<_, DbWebhookDelivery>(
+            r#"
+            INSERT INTO webhook_deliveries (endpoint_id, event_type, payload, status, attempts, next_attempt_at, created_at, updated_at)
+            VALUES ($1, $2, $3, 'pending', 0, $4, $4, $4)
+            RETURNING id, endpoint_id, event_type, payload, status, attempts, next_attempt_at, last_attempt_at, last_response_status, created_at, updated_at
+            "#
+        )
+        .bind(endpoint_id)
+        .bind(event_type)
+        .bind(payload)
+        .bind(now)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(row)
+    }
+
+    // Claim due deliveries and push their next attempt out by a lease,
+    // so a crashed worker's deliveries are picked up again instead of being lost
+    pub async fn claim_due_deliveries(pool: &PgPool, limit: i64, lease_secs: i64) -> Result<Vec<DbWebhookDelivery>, sqlx::Error> {
+        let now = Utc::now();
+
+        let rows = sqlx::query_as::<_, DbWebhookDelivery>(
+            r#"
+            UPDATE webhook_deliveries
+            SET next_attempt_at = $2 + make_interval(secs => $3), locked_until = $2 + make_interval(secs => $3), updated_at = $2
+            WHERE id IN (
+                SELECT id FROM webhook_deliveries
+                WHERE status = 'pending' AND next_attempt_at <= $2
+                ORDER BY next_attempt_at ASC
+                LIMIT $1
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING id, endpoint_id, event_type, payload, status, attempts, next_attempt_at, last_attempt_at, last_response_status, created_at, updated_at
+            "#
+        )
+        .bind(limit)
+        .bind(now)
+        .bind(lease_secs as f64)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows)
+    }
+
+    // Log one HTTP attempt and move the delivery to its next state. `lease` is the
+    // next_attempt_at the delivery was claimed with; returns false (and records nothing)
+    // when the lease ran out and the delivery is no longer this worker's.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn record_attempt(
+        pool: &PgPool,
+        delivery_id: i64,
+        lease: Option<DateTime<Utc>>,
+        attempt: i32,
+        response_status: Option<i32>,
+        response_body: Option<&str>,
+        error: Option<&str>,
+        duration_ms: i64,
+        status: &str,
+        next_attempt_at: Option<DateTime<Utc>>,
+    ) -> Result<bool, sqlx::Error> {
+        let now = Utc::now();
+        let mut tx = pool.begin().await?;
+
+        let updated = sqlx::query(
+            "UPDATE webhook_deliveries
+             SET status = $2, attempts = $3, next_attempt_at = $4, last_attempt_at = $5, last_response_status = $6, locked_until = NULL, updated_at = $5
+             WHERE id = $1 AND status = 'pending' AND locked_until = $7"
+        )
+        .bind(delivery_id)
+        .bind(status)
+        .bind(attempt)
+        .bind(next_attempt_at)
+        .bind(now)
+        .bind(response_status)
+        .bind(lease)
+        .execute(&mut *tx)
+        .await?;
+        if updated.rows_affected() == 0 {
+            return Ok(false);
+        }
+
+        sqlx::query(
+            "INSERT INTO webhook_delivery_attempts (delivery_id, attempt, response_status, response_body, error, duration_ms, created_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7)"
+        )
+        .bind(delivery_id)
+        .bind(attempt)
+        .bind(response_status)
+        .bind(response_body)
+        .bind(error)
+        .bind(duration_ms)
+        .bind(now)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    pub async fn get_delivery_by_id(pool: &PgPool, id: i64) -> Result<Option<DbWebhookDelivery>, sqlx::Error> {
+        let row = sqlx::query_as::<_, DbWebhookDelivery>(
+            "SELECT id, endpoint_id, event_type, payload, status, attempts, next_attempt_at, last_attempt_at, last_response_status, created_at, updated_at
+             FROM webhook_deliveries WHERE id = $1"
+        )
+        .bind(id)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(row)
+    }
+
+    pub async fn get_deliveries_by_endpoint(pool: &PgPool, endpoint_id: i64, offset: i64, limit: i64) -> Result<Vec<DbWebhookDelivery>, sqlx::Error> {
+        let rows = sqlx::query_as::<_, DbWebhookDelivery>(
+            "SELECT id, endpoint_id, event_type, payload, status, attempts, next_attempt_at, last_attempt_at, last_response_status, created_at, updated_at
+             FROM webhook_deliveries WHERE endpoint_id = $1
+             ORDER BY id DESC
+             OFFSET $2 LIMIT $3"
+        )
+        .bind(endpoint_id)
+        .bind(offset)
+        .bind(limit)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows)
+    }
+
+    pub async fn get_delivery_attempts(pool: &PgPool, delivery_id: i64) -> Result<Vec<DbWebhookDeliveryAttempt>, sqlx::Error> {
+        let rows = sqlx::query_as::<_, DbWebhookDeliveryAttempt>(
+            "SELECT id, delivery_id, attempt, response_status, response_body, error, duration_ms, created_at
+             FROM webhook_delivery_attempts WHERE delivery_id = $1
+             ORDER BY attempt ASC"
+        )
+        .bind(delivery_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows)
+    }
+
+    // Put a delivery back in the queue for an immediate attempt. Returns None when it
+    // doesn't exist or a worker is delivering it right now.
+    pub async fn requeue_delivery(pool: &PgPool, id: i64) -> Result<Option<DbWebhookDelivery>, sqlx::Error> {
+        let now = Utc::now();
+
+        let row = sqlx::query_as::<_, DbWebhookDelivery>(
+            r#"
+            UPDATE webhook_deliveries
+            SET status = 'pending', next_attempt_at = $2, locked_until = NULL, updated_at = $2
+            WHERE id = $1 AND NOT (status = 'pending' AND locked_until IS NOT NULL AND locked_until > $2)
+            RETURNING id, endpoint_id, event_type, payload, status, attempts, next_attempt_at, last_attempt_at, last_response_status, created_at, updated_at
+            "#
+        )
+        .bind(id)
+        .bind(now)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(row)
+    }
+}
+
+const JOB_COLUMNS: &str = "id, kind, payload, status, attempts, max_attempts, run_at, locked_until, last_error, idempotency_key, created_at, updated_at, completed_at";
+
+pub struct JobQueries;
+
+impl JobQueries {
+    // Returns None when a job with the same idempotency key already exists
+    pub async fn enqueue(
+        pool: &PgPool,
+        kind: &str,
+        payload: &serde_json::Value,
+        run_at: DateTime<Utc>,
+        idempotency_key: Option<&str>,
+        max_attempts: i32,
+    ) -> Result<Option<DbJob>, sqlx::Error> {
+        let row = sqlx::query_as::<_, DbJob>(&format!(
+            "INSERT INTO jobs (kind, payload, run_at, idempotency_key, max_attempts) VALUES ($1, $2, $3, $4, $5)
+             ON CONFLICT (idempotency_key) WHERE idempotency_key IS NOT NULL DO NOTHING
+             RETURNING {}",
+            JOB_COLUMNS
+        ))
+        .bind(kind)
+        .bind(payload)
+        .bind(run_at)
+        .bind(idempotency_key)
+        .bind(max_attempts)
+        .fetch_optional(pool)
+        .await?;
+        Ok(row)
+    }
+
+    // Lease due jobs to this worker. Jobs whose lease ran out (the worker died mid-run) are
+    // picked up again; SKIP LOCKED keeps other instances from claiming the same rows.
+    pub async fn claim_due_jobs(pool: &PgPool, limit: i64, lease_secs: i64) -> Result<Vec<DbJob>, sqlx::Error> {
+        let now = Utc::now();
+        let rows = sqlx::query_as::<_, DbJob>(&format!(
+            r#"
+            UPDATE jobs
+            SET status = 'running', attempts = attempts + 1, locked_until = $2 + make_interval(secs => $3), updated_at = $2
+            WHERE id IN (
+                SELECT id FROM jobs
+                WHERE (status = 'pending' AND run_at <= $2) OR (status = 'running' AND locked_until < $2)
+                ORDER BY run_at ASC
+                LIMIT $1
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING {}
+            "#,
+            JOB_COLUMNS
+        ))
+        .bind(limit)
+        .bind(now)
+        .bind(lease_secs as f64)
+        .fetch_all(pool)
+        .await?;
+        Ok(rows)
+    }
+
+    // The result of a run is only recorded while the worker still holds the lease it claimed
+    // (`lease` is the claimed `locked_until`); returns false when the lease ran out and the
+    // job was claimed again, so a late result cannot overwrite the new run's.
+    pub async fn complete_job(pool: &PgPool, id: i64, lease: Option<DateTime<Utc>>) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            "UPDATE jobs SET status = 'completed', locked_until = NULL, last_error = NULL, completed_at = NOW(), updated_at = NOW()
+             WHERE id = $1 AND status = 'running' AND locked_until = $2"
+        )
+        .bind(id)
+        .bind(lease)
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    // Put a job back for a later run without counting the current one as a failed attempt
+    pub async fn reschedule_job(pool: &PgPool, id: i64, lease: Option<DateTime<Utc>>, run_at: DateTime<Utc>) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            "UPDATE jobs SET status = 'pending', attempts = GREATEST(attempts - 1, 0), run_at = $3, locked_until = NULL, updated_at = NOW()
+             WHERE id = $1 AND status = 'running' AND locked_until = $2"
+        )
+        .bind(id)
+        .bind(lease)
+        .bind(run_at)
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    // Record a failed run; without a retry time the job is dead-lettered
+    pub async fn fail_job(pool: &PgPool, id: i64, lease: Option<DateTime<Utc>>, error: &str, retry_at: Option<DateTime<Utc>>) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            "UPDATE jobs SET status = CASE WHEN $4::timestamptz IS NULL THEN 'dead' ELSE 'pending' END,
+                 run_at = COALESCE($4, run_at), locked_until = NULL, last_error = $3, updated_at = NOW()
+             WHERE id = $1 AND status = 'running' AND locked_until = $2"
+        )
+        .bind(id)
+        .bind(lease)
+        .bind(error)
+        .bind(retry_at)
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+const OUTBOX_EMAIL_COLUMNS: &str = "id, user_id, submitter_id, kind, transport, from_email, to_email, to_name, subject, raw_message, status, attempts, last_error, last_attempt_at, sent_at, created_at, updated_at";
+
+pub struct EmailOutboxQueries;
+
+impl EmailOutboxQueries {
+    pub async fn create_email(pool: &PgPool, data: CreateOutboxEmail) -> Result<DbOutboxEmail, sqlx::Error> {
+        let row = sqlx::query_as::<_, DbOutboxEmail>(&format!(
+            "INSERT INTO email_outbox (user_id, submitter_id, kind, transport, from_email, to_email, to_name, subject, raw_message)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+             RETURNING {}",
+            OUTBOX_EMAIL_COLUMNS
+        ))
+        .bind(data.user_id)
+        .bind(data.submitter_id)
+        .bind(data.kind)
+        .bind(data.transport)
+        .bind(data.from_email)
+        .bind(data.to_email)
+        .bind(data.to_name)
+        .bind(data.subject)
+        .bind(data.raw_message)
+        .fetch_one(pool)
+        .await?;
+        Ok(row)
+    }
+
+    // Record the outcome of one delivery attempt; a later successful resend clears the error
+    pub async fn record_attempt(pool: &PgPool, id: i64, transport: &str, error: Option<&str>) -> Result<Option<DbOutboxEmail>, sqlx::Error> {
+        let row = sqlx::query_as::<_, DbOutboxEmail>(&format!(
+            "UPDATE email_outbox
+             SET status = CASE WHEN $3::text IS NULL THEN 'sent' ELSE 'failed' END,
+                 attempts = attempts + 1, transport = $2, last_error = $3, last_attempt_at = NOW(),
+                 sent_at = CASE WHEN $3::text IS NULL THEN NOW() ELSE sent_at END, updated_at = NOW()
+             WHERE id = $1
+             RETURNING {}",
+            OUTBOX_EMAIL_COLUMNS
+        ))
+        .bind(id)
+        .bind(transport)
+        .bind(error)
+        .fetch_optional(pool)
+        .await?;
+        Ok(row)
+    }
+
+    pub async fn get_email_by_id(pool: &PgPool, id: i64) -> Result<Option<DbOutboxEmail>, sqlx::Error> {
+        let row = sqlx::query_as::<_, DbOutboxEmail>(&format!("SELECT {} FROM email_outbox WHERE id = $1", OUTBOX_EMAIL_COLUMNS))
+            .bind(id)
+            .fetch_optional(pool)
+            .await?;
+        Ok(row)
+    }
+
+    pub async fn get_emails_by_submitter(pool: &PgPool, submitter_id: i64) -> Result<Vec<DbOutboxEmail>, sqlx::Error> {
+        let rows = sqlx::query_as::<_, DbOutboxEmail>(&format!(
+            "SELECT {} FROM email_outbox WHERE submitter_id = $1 ORDER BY created_at DESC, id DESC",
+            OUTBOX_EMAIL_COLUMNS
+        ))
+        .bind(submitter_id)
+        .fetch_all(pool)
+        .await?;
+        Ok(rows)
+    }
+
+    pub async fn get_emails_by_user(pool: &PgPool, user_id: i64, status: Option<&str>, offset: i64, limit: i64) -> Result<Vec<DbOutboxEmail>, sqlx::Error> {
+        let rows = sqlx::query_as::<_, DbOutboxEmail>(&format!(
+            "SELECT {} FROM email_outbox
+             WHERE user_id = $1 AND ($2::text IS NULL OR status = $2)
+             ORDER BY created_at DESC, id DESC
+             OFFSET $3 LIMIT $4",
+            OUTBOX_EMAIL_COLUMNS
+        ))
+        .bind(user_id)
+        .bind(status)
+        .bind(offset)
+        .bind(limit)
+        .fetch_all(pool)
+        .await?;
+        Ok(rows)
+    }
+
+    /// Drop the rendered message of emails created before `before`; returns how many were cleared
+    pub async fn purge_raw_messages(pool: &PgPool, before: DateTime<Utc>) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query(
+            "UPDATE email_outbox SET raw_message = NULL, updated_at = NOW()
+             WHERE raw_message IS NOT NULL AND status <> 'pending' AND created_at < $1"
+        )
+        .bind(before)
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Lowercased recipients that already received a `kind` email for any of
+    /// the given submitters, optionally only counting sends since `since`.
+    pub async fn get_sent_recipients(
+        pool: &PgPool,
+        kind: &str,
+        submitter_ids: &[i64],
+        since: Option<DateTime<Utc>>,
+    ) -> Result<HashSet<String>, sqlx::Error> {
+        let rows: Vec<(String,)> = sqlx::query_as(
+            "SELECT DISTINCT lower(to_email) FROM email_outbox
+             WHERE kind = $1 AND status = 'sent' AND submitter_id = ANY($2)
+               AND ($3::timestamptz IS NULL OR created_at >= $3)"
+        )
+        .bind(kind)
+        .bind(submitter_ids)
+        .bind(since)
+        .fetch_all(pool)
+        .await?;
+        Ok(rows.into_iter().map(|(email,)| email).collect())
+    }
 }
 
 impl SubmissionFieldQueries {
     pub async fn create_submission_field(pool: &PgPool, field_data: CreateSubmissionField) -> Result<DbSubmissionField, sqlx::Error> {
         let now = Utc::now();
         let row = sqlx::query(
-            "INSERT INTO submission_fields (submitter_id, template_field_id, name, field_type, required, display_order, position, options, metadata, partner, created_at, updated_at)
-             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
-             RETURNING id, submitter_id, template_field_id, name, field_type, required, display_order, position, options, metadata, partner, created_at, updated_at"
+            "INSERT INTO submission_fields (submitter_id, template_field_id, name, field_type, required, display_order, position, options, metadata, partner, conditions, validation, prefilled_value, readonly, created_at, updated_at, role_id)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17)
+             RETURNING id, submitter_id, template_field_id, name, field_type, required, display_order, position, options, metadata, partner, conditions, validation, prefilled_value, readonly, created_at, updated_at, role_id"
         )
         .bind(field_data.submitter_id)
         .bind(field_data.template_field_id)
@@ -2239,8 +3392,13 @@ impl SubmissionFieldQueries {
         .bind(field_data.options)
         .bind(field_data.metadata)
         .bind(field_data.partner)
+        .bind(field_data.conditions)
+        .bind(field_data.validation)
+        .bind(field_data.prefilled_value)
+        .bind(field_data.readonly)
         .bind(now)
         .bind(now)
+        .bind(field_data.role_id)
         .fetch_one(pool)
         .await?;
 
@@ -2256,14 +3414,19 @@ impl SubmissionFieldQueries {
             options: row.get(8),
             metadata: row.get(9),
             partner: row.get(10),
-            created_at: row.get(11),
-            updated_at: row.get(12),
+            conditions: row.get(11),
+            validation: row.get(12),
+            prefilled_value: row.get(13),
+            readonly: row.get(14),
+            created_at: row.get(15),
+            updated_at: row.get(16),
+            role_id: row.get(17),
         })
     }
 
     pub async fn get_submission_fields_by_submitter_id(pool: &PgPool, submitter_id: i64) -> Result<Vec<DbSubmissionField>, sqlx::Error> {
         let rows = sqlx::query(
-            "SELECT id, submitter_id, template_field_id, name, field_type, required, display_order, position, options, metadata, partner, created_at, updated_at
+            "SELECT id, submitter_id, template_field_id, name, field_type, required, display_order, position, options, metadata, partner, conditions, validation, prefilled_value, readonly, created_at, updated_at, role_id
              FROM submission_fields WHERE submitter_id = $1 ORDER BY display_order"
         )
         .bind(submitter_id)
@@ -2284,8 +3447,13 @@ impl SubmissionFieldQueries {
                 options: row.get(8),
                 metadata: row.get(9),
                 partner: row.get(10),
-                created_at: row.get(11),
-                updated_at: row.get(12),
+                conditions: row.get(11),
+                validation: row.get(12),
+                prefilled_value: row.get(13),
+                readonly: row.get(14),
+                created_at: row.get(15),
+                updated_at: row.get(16),
+                role_id: row.get(17),
             });
         }
         Ok(fields)
@@ -2317,7 +3485,7 @@ impl SignatureQueries {
     ) -> Result<Option<DbSubmitter>, sqlx::Error> {
         let row = sqlx::query(
             r#"
-            SELECT id, template_id, user_id, name, email, status, signed_at, token, bulk_signatures, ip_address, user_agent, reminder_config, last_reminder_sent_at, reminder_count, created_at, updated_at, decline_reason, session_id, viewed_at, timezone
+            SELECT id, template_id, user_id, name, email, status, signed_at, token, bulk_signatures, ip_address, user_agent, reminder_config, last_reminder_sent_at, reminder_count, created_at, updated_at, decline_reason, session_id, viewed_at, timezone, submission_id, signing_order, invited_at, role_id, locale
             FROM submitters
             WHERE id = $1 AND bulk_signatures IS NOT NULL
             "#
@@ -2348,6 +3516,11 @@ impl SignatureQueries {
                 session_id: row.get(17),
                 viewed_at: row.get(18),
                 timezone: row.get(19),
+                submission_id: row.get(20),
+                signing_order: row.get(21),
+                invited_at: row.get(22),
+                role_id: row.get(23),
+                locale: row.get(24),
             template_name: None,
             })),
             None => Ok(None),
@@ -2658,7 +3831,7 @@ impl OAuthTokenQueries {
 impl GlobalSettingsQueries {
     pub async fn get_global_settings(pool: &PgPool) -> Result<Option<DbGlobalSettings>, sqlx::Error> {
         let row = sqlx::query(
-            "SELECT id, user_id, company_name, timezone, locale, logo_url, force_2fa_with_authenticator_app, add_signature_id_to_the_documents, require_signing_reason, allow_typed_text_signatures, allow_to_resubmit_completed_forms, allow_to_decline_documents, remember_and_pre_fill_signatures, require_authentication_for_file_download_links, combine_completed_documents_and_audit_log, expirable_file_download_links, enable_confetti, completion_title, completion_body, redirect_title, redirect_url, created_at, updated_at FROM global_settings WHERE user_id IS NULL"
+            "SELECT id, user_id, company_name, timezone, locale, logo_url, force_2fa_with_authenticator_app, add_signature_id_to_the_documents, require_signing_reason, allow_typed_text_signatures, allow_to_resubmit_completed_forms, allow_to_decline_documents, remember_and_pre_fill_signatures, require_authentication_for_file_download_links, combine_completed_documents_and_audit_log, expirable_file_download_links, enable_confetti, completion_title, completion_body, redirect_title, redirect_url, allow_to_delegate_signing, email_from_name, email_reply_to, email_layout, created_at, updated_at FROM global_settings WHERE user_id IS NULL"
         )
         .fetch_optional(pool)
         .await?;
@@ -2687,6 +3860,10 @@ impl GlobalSettingsQueries {
                 completion_body: row.try_get("completion_body")?,
                 redirect_title: row.try_get("redirect_title")?,
                 redirect_url: row.try_get("redirect_url")?,
+                allow_to_delegate_signing: row.try_get("allow_to_delegate_signing")?,
+                email_from_name: row.try_get("email_from_name")?,
+                email_reply_to: row.try_get("email_reply_to")?,
+                email_layout: row.try_get("email_layout")?,
                 created_at: row.try_get("created_at")?,
                 updated_at: row.try_get("updated_at")?,
             })),
@@ -2697,7 +3874,7 @@ impl GlobalSettingsQueries {
     pub async fn get_user_settings(pool: &PgPool, user_id: i32) -> Result<Option<DbGlobalSettings>, sqlx::Error> {
         // First try to get settings by user_id (user-specific settings)
         let user_row = sqlx::query(
-            "SELECT id, user_id, account_id, company_name, timezone, locale, logo_url, force_2fa_with_authenticator_app, add_signature_id_to_the_documents, require_signing_reason, allow_typed_text_signatures, allow_to_resubmit_completed_forms, allow_to_decline_documents, remember_and_pre_fill_signatures, require_authentication_for_file_download_links, combine_completed_documents_and_audit_log, expirable_file_download_links, enable_confetti, completion_title, completion_body, redirect_title, redirect_url, created_at, updated_at FROM global_settings WHERE user_id = $1"
+            "SELECT id, user_id, account_id, company_name, timezone, locale, logo_url, force_2fa_with_authenticator_app, add_signature_id_to_the_documents, require_signing_reason, allow_typed_text_signatures, allow_to_resubmit_completed_forms, allow_to_decline_documents, remember_and_pre_fill_signatures, require_authentication_for_file_download_links, combine_completed_documents_and_audit_log, expirable_file_download_links, enable_confetti, completion_title, completion_body, redirect_title, redirect_url, allow_to_delegate_signing, email_from_name, email_reply_to, email_layout, created_at, updated_at FROM global_settings WHERE user_id = $1"
         )
         .bind(user_id)
         .fetch_optional(pool)
@@ -2727,6 +3904,10 @@ impl GlobalSettingsQueries {
                 completion_body: row.try_get("completion_body")?,
                 redirect_title: row.try_get("redirect_title")?,
                 redirect_url: row.try_get("redirect_url")?,
+                allow_to_delegate_signing: row.try_get("allow_to_delegate_signing")?,
+                email_from_name: row.try_get("email_from_name")?,
+                email_reply_to: row.try_get("email_reply_to")?,
+                email_layout: row.try_get("email_layout")?,
                 created_at: row.try_get("created_at")?,
                 updated_at: row.try_get("updated_at")?,
             }));
@@ -2737,7 +3918,7 @@ impl GlobalSettingsQueries {
         if let Some(user) = user {
             if let Some(acc_id) = user.account_id {
                 let account_row = sqlx::query(
-                    "SELECT id, user_id, account_id, company_name, timezone, locale, logo_url, force_2fa_with_authenticator_app, add_signature_id_to_the_documents, require_signing_reason, allow_typed_text_signatures, allow_to_resubmit_completed_forms, allow_to_decline_documents, remember_and_pre_fill_signatures, require_authentication_for_file_download_links, combine_completed_documents_and_audit_log, expirable_file_download_links, enable_confetti, completion_title, completion_body, redirect_title, redirect_url, created_at, updated_at FROM global_settings WHERE account_id = $1"
+                    "SELECT id, user_id, account_id, company_name, timezone, locale, logo_url, force_2fa_with_authenticator_app, add_signature_id_to_the_documents, require_signing_reason, allow_typed_text_signatures, allow_to_resubmit_completed_forms, allow_to_decline_documents, remember_and_pre_fill_signatures, require_authentication_for_file_download_links, combine_completed_documents_and_audit_log, expirable_file_download_links, enable_confetti, completion_title, completion_body, redirect_title, redirect_url, allow_to_delegate_signing, email_from_name, email_reply_to, email_layout, created_at, updated_at FROM global_settings WHERE account_id = $1"
                 )
                 .bind(acc_id)
                 .fetch_optional(pool)
@@ -2767,6 +3948,10 @@ impl GlobalSettingsQueries {
                         completion_body: row.try_get("completion_body")?,
                         redirect_title: row.try_get("redirect_title")?,
                         redirect_url: row.try_get("redirect_url")?,
+                        allow_to_delegate_signing: row.try_get("allow_to_delegate_signing")?,
+                        email_from_name: row.try_get("email_from_name")?,
+                        email_reply_to: row.try_get("email_reply_to")?,
+                        email_layout: row.try_get("email_layout")?,
                         created_at: row.try_get("created_at")?,
                         updated_at: row.try_get("updated_at")?,
                     }));
@@ -2788,7 +3973,7 @@ impl GlobalSettingsQueries {
         };
 
         // Check if settings already exist for this user
-        let query_str = "SELECT id, user_id, account_id, company_name, timezone, locale, logo_url, force_2fa_with_authenticator_app, add_signature_id_to_the_documents, require_signing_reason, allow_typed_text_signatures, allow_to_resubmit_completed_forms, allow_to_decline_documents, remember_and_pre_fill_signatures, require_authentication_for_file_download_links, combine_completed_documents_and_audit_log, expirable_file_download_links, enable_confetti, completion_title, completion_body, redirect_title, redirect_url, created_at, updated_at FROM global_settings WHERE user_id = $1";
+        let query_str = "SELECT id, user_id, account_id, company_name, timezone, locale, logo_url, force_2fa_with_authenticator_app, add_signature_id_to_the_documents, require_signing_reason, allow_typed_text_signatures, allow_to_resubmit_completed_forms, allow_to_decline_documents, remember_and_pre_fill_signatures, require_authentication_for_file_download_links, combine_completed_documents_and_audit_log, expirable_file_download_links, enable_confetti, completion_title, completion_body, redirect_title, redirect_url, allow_to_delegate_signing, email_from_name, email_reply_to, email_layout, created_at, updated_at FROM global_settings WHERE user_id = $1";
 
         if let Some(existing) = sqlx::query(query_str)
             .bind(user_id)
@@ -2817,6 +4002,10 @@ impl GlobalSettingsQueries {
                 completion_body: existing.try_get("completion_body")?,
                 redirect_title: existing.try_get("redirect_title")?,
                 redirect_url: existing.try_get("redirect_url")?,
+                allow_to_delegate_signing: existing.try_get("allow_to_delegate_signing")?,
+                email_from_name: existing.try_get("email_from_name")?,
+                email_reply_to: existing.try_get("email_reply_to")?,
+                email_layout: existing.try_get("email_layout")?,
                 created_at: existing.try_get("created_at")?,
                 updated_at: existing.try_get("updated_at")?,
             });
@@ -2827,7 +4016,7 @@ impl GlobalSettingsQueries {
             r#"
             INSERT INTO global_settings (user_id, account_id, force_2fa_with_authenticator_app, add_signature_id_to_the_documents, require_signing_reason, allow_typed_text_signatures, allow_to_resubmit_completed_forms, allow_to_decline_documents, remember_and_pre_fill_signatures, require_authentication_for_file_download_links, combine_completed_documents_and_audit_log, expirable_file_download_links, enable_confetti, created_at, updated_at)
             VALUES ($1, $2, false, false, false, true, false, false, false, false, false, false, false, $3, $3)
-            RETURNING id, user_id, account_id, company_name, timezone, locale, logo_url, force_2fa_with_authenticator_app, add_signature_id_to_the_documents, require_signing_reason, allow_typed_text_signatures, allow_to_resubmit_completed_forms, allow_to_decline_documents, remember_and_pre_fill_signatures, require_authentication_for_file_download_links, combine_completed_documents_and_audit_log, expirable_file_download_links, enable_confetti, NULL as completion_title, NULL as completion_body, NULL as redirect_title, NULL as redirect_url, created_at, updated_at
+            RETURNING id, user_id, account_id, company_name, timezone, locale, logo_url, force_2fa_with_authenticator_app, add_signature_id_to_the_documents, require_signing_reason, allow_typed_text_signatures, allow_to_resubmit_completed_forms, allow_to_decline_documents, remember_and_pre_fill_signatures, require_authentication_for_file_download_links, combine_completed_documents_and_audit_log, expirable_file_download_links, enable_confetti, NULL as completion_title, NULL as completion_body, NULL as redirect_title, NULL as redirect_url, allow_to_delegate_signing, email_from_name, email_reply_to, email_layout, created_at, updated_at
             "#
         )
         .bind(user_id)
@@ -2859,6 +4048,10 @@ impl GlobalSettingsQueries {
             completion_body: row.try_get("completion_body")?,
             redirect_title: row.try_get("redirect_title")?,
             redirect_url: row.try_get("redirect_url")?,
+            allow_to_delegate_signing: row.try_get("allow_to_delegate_signing")?,
+            email_from_name: row.try_get("email_from_name")?,
+            email_reply_to: row.try_get("email_reply_to")?,
+            email_layout: row.try_get("email_layout")?,
             created_at: row.try_get("created_at")?,
             updated_at: row.try_get("updated_at")?,
         })
@@ -2877,7 +4070,11 @@ impl GlobalSettingsQueries {
                 remember_and_pre_fill_signatures = $11, require_authentication_for_file_download_links = $12, 
                 combine_completed_documents_and_audit_log = $13, expirable_file_download_links = $14,
                 enable_confetti = $15,
-                updated_at = $16
+                allow_to_delegate_signing = COALESCE($16, allow_to_delegate_signing),
+                email_from_name = CASE WHEN $17 IS NULL THEN email_from_name ELSE NULLIF($17, '') END,
+                email_reply_to = CASE WHEN $18 IS NULL THEN email_reply_to ELSE NULLIF($18, '') END,
+                email_layout = CASE WHEN $19 IS NULL THEN email_layout ELSE NULLIF($19, '') END,
+                updated_at = $20
             WHERE user_id IS NULL
             "#
         )
@@ -2896,6 +4093,10 @@ impl GlobalSettingsQueries {
         .bind(settings.combine_completed_documents_and_audit_log)
         .bind(settings.expirable_file_download_links)
         .bind(settings.enable_confetti)
+        .bind(settings.allow_to_delegate_signing)
+        .bind(settings.email_from_name.as_deref())
+        .bind(settings.email_reply_to.as_deref())
+        .bind(settings.email_layout.as_deref())
         .bind(now)
         .execute(pool)
         .await?;
@@ -2939,9 +4140,13 @@ impl GlobalSettingsQueries {
                     completion_body = COALESCE($19, completion_body),
                     redirect_title = COALESCE($20, redirect_title),
                     redirect_url = COALESCE($21, redirect_url),
-                    updated_at = $22
+                    allow_to_delegate_signing = COALESCE($22, allow_to_delegate_signing),
+                    email_from_name = CASE WHEN $23 IS NULL THEN email_from_name ELSE NULLIF($23, '') END,
+                    email_reply_to = CASE WHEN $24 IS NULL THEN email_reply_to ELSE NULLIF($24, '') END,
+                    email_layout = CASE WHEN $25 IS NULL THEN email_layout ELSE NULLIF($25, '') END,
+                    updated_at = $26
                 WHERE account_id = $2
-                RETURNING id, user_id, account_id, company_name, timezone, locale, logo_url, force_2fa_with_authenticator_app, add_signature_id_to_the_documents, require_signing_reason, allow_typed_text_signatures, allow_to_resubmit_completed_forms, allow_to_decline_documents, remember_and_pre_fill_signatures, require_authentication_for_file_download_links, combine_completed_documents_and_audit_log, expirable_file_download_links, enable_confetti, completion_title, completion_body, redirect_title, redirect_url, created_at, updated_at
+                RETURNING id, user_id, account_id, company_name, timezone, locale, logo_url, force_2fa_with_authenticator_app, add_signature_id_to_the_documents, require_signing_reason, allow_typed_text_signatures, allow_to_resubmit_completed_forms, allow_to_decline_documents, remember_and_pre_fill_signatures, require_authentication_for_file_download_links, combine_completed_documents_and_audit_log, expirable_file_download_links, enable_confetti, completion_title, completion_body, redirect_title, redirect_url, allow_to_delegate_signing, email_from_name, email_reply_to, email_layout, created_at, updated_at
                 "#
             )
             .bind(user_id)
@@ -2965,6 +4170,10 @@ impl GlobalSettingsQueries {
             .bind(settings.completion_body.as_deref())
             .bind(settings.redirect_title.as_deref())
             .bind(settings.redirect_url.as_deref())
+            .bind(settings.allow_to_delegate_signing)
+            .bind(settings.email_from_name.as_deref())
+            .bind(settings.email_reply_to.as_deref())
+            .bind(settings.email_layout.as_deref())
             .bind(now)
             .fetch_optional(pool)
             .await?;
@@ -2975,9 +4184,9 @@ impl GlobalSettingsQueries {
                 // No existing row found, INSERT new one
                 sqlx::query(
                     r#"
-                    INSERT INTO global_settings (user_id, account_id, company_name, timezone, locale, logo_url, force_2fa_with_authenticator_app, add_signature_id_to_the_documents, require_signing_reason, allow_typed_text_signatures, allow_to_resubmit_completed_forms, allow_to_decline_documents, remember_and_pre_fill_signatures, require_authentication_for_file_download_links, combine_completed_documents_and_audit_log, expirable_file_download_links, enable_confetti, completion_title, completion_body, redirect_title, redirect_url, created_at, updated_at)
-                    VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22, $23)
-                    RETURNING id, user_id, account_id, company_name, timezone, locale, logo_url, force_2fa_with_authenticator_app, add_signature_id_to_the_documents, require_signing_reason, allow_typed_text_signatures, allow_to_resubmit_completed_forms, allow_to_decline_documents, remember_and_pre_fill_signatures, require_authentication_for_file_download_links, combine_completed_documents_and_audit_log, expirable_file_download_links, enable_confetti, completion_title, completion_body, redirect_title, redirect_url, created_at, updated_at
+                    INSERT INTO global_settings (user_id, account_id, company_name, timezone, locale, logo_url, force_2fa_with_authenticator_app, add_signature_id_to_the_documents, require_signing_reason, allow_typed_text_signatures, allow_to_resubmit_completed_forms, allow_to_decline_documents, remember_and_pre_fill_signatures, require_authentication_for_file_download_links, combine_completed_documents_and_audit_log, expirable_file_download_links, enable_confetti, completion_title, completion_body, redirect_title, redirect_url, allow_to_delegate_signing, email_from_name, email_reply_to, email_layout, created_at, updated_at)
+                    VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22, NULLIF($23, ''), NULLIF($24, ''), NULLIF($25, ''), $26, $27)
+                    RETURNING id, user_id, account_id, company_name, timezone, locale, logo_url, force_2fa_with_authenticator_app, add_signature_id_to_the_documents, require_signing_reason, allow_typed_text_signatures, allow_to_resubmit_completed_forms, allow_to_decline_documents, remember_and_pre_fill_signatures, require_authentication_for_file_download_links, combine_completed_documents_and_audit_log, expirable_file_download_links, enable_confetti, completion_title, completion_body, redirect_title, redirect_url, allow_to_delegate_signing, email_from_name, email_reply_to, email_layout, created_at, updated_at
                     "#
                 )
                 .bind(user_id)
@@ -3001,6 +4210,10 @@ impl GlobalSettingsQueries {
                 .bind(settings.completion_body.as_deref())
                 .bind(settings.redirect_title.as_deref())
                 .bind(settings.redirect_url.as_deref())
+                .bind(settings.allow_to_delegate_signing.unwrap_or(false))
+                .bind(settings.email_from_name.as_deref())
+                .bind(settings.email_reply_to.as_deref())
+                .bind(settings.email_layout.as_deref())
                 .bind(now)
                 .bind(now)
                 .fetch_one(pool)
@@ -3030,9 +4243,13 @@ impl GlobalSettingsQueries {
                         completion_body = COALESCE($18, completion_body),
                         redirect_title = COALESCE($19, redirect_title),
                         redirect_url = COALESCE($20, redirect_url),
-                        updated_at = $21
+                        allow_to_delegate_signing = COALESCE($21, allow_to_delegate_signing),
+                        email_from_name = CASE WHEN $22 IS NULL THEN email_from_name ELSE NULLIF($22, '') END,
+                        email_reply_to = CASE WHEN $23 IS NULL THEN email_reply_to ELSE NULLIF($23, '') END,
+                        email_layout = CASE WHEN $24 IS NULL THEN email_layout ELSE NULLIF($24, '') END,
+                        updated_at = $25
                     WHERE user_id = $1 AND account_id IS NULL
-                    RETURNING id, user_id, account_id, company_name, timezone, locale, logo_url, force_2fa_with_authenticator_app, add_signature_id_to_the_documents, require_signing_reason, allow_typed_text_signatures, allow_to_resubmit_completed_forms, allow_to_decline_documents, remember_and_pre_fill_signatures, require_authentication_for_file_download_links, combine_completed_documents_and_audit_log, expirable_file_download_links, enable_confetti, completion_title, completion_body, redirect_title, redirect_url, created_at, updated_at
+                    RETURNING id, user_id, account_id, company_name, timezone, locale, logo_url, force_2fa_with_authenticator_app, add_signature_id_to_the_documents, require_signing_reason, allow_typed_text_signatures, allow_to_resubmit_completed_forms, allow_to_decline_documents, remember_and_pre_fill_signatures, require_authentication_for_file_download_links, combine_completed_documents_and_audit_log, expirable_file_download_links, enable_confetti, completion_title, completion_body, redirect_title, redirect_url, allow_to_delegate_signing, email_from_name, email_reply_to, email_layout, created_at, updated_at
                     "#
                 )
                 .bind(user_id)
@@ -3055,6 +4272,10 @@ impl GlobalSettingsQueries {
                 .bind(settings.completion_body.as_deref())
                 .bind(settings.redirect_title.as_deref())
                 .bind(settings.redirect_url.as_deref())
+                .bind(settings.allow_to_delegate_signing)
+                .bind(settings.email_from_name.as_deref())
+                .bind(settings.email_reply_to.as_deref())
+                .bind(settings.email_layout.as_deref())
                 .bind(now)
                 .fetch_optional(pool)
                 .await?;
@@ -3065,9 +4286,9 @@ impl GlobalSettingsQueries {
                     // No existing row found, INSERT new one
                     sqlx::query(
                         r#"
-                        INSERT INTO global_settings (user_id, account_id, company_name, timezone, locale, logo_url, force_2fa_with_authenticator_app, add_signature_id_to_the_documents, require_signing_reason, allow_typed_text_signatures, allow_to_resubmit_completed_forms, allow_to_decline_documents, remember_and_pre_fill_signatures, require_authentication_for_file_download_links, combine_completed_documents_and_audit_log, expirable_file_download_links, enable_confetti, completion_title, completion_body, redirect_title, redirect_url, created_at, updated_at)
-                        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22, $23)
-                        RETURNING id, user_id, account_id, company_name, timezone, locale, logo_url, force_2fa_with_authenticator_app, add_signature_id_to_the_documents, require_signing_reason, allow_typed_text_signatures, allow_to_resubmit_completed_forms, allow_to_decline_documents, remember_and_pre_fill_signatures, require_authentication_for_file_download_links, combine_completed_documents_and_audit_log, expirable_file_download_links, enable_confetti, completion_title, completion_body, redirect_title, redirect_url, created_at, updated_at
+                        INSERT INTO global_settings (user_id, account_id, company_name, timezone, locale, logo_url, force_2fa_with_authenticator_app, add_signature_id_to_the_documents, require_signing_reason, allow_typed_text_signatures, allow_to_resubmit_completed_forms, allow_to_decline_documents, remember_and_pre_fill_signatures, require_authentication_for_file_download_links, combine_completed_documents_and_audit_log, expirable_file_download_links, enable_confetti, completion_title, completion_body, redirect_title, redirect_url, allow_to_delegate_signing, email_from_name, email_reply_to, email_layout, created_at, updated_at)
+                        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22, NULLIF($23, ''), NULLIF($24, ''), NULLIF($25, ''), $26, $27)
+                        RETURNING id, user_id, account_id, company_name, timezone, locale, logo_url, force_2fa_with_authenticator_app, add_signature_id_to_the_documents, require_signing_reason, allow_typed_text_signatures, allow_to_resubmit_completed_forms, allow_to_decline_documents, remember_and_pre_fill_signatures, require_authentication_for_file_download_links, combine_completed_documents_and_audit_log, expirable_file_download_links, enable_confetti, completion_title, completion_body, redirect_title, redirect_url, allow_to_delegate_signing, email_from_name, email_reply_to, email_layout, created_at, updated_at
                         "#
                     )
                     .bind(user_id)
@@ -3091,6 +4312,10 @@ impl GlobalSettingsQueries {
                     .bind(settings.completion_body.as_deref())
                     .bind(settings.redirect_title.as_deref())
                     .bind(settings.redirect_url.as_deref())
+                    .bind(settings.allow_to_delegate_signing.unwrap_or(false))
+                    .bind(settings.email_from_name.as_deref())
+                    .bind(settings.email_reply_to.as_deref())
+                    .bind(settings.email_layout.as_deref())
                     .bind(now)
                     .bind(now)
                     .fetch_one(pool)
@@ -3121,6 +4346,10 @@ impl GlobalSettingsQueries {
                 completion_body: row.try_get("completion_body")?,
                 redirect_title: row.try_get("redirect_title")?,
                 redirect_url: row.try_get("redirect_url")?,
+                allow_to_delegate_signing: row.try_get("allow_to_delegate_signing")?,
+                email_from_name: row.try_get("email_from_name")?,
+                email_reply_to: row.try_get("email_reply_to")?,
+                email_layout: row.try_get("email_layout")?,
                 created_at: row.try_get("created_at")?,
                 updated_at: row.try_get("updated_at")?,
             })
@@ -22,6 +22,9 @@ use routes::web::{create_router, AppState, AppStateData};
 use database::connection::{establish_connection, run_migrations};
 use services::queue::PaymentQueue;
 use services::reminder_queue::ReminderQueue;
+use services::jobs::{self, JobWorker};
+use services::expiry::ExpirySweeper;
+use services::webhooks::WebhookQueue;
 use models::user::User;
 use models::template::Template;
 
@@ -60,22 +63,47 @@ use models::template::Template;
         routes::templates::download_file,
         routes::templates::preview_file,
         routes::templates::get_template_fields,
+        routes::templates::get_template_roles,
+        routes::templates::create_template_role,
+        routes::templates::update_template_role,
+        routes::templates::delete_template_role,
         routes::templates::create_template_field,
         routes::templates::upload_template_field_file,
         routes::templates::update_template_field,
         routes::templates::delete_template_field,
         routes::submissions::create_submission,
+        routes::submissions::get_submissions,
+        routes::submissions::get_submission,
+        routes::submissions::archive_submission,
+        routes::submissions::extend_submission,
+        routes::submissions::void_submission,
+        routes::submissions::download_signed_document,
+        routes::submissions::create_bulk_send,
+        routes::submissions::get_bulk_send,
         routes::submitters::get_public_submitter_fields,
         routes::submitters::get_public_submitter_signatures,
         routes::submitters::get_public_submitter,
         routes::submitters::update_public_submitter,
         routes::submitters::submit_bulk_signatures,
+        routes::submitters::track_email_open,
         routes::submitters::get_submitters,
         routes::submitters::get_submitter,
         routes::submitters::update_submitter,
         routes::submitters::delete_submitter,
+        routes::submitters::reassign_submitter,
+        routes::submitters::delegate_submitter,
         routes::submitters::get_me,
         routes::submitters::get_submitter_audit_log,
+        routes::webhooks::get_webhooks,
+        routes::webhooks::create_webhook,
+        routes::webhooks::update_webhook,
+        routes::webhooks::delete_webhook,
+        routes::webhooks::get_webhook_deliveries,
+        routes::webhooks::get_webhook_delivery,
+        routes::webhooks::redeliver_webhook,
+        routes::emails::get_emails,
+        routes::emails::get_submitter_emails,
+        routes::emails::resend_email,
         routes::reminder_settings::get_reminder_settings,
         routes::reminder_settings::update_reminder_settings,
         routes::global_settings::get_user_settings,
@@ -100,14 +128,37 @@ use models::template::Template;
             common::responses::ApiResponse<serde_json::Value>,
             models::submitter::Submitter,
             common::responses::ApiResponse<Vec<models::submitter::Submitter>>,
+            models::submission::Submission,
+            models::submission::CreateSubmissionRequest,
+            models::submission::ExtendSubmissionRequest,
+            models::submission::VoidSubmissionRequest,
+            models::submission::SignedDocument,
+            models::submission::BulkSend,
+            models::submission::BulkSendColumn,
+            models::submission::BulkSendRowResult,
+            models::submission::BulkSendUpload,
+            common::responses::ApiResponse<models::submission::Submission>,
+            common::responses::ApiResponse<Vec<models::submission::Submission>>,
+            common::responses::ApiResponse<models::submission::BulkSend>,
+            common::responses::ApiResponse<Vec<models::submission::BulkSendRowResult>>,
             common::responses::ApiResponse<String>,
             common::responses::ApiResponse<Vec<models::template::TemplateField>>,
             common::responses::ApiResponse<models::template::TemplateField>,
+            common::responses::ApiResponse<Vec<models::template::TemplateRole>>,
+            common::responses::ApiResponse<models::template::TemplateRole>,
             common::responses::ApiResponse<Vec<models::user::TeamMember>>,
             models::user::TeamMember,
             models::template::CreateTemplateFieldRequest,
             models::template::UpdateTemplateFieldRequest,
+            models::template::TemplateRole,
+            models::template::CreateTemplateRoleRequest,
+            models::template::UpdateTemplateRoleRequest,
             models::template::FieldPosition,
+            models::template::FieldCondition,
+            models::template::ConditionAction,
+            models::template::ConditionOperator,
+            models::template::FieldValidation,
+            models::template::ValueFormat,
             models::template::TemplateField,
             models::template::TemplateFolder,
             models::template::CreateFolderRequest,
@@ -115,9 +166,23 @@ use models::template::Template;
             models::submitter::PublicSubmitterFieldsResponse,
             models::submitter::PublicSubmitterSignaturesResponse,
             models::submitter::ReminderConfig,
+            models::submitter::PrefilledFieldValue,
+            models::submitter::ReassignSubmitterRequest,
             routes::reminder_settings::UserReminderSettingsResponse,
             routes::reminder_settings::UpdateReminderSettingsRequest,
             common::responses::ApiResponse<routes::reminder_settings::UserReminderSettingsResponse>,
+            models::webhook::WebhookEndpoint,
+            models::webhook::CreateWebhookRequest,
+            models::webhook::UpdateWebhookRequest,
+            models::webhook::WebhookDelivery,
+            models::webhook::WebhookDeliveryAttempt,
+            common::responses::ApiResponse<models::webhook::WebhookEndpoint>,
+            common::responses::ApiResponse<Vec<models::webhook::WebhookEndpoint>>,
+            common::responses::ApiResponse<models::webhook::WebhookDelivery>,
+            common::responses::ApiResponse<Vec<models::webhook::WebhookDelivery>>,
+            models::email_outbox::OutboxEmail,
+            common::responses::ApiResponse<models::email_outbox::OutboxEmail>,
+            common::responses::ApiResponse<Vec<models::email_outbox::OutboxEmail>>,
             database::models::DbGlobalSettings
         )
     ),
@@ -127,7 +192,9 @@ use models::template::Template;
         (name = "templates", description = "Template management endpoints"),
         (name = "template_fields", description = "Template field management endpoints"),
         (name = "submissions", description = "Document submission endpoints"),
-        (name = "submitters", description = "Submitter management endpoints")
+        (name = "submitters", description = "Submitter management endpoints"),
+        (name = "webhooks", description = "Outgoing webhook endpoints and delivery logs"),
+        (name = "emails", description = "Outgoing email outbox, delivery status and resend")
     ),
     security(("bearer_auth" = [])),
 )]
@@ -173,13 +240,13 @@ async fn main() {
     let otp_cache = crate::services::cache::OtpCache::new();
     
     // Initialize email service for reminders
-    let email_service = match crate::services::email::EmailService::new() {
+    let email_service = match crate::services::email::EmailService::new(&pool) {
         Ok(service) => service,
         Err(e) => {
             eprintln!("⚠️  Warning: Failed to initialize email service: {}", e);
             eprintln!("⚠️  Reminder emails will not be sent. Please configure SMTP settings.");
             // Create a fallback - we'll handle this gracefully in the reminder queue
-            crate::services::email::EmailService::new().unwrap_or_else(|_| {
+            crate::services::email::EmailService::new(&pool).unwrap_or_else(|_| {
                 panic!("Email service is required for the application to run");
             })
         }
@@ -187,7 +254,15 @@ async fn main() {
     
     // Get base URL for signature links
     let base_url = std::env::var("BASE_URL").unwrap_or_else(|_| "http://localhost:8080".to_string());
-    let reminder_queue = ReminderQueue::new(db_pool_arc.clone(), email_service, base_url);
+    let expiry_sweeper = ExpirySweeper::new(db_pool_arc.clone(), email_service.clone());
+    let reminder_queue = ReminderQueue::new(email_service, base_url);
+    let job_worker = JobWorker::new(db_pool_arc.clone(), 5)
+        .with_handler(jobs::JOB_RECORD_PAYMENT, Arc::new(services::queue::record_payment_job))
+        .with_handler(jobs::JOB_SEND_REMINDER, reminder_queue.handler())
+        .with_handler(jobs::JOB_AUTO_SIGN, Arc::new(routes::submitters::auto_sign_job))
+        .with_handler(jobs::JOB_COMPLETION_EMAILS, Arc::new(routes::submitters::completion_emails_job))
+        .with_handler(jobs::JOB_BULK_SEND, Arc::new(routes::submissions::bulk_send_job))
+        .with_handler(jobs::JOB_PURGE_EMAIL_BODIES, Arc::new(services::email::purge_email_bodies_job));
     
     let app_state_data = AppStateData {
         db_pool: pool,
@@ -196,19 +271,23 @@ async fn main() {
     };
     let app_state: AppState = Arc::new(Mutex::new(app_state_data));
 
-    // Start the payment queue processor
-    let payment_queue_clone = payment_queue.clone();
+    // Start the job worker (payments, reminders, final PDFs, completion emails)
     tokio::spawn(async move {
-        payment_queue_clone.process_parallel(5).await; // Xử lý tối đa 5 payment cùng lúc
+        job_worker.start_processing().await;
     });
 
-    // Start the reminder queue processor
-    let reminder_queue_clone = reminder_queue.clone();
+    // Start the submission expiry sweeper
     tokio::spawn(async move {
-        reminder_queue_clone.start_processing().await;
+        expiry_sweeper.start_processing().await;
+    });
+
+    // Start the webhook delivery queue processor
+    let webhook_queue = WebhookQueue::new(db_pool_arc.clone());
+    tokio::spawn(async move {
+        webhook_queue.start_processing().await;
     });
     
-    println!("✅ Background services started (Payment Queue, Reminder Queue)");
+    println!("✅ Background services started (Job Worker, Expiry Sweeper, Webhook Queue)");
 
     // Create API routes
     let api_routes = create_router();
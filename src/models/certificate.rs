@@ -106,6 +106,10 @@ pub struct PDFSignatureSettings {
     pub account_id: Option<i64>,
     pub filename_format: String,
     pub default_certificate_id: Option<i64>,
+    pub tsa_enabled: bool,
+    pub tsa_url: Option<String>,
+    pub signature_profile: String,
+    pub field_font: String,
     pub created_at: Option<NaiveDateTime>,
     pub updated_at: Option<NaiveDateTime>,
 }
@@ -114,6 +118,10 @@ pub struct PDFSignatureSettings {
 pub struct UpdatePDFSignatureSettings {
     pub filename_format: Option<String>,
     pub default_certificate_id: Option<i64>,
+    pub tsa_enabled: Option<bool>,
+    pub tsa_url: Option<String>,
+    pub signature_profile: Option<String>,
+    pub field_font: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -164,6 +172,22 @@ pub struct PDFSignatureDetails {
     pub is_valid: bool,
     pub is_trusted: bool,
     pub trusted_certificate_name: Option<String>, // Name of the matched trusted certificate
+    pub timestamp_time: Option<DateTime<Utc>>, // RFC 3161 timestamp, if the signature carries one
+    pub timestamp_authority: Option<CertificateBasicInfo>,
+    pub timestamp_valid: Option<bool>,
+    pub revision_count: usize, // Incremental-update revisions in the file
+    pub signatures: Vec<SignatureRevisionInfo>,
+}
+
+/// One signature and the document revision it covers
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SignatureRevisionInfo {
+    pub field_name: Option<String>,
+    pub signer_name: Option<String>,
+    pub signature_subfilter: Option<String>,
+    pub revision: Option<usize>, // 1-based; None when the ByteRange does not end on a revision boundary
+    pub covers_whole_document: bool,
+    pub is_intact: bool, // ByteRange ends on a revision and the signed digest matches
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct OutboxEmail {
+    pub id: i64,
+    pub submitter_id: Option<i64>,
+    pub kind: String,
+    pub transport: String,
+    pub to_email: String,
+    pub to_name: Option<String>,
+    pub subject: String,
+    pub status: String, // pending, sent, failed
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub last_attempt_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub sent_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<crate::database::models::DbOutboxEmail> for OutboxEmail {
+    fn from(db_email: crate::database::models::DbOutboxEmail) -> Self {
+        OutboxEmail {
+            id: db_email.id,
+            submitter_id: db_email.submitter_id,
+            kind: db_email.kind,
+            transport: db_email.transport,
+            to_email: db_email.to_email,
+            to_name: db_email.to_name,
+            subject: db_email.subject,
+            status: db_email.status,
+            attempts: db_email.attempts,
+            last_error: db_email.last_error,
+            last_attempt_at: db_email.last_attempt_at,
+            sent_at: db_email.sent_at,
+            created_at: db_email.created_at,
+        }
+    }
+}
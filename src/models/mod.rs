@@ -6,4 +6,6 @@ pub mod signature;
 pub mod role;
 pub mod email_template;
 pub mod account;
-pub mod certificate;
\ No newline at end of file
+pub mod certificate;
+pub mod webhook;
+pub mod email_outbox;
\ No newline at end of file
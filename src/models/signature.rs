@@ -96,6 +96,15 @@ pub struct BulkSignatureItem {
     pub reason: Option<String>,
 }
 
+/// One rejected field of a bulk signature request
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct FieldValidationError {
+    pub field_id: i64,
+    pub field_name: String,
+    pub code: String, // required, pattern, min_length, max_length, number, min, max, date_format, email, phone
+    pub message: String,
+}
+
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct SignatureInfo {
     pub submitter_id: i64,
@@ -9,7 +9,10 @@ pub struct Submission {
     pub id: i64,
     pub template_id: i64,
     pub user_id: i64,
-    pub status: String, // pending, completed, expired
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub status: String, // pending, completed, expired, declined, voided
+    pub signing_mode: String, // parallel, sequential
     #[serde(skip_serializing_if = "Option::is_none")]
     pub documents: Option<Vec<Document>>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -18,6 +21,28 @@ pub struct Submission {
     pub updated_at: DateTime<Utc>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub expires_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub completed_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archived_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signed_document: Option<SignedDocument>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub voided_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub void_reason: Option<String>,
+}
+
+/// Final signed PDF of a completed submission
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SignedDocument {
+    pub sha256: String,
+    pub size: i64,
+    /// Whether the PDF carries a digital (PKCS#7) signature
+    pub digitally_signed: bool,
+    pub created_at: Option<DateTime<Utc>>,
+    /// Authenticated download URL
+    pub url: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -38,10 +63,90 @@ pub struct CreateSubmissionRequest {
     pub name: Option<String>,
     pub submitters: Vec<CreateSubmitterRequest>,
     pub expires_at: Option<DateTime<Utc>>,
+    /// "parallel" (default) invites everyone at once, "sequential" invites submitters by their order
+    #[serde(default)]
+    pub signing_mode: Option<String>,
+}
+
+/// Reason for voiding a submission, included in the cancellation email
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct VoidSubmissionRequest {
+    pub reason: String,
+}
+
+/// New expiry for a pending or expired submission
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ExtendSubmissionRequest {
+    pub expires_at: DateTime<Utc>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct UpdateSubmissionRequest {
     pub status: Option<String>,
     pub submitters: Option<Vec<Submitter>>,
-}
\ No newline at end of file
+}
+/// Where a spreadsheet column goes when bulk sending a template
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BulkSendColumn {
+    /// Column header as it appears in the first row of the file
+    pub column: String,
+    /// Submitter role (the template fields' partner); defaults to the field's partner or the first role
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role: Option<String>,
+    /// "name", "email" or "field"
+    pub target: String,
+    /// Template field filled from this column when target is "field"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub field_name: Option<String>,
+    /// The signer sees the value but cannot change it
+    #[serde(default)]
+    pub readonly: bool,
+}
+
+/// Multipart form of `POST /api/submissions/bulk`; only used for the API docs
+#[allow(dead_code)]
+#[derive(ToSchema)]
+pub struct BulkSendUpload {
+    /// CSV or XLSX file whose first row holds the column headers
+    #[schema(value_type = String, format = Binary)]
+    pub file: Vec<u8>,
+    pub template_id: i64,
+    /// JSON array of BulkSendColumn; by default `Email`/`Name`, `<Role> Email`/`<Role> Name`
+    /// and template field names are matched against the headers
+    pub mapping: Option<String>,
+    pub name: Option<String>,
+    pub signing_mode: Option<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// Background job sending one submission per spreadsheet row
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BulkSend {
+    pub id: i64,
+    pub template_id: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filename: Option<String>,
+    pub status: String, // pending, processing, completed, failed
+    pub total_rows: i32,
+    pub processed_rows: i32,
+    pub succeeded_rows: i32,
+    pub failed_rows: i32,
+    pub results: Vec<BulkSendRowResult>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+/// Outcome of one spreadsheet row of a bulk send
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct BulkSendRowResult {
+    /// Row number in the spreadsheet; the header is row 1
+    pub row: i32,
+    pub status: String, // invalid, created, failed
+    pub emails: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub submission_id: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
@@ -34,10 +34,20 @@ impl Default for ReminderConfig {
 pub struct Submitter {
     pub id: Option<i64>,
     pub template_id: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub submission_id: Option<i64>,
     pub user_id: Option<i64>,
     pub name: String,
     pub email: String,
-    pub status: String, // pending, sent, viewed, signed, completed, declined
+    pub status: String, // waiting, pending, sent, viewed, signed, completed, declined
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signing_order: Option<i32>,
+    /// Template role this submitter signs as
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub role_id: Option<i64>,
+    /// Language of the submitter's emails and signing page errors; None uses the account locale
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
     pub signed_at: Option<DateTime<Utc>>,
     pub token: String, // unique token for access
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -71,12 +81,51 @@ pub struct UpdateSubmitterRequest {
 pub struct PublicUpdateSubmitterRequest {
 }
 
+/// Person who takes over a submitter's fields and signing turn
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ReassignSubmitterRequest {
+    pub name: String,
+    pub email: String,
+    /// Recorded in the audit trail
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct CreateSubmitterRequest {
     pub name: String,
     pub email: String,
+    /// Signing order for sequential submissions; equal values sign in parallel (default: position in the list)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order: Option<i32>,
+    /// Template role to sign as, by id or by name; submitters without one take the
+    /// template's remaining roles in order
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role_id: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role: Option<String>,
+    /// Language of the submitter's emails, e.g. "fr" or "de-DE" (default: the account locale)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub reminder_config: Option<ReminderConfig>,
+    /// Field values filled in by the sender, e.g. from a CRM
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub values: Vec<PrefilledFieldValue>,
+}
+
+/// Value the sender fills in for one of the template fields; the field is
+/// matched by `field_id` or, when that is absent, by `field_name`
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PrefilledFieldValue {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub field_id: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub field_name: Option<String>,
+    pub value: String,
+    /// The signer sees the value but cannot change it
+    #[serde(default)]
+    pub readonly: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
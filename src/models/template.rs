@@ -42,6 +42,8 @@ pub struct Template {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub template_fields: Option<Vec<TemplateField>>, // New: fields from separate table
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub roles: Option<Vec<TemplateRole>>, // Signing parties the fields are assigned to
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub submitters: Option<Vec<Submitter>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub documents: Option<Vec<Document>>,
@@ -59,6 +61,78 @@ pub struct FieldPosition {
     pub default_value: Option<String>, // Default value content for the field
 }
 
+/// What a condition does to the field it is attached to
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ConditionAction {
+    /// The field is only shown (and only drawn) while the condition holds
+    #[default]
+    Show,
+    /// The field becomes required while the condition holds
+    Require,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ConditionOperator {
+    Checked,
+    Unchecked,
+    Equals,
+    NotEquals,
+    /// One of the comma separated values of a multiple-choice field
+    Contains,
+    Empty,
+    NotEmpty,
+}
+
+/// Declarative rule on another field of the same template, e.g.
+/// `{"field": "Has company", "operator": "checked", "action": "require"}`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct FieldCondition {
+    /// Name of the controlling field
+    pub field: String,
+    #[serde(default)]
+    pub action: ConditionAction,
+    pub operator: ConditionOperator,
+    /// Compared value for `equals`, `not_equals` and `contains`
+    #[serde(default)]
+    pub value: Option<String>,
+}
+
+/// Built-in formats a text value can be checked against
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ValueFormat {
+    Email,
+    Phone,
+}
+
+/// Server-side rules a submitted value must satisfy; empty values are only
+/// checked by `required`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct FieldValidation {
+    /// Regular expression the whole value must match
+    #[serde(default)]
+    pub pattern: Option<String>,
+    #[serde(default)]
+    pub min_length: Option<usize>,
+    #[serde(default)]
+    pub max_length: Option<usize>,
+    /// Numeric bounds (inclusive); the value must then be a number
+    #[serde(default)]
+    pub min: Option<f64>,
+    #[serde(default)]
+    pub max: Option<f64>,
+    /// strftime-style format such as `%d/%m/%Y`
+    #[serde(default)]
+    pub date_format: Option<String>,
+    #[serde(default)]
+    pub format: Option<ValueFormat>,
+    /// Replaces the generated error message
+    #[serde(default)]
+    pub message: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct TemplateField {
     pub id: i64,
@@ -69,11 +143,44 @@ pub struct TemplateField {
     pub display_order: i32,
     pub position: Option<FieldPosition>,
     pub options: Option<Value>, // for select/radio fields
-    pub partner: Option<String>, // Which partner/signer this field belongs to
+    pub partner: Option<String>, // Name of the field's role, kept for older clients
+    /// Role whose submitter fills this field; fields without a role can be filled by anyone
+    #[serde(default)]
+    pub role_id: Option<i64>,
+    #[serde(default)]
+    pub conditions: Vec<FieldCondition>,
+    #[serde(default)]
+    pub validation: Option<FieldValidation>,
+    /// Set on a submitter's fields whose value was prefilled and locked by the sender
+    #[serde(default)]
+    pub readonly: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+/// Signing party of a template, e.g. "Buyer" or "Seller"
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TemplateRole {
+    pub id: i64,
+    pub template_id: i64,
+    pub name: String,
+    pub display_order: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CreateTemplateRoleRequest {
+    pub name: String,
+    pub display_order: Option<i32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct UpdateTemplateRoleRequest {
+    pub name: Option<String>,
+    pub display_order: Option<i32>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct Submitter {
     pub name: String,
@@ -114,8 +221,14 @@ pub struct CreateTemplateFieldRequest {
     pub display_order: Option<i32>,
     pub position: Option<FieldPosition>,
     pub options: Option<Value>,
-    pub partner: Option<String>, // Which partner/signer this field belongs to
+    pub partner: Option<String>, // Role name; used (and created if missing) when role_id is not given
+    #[serde(default)]
+    pub role_id: Option<i64>, // Role whose submitter fills this field
     pub default_value: Option<String>, // Default value for the field
+    #[serde(default)]
+    pub conditions: Option<Vec<FieldCondition>>, // Show/require rules on other fields
+    #[serde(default)]
+    pub validation: Option<FieldValidation>, // Rules enforced when the value is submitted
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -126,8 +239,14 @@ pub struct UpdateTemplateFieldRequest {
     pub display_order: Option<i32>,
     pub position: Option<FieldPosition>,
     pub options: Option<Value>,
-    pub partner: Option<String>, // Which partner/signer this field belongs to
+    pub partner: Option<String>, // Role name; used (and created if missing) when role_id is not given
+    #[serde(default)]
+    pub role_id: Option<i64>, // Role whose submitter fills this field
     pub default_value: Option<String>, // Default value for the field
+    #[serde(default)]
+    pub conditions: Option<Vec<FieldCondition>>, // Show/require rules on other fields
+    #[serde(default)]
+    pub validation: Option<FieldValidation>, // Rules enforced when the value is submitted
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
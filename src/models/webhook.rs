@@ -0,0 +1,100 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct WebhookEndpoint {
+    pub id: i64,
+    pub url: String,
+    pub events: Vec<String>,
+    pub is_active: bool,
+    /// Signing secret; only returned when the endpoint is created
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<crate::database::models::DbWebhookEndpoint> for WebhookEndpoint {
+    fn from(db_endpoint: crate::database::models::DbWebhookEndpoint) -> Self {
+        WebhookEndpoint {
+            id: db_endpoint.id,
+            url: db_endpoint.url,
+            events: serde_json::from_value(db_endpoint.events).unwrap_or_default(),
+            is_active: db_endpoint.is_active,
+            secret: None,
+            created_at: db_endpoint.created_at,
+            updated_at: db_endpoint.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CreateWebhookRequest {
+    pub url: String,
+    /// Event types to subscribe to, e.g. "submission.created", "submission.completed"
+    pub events: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct UpdateWebhookRequest {
+    pub url: Option<String>,
+    pub events: Option<Vec<String>>,
+    pub is_active: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct WebhookDelivery {
+    pub id: i64,
+    pub endpoint_id: i64,
+    pub event_type: String,
+    pub payload: serde_json::Value,
+    pub status: String, // pending, succeeded, failed
+    pub attempts: i32,
+    pub next_attempt_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub last_attempt_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub last_response_status: Option<i32>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attempt_log: Option<Vec<WebhookDeliveryAttempt>>,
+}
+
+impl From<crate::database::models::DbWebhookDelivery> for WebhookDelivery {
+    fn from(db_delivery: crate::database::models::DbWebhookDelivery) -> Self {
+        WebhookDelivery {
+            id: db_delivery.id,
+            endpoint_id: db_delivery.endpoint_id,
+            event_type: db_delivery.event_type,
+            payload: db_delivery.payload,
+            status: db_delivery.status,
+            attempts: db_delivery.attempts,
+            next_attempt_at: db_delivery.next_attempt_at,
+            last_attempt_at: db_delivery.last_attempt_at,
+            last_response_status: db_delivery.last_response_status,
+            created_at: db_delivery.created_at,
+            attempt_log: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct WebhookDeliveryAttempt {
+    pub attempt: i32,
+    pub response_status: Option<i32>,
+    pub response_body: Option<String>,
+    pub error: Option<String>,
+    pub duration_ms: i64,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<crate::database::models::DbWebhookDeliveryAttempt> for WebhookDeliveryAttempt {
+    fn from(db_attempt: crate::database::models::DbWebhookDeliveryAttempt) -> Self {
+        WebhookDeliveryAttempt {
+            attempt: db_attempt.attempt,
+            response_status: db_attempt.response_status,
+            response_body: db_attempt.response_body,
+            error: db_attempt.error,
+            duration_ms: db_attempt.duration_ms,
+            created_at: db_attempt.created_at,
+        }
+    }
+}
@@ -1,16 +1,17 @@
 use axum::{
     extract::{State, Extension},
     http::StatusCode,
-    response::Json,
+    response::{IntoResponse, Json, Response},
     routing::{get, put, post},
     Router,
 };
 use crate::common::responses::ApiResponse;
-use crate::common::utils::{validate_email_template, clean_text_content};
+use crate::common::utils::clean_text_content;
 use crate::database::queries::EmailTemplateQueries;
 use crate::database::models::UpdateEmailTemplate;
 use crate::routes::web::AppState;
 use crate::models::email_template::EmailTemplate;
+use crate::services::email_template_engine::{self, TemplateCheck};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
@@ -47,6 +48,48 @@ impl From<EmailTemplate> for EmailTemplateResponse {
     }
 }
 
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct ValidateEmailTemplateRequest {
+    /// invitation, reminder, completion, copy or cancellation
+    pub template_type: String,
+    pub subject: String,
+    pub body: String,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct EmailTemplateValidation {
+    pub valid: bool,
+    /// Syntax errors, with the line they occur on
+    pub errors: Vec<String>,
+    /// Variables used by the template that emails of its type do not provide
+    pub unknown_variables: Vec<String>,
+    /// Variables emails of this type provide
+    pub available_variables: Vec<String>,
+}
+
+impl EmailTemplateValidation {
+    fn new(template_type: &str, check: TemplateCheck) -> Self {
+        EmailTemplateValidation {
+            valid: check.is_valid(),
+            available_variables: email_template_engine::known_variables(template_type)
+                .unwrap_or_default()
+                .into_iter()
+                .map(String::from)
+                .collect(),
+            errors: check.errors,
+            unknown_variables: check.unknown_variables,
+        }
+    }
+
+    fn summary(&self) -> String {
+        let mut problems = self.errors.clone();
+        if !self.unknown_variables.is_empty() {
+            problems.push(format!("Unknown variables: {}", self.unknown_variables.join(", ")));
+        }
+        problems.join("; ")
+    }
+}
+
 /// Get all email templates for the current user
 #[utoipa::path(
     get,
@@ -110,6 +153,7 @@ pub async fn get_email_template(
     responses(
         (status = 200, description = "Email template updated successfully", body = ApiResponse<EmailTemplateResponse>),
         (status = 404, description = "Email template not found"),
+        (status = 422, description = "The template has syntax errors or unknown variables", body = ApiResponse<EmailTemplateValidation>),
         (status = 500, description = "Internal server error")
     ),
     security(("bearer_auth" = []))
@@ -119,19 +163,70 @@ pub async fn update_email_template(
     Extension(user_id): Extension<i64>,
     axum::extract::Path(id): axum::extract::Path<i64>,
     Json(payload): Json<UpdateEmailTemplate>,
-) -> (StatusCode, Json<ApiResponse<EmailTemplateResponse>>) {
+) -> Response {
     let pool = &state.lock().await.db_pool;
 
+    if payload.template_type.is_some() || payload.subject.is_some() || payload.body.is_some() {
+        let existing = match EmailTemplateQueries::get_template_by_id(pool, id, user_id).await {
+            Ok(Some(template)) => template,
+            Ok(None) => return ApiResponse::<EmailTemplateResponse>::not_found("Email template not found".to_string()).into_response(),
+            Err(e) => return ApiResponse::<EmailTemplateResponse>::internal_error(format!("Failed to get email template: {}", e)).into_response(),
+        };
+        let template_type = payload.template_type.as_deref().unwrap_or(&existing.template_type);
+        let check = email_template_engine::check_email_template(
+            template_type,
+            payload.subject.as_deref().unwrap_or(&existing.subject),
+            payload.body.as_deref().unwrap_or(&existing.body),
+        );
+        if !check.is_valid() {
+            let validation = EmailTemplateValidation::new(template_type, check);
+            let summary = validation.summary();
+            return ApiResponse::unprocessable(validation, summary).into_response();
+        }
+    }
+
     match EmailTemplateQueries::update_template(pool, id, user_id, payload).await {
         Ok(Some(template)) => {
             let response = EmailTemplateResponse::from(EmailTemplate::from(template));
-            ApiResponse::success(response, "Email template updated successfully".to_string())
+            ApiResponse::success(response, "Email template updated successfully".to_string()).into_response()
         }
-        Ok(None) => ApiResponse::not_found("Email template not found".to_string()),
-        Err(e) => ApiResponse::internal_error(format!("Failed to update email template: {}", e)),
+        Ok(None) => ApiResponse::<EmailTemplateResponse>::not_found("Email template not found".to_string()).into_response(),
+        Err(e) => ApiResponse::<EmailTemplateResponse>::internal_error(format!("Failed to update email template: {}", e)).into_response(),
     }
 }
 
+/// Check an email template's subject and body for syntax errors and variables
+/// that emails of its type do not provide, without saving anything
+#[utoipa::path(
+    post,
+    path = "/api/email-templates/validate",
+    request_body = ValidateEmailTemplateRequest,
+    responses(
+        (status = 200, description = "Email template checked", body = ApiResponse<EmailTemplateValidation>),
+        (status = 400, description = "Unknown template type")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn validate_email_template(
+    Json(payload): Json<ValidateEmailTemplateRequest>,
+) -> (StatusCode, Json<ApiResponse<EmailTemplateValidation>>) {
+    if email_template_engine::known_variables(&payload.template_type).is_none() {
+        return ApiResponse::bad_request(format!(
+            "Invalid template_type '{}'. Must be 'invitation', 'reminder', 'completion', 'copy' or 'cancellation'",
+            payload.template_type
+        ));
+    }
+
+    let check = email_template_engine::check_email_template(&payload.template_type, &payload.subject, &payload.body);
+    let validation = EmailTemplateValidation::new(&payload.template_type, check);
+    let message = if validation.valid {
+        "Email template is valid".to_string()
+    } else {
+        validation.summary()
+    };
+    ApiResponse::success(validation, message)
+}
+
 /// Validate and clean email templates
 #[utoipa::path(
     post,
@@ -165,9 +260,7 @@ pub async fn validate_and_clean_email_templates(
         let cleaned_body = clean_text_content(&original_body);
 
         // Check if template needs updating
-        let needs_update = !validate_email_template(&original_subject, &original_body) ||
-                          cleaned_subject != original_subject ||
-                          cleaned_body != original_body;
+        let needs_update = cleaned_subject != original_subject || cleaned_body != original_body;
 
         if needs_update {
             // Update the template in database
@@ -206,5 +299,6 @@ pub fn create_router() -> Router<AppState> {
         .route("/email-templates", get(get_email_templates))
         .route("/email-templates/:id", get(get_email_template))
         .route("/email-templates/:id", put(update_email_template))
+        .route("/email-templates/validate", post(validate_email_template))
         .route("/email-templates/validate-clean", axum::routing::post(validate_and_clean_email_templates))
 }
\ No newline at end of file
@@ -0,0 +1,209 @@
+use axum::{
+    extract::{Path, Query, State, Extension},
+    http::StatusCode,
+    response::Json,
+    routing::{get, post},
+    Router,
+};
+use serde::Deserialize;
+
+use crate::common::responses::ApiResponse;
+use crate::database::connection::DbPool;
+use crate::database::models::DbOutboxEmail;
+use crate::database::queries::{EmailOutboxQueries, SubmitterQueries};
+use crate::models::email_outbox::OutboxEmail;
+use crate::routes::submissions::can_access_submitter;
+use crate::routes::web::AppState;
+use crate::services::email::{is_credential_kind, resend_outbox_email};
+use crate::services::expiry;
+use crate::services::i18n;
+
+#[derive(Deserialize)]
+pub struct GetEmailsQuery {
+    pub status: Option<String>,
+    pub page: Option<i64>,
+    pub limit: Option<i64>,
+}
+
+// Emails about a submitter follow the submitter's access rule; others belong to their user
+async fn can_access_email(pool: &DbPool, email: &DbOutboxEmail, user_id: i64) -> Result<bool, sqlx::Error> {
+    if email.user_id == Some(user_id) {
+        return Ok(true);
+    }
+    match email.submitter_id {
+        Some(submitter_id) => match SubmitterQueries::get_submitter_by_id(pool, submitter_id).await? {
+            Some(submitter) => can_access_submitter(pool, &submitter, user_id).await,
+            None => Ok(false),
+        },
+        None => Ok(false),
+    }
+}
+
+// An invitation or reminder carries a signing link, which is only worth resending while
+// the same person still has to sign through it
+async fn check_resendable(pool: &DbPool, email: &DbOutboxEmail) -> Result<(), String> {
+    if email.status == "pending" {
+        return Err("This email is still being sent".to_string());
+    }
+    if is_credential_kind(&email.kind) {
+        return Err("This email carried a sign-in credential and is not kept; ask the user to request a new one".to_string());
+    }
+    if email.raw_message.is_none() {
+        return Err("This email is past its retention period and is no longer kept".to_string());
+    }
+    if !matches!(email.kind.as_str(), "invitation" | "reminder") {
+        return Ok(());
+    }
+    let Some(submitter_id) = email.submitter_id else {
+        return Ok(());
+    };
+    let submitter = SubmitterQueries::get_submitter_by_id(pool, submitter_id).await
+        .map_err(|e| format!("Failed to get submitter: {}", e))?
+        .ok_or_else(|| "The submitter of this email no longer exists".to_string())?;
+    if !submitter.email.eq_ignore_ascii_case(&email.to_email) {
+        return Err(format!("The submitter was reassigned to {}; this signing link no longer works", submitter.email));
+    }
+    if !matches!(submitter.status.as_str(), "pending" | "sent" | "viewed") {
+        return Err(format!("The submitter is {} and has nothing left to sign", submitter.status));
+    }
+    match expiry::closed_link_reason(pool, &submitter).await {
+        Ok(None) => Ok(()),
+        Ok(Some(reason)) => Err(reason.message(i18n::DEFAULT_LOCALE)),
+        Err(e) => Err(format!("Failed to check submission: {}", e)),
+    }
+}
+
+/// Every email sent to or about a submitter, newest first
+#[utoipa::path(
+    get,
+    path = "/api/submitters/{id}/emails",
+    params(
+        ("id" = i64, Path, description = "Submitter ID")
+    ),
+    responses(
+        (status = 200, description = "Submitter emails retrieved successfully", body = ApiResponse<Vec<OutboxEmail>>),
+        (status = 403, description = "Access denied"),
+        (status = 404, description = "Submitter not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "emails"
+)]
+pub async fn get_submitter_emails(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<i64>,
+    Path(submitter_id): Path<i64>,
+) -> (StatusCode, Json<ApiResponse<Vec<OutboxEmail>>>) {
+    let pool = &state.lock().await.db_pool;
+
+    let submitter = match SubmitterQueries::get_submitter_by_id(pool, submitter_id).await {
+        Ok(Some(submitter)) => submitter,
+        Ok(None) => return ApiResponse::not_found("Submitter not found".to_string()),
+        Err(e) => return ApiResponse::internal_error(format!("Failed to get submitter: {}", e)),
+    };
+    match can_access_submitter(pool, &submitter, user_id).await {
+        Ok(true) => {}
+        Ok(false) => return ApiResponse::forbidden("Access denied".to_string()),
+        Err(e) => return ApiResponse::internal_error(format!("Failed to get user: {}", e)),
+    }
+
+    match EmailOutboxQueries::get_emails_by_submitter(pool, submitter_id).await {
+        Ok(emails) => {
+            let response: Vec<OutboxEmail> = emails.into_iter().map(OutboxEmail::from).collect();
+            ApiResponse::success(response, "Submitter emails retrieved successfully".to_string())
+        }
+        Err(e) => ApiResponse::internal_error(format!("Failed to get submitter emails: {}", e)),
+    }
+}
+
+/// Emails sent on behalf of the current user, newest first
+#[utoipa::path(
+    get,
+    path = "/api/emails",
+    params(
+        ("status" = Option<String>, Query, description = "Only emails with this status: pending, sent or failed"),
+        ("page" = Option<i64>, Query, description = "Page number (default 1)"),
+        ("limit" = Option<i64>, Query, description = "Items per page (default 20, max 100)")
+    ),
+    responses(
+        (status = 200, description = "Emails retrieved successfully", body = ApiResponse<Vec<OutboxEmail>>),
+        (status = 400, description = "Invalid status"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "emails"
+)]
+pub async fn get_emails(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<i64>,
+    Query(params): Query<GetEmailsQuery>,
+) -> (StatusCode, Json<ApiResponse<Vec<OutboxEmail>>>) {
+    let pool = &state.lock().await.db_pool;
+
+    if let Some(status) = params.status.as_deref() {
+        if !matches!(status, "pending" | "sent" | "failed") {
+            return ApiResponse::bad_request(format!("Invalid status '{}'. Must be 'pending', 'sent' or 'failed'", status));
+        }
+    }
+    let page = params.page.unwrap_or(1).max(1);
+    let limit = params.limit.unwrap_or(20).clamp(1, 100);
+    let offset = (page - 1) * limit;
+
+    match EmailOutboxQueries::get_emails_by_user(pool, user_id, params.status.as_deref(), offset, limit).await {
+        Ok(emails) => {
+            let response: Vec<OutboxEmail> = emails.into_iter().map(OutboxEmail::from).collect();
+            ApiResponse::success(response, "Emails retrieved successfully".to_string())
+        }
+        Err(e) => ApiResponse::internal_error(format!("Failed to get emails: {}", e)),
+    }
+}
+
+/// Send an email again, exactly as it was first sent
+#[utoipa::path(
+    post,
+    path = "/api/emails/{id}/resend",
+    params(
+        ("id" = i64, Path, description = "Outbox email ID")
+    ),
+    responses(
+        (status = 200, description = "Email resent", body = ApiResponse<OutboxEmail>),
+        (status = 400, description = "The email can no longer be resent"),
+        (status = 404, description = "Email not found"),
+        (status = 500, description = "Delivery failed; the attempt is recorded on the email")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "emails"
+)]
+pub async fn resend_email(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<i64>,
+    Path(id): Path<i64>,
+) -> (StatusCode, Json<ApiResponse<OutboxEmail>>) {
+    let pool = &state.lock().await.db_pool;
+
+    let email = match EmailOutboxQueries::get_email_by_id(pool, id).await {
+        Ok(Some(email)) => email,
+        Ok(None) => return ApiResponse::not_found("Email not found".to_string()),
+        Err(e) => return ApiResponse::internal_error(format!("Failed to get email: {}", e)),
+    };
+    match can_access_email(pool, &email, user_id).await {
+        Ok(true) => {}
+        Ok(false) => return ApiResponse::not_found("Email not found".to_string()),
+        Err(e) => return ApiResponse::internal_error(format!("Failed to check access: {}", e)),
+    }
+    if let Err(reason) = check_resendable(pool, &email).await {
+        return ApiResponse::bad_request(reason);
+    }
+
+    match resend_outbox_email(pool, &email).await {
+        Ok(updated) => ApiResponse::success(OutboxEmail::from(updated), "Email resent".to_string()),
+        Err(e) => ApiResponse::internal_error(format!("Failed to resend email: {}", e)),
+    }
+}
+
+pub fn create_router() -> Router<AppState> {
+    Router::new()
+        .route("/emails", get(get_emails))
+        .route("/emails/:id/resend", post(resend_email))
+        .route("/submitters/:id/emails", get(get_submitter_emails))
+}
@@ -14,6 +14,7 @@ use crate::database::queries::GlobalSettingsQueries;
 use crate::database::queries::UserQueries;
 use crate::models::user::User;
 use crate::routes::web::AppState;
+use crate::services::email_branding;
 
 #[utoipa::path(
     get,
@@ -51,6 +52,7 @@ pub async fn get_user_settings(
     request_body = UpdateGlobalSettings,
     responses(
         (status = 200, description = "User settings updated successfully", body = ApiResponse<DbGlobalSettings>),
+        (status = 400, description = "Invalid email sender or layout settings", body = ApiResponse<DbGlobalSettings>),
         (status = 401, description = "Unauthorized", body = ApiResponse<DbGlobalSettings>),
         (status = 500, description = "Internal server error", body = ApiResponse<DbGlobalSettings>)
     ),
@@ -65,6 +67,14 @@ pub async fn update_user_settings(
     let user_id_i32 = user_id as i32;
     println!("update_user_settings called with user_id: {}, payload: {:?}", user_id, payload);
 
+    if let Err(e) = email_branding::validate_settings(
+        payload.email_from_name.as_deref(),
+        payload.email_reply_to.as_deref(),
+        payload.email_layout.as_deref(),
+    ) {
+        return ApiResponse::bad_request(e);
+    }
+
     match GlobalSettingsQueries::update_user_settings(pool, user_id as i32, payload).await {
         Ok(settings) => {
             println!("update_user_settings success: {:?}", settings);
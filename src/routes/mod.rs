@@ -10,4 +10,6 @@ pub mod global_settings;
 pub mod email_templates;
 pub mod team;
 pub mod pdf_signature;
-pub mod pdf_preferences;
\ No newline at end of file
+pub mod pdf_preferences;
+pub mod webhooks;
+pub mod emails;
\ No newline at end of file
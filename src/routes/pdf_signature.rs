@@ -7,7 +7,7 @@ use serde_json::json;
 use chrono::{Utc, DateTime};
 use sha2::{Sha256, Digest};
 use sqlx::Row;
-use lopdf::Document;
+use lopdf::{Document, Object};
 use x509_parser::prelude::*;
 use openssl::pkcs7::{Pkcs7, Pkcs7Flags};
 use openssl::stack::Stack;
@@ -17,16 +17,26 @@ use openssl::x509::X509StoreContext;
 use crate::{
     common::responses::ApiResponse,
     routes::web::AppState,
+    routes::submitters::build_signature_appearance,
     models::certificate::{
         Certificate, CertificateInfo, CertificateStatus, CertificateBasicInfo,
         PDFSignatureSettings, UpdatePDFSignatureSettings,
-        PDFVerificationResult, PDFSignatureDetails,
+        PDFVerificationResult, PDFSignatureDetails, SignatureRevisionInfo,
     },
-    database::queries::UserQueries,
+    database::queries::{SubmitterQueries, UserQueries},
     services::digital_signature::{
         parse_pkcs12_certificate, encrypt_password, extract_certificate_info,
-        create_pkcs7_signature_with_cert, calculate_byte_range, verify_password,
+        verify_password,
     },
+    services::pades::{
+        self, load_signature_profile, SignatureAppearance, SignatureMetadata, SignatureProfile, SigningIdentity,
+        PROFILE_PKCS7_DETACHED, SUPPORTED_PROFILES,
+    },
+    services::pdf_fonts::{self, FONT_NOTO_SANS},
+    services::timestamp::{
+        extract_signature_timestamp, load_timestamp_authority, TimestampAuthority, LOCAL_TSA_URL,
+    },
+    services::storage::StorageService,
 };
 
 /// Load all trusted certificates for a user from database
@@ -259,8 +269,9 @@ fn verify_pkcs7_signature(pdf_data: &[u8], signature_bytes: &[u8], byte_range: &
         .map_err(|_| "Failed to create certificate store")?;
     let store = store_builder.build();
 
-    // For detached signature, verify against the signed content
-    match pkcs7.verify(&Stack::new().unwrap(), &store, Some(&signed_content), None, Pkcs7Flags::empty()) {
+    // For detached signature, verify against the signed content. Only integrity is
+    // checked here; trust is decided against the user's trusted certificates.
+    match pkcs7.verify(&Stack::new().unwrap(), &store, Some(&signed_content), None, Pkcs7Flags::NOVERIFY) {
         Ok(_) => Ok(true),
         Err(e) => {
             eprintln!("PKCS#7 verification failed: {}", e);
@@ -357,6 +368,11 @@ fn extract_pdf_signatures(pdf_data: &[u8], trusted_certs: &[Certificate]) -> Res
     let mut signature_format: Option<String> = None;
     let mut all_signatures_valid = true; // Track if all signatures are cryptographically valid
     let mut pdf_cert_info: Option<CertificateBasicInfo> = None; // Store extracted certificate for matching
+    let mut timestamp_time: Option<DateTime<Utc>> = None;
+    let mut timestamp_authority: Option<CertificateBasicInfo> = None;
+    let mut timestamp_valid: Option<bool> = None;
+    let revision_count = pades::revision_count(pdf_data);
+    let mut signatures: Vec<SignatureRevisionInfo> = Vec::new();
     
     // Try multiple methods to find signatures
     debug_info.push_str("🔍 Searching for signatures...\n");
@@ -399,6 +415,7 @@ fn extract_pdf_signatures(pdf_data: &[u8], trusted_certs: &[Certificate]) -> Res
                                                                     if let Ok(sig_obj) = doc.get_object(sig_obj_id) {
                                                                         if let Ok(sig_dict) = sig_obj.as_dict() {
                                                                             let mut sig_info = format!("Signature #{}\n", signature_count);
+                                                                            let mut digest_valid = false;
                                                                             
                                                                             // Extract Type
                                                                             if let Ok(sig_type) = sig_dict.get(b"Type") {
@@ -516,6 +533,25 @@ fn extract_pdf_signatures(pdf_data: &[u8], trusted_certs: &[Certificate]) -> Res
                                                                                 }
                                                                             }
                                                                             
+                                                                            // Incremental updates: each signature covers the revision it was appended in
+                                                                            let revision = sig_dict.get(b"ByteRange").and_then(Object::as_array).ok()
+                                                                                .and_then(|range| match range.iter().map(|v| v.as_i64().ok()).collect::<Option<Vec<_>>>()?.as_slice() {
+                                                                                    &[0, _, offset2, len2] if offset2 >= 0 && len2 >= 0 => pades::signed_revision(pdf_data, (offset2 + len2) as usize),
+                                                                                    _ => None,
+                                                                                });
+                                                                            match revision {
+                                                                                Some(revision) if revision == revision_count => {
+                                                                                    sig_info.push_str(&format!("  Revision: {} of {} (covers the whole document) ✓\n", revision, revision_count));
+                                                                                }
+                                                                                Some(revision) => {
+                                                                                    sig_info.push_str(&format!("  Revision: {} of {} (later revisions appended) ✓\n", revision, revision_count));
+                                                                                }
+                                                                                None => {
+                                                                                    sig_info.push_str("  Revision: ⚠️  ByteRange does not end at a revision boundary\n");
+                                                                                    all_signatures_valid = false;
+                                                                                }
+                                                                            }
+                                                                            
                                                                             // Extract Contents (signature bytes)
                                                                             if let Ok(contents) = sig_dict.get(b"Contents") {
                                                                                 if let Ok(contents_bytes) = contents.as_str() {
@@ -554,6 +590,7 @@ fn extract_pdf_signatures(pdf_data: &[u8], trusted_certs: &[Certificate]) -> Res
                                                                                             match verify_pkcs7_signature(pdf_data, contents_bytes, &byte_range) {
                                                                                                 Ok(true) => {
                                                                                                     sig_info.push_str("  🔐 Cryptographic verification: VALID ✓\n");
+                                                                                                    digest_valid = true;
                                                                                                 },
                                                                                                 Ok(false) => {
                                                                                                     sig_info.push_str("  🔐 Cryptographic verification: INVALID ⚠️\n");
@@ -600,6 +637,24 @@ fn extract_pdf_signatures(pdf_data: &[u8], trusted_certs: &[Certificate]) -> Res
                                                                                     pdf_cert_info = Some(cert_info.clone());
                                                                                 }
                                                                             }
+
+                                                                            // RFC 3161 timestamp token (unsigned attribute)
+                                                                            if let Some(timestamp) = extract_signature_timestamp(contents_bytes) {
+                                                                                let tsa_info = timestamp.tsa_certificate.as_deref().and_then(parse_pkcs7_certificate);
+                                                                                sig_info.push_str(&format!("  ⏱️  Timestamp: {} ({})\n",
+                                                                                    timestamp.time,
+                                                                                    if timestamp.is_valid { "VALID ✓" } else { "INVALID ⚠️" }));
+                                                                                if let Some(ref subject) = tsa_info.as_ref().and_then(|info| info.subject.clone()) {
+                                                                                    sig_info.push_str(&format!("     TSA: {}\n", subject));
+                                                                                }
+                                                                                sig_info.push_str(&format!("     Serial: {}\n", timestamp.serial_number));
+
+                                                                                if timestamp_time.is_none() {
+                                                                                    timestamp_time = Some(timestamp.time);
+                                                                                    timestamp_authority = tsa_info;
+                                                                                    timestamp_valid = Some(timestamp.is_valid);
+                                                                                }
+                                                                            }
                                                                         } else if hex_str.starts_with("3030") {
                                                                             sig_info.push_str("  Format: Placeholder/ASCII zeros (not real signature) ⚠️\n");
                                                                             all_signatures_valid = false;
@@ -633,6 +688,16 @@ fn extract_pdf_signatures(pdf_data: &[u8], trusted_certs: &[Certificate]) -> Res
                                                                                 }
                                                                             }
                                                                             
+                                                                            let text = |dict: &lopdf::Dictionary, key: &[u8]| dict.get(key).and_then(Object::as_str).ok()
+                                                                                .map(|bytes| String::from_utf8_lossy(bytes).into_owned());
+                                                                            signatures.push(SignatureRevisionInfo {
+                                                                                field_name: text(field_dict, b"T"),
+                                                                                signer_name: text(sig_dict, b"Name"),
+                                                                                signature_subfilter: sig_dict.get(b"SubFilter").and_then(Object::as_name_str).ok().map(str::to_string),
+                                                                                revision,
+                                                                                covers_whole_document: revision == Some(revision_count),
+                                                                                is_intact: revision.is_some() && digest_valid,
+                                                                            });
                                                                             signature_details.push(sig_info);
                                                                         }
                                                                     }
@@ -958,6 +1023,11 @@ fn extract_pdf_signatures(pdf_data: &[u8], trusted_certs: &[Certificate]) -> Res
             is_valid,
             is_trusted,
             trusted_certificate_name: trusted_cert_name,
+            timestamp_time,
+            timestamp_authority,
+            timestamp_valid,
+            revision_count,
+            signatures,
         }),
     })
 }
@@ -1335,7 +1405,7 @@ pub async fn get_pdf_signature_settings(
     
     let query = r#"
         SELECT id, user_id, account_id, filename_format, 
-               default_certificate_id, created_at, updated_at
+               default_certificate_id, tsa_enabled, tsa_url, signature_profile, field_font, created_at, updated_at
         FROM pdf_signature_settings
         WHERE user_id = $1 OR account_id = $2
         LIMIT 1
@@ -1361,6 +1431,10 @@ pub async fn get_pdf_signature_settings(
             account_id: row.get("account_id"),
             filename_format: row.get("filename_format"),
             default_certificate_id: row.get("default_certificate_id"),
+            tsa_enabled: row.get("tsa_enabled"),
+            tsa_url: row.get("tsa_url"),
+            signature_profile: row.get("signature_profile"),
+            field_font: row.get("field_font"),
             created_at: Some(row.get("created_at")),
             updated_at: Some(row.get("updated_at")),
         }
@@ -1371,6 +1445,10 @@ pub async fn get_pdf_signature_settings(
             account_id: db_user.account_id,
             filename_format: "{document.name}".to_string(),
             default_certificate_id: None,
+            tsa_enabled: false,
+            tsa_url: None,
+            signature_profile: PROFILE_PKCS7_DETACHED.to_string(),
+            field_font: FONT_NOTO_SANS.to_string(),
             created_at: None,
             updated_at: None,
         }
@@ -1404,9 +1482,40 @@ pub async fn update_pdf_signature_settings(
             Json(json!({ "error": "User not found" }))
         ))?;
     
+    if let Some(tsa_url) = payload.tsa_url.as_deref().map(str::trim).filter(|url| !url.is_empty()) {
+        // The server calls this URL, so it must reach a public address
+        let checked = match tsa_url {
+            LOCAL_TSA_URL if cfg!(test) => Ok(()),
+            LOCAL_TSA_URL => Err("The local timestamp authority is only available in tests".to_string()),
+            url => crate::services::webhooks::resolve_public_url(url, "Timestamp authority").await.map(|_| ()),
+        };
+        if let Err(error) = checked {
+            return Err((StatusCode::BAD_REQUEST, Json(json!({ "error": error }))));
+        }
+    }
+
+    let profile = match payload.signature_profile.as_deref() {
+        Some(value) => Some(SignatureProfile::from_setting(value).ok_or_else(|| (
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": format!("Unsupported signature profile '{}'. Supported: {}", value, SUPPORTED_PROFILES.join(", ")) }))
+        ))?),
+        None => None,
+    };
+
+    let field_font = payload.field_font.as_deref().map(str::trim);
+    if let Some(font) = field_font {
+        let available = pdf_fonts::available_fonts();
+        if !available.iter().any(|key| key == font) {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(json!({ "error": format!("Unsupported field font '{}'. Available: {}", font, available.join(", ")) }))
+            ));
+        }
+    }
+
     // Check if settings exist
     let existing_query = r#"
-        SELECT id FROM pdf_signature_settings
+        SELECT id, tsa_enabled, tsa_url, signature_profile FROM pdf_signature_settings
         WHERE user_id = $1 OR account_id = $2
         LIMIT 1
     "#;
@@ -1424,6 +1533,26 @@ pub async fn update_pdf_signature_settings(
             )
         })?;
 
+    // B-T and B-LT cannot be produced without a timestamp authority. Checked against the
+    // profile the settings end up with, so the TSA cannot be turned off under a saved B-T/B-LT.
+    let effective_profile = profile.or_else(|| existing.as_ref()
+        .and_then(|row| row.get::<Option<String>, _>("signature_profile"))
+        .and_then(|value| SignatureProfile::from_setting(&value)));
+    if effective_profile.is_some_and(SignatureProfile::requires_timestamp) {
+        let tsa_enabled = payload.tsa_enabled
+            .unwrap_or_else(|| existing.as_ref().map(|row| row.get("tsa_enabled")).unwrap_or(false));
+        let tsa_url = match &payload.tsa_url {
+            Some(url) => Some(url.trim().to_string()).filter(|url| !url.is_empty()),
+            None => existing.as_ref().and_then(|row| row.get("tsa_url")),
+        };
+        if !tsa_enabled || tsa_url.is_none() {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(json!({ "error": "PAdES B-T and B-LT profiles require an enabled timestamp authority" }))
+            ));
+        }
+    }
+
     if existing.is_some() {
         // Update existing settings
         if let Some(filename_format) = &payload.filename_format {
@@ -1439,6 +1568,60 @@ pub async fn update_pdf_signature_settings(
                 })?;
         }
 
+        if let Some(tsa_enabled) = payload.tsa_enabled {
+            sqlx::query("UPDATE pdf_signature_settings SET tsa_enabled = $1 WHERE user_id = $2 OR account_id = $3")
+                .bind(tsa_enabled)
+                .bind(db_user.id)
+                .bind(db_user.account_id)
+                .execute(pool)
+                .await
+                .map_err(|e| {
+                    eprintln!("Database error: {:?}", e);
+                    (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({ "error": "Failed to update settings" })))
+                })?;
+        }
+
+        if let Some(tsa_url) = &payload.tsa_url {
+            // An empty string clears the URL
+            let tsa_url = Some(tsa_url.trim()).filter(|url| !url.is_empty());
+            sqlx::query("UPDATE pdf_signature_settings SET tsa_url = $1 WHERE user_id = $2 OR account_id = $3")
+                .bind(tsa_url)
+                .bind(db_user.id)
+                .bind(db_user.account_id)
+                .execute(pool)
+                .await
+                .map_err(|e| {
+                    eprintln!("Database error: {:?}", e);
+                    (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({ "error": "Failed to update settings" })))
+                })?;
+        }
+
+        if let Some(profile) = profile {
+            sqlx::query("UPDATE pdf_signature_settings SET signature_profile = $1 WHERE user_id = $2 OR account_id = $3")
+                .bind(profile.as_setting())
+                .bind(db_user.id)
+                .bind(db_user.account_id)
+                .execute(pool)
+                .await
+                .map_err(|e| {
+                    eprintln!("Database error: {:?}", e);
+                    (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({ "error": "Failed to update settings" })))
+                })?;
+        }
+
+        if let Some(font) = field_font {
+            sqlx::query("UPDATE pdf_signature_settings SET field_font = $1 WHERE user_id = $2 OR account_id = $3")
+                .bind(font)
+                .bind(db_user.id)
+                .bind(db_user.account_id)
+                .execute(pool)
+                .await
+                .map_err(|e| {
+                    eprintln!("Database error: {:?}", e);
+                    (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({ "error": "Failed to update settings" })))
+                })?;
+        }
+
         // Fetch updated settings
         drop(state_lock);
         get_pdf_signature_settings(State(state), Extension(user_id)).await
@@ -1446,10 +1629,10 @@ pub async fn update_pdf_signature_settings(
         // Insert new settings
         let query = r#"
             INSERT INTO pdf_signature_settings 
-            (user_id, account_id, filename_format, default_certificate_id)
-            VALUES ($1, $2, $3, $4)
+            (user_id, account_id, filename_format, default_certificate_id, tsa_enabled, tsa_url, signature_profile, field_font)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
             RETURNING id, user_id, account_id, filename_format, 
-                      default_certificate_id, created_at, updated_at
+                      default_certificate_id, tsa_enabled, tsa_url, signature_profile, field_font, created_at, updated_at
         "#;
 
         let row = sqlx::query(query)
@@ -1457,6 +1640,10 @@ pub async fn update_pdf_signature_settings(
             .bind(db_user.account_id)
             .bind(payload.filename_format.unwrap_or_else(|| "{document.name}".to_string()))
             .bind(payload.default_certificate_id)
+            .bind(payload.tsa_enabled.unwrap_or(false))
+            .bind(payload.tsa_url.as_deref().map(str::trim).filter(|url| !url.is_empty()))
+            .bind(profile.unwrap_or(SignatureProfile::Pkcs7Detached).as_setting())
+            .bind(field_font.unwrap_or(FONT_NOTO_SANS))
             .fetch_one(pool)
             .await
             .map_err(|e| {
@@ -1477,6 +1664,10 @@ pub async fn update_pdf_signature_settings(
                 account_id: row.get("account_id"),
                 filename_format: row.get("filename_format"),
                 default_certificate_id: row.get("default_certificate_id"),
+                tsa_enabled: row.get("tsa_enabled"),
+                tsa_url: row.get("tsa_url"),
+                signature_profile: row.get("signature_profile"),
+                field_font: row.get("field_font"),
                 created_at: Some(row.get("created_at")),
                 updated_at: Some(row.get("updated_at")),
             }),
@@ -1600,6 +1791,11 @@ pub async fn verify_pdf_signature(
                 is_valid: false,
                 is_trusted: false,
                 trusted_certificate_name: None,
+                timestamp_time: None,
+                timestamp_authority: None,
+                timestamp_valid: None,
+                revision_count: 0,
+                signatures: Vec::new(),
             }),
         },
     };
@@ -1773,6 +1969,15 @@ pub async fn sign_pdf_with_certificate(
         }
     };
     
+    let (profile, tsa) = load_signing_settings(pool, db_user.id, db_user.account_id).await
+        .map_err(|e| {
+            eprintln!("Failed to load signing settings: {:?}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "error": "Failed to load signature settings" }))
+            )
+        })?;
+
     // Sign PDF
     let signed_pdf = sign_pdf_with_uploaded_certificate(
         &pdf_bytes,
@@ -1780,7 +1985,10 @@ pub async fn sign_pdf_with_certificate(
         &pkey,
         reason.as_deref().unwrap_or("Signed with uploaded certificate"),
         location.as_deref().unwrap_or("Letmesign Platform"),
-    ).map_err(|e| {
+        profile,
+        tsa.as_ref(),
+        None,
+    ).await.map_err(|e| {
         (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(json!({ "error": format!("Failed to sign PDF: {}", e) }))
@@ -1928,6 +2136,15 @@ pub async fn sign_pdf_with_certificate_id(
             )
         })?;
     
+    let (profile, tsa) = load_signing_settings(pool, db_user.id, db_user.account_id).await
+        .map_err(|e| {
+            eprintln!("Failed to load signing settings: {:?}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "error": "Failed to load signature settings" }))
+            )
+        })?;
+
     // Sign PDF
     let signed_pdf = sign_pdf_with_uploaded_certificate(
         &pdf_bytes,
@@ -1935,7 +2152,10 @@ pub async fn sign_pdf_with_certificate_id(
         &pkey,
         reason.as_deref().unwrap_or("Signed with uploaded certificate"),
         location.as_deref().unwrap_or("Letmesign Platform"),
-    ).map_err(|e| {
+        profile,
+        tsa.as_ref(),
+        None,
+    ).await.map_err(|e| {
         (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(json!({ "error": format!("Failed to sign PDF: {}", e) }))
@@ -1949,142 +2169,48 @@ pub async fn sign_pdf_with_certificate_id(
     ))
 }
 
+/// Load the account's signature profile and timestamp authority
+async fn load_signing_settings(
+    pool: &sqlx::PgPool,
+    user_id: i64,
+    account_id: Option<i64>,
+) -> anyhow::Result<(SignatureProfile, Option<TimestampAuthority>)> {
+    let profile = load_signature_profile(pool, user_id, account_id).await?;
+    let tsa = load_timestamp_authority(pool, user_id, account_id).await?;
+    Ok((profile, tsa))
+}
+
 /// Helper function to sign PDF with uploaded certificate
-fn sign_pdf_with_uploaded_certificate(
+#[allow(clippy::too_many_arguments)]
+async fn sign_pdf_with_uploaded_certificate(
     pdf_bytes: &[u8],
     cert: &openssl::x509::X509,
     pkey: &openssl::pkey::PKey<openssl::pkey::Private>,
     reason: &str,
     location: &str,
+    profile: SignatureProfile,
+    tsa: Option<&TimestampAuthority>,
+    appearance: Option<&SignatureAppearance>,
 ) -> Result<Vec<u8>, String> {
-    use lopdf::{Object, Dictionary};
-    
-    // SIMPLIFIED APPROACH: Just create PKCS#7 signature of entire PDF
-    // This doesn't create a properly embedded signature but allows testing verification
-    
-    // Create PKCS#7 signature of the PDF content
-    use openssl::pkcs7::{Pkcs7, Pkcs7Flags};
-    use openssl::stack::Stack;
-    
-    let mut cert_stack = Stack::new().map_err(|e| format!("Stack error: {}", e))?;
-    let flags = Pkcs7Flags::DETACHED | Pkcs7Flags::BINARY;
-    
-    let pkcs7 = Pkcs7::sign(cert, pkey, &cert_stack, pdf_bytes, flags)
-        .map_err(|e| format!("PKCS#7 sign failed: {}", e))?;
-    
-    let signature_der = pkcs7.to_der()
-        .map_err(|e| format!("DER encode failed: {}", e))?;
-    
-    // Calculate byte range for signature
-    // ByteRange format: [0, offset_before_sig, offset_after_sig, length_after_sig]
-    // For simplicity, we'll use a placeholder approach
-    let signature_size = signature_der.len() * 2 + 2; // Hex encoding doubles size, plus < >
-    let byte_range: [u32; 4] = [
-        0,
-        pdf_bytes.len() as u32,
-        (pdf_bytes.len() + signature_size) as u32,
-        0,
-    ];
-    
-    // For now, return original PDF with signature in metadata (not a proper PDF signature)
-    // This is just to test the verification logic
-    let mut doc = Document::load_mem(pdf_bytes)
-        .map_err(|e| format!("Failed to load PDF: {}", e))?;
-    
-    // Create signature dictionary
-    let mut sig_dict = Dictionary::new();
-    sig_dict.set("Type", Object::Name(b"Sig".to_vec()));
-    sig_dict.set("Filter", Object::Name(b"Adobe.PPKLite".to_vec()));
-    sig_dict.set("SubFilter", Object::Name(b"adbe.pkcs7.detached".to_vec()));
-    
-    // Add metadata
-    let now = chrono::Utc::now();
-    let date_str = format!("D:{}", now.format("%Y%m%d%H%M%S+00'00'"));
-    sig_dict.set("M", Object::String(date_str.into_bytes(), lopdf::StringFormat::Literal));
-    
     // Extract signer name from certificate
     let signer_name = cert.subject_name().entries()
         .find(|e| e.object().nid() == openssl::nid::Nid::COMMONNAME)
         .and_then(|e| e.data().as_utf8().ok())
         .map(|s| s.to_string())
         .unwrap_or_else(|| "Unknown".to_string());
-    
-    sig_dict.set("Name", Object::String(signer_name.as_bytes().to_vec(), lopdf::StringFormat::Literal));
-    sig_dict.set("Reason", Object::String(reason.as_bytes().to_vec(), lopdf::StringFormat::Literal));
-    sig_dict.set("Location", Object::String(location.as_bytes().to_vec(), lopdf::StringFormat::Literal));
-    
-    // Set ByteRange
-    sig_dict.set("ByteRange", Object::Array(vec![
-        Object::Integer(byte_range[0] as i64),
-        Object::Integer(byte_range[1] as i64),
-        Object::Integer(byte_range[2] as i64),
-        Object::Integer(byte_range[3] as i64),
-    ]));
-    
-    // Create PKCS#7 signature
-    let pkcs7_signature = create_pkcs7_signature_with_cert(
-        pdf_bytes,
-        &byte_range,
-        cert,
-        pkey,
-        None, // No additional cert chain for uploaded certs
-    ).map_err(|e| format!("Failed to create PKCS#7 signature: {}", e))?;
-    
-    sig_dict.set("Contents", Object::String(pkcs7_signature, lopdf::StringFormat::Hexadecimal));
-    
-    // Add signature object
-    let sig_obj_id = doc.add_object(sig_dict);
-    
-    // Create signature field
-    let mut sig_field = Dictionary::new();
-    sig_field.set("FT", Object::Name(b"Sig".to_vec()));
-    sig_field.set("T", Object::String(b"CertificateSignature".to_vec(), lopdf::StringFormat::Literal));
-    sig_field.set("V", Object::Reference(sig_obj_id));
-    sig_field.set("Rect", Object::Array(vec![
-        Object::Integer(0),
-        Object::Integer(0),
-        Object::Integer(0),
-        Object::Integer(0),
-    ]));
-    
-    let sig_field_id = doc.add_object(sig_field);
-    
-    // Add to or create AcroForm
-    let acroform_ref_copy = {
-        let catalog = doc.catalog_mut()
-            .map_err(|e| format!("Failed to get catalog: {}", e))?;
-        catalog.get(b"AcroForm").ok().and_then(|r| r.as_reference().ok())
+
+    let identity = SigningIdentity { certificate: cert, key: pkey, chain: &[] };
+    let metadata = SignatureMetadata {
+        field_name: "CertificateSignature",
+        name: &signer_name,
+        reason,
+        location,
+        contact_info: None,
+        appearance,
     };
-    
-    if let Some(acroform_id) = acroform_ref_copy {
-        // Add to existing AcroForm
-        if let Ok(acroform_obj) = doc.get_object_mut(acroform_id) {
-            if let Ok(acroform_dict) = acroform_obj.as_dict_mut() {
-                if let Ok(fields) = acroform_dict.get_mut(b"Fields") {
-                    if let Ok(fields_array) = fields.as_array_mut() {
-                        fields_array.push(Object::Reference(sig_field_id));
-                    }
-                } else {
-                    acroform_dict.set("Fields", Object::Array(vec![Object::Reference(sig_field_id)]));
-                }
-            }
-        }
-    } else {
-        // Create new AcroForm
-        let mut acroform = Dictionary::new();
-        acroform.set("Fields", Object::Array(vec![Object::Reference(sig_field_id)]));
-        let acroform_id = doc.add_object(acroform);
-        let catalog = doc.catalog_mut()
-            .map_err(|e| format!("Failed to get catalog: {}", e))?;
-        catalog.set("AcroForm", Object::Reference(acroform_id));
-    }
-    
-    // Save
-    let mut output = Vec::new();
-    doc.save_to(&mut output)
-        .map_err(|e| format!("Failed to save PDF: {}", e))?;
-    
-    Ok(output)
+
+    pades::sign_pdf(pdf_bytes, &identity, &metadata, profile, tsa).await
+        .map_err(|e| format!("Failed to sign PDF: {}", e))
 }
 
 /// Sign a visual PDF with digital signature structure
@@ -2112,6 +2238,7 @@ pub async fn sign_visual_pdf(
     let mut signer_email: Option<String> = None;
     let mut signer_name: Option<String> = None;
     let mut reason: Option<String> = None;
+    let mut submitter_id: Option<i64> = None;
     
     // Parse multipart form
     while let Some(field) = multipart.next_field().await.unwrap_or(None) {
@@ -2121,6 +2248,9 @@ pub async fn sign_visual_pdf(
             "pdf" => {
                 pdf_data = Some(field.bytes().await.unwrap_or_default().to_vec());
             },
+            "submitter_id" => {
+                submitter_id = String::from_utf8_lossy(&field.bytes().await.unwrap_or_default()).trim().parse().ok();
+            },
             "signer_email" => {
                 signer_email = Some(String::from_utf8_lossy(&field.bytes().await.unwrap_or_default()).to_string());
             },
@@ -2144,6 +2274,29 @@ pub async fn sign_visual_pdf(
     let email = signer_email.unwrap_or_else(|| db_user.email.clone());
     let name = signer_name.unwrap_or_else(|| db_user.email.clone());
     let sign_reason = reason.unwrap_or_else(|| format!("Signed by {} via letmesign", name));
+
+    // With a submitter, the signature gets a visible widget on their template signature field
+    let appearance = match submitter_id {
+        Some(submitter_id) => {
+            let submitter = SubmitterQueries::get_submitter_by_id(pool, submitter_id).await
+                .map_err(|_| (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(json!({ "error": "Failed to fetch submitter" }))
+                ))?
+                .filter(|submitter| submitter.user_id == db_user.id)
+                .ok_or_else(|| (
+                    StatusCode::NOT_FOUND,
+                    Json(json!({ "error": "Submitter not found" }))
+                ))?;
+            let storage_service = StorageService::new().await
+                .map_err(|e| (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(json!({ "error": format!("Failed to initialize storage: {}", e) }))
+                ))?;
+            build_signature_appearance(pool, &storage_service, &submitter, &pdf_bytes).await
+        }
+        None => None,
+    };
     
     // Add REAL cryptographic signature to PDF
     let signed_pdf = add_real_digital_signature_to_pdf(
@@ -2153,7 +2306,8 @@ pub async fn sign_visual_pdf(
         &pdf_bytes,
         &name,
         &email,
-        &sign_reason
+        &sign_reason,
+        appearance.as_ref(),
     ).await.map_err(|e| (
         StatusCode::INTERNAL_SERVER_ERROR,
         Json(json!({ "error": format!("Failed to add signature: {}", e) }))
@@ -2179,6 +2333,7 @@ pub async fn sign_visual_pdf(
 }
 
 /// Helper function to add REAL digital signature to PDF
+#[allow(clippy::too_many_arguments)]
 async fn add_real_digital_signature_to_pdf(
     pool: &sqlx::PgPool,
     user_id: i64,
@@ -2187,8 +2342,8 @@ async fn add_real_digital_signature_to_pdf(
     signer_name: &str,
     signer_email: &str,
     reason: &str,
+    appearance: Option<&SignatureAppearance>,
 ) -> Result<Vec<u8>, String> {
-    use lopdf::{Object, Dictionary};
     use crate::services::digital_signature::*;
     
     // Load CA certificates
@@ -2210,111 +2365,36 @@ async fn add_real_digital_signature_to_pdf(
         signer_name
     ).map_err(|e| format!("Failed to generate signing certificate: {}", e))?;
     
-    // Load PDF
-    let mut doc = Document::load_mem(pdf_bytes)
-        .map_err(|e| format!("Failed to load PDF: {}", e))?;
-    
-    // Reserve space for signature (estimate 8KB for PKCS#7 with cert chain)
-    let signature_size = 8192;
-    let byte_range = calculate_byte_range(pdf_bytes.len(), signature_size);
-    
-    // Create signature dictionary
-    let mut sig_dict = Dictionary::new();
-    sig_dict.set("Type", Object::Name(b"Sig".to_vec()));
-    sig_dict.set("Filter", Object::Name(b"Adobe.PPKLite".to_vec()));
-    sig_dict.set("SubFilter", Object::Name(b"adbe.pkcs7.detached".to_vec()));
-    
-    // Add metadata
-    let now = Utc::now();
-    let date_str = format!("D:{}", now.format("%Y%m%d%H%M%S+00'00'"));
-    sig_dict.set("M", Object::String(date_str.into_bytes(), lopdf::StringFormat::Literal));
-    sig_dict.set("Name", Object::String(signer_name.as_bytes().to_vec(), lopdf::StringFormat::Literal));
-    sig_dict.set("Reason", Object::String(reason.as_bytes().to_vec(), lopdf::StringFormat::Literal));
-    sig_dict.set("ContactInfo", Object::String(signer_email.as_bytes().to_vec(), lopdf::StringFormat::Literal));
-    sig_dict.set("Location", Object::String(b"Letmesign Platform".to_vec(), lopdf::StringFormat::Literal));
-    
-    // Set ByteRange
-    sig_dict.set("ByteRange", Object::Array(vec![
-        Object::Integer(byte_range[0] as i64),
-        Object::Integer(byte_range[1] as i64),
-        Object::Integer(byte_range[2] as i64),
-        Object::Integer(byte_range[3] as i64),
-    ]));
-    
-    // Create REAL PKCS#7 signature with certificate chain
+    let (profile, tsa) = load_signing_settings(pool, user_id, account_id).await
+        .map_err(|e| format!("Failed to load signature settings: {}", e))?;
+
+    // Sign with the full certificate chain so verifiers can build the path to our root
     let cert_chain = vec![sub_ca_cert, root_ca_cert];
-    let pkcs7_signature = create_pkcs7_signature(
-        pdf_bytes,
-        &byte_range,
-        &signing_cert,
-        &signing_keypair,
-        &cert_chain
-    ).map_err(|e| format!("Failed to create PKCS#7 signature: {}", e))?;
-    
-    sig_dict.set("Contents", Object::String(pkcs7_signature, lopdf::StringFormat::Hexadecimal));
-    
-    // Add signature object
-    let sig_obj_id = doc.add_object(sig_dict);
-    
-    // Create signature field
-    let mut sig_field = Dictionary::new();
-    sig_field.set("FT", Object::Name(b"Sig".to_vec()));
-    sig_field.set("T", Object::String(b"LetmesignSignature".to_vec(), lopdf::StringFormat::Literal));
-    sig_field.set("V", Object::Reference(sig_obj_id));
-    sig_field.set("Rect", Object::Array(vec![
-        Object::Integer(0),
-        Object::Integer(0),
-        Object::Integer(0),
-        Object::Integer(0),
-    ]));
-    
-    let sig_field_id = doc.add_object(sig_field);
-    
-    // Add to or create AcroForm
-    let acroform_ref_copy = {
-        let catalog = doc.catalog_mut()
-            .map_err(|e| format!("Failed to get catalog: {}", e))?;
-        catalog.get(b"AcroForm").ok().and_then(|r| r.as_reference().ok())
+    let identity = SigningIdentity {
+        certificate: &signing_cert,
+        key: &signing_keypair,
+        chain: &cert_chain,
     };
-    
-    if let Some(acroform_id) = acroform_ref_copy {
-        // Add to existing AcroForm
-        if let Ok(acroform_obj) = doc.get_object_mut(acroform_id) {
-            if let Ok(acroform_dict) = acroform_obj.as_dict_mut() {
-                if let Ok(fields) = acroform_dict.get_mut(b"Fields") {
-                    if let Ok(fields_array) = fields.as_array_mut() {
-                        fields_array.push(Object::Reference(sig_field_id));
-                    }
-                } else {
-                    acroform_dict.set("Fields", Object::Array(vec![Object::Reference(sig_field_id)]));
-                }
-            }
-        }
-    } else {
-        // Create new AcroForm
-        let mut acroform = Dictionary::new();
-        acroform.set("Fields", Object::Array(vec![Object::Reference(sig_field_id)]));
-        
-        // Add signature field to catalog
-        let acroform_id = doc.add_object(acroform);
-        let catalog = doc.catalog_mut()
-            .map_err(|e| format!("Failed to get catalog: {}", e))?;
-        catalog.set("AcroForm", Object::Reference(acroform_id));
-    }
-    
-    // Save final PDF with signature
-    let mut output = Vec::new();
-    doc.save_to(&mut output)
-        .map_err(|e| format!("Failed to save PDF: {}", e))?;
-    
-    Ok(output)
+    let metadata = SignatureMetadata {
+        field_name: "LetmesignSignature",
+        name: signer_name,
+        reason,
+        location: "Letmesign Platform",
+        contact_info: Some(signer_email),
+        appearance,
+    };
+
+    pades::sign_pdf(pdf_bytes, &identity, &metadata, profile, tsa.as_ref()).await
+        .map_err(|e| format!("Failed to sign PDF: {}", e))
 }
+
 /// Auto-sign a submission PDF when all submitters complete
 /// This function is called automatically in the background
 pub async fn auto_sign_submission_pdf(
     pool: &sqlx::PgPool,
     user_id: i64,
     pdf_bytes: &[u8],
+    appearance: Option<&SignatureAppearance>,
 ) -> Result<Vec<u8>, String> {
     eprintln!("🔐 Auto-signing PDF for user {}...", user_id);
     
@@ -2357,13 +2437,23 @@ pub async fn auto_sign_submission_pdf(
     
     eprintln!("✍️  Signing with reason: '{}'", reason);
     
+    let account_id = UserQueries::get_user_by_id(pool, user_id).await
+        .map_err(|e| format!("Database error: {}", e))?
+        .and_then(|user| user.account_id);
+    let (profile, tsa) = load_signing_settings(pool, user_id, account_id).await
+        .map_err(|e| format!("Failed to load signature settings: {}", e))?;
+    
     let signed_pdf = sign_pdf_with_uploaded_certificate(
         pdf_bytes,
         &cert,
         &pkey,
         reason,
         location,
+        profile,
+        tsa.as_ref(),
+        appearance,
     )
+    .await
     .map_err(|e| format!("Failed to sign PDF: {}", e))?;
     
     eprintln!("✅ Auto-sign successful! Signed PDF size: {} bytes", signed_pdf.len());
@@ -107,8 +107,10 @@ pub async fn stripe_webhook_handler(
                     println!("🔍 Parsing client_ref: '{}'", client_ref);
                     if let Ok(user_id) = client_ref.parse::<i64>() {
                         println!("✅ Parsed user_id: {}", user_id);
-                        let db_pool = &state.lock().await.db_pool;
-                        println!("✅ Got db_pool");
+                        let (db_pool, payment_queue) = {
+                            let state = state.lock().await;
+                            (state.db_pool.clone(), state.payment_queue.clone())
+                        };
                         
                         let session_id = session.get("id").and_then(|v| v.as_str()).map(|s| s.to_string());
                         println!("🔍 Stripe session_id: {:?}", session_id);
@@ -126,36 +128,23 @@ pub async fn stripe_webhook_handler(
                             })),
                         };
                         
-                        if let Ok(record) = SubscriptionQueries::create_payment_record(db_pool, data).await {
-                            println!("💾 Created payment record {}", record.id);
-                            
-                            // ✅ Update subscription status và expires_at cho user
-                            let expires_at = Utc::now() + Duration::days(30);
-                            println!("🔍 Calling update_user_subscription_status for user_id: {}", user_id);
-                            if let Err(e) = SubscriptionQueries::update_user_subscription_status(
-                                db_pool,
-                                user_id,
-                                "premium",
-                                Some(expires_at)
-                            ).await {
-                                eprintln!("❌ Failed to update subscription status: {}", e);
-                            } else {
-                                println!("✅ Updated subscription status to premium, expires at: {}", expires_at);
-                            }
+                        // The record is written by the job worker; a redelivered event is queued only once
+                        if let Err(e) = payment_queue.enqueue(data).await {
+                            eprintln!("❌ Failed to queue payment record: {}", e);
+                        }
+
+                        // ✅ Update subscription status và expires_at cho user
+                        let expires_at = Utc::now() + Duration::days(30);
+                        println!("🔍 Calling update_user_subscription_status for user_id: {}", user_id);
+                        if let Err(e) = SubscriptionQueries::update_user_subscription_status(
+                            &db_pool,
+                            user_id,
+                            "premium",
+                            Some(expires_at)
+                        ).await {
+                            eprintln!("❌ Failed to update subscription status: {}", e);
                         } else {
-                            // Vẫn update subscription status dù payment record đã tồn tại
-                            let expires_at = Utc::now() + Duration::days(30);
-                            println!("🔍 Calling update_user_subscription_status for user_id: {}", user_id);
-                            if let Err(e) = SubscriptionQueries::update_user_subscription_status(
-                                db_pool,
-                                user_id,
-                                "premium",
-                                Some(expires_at)
-                            ).await {
-                                eprintln!("❌ Failed to update subscription status: {}", e);
-                            } else {
-                                println!("✅ Updated subscription status to premium, expires at: {}", expires_at);
-                            }
+                            println!("✅ Updated subscription status to premium, expires at: {}", expires_at);
                         }
                     } else {
                         eprintln!("❌ Failed to parse client_ref as user_id: '{}'", client_ref);
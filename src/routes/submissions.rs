@@ -1,74 +1,542 @@
 use axum::{
-    extract::State,
+    extract::{Multipart, Path, Query, State},
     http::StatusCode,
     response::Json,
-    routing::post,
+    routing::{get, post},
     Router,
     Extension,
     middleware,
 };
+use axum::{body::Body, http::{header, HeaderMap}, response::{IntoResponse, Response}};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use crate::common::token::generate_token;
 
 use crate::common::responses::ApiResponse;
-use crate::models::submission::{Submission, CreateSubmissionRequest};
-use crate::models::submitter::Submitter;
+use crate::models::submission::{Submission, CreateSubmissionRequest, ExtendSubmissionRequest, VoidSubmissionRequest, SignedDocument, BulkSend, BulkSendColumn, BulkSendRowResult};
+use crate::models::submitter::{Submitter, PrefilledFieldValue};
 use crate::database::connection::DbPool;
-use crate::database::models::CreateSubmitter;
-use crate::database::queries::{SubmitterQueries, TemplateQueries, SubmissionFieldQueries, EmailTemplateQueries};
+use crate::database::models::{CreateSubmitter, CreateSubmission, DbBulkSend, DbSubmission, DbSubmitter, DbTemplate, DbTemplateRole};
+use crate::database::queries::{BulkSendQueries, TemplateRoleQueries, SubmitterQueries, SubmissionQueries, TemplateQueries, SubmissionFieldQueries, EmailTemplateQueries};
 use crate::database::models::CreateSubmissionField;
 use crate::routes::subscription::{can_user_submit, increment_usage_count_by};
 use crate::routes::templates::convert_db_template_to_template;
 use crate::common::jwt::auth_middleware;
 use crate::common::authorization::require_admin_or_team_member;
-use crate::services::email::EmailService;
+use crate::services::email::{EmailContext, EmailService};
+use crate::services::storage::StorageService;
+use crate::services::field_validation::{self, parse_validation};
+use crate::services::formula;
+use crate::services::{bulk_send, expiry, i18n::{self, Localizer}, spreadsheet, template_roles};
+use crate::services::jobs::{self, JobOutcome};
+use futures::future::BoxFuture;
 
 use crate::routes::web::AppState;
+use crate::services::pdf_preferences::{get_user_pdf_settings, generate_download_filename};
 
-use crate::common::utils::replace_template_variables;
+use crate::services::email_branding::EmailBranding;
+use crate::services::email_template_engine;
 
-#[utoipa::path(
-    post,
-    path = "/api/submissions",
-    tag = "submissions",
-    request_body = CreateSubmissionRequest,
-    responses(
-        (status = 201, description = "Submission created successfully", body = ApiResponse<Submission>),
-        (status = 400, description = "Bad request", body = ApiResponse<Submission>),
-        (status = 404, description = "Template not found", body = ApiResponse<Submission>)
-    ),
-    security(("bearer_auth" = []))
-)]
-pub async fn create_submission(
-    State(state): State<AppState>,
-    Extension(user_id): Extension<i64>,
-    Json(payload): Json<CreateSubmissionRequest>,
-) -> (StatusCode, Json<ApiResponse<Submission>>) {
+#[derive(Deserialize)]
+pub struct GetSubmissionsQuery {
+    pub page: Option<i64>,
+    pub limit: Option<i64>,
+    pub status: Option<String>,
+    pub include_archived: Option<bool>,
+}
+
+pub fn convert_db_submitter_to_submitter(db_submitter: DbSubmitter) -> Submitter {
+    let reminder_config = db_submitter.reminder_config.as_ref()
+        .and_then(|v| serde_json::from_value(v.clone()).ok());
+
+    Submitter {
+        id: Some(db_submitter.id),
+        template_id: Some(db_submitter.template_id),
+        submission_id: db_submitter.submission_id,
+        signing_order: Some(db_submitter.signing_order),
+        role_id: db_submitter.role_id,
+        locale: db_submitter.locale.clone(),
+        user_id: Some(db_submitter.user_id),
+        name: db_submitter.name,
+        email: db_submitter.email,
+        status: db_submitter.status,
+        signed_at: db_submitter.signed_at,
+        token: db_submitter.token,
+        bulk_signatures: db_submitter.bulk_signatures,
+        reminder_config,
+        last_reminder_sent_at: db_submitter.last_reminder_sent_at,
+        reminder_count: db_submitter.reminder_count,
+        created_at: db_submitter.created_at,
+        updated_at: db_submitter.updated_at,
+        session_id: db_submitter.session_id,
+        template_name: db_submitter.template_name,
+        decline_reason: db_submitter.decline_reason,
+        can_download: None,
+        global_settings: None,
+    }
+}
+
+pub fn convert_db_submission_to_submission(db_submission: DbSubmission, submitters: Option<Vec<Submitter>>) -> Submission {
+    let signed_document = match (&db_submission.signed_document_sha256, db_submission.signed_document_size) {
+        (Some(sha256), Some(size)) if db_submission.signed_document_key.is_some() => Some(SignedDocument {
+            sha256: sha256.clone(),
+            size,
+            digitally_signed: db_submission.signed_document_digitally_signed,
+            created_at: db_submission.signed_document_created_at,
+            url: format!("/api/submissions/{}/document", db_submission.id),
+        }),
+        _ => None,
+    };
+
+    Submission {
+        id: db_submission.id,
+        template_id: db_submission.template_id,
+        user_id: db_submission.user_id,
+        name: db_submission.name,
+        status: db_submission.status,
+        signing_mode: db_submission.signing_mode,
+        documents: None,
+        submitters,
+        created_at: db_submission.created_at,
+        updated_at: db_submission.updated_at,
+        expires_at: db_submission.expires_at,
+        completed_at: db_submission.completed_at,
+        archived_at: db_submission.archived_at,
+        signed_document,
+        voided_at: db_submission.voided_at,
+        void_reason: db_submission.void_reason,
+    }
+}
+
+// Upload the final PDF of a completed submission and record its SHA-256 hash
+pub async fn store_signed_document(
+    pool: &PgPool,
+    storage: &StorageService,
+    submission_id: i64,
+    pdf_bytes: Vec<u8>,
+    digitally_signed: bool,
+) -> Result<DbSubmission, String> {
+    let sha256 = format!("{:x}", Sha256::digest(&pdf_bytes));
+    let size = pdf_bytes.len() as i64;
+    // Random component keeps the key unguessable since storage objects may be publicly readable
+    let key = format!("submissions/{}/signed_{}.pdf", submission_id, generate_token());
+
+    storage.upload_file_with_key(pdf_bytes, &key, "application/pdf")
+        .await
+        .map_err(|e| format!("Failed to upload signed PDF: {}", e))?;
+
+    SubmissionQueries::set_signed_document(pool, submission_id, &key, &sha256, size, digitally_signed)
+        .await
+        .map_err(|e| format!("Failed to record signed PDF: {}", e))?
+        .ok_or_else(|| "Submission not found".to_string())
+}
+
+// Download the stored final PDF, verifying it against the recorded hash.
+// Returns Ok(None) when the submission has no stored document yet.
+pub async fn load_signed_document(
+    pool: &PgPool,
+    submission_id: i64,
+) -> Result<Option<Vec<u8>>, String> {
+    let db_submission = match SubmissionQueries::get_submission_by_id(pool, submission_id).await {
+        Ok(Some(submission)) => submission,
+        Ok(None) => return Ok(None),
+        Err(e) => return Err(format!("Failed to get submission: {}", e)),
+    };
+
+    let (key, expected_sha256) = match (db_submission.signed_document_key, db_submission.signed_document_sha256) {
+        (Some(key), Some(sha256)) => (key, sha256),
+        _ => return Ok(None),
+    };
+
+    let storage = StorageService::new()
+        .await
+        .map_err(|e| format!("Failed to initialize storage: {}", e))?;
+    let pdf_bytes = storage.download_file(&key)
+        .await
+        .map_err(|e| format!("Failed to download signed PDF: {}", e))?;
+
+    let actual_sha256 = format!("{:x}", Sha256::digest(&pdf_bytes));
+    if actual_sha256 != expected_sha256 {
+        return Err(format!("Signed PDF for submission {} does not match its recorded SHA-256 hash", submission_id));
+    }
+
+    Ok(Some(pdf_bytes))
+}
+
+// Owner always has access; other users need to be in the same account with a team role
+async fn can_access_submission(pool: &PgPool, db_submission: &DbSubmission, user_id: i64) -> Result<bool, sqlx::Error> {
+    can_access_owned(pool, db_submission.user_id, db_submission.account_id, user_id).await
+}
+
+// Owner, or a team member of the account the submitter's envelope belongs to
+pub async fn can_access_submitter(pool: &PgPool, db_submitter: &DbSubmitter, user_id: i64) -> Result<bool, sqlx::Error> {
+    if db_submitter.user_id == user_id {
+        return Ok(true);
+    }
+    let account_id = match db_submitter.submission_id {
+        Some(submission_id) => SubmissionQueries::get_submission_by_id(pool, submission_id).await?
+            .and_then(|submission| submission.account_id),
+        None => crate::database::queries::UserQueries::get_user_by_id(pool, db_submitter.user_id).await?
+            .and_then(|owner| owner.account_id),
+    };
+    can_access_owned(pool, db_submitter.user_id, account_id, user_id).await
+}
+
+// Owner, or a team member of the owning account
+async fn can_access_owned(pool: &PgPool, owner_id: i64, owner_account_id: Option<i64>, user_id: i64) -> Result<bool, sqlx::Error> {
+    if owner_id == user_id {
+        return Ok(true);
+    }
+
+    match crate::database::queries::UserQueries::get_user_by_id(pool, user_id).await? {
+        Some(user) => Ok(user.account_id.is_some()
+            && user.account_id == owner_account_id
+            && matches!(
+                user.role,
+                crate::models::role::Role::Editor |
+                crate::models::role::Role::Admin |
+                crate::models::role::Role::Member
+            )),
+        None => Ok(false),
+    }
+}
+
+// Send the invitation email for one submitter using the user's default invitation template.
+// Returns true when an email was actually sent (used for usage counting).
+pub async fn send_invitation_email(
+    pool: &PgPool,
+    db_template: &DbTemplate,
+    submitter: &DbSubmitter,
+) -> bool {
+    let user_id = submitter.user_id;
+    let submitter_name = submitter.name.as_str();
+    let submitter_email = submitter.email.as_str();
+    let token = submitter.token.as_str();
+    let template = convert_db_template_to_template(db_template.clone());
+    let branding = EmailBranding::for_user(pool, user_id).await.for_recipient(submitter);
+    let email_service = match EmailService::new(pool) {
+        Ok(service) => service.with_context(EmailContext {
+            user_id: Some(user_id),
+            submitter_id: Some(submitter.id),
+            kind: Some("invitation".to_string()),
+        }).with_branding(&branding),
+        Err(e) => {
+            eprintln!("Failed to initialize email service: {}", e);
+            return false;
+        }
+    };
+
+    // Try to get user's default invitation template
+    let email_template = match EmailTemplateQueries::get_default_template_by_type(pool, user_id, "invitation").await {
+        Ok(Some(email_template)) => email_template,
+        _ => {
+            // No email template found, skip sending email
+            eprintln!("No email template found for user {}, skipping email send", user_id);
+            return false;
+        }
+    };
+
+    let base_url = std::env::var("BASE_URL").unwrap_or_else(|_| "http://localhost:8081".to_string());
+    let signature_link = format!("{}/templates/{}/edit", base_url, token);
+    let mut context = branding.context();
+    email_template_engine::insert_submitter_variables(pool, &mut context, submitter, &template.name).await;
+    context.insert("submitter.link", signature_link.as_str());
+
+    let (subject, mut body) = match email_template_engine::render_email(
+        &email_template.subject,
+        &email_template.body,
+        &email_template.body_format,
+        &context,
+    ) {
+        Ok(rendered) => rendered,
+        Err(e) => {
+            eprintln!("Failed to render invitation email for {}: {}", submitter_email, e);
+            return false;
+        }
+    };
 
+    // If the template doesn't use submitter.link, append the link by default
+    if !email_template.body.contains("submitter.link") {
+        if email_template.body_format == "html" {
+            let link_html = format!("<br><br><strong></strong> <a href=\"{}\">{}</a>", signature_link, signature_link);
+            body.push_str(&link_html);
+        } else {
+            let link_text = format!("\n\n{}", signature_link);
+            body.push_str(&link_text);
+        }
+    }
+
+    // Tracking pixel so opening the email shows up in the audit trail
+    if email_template.body_format == "html" {
+        body.push_str(&format!(
+            "<img src=\"{}/public/submissions/{}/open.gif\" width=\"1\" height=\"1\" alt=\"\" style=\"display:none\">",
+            base_url, token
+        ));
+    }
+
+    let body = match branding.apply_layout(body, &email_template.body_format, &context) {
+        Ok(body) => body,
+        Err(e) => {
+            eprintln!("Failed to render invitation email for {}: {}", submitter_email, e);
+            return false;
+        }
+    };
+
+    // Generate attachments if needed
+    let mut document_path = None;
+
+    if email_template.attach_documents {
+        // Generate original PDF for invitation
+        if let Ok(storage_service) = crate::services::storage::StorageService::new().await {
+            if let Some(documents) = &db_template.documents {
+                if let Ok(docs) = serde_json::from_value::<Vec<crate::models::template::Document>>(documents.clone()) {
+                    if let Some(first_doc) = docs.first() {
+                        if let Ok(pdf_bytes) = storage_service.download_file(&first_doc.url).await {
+                            let temp_file = std::env::temp_dir().join(format!("original_document_{}.pdf", db_template.id));
+                            if tokio::fs::write(&temp_file, pdf_bytes).await.is_ok() {
+                                document_path = Some(temp_file.to_string_lossy().to_string());
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    let sent = match email_service.send_template_email(
+        submitter_email,
+        submitter_name,
+        &subject,
+        &body,
+        &email_template.body_format,
+        email_template.attach_documents,
+        email_template.attach_audit_log,
+        document_path.as_deref(),
+        None, // No audit log for invitation
+    ).await {
+        Ok(_) => true,
+        Err(e) => {
+            eprintln!("Failed to send template email to {}: {}", submitter_email, e);
+            false
+        }
+    };
+
+    // Clean up temporary file
+    if let Some(path) = document_path {
+        let _ = tokio::fs::remove_file(path).await;
+    }
+
+    sent
+}
+
+// Tell a submitter that the submission they were invited to was voided, using the
+// user's default cancellation template. Returns true when an email was sent.
+async fn send_cancellation_email(
+    pool: &PgPool,
+    user_id: i64,
+    template_name: &str,
+    submitter: &DbSubmitter,
+    reason: &str,
+) -> bool {
+    let branding = EmailBranding::for_user(pool, user_id).await.for_recipient(submitter);
+    let email_service = match EmailService::new(pool) {
+        Ok(service) => service.with_context(EmailContext {
+            user_id: Some(user_id),
+            submitter_id: Some(submitter.id),
+            kind: Some("cancellation".to_string()),
+        }).with_branding(&branding),
+        Err(e) => {
+            eprintln!("Failed to initialize email service: {}", e);
+            return false;
+        }
+    };
+
+    let email_template = match EmailTemplateQueries::get_default_template_by_type(pool, user_id, "cancellation").await {
+        Ok(Some(email_template)) => email_template,
+        _ => {
+            eprintln!("No cancellation email template found for user {}, skipping email send", user_id);
+            return false;
+        }
+    };
+
+    let mut context = branding.context();
+    email_template_engine::insert_submitter_variables(pool, &mut context, submitter, template_name).await;
+    context.insert("void.reason", reason);
+
+    let (subject, body) = match branding.render(&email_template, &context) {
+        Ok(rendered) => rendered,
+        Err(e) => {
+            eprintln!("Failed to render cancellation email for {}: {}", submitter.email, e);
+            return false;
+        }
+    };
+
+    match email_service.send_template_email(
+        &submitter.email,
+        &submitter.name,
+        &subject,
+        &body,
+        &email_template.body_format,
+        false,
+        false,
+        None,
+        None,
+    ).await {
+        Ok(_) => true,
+        Err(e) => {
+            eprintln!("Failed to send cancellation email to {}: {}", submitter.email, e);
+            false
+        }
+    }
+}
+
+// Record that a submitter was invited to sign
+async fn record_sent_event(pool: &PgPool, submitter: &DbSubmitter, email_sent: bool) {
+    let details = if email_sent {
+        format!("Document sent to {} for signature", submitter.email)
+    } else {
+        format!("Signing link issued to {} (no invitation email sent)", submitter.email)
+    };
+    crate::services::audit::record_event(
+        pool,
+        submitter,
+        crate::services::audit::EVENT_SENT,
+        "System",
+        details,
+        crate::services::audit::AuditContext::system(),
+        Some(serde_json::json!({ "email_sent": email_sent })),
+    ).await;
+}
+
+// Invite the next signing group of a sequential submission once nobody earlier in the order is still signing.
+// Returns the number of submitters that were invited.
+pub async fn invite_next_signing_group(pool: &PgPool, submission_id: i64) -> Result<usize, String> {
+    let submitters = SubmitterQueries::get_submitters_by_submission_id(pool, submission_id)
+        .await
+        .map_err(|e| format!("Failed to get submitters: {}", e))?;
+
+    // A declined envelope does not move forward
+    if submitters.iter().any(|s| s.status == "declined") {
+        return Ok(0);
+    }
+
+    let next_order = match submitters.iter().filter(|s| s.status == "waiting").map(|s| s.signing_order).min() {
+        Some(order) => order,
+        None => return Ok(0),
+    };
+
+    let still_signing = submitters.iter().any(|s| {
+        s.signing_order < next_order && s.status != "signed" && s.status != "completed"
+    });
+    if still_signing {
+        return Ok(0);
+    }
+
+    let db_template = TemplateQueries::get_template_by_id(pool, submitters[0].template_id)
+        .await
+        .map_err(|e| format!("Failed to get template: {}", e))?
+        .ok_or("Template not found")?;
+
+    let mut invited = 0;
+    let mut emails_sent_count = 0;
+    for submitter in submitters.iter().filter(|s| s.status == "waiting" && s.signing_order == next_order) {
+        let claimed = SubmitterQueries::invite_submitter(pool, submitter.id)
+            .await
+            .map_err(|e| format!("Failed to invite submitter {}: {}", submitter.id, e))?;
+        // Whoever moved the submitter out of waiting sends the invitation
+        if !claimed {
+            continue;
+        }
+        invited += 1;
+
+        let email_sent = send_invitation_email(pool, &db_template, submitter).await;
+        if email_sent {
+            emails_sent_count += 1;
+        }
+        record_sent_event(pool, submitter, email_sent).await;
+        crate::services::reminder_queue::schedule_next_reminder(pool, submitter.id).await;
+    }
+
+    if emails_sent_count > 0 {
+        if let Err(e) = increment_usage_count_by(pool, submitters[0].user_id, emails_sent_count).await {
+            eprintln!("Warning: Failed to increment usage count for user {} by {}: {}", submitters[0].user_id, emails_sent_count, e);
+        }
+    }
+
+    println!("📨 Submission {}: invited {} submitter(s) with signing order {}", submission_id, invited, next_order);
+    Ok(invited)
+}
+
+// Match a submitter's prefilled values to the template fields their role fills: field id -> (value, readonly)
+pub fn resolve_prefilled_values(
+    template_fields: &[crate::database::models::DbTemplateField],
+    role_id: Option<i64>,
+    values: &[PrefilledFieldValue],
+) -> Result<std::collections::HashMap<i64, (String, bool)>, String> {
+    let mut resolved = std::collections::HashMap::new();
+    for prefilled in values {
+        let field = match (prefilled.field_id, prefilled.field_name.as_deref()) {
+            (Some(id), _) => template_fields.iter().find(|f| f.id == id)
+                .ok_or_else(|| format!("field {} does not exist in this template", id))?,
+            // A name shared by several roles means this submitter's field
+            (None, Some(name)) => template_fields.iter()
+                .find(|f| f.name == name && template_roles::can_fill(f.role_id, role_id))
+                .or_else(|| template_fields.iter().find(|f| f.name == name))
+                .ok_or_else(|| format!("field '{}' does not exist in this template", name))?,
+            (None, None) => return Err("each value needs a field_id or field_name".to_string()),
+        };
+        if !template_roles::can_fill(field.role_id, role_id) {
+            return Err(format!("field '{}' belongs to another role", field.name));
+        }
+        if field.field_type == formula::FIELD_TYPE_FORMULA {
+            return Err(format!("field '{}' is calculated and cannot be prefilled", field.name));
+        }
+        let rules = parse_validation(field.validation.as_ref());
+        if let Err(violation) = field_validation::check_value(&field.field_type, rules.as_ref(), &prefilled.value) {
+            return Err(format!("field '{}': {}", field.name, violation.message));
+        }
+        if resolved.insert(field.id, (prefilled.value.clone(), prefilled.readonly)).is_some() {
+            return Err(format!("field '{}' is given more than once", field.name));
+        }
+    }
+    Ok(resolved)
+}
+
+// Duplicate emails and signing mode are checked the same way for single and bulk sends
+pub fn validate_submission_request(payload: &CreateSubmissionRequest) -> Result<(), String> {
     // Check for duplicate emails in the submission
     let emails: std::collections::HashSet<_> = payload.submitters.iter().map(|s| &s.email).collect();
     if emails.len() != payload.submitters.len() {
-        return ApiResponse::bad_request("Duplicate emails in submission".to_string());
+        return Err("Duplicate emails in submission".to_string());
     }
 
-    // Generate a unique session_id for this submission
-    let submission_session_id = generate_token();
+    let signing_mode = payload.signing_mode.as_deref().unwrap_or("parallel");
+    if signing_mode != "parallel" && signing_mode != "sequential" {
+        return Err("Invalid signing_mode. Must be 'parallel' or 'sequential'".to_string());
+    }
 
-    let pool = &state.lock().await.db_pool;
+    if payload.expires_at.is_some_and(|expires_at| expires_at <= Utc::now()) {
+        return Err("expires_at must be in the future".to_string());
+    }
+    Ok(())
+}
 
-    // Check usage limits considering the number of emails being sent
-    let emails_to_send = payload.submitters.len() as i32;
+// Check the sender's plan against the number of emails about to be sent; returns the sender's account
+pub async fn check_send_allowance(pool: &PgPool, user_id: i64, emails_to_send: i32) -> Result<Option<i64>, String> {
     match crate::database::queries::UserQueries::get_user_by_id(pool, user_id).await {
         Ok(Some(user)) => {
             match user.subscription_status.as_str() {
                 "premium" => {
                     if let Some(expires_at) = user.subscription_expires_at {
                         if expires_at <= chrono::Utc::now() {
-                            return ApiResponse::forbidden("Your premium subscription has expired. Please renew to continue sending documents.".to_string());
+                            return Err("Your premium subscription has expired. Please renew to continue sending documents.".to_string());
                         }
                     } else {
-                        return ApiResponse::forbidden("Invalid premium subscription. Please contact support.".to_string());
+                        return Err("Invalid premium subscription. Please contact support.".to_string());
                     }
                 },
                 "free" => {
@@ -76,11 +544,11 @@ pub async fn create_submission(
                     let remaining_sends = 10 - current_usage;
                     
                     if current_usage >= 10 {
-                        return ApiResponse::forbidden("You have reached the free email sending limit (10 emails). Please upgrade to the Premium plan to continue sending documents.".to_string());
+                        return Err("You have reached the free email sending limit (10 emails). Please upgrade to the Premium plan to continue sending documents.".to_string());
                     }
                     
                     if current_usage + emails_to_send > 10 {
-                        return ApiResponse::forbidden(format!("You are trying to send {} emails, but you only have {} free sends remaining. Please upgrade to the Premium plan to send more emails.", emails_to_send, remaining_sends));
+                        return Err(format!("You are trying to send {} emails, but you only have {} free sends remaining. Please upgrade to the Premium plan to send more emails.", emails_to_send, remaining_sends));
                     }
                     
                     // Show warning if this will use up remaining free sends
@@ -90,257 +558,1157 @@ pub async fn create_submission(
                     }
                 },
                 _ => {
-                    return ApiResponse::forbidden("Invalid subscription status. Please contact support.".to_string());
+                    return Err("Invalid subscription status. Please contact support.".to_string());
                 }
             }
+            Ok(user.account_id)
         },
-        _ => return ApiResponse::forbidden("User not found".to_string()),
+        Ok(None) => Err("User not found".to_string()),
+        Err(e) => Err(format!("Database error: {}", e)),
     }
+}
 
-    // Check if template exists
-    match TemplateQueries::get_template_by_id(pool, payload.template_id).await {
-        Ok(Some(db_template)) => {
-            // Check if user has permission to access this template
-            match crate::database::queries::UserQueries::get_user_by_id(pool, user_id).await {
-                Ok(Some(user)) => {
-                    // Allow access if user is the owner OR if user has Editor/Admin/Member role (Members can send signature requests from all team templates)
-                    let has_access = db_template.user_id == user_id || 
-                            matches!(
-                                user.role, 
-                                crate::models::role::Role::Editor |
-                                crate::models::role::Role::Admin |
-                                crate::models::role::Role::Member |
-                                crate::models::role::Role::Agent
-                            );
-                    
-                    if !has_access {
-                        return ApiResponse::forbidden("You do not have access to this form".to_string());
+// Owner, or a team member whose role may send signature requests from all team templates
+pub async fn can_send_template(pool: &PgPool, db_template: &DbTemplate, user_id: i64) -> Result<bool, sqlx::Error> {
+    let Some(user) = crate::database::queries::UserQueries::get_user_by_id(pool, user_id).await? else {
+        return Ok(false);
+    };
+    Ok(db_template.user_id == user_id ||
+        matches!(
+            user.role,
+            crate::models::role::Role::Editor |
+            crate::models::role::Role::Admin |
+            crate::models::role::Role::Member |
+            crate::models::role::Role::Agent
+        ))
+}
+
+// Per-submitter data resolved against the template before anything is created
+pub struct PreparedSubmitter {
+    pub role_id: Option<i64>,
+    pub locale: Option<&'static str>,
+    pub prefilled_values: std::collections::HashMap<i64, (String, bool)>,
+}
+
+// Resolve roles and prefilled values up front so a bad value does not leave a half-created envelope
+pub fn prepare_submitters(
+    template_fields: &[crate::database::models::DbTemplateField],
+    roles: &[DbTemplateRole],
+    payload: &CreateSubmissionRequest,
+) -> Result<Vec<PreparedSubmitter>, String> {
+    let role_ids = template_roles::assign_roles(roles, &payload.submitters)?;
+    payload.submitters.iter().zip(role_ids)
+        .map(|(submitter, role_id)| {
+            let prefilled_values = resolve_prefilled_values(template_fields, role_id, &submitter.values)
+                .map_err(|e| format!("Invalid values for {}: {}", submitter.email, e))?;
+            let locale = submitter.locale.as_deref()
+                .map(i18n::validate_locale)
+                .transpose()
+                .map_err(|e| format!("Invalid locale for {}: {}", submitter.email, e))?;
+            Ok(PreparedSubmitter { role_id, locale, prefilled_values })
+        })
+        .collect()
+}
+
+// Submitters without an explicit order sign in the order they were listed
+fn signing_orders(payload: &CreateSubmissionRequest) -> Vec<i32> {
+    payload.submitters.iter().enumerate()
+        .map(|(index, s)| s.order.unwrap_or(index as i32))
+        .collect()
+}
+
+// Invitations sent when the envelope is created: everyone, or only the first signing group
+fn initial_invites(payload: &CreateSubmissionRequest) -> i32 {
+    let orders = signing_orders(payload);
+    if payload.signing_mode.as_deref() != Some("sequential") {
+        return orders.len() as i32;
+    }
+    let first = orders.iter().copied().min().unwrap_or(0);
+    orders.iter().filter(|order| **order == first).count() as i32
+}
+
+// Create one envelope with its submitters and their submission fields and invite the first
+// signing group; returns the envelope and the number of invitations sent, which the caller
+// counts against the sender's plan
+pub async fn create_envelope(
+    pool: &PgPool,
+    user_id: i64,
+    account_id: Option<i64>,
+    db_template: &DbTemplate,
+    template_fields: &[crate::database::models::DbTemplateField],
+    payload: &CreateSubmissionRequest,
+    prepared: &[PreparedSubmitter],
+) -> Result<(Submission, i32), String> {
+    let signing_mode = payload.signing_mode.clone().unwrap_or_else(|| "parallel".to_string());
+
+    let signing_orders = signing_orders(payload);
+    let first_signing_order = signing_orders.iter().copied().min().unwrap_or(0);
+
+    // Generate a unique session_id for this submission
+    let submission_session_id = generate_token();
+
+    // Every send is its own envelope, even when the same template is sent several times
+    let create_submission = CreateSubmission {
+        template_id: payload.template_id,
+        user_id,
+        account_id,
+        name: payload.name.clone().or_else(|| Some(db_template.name.clone())),
+        signing_mode: signing_mode.clone(),
+        session_id: Some(submission_session_id.clone()),
+        expires_at: payload.expires_at,
+    };
+    let db_submission = match SubmissionQueries::create_submission(pool, create_submission).await {
+        Ok(submission) => submission,
+        Err(e) => return Err(format!("Failed to create submission: {}", e)),
+    };
+
+    let mut created_submitters = Vec::new();
+    let mut emails_sent_count = 0;
+
+    for ((submitter, &signing_order), prepared) in payload.submitters.iter().zip(signing_orders.iter()).zip(prepared.iter()) {
+        let token = generate_token();
+
+        // In sequential mode only the first signing group is invited right away
+        let status = if signing_mode == "sequential" && signing_order > first_signing_order {
+            "waiting"
+        } else {
+            "pending"
+        };
+        
+        // Get reminder config: use provided config or user's default settings
+        let reminder_config_json = if let Some(config) = &submitter.reminder_config {
+            // Use explicitly provided config
+            serde_json::to_value(config).ok()
+        } else {
+            // Get user's default reminder settings
+            match crate::database::queries::UserReminderSettingsQueries::get_or_create_default(pool, user_id).await {
+                Ok(user_settings) => {
+                    // Check if all hours are configured (not NULL) - auto enabled
+                    if let (Some(first), Some(second), Some(third)) = (
+                        user_settings.first_reminder_hours,
+                        user_settings.second_reminder_hours,
+                        user_settings.third_reminder_hours
+                    ) {
+                        // Convert user settings to ReminderConfig
+                        let config = crate::models::submitter::ReminderConfig {
+                            first_reminder_hours: first,
+                            second_reminder_hours: second,
+                            third_reminder_hours: third,
+                        };
+                        serde_json::to_value(&config).ok()
+                    } else {
+                        // Hours not configured yet - reminders disabled
+                        None
                     }
                 }
-                _ => return ApiResponse::forbidden("User not found".to_string()),
+                _ => None, // Error getting settings
             }
+        };
+        
+        let create_submitter = CreateSubmitter {
+            template_id: payload.template_id,
+            user_id: user_id,
+            name: submitter.name.clone(),
+            email: submitter.email.clone(),
+            status: status.to_string(),
+            token: token.clone(),
+            reminder_config: reminder_config_json,
+            session_id: Some(submission_session_id.clone()),
+            submission_id: Some(db_submission.id),
+            signing_order,
+            role_id: prepared.role_id,
+            locale: prepared.locale.map(str::to_string),
+        };
 
-            // In merged schema, we create submitters directly without a separate submission record
-            let mut created_submitters = Vec::new();
-            let mut emails_sent_count = 0;
-
-            for submitter in &payload.submitters {
-                let token = generate_token();
-                
-                // Get reminder config: use provided config or user's default settings
-                let reminder_config_json = if let Some(config) = &submitter.reminder_config {
-                    // Use explicitly provided config
-                    serde_json::to_value(config).ok()
-                } else {
-                    // Get user's default reminder settings
-                    match crate::database::queries::UserReminderSettingsQueries::get_or_create_default(pool, user_id).await {
-                        Ok(user_settings) => {
-                            // Check if all hours are configured (not NULL) - auto enabled
-                            if let (Some(first), Some(second), Some(third)) = (
-                                user_settings.first_reminder_hours,
-                                user_settings.second_reminder_hours,
-                                user_settings.third_reminder_hours
-                            ) {
-                                // Convert user settings to ReminderConfig
-                                let config = crate::models::submitter::ReminderConfig {
-                                    first_reminder_hours: first,
-                                    second_reminder_hours: second,
-                                    third_reminder_hours: third,
-                                };
-                                serde_json::to_value(&config).ok()
-                            } else {
-                                // Hours not configured yet - reminders disabled
-                                None
-                            }
-                        }
-                        _ => None, // Error getting settings
-                    }
-                };
-                
-                let create_submitter = CreateSubmitter {
-                    template_id: payload.template_id,
-                    user_id: user_id,
-                    name: submitter.name.clone(),
-                    email: submitter.email.clone(),
-                    status: "pending".to_string(),
-                    token: token.clone(),
-                    reminder_config: reminder_config_json,
-                    session_id: Some(submission_session_id.clone()),
+        match SubmitterQueries::create_submitter(pool, create_submitter).await {
+            Ok(db_submitter) => {
+                let reminder_config = db_submitter.reminder_config.as_ref()
+                    .and_then(|v| serde_json::from_value(v.clone()).ok());
+                    
+                // Kept for the audit trail; the fields below are moved into the API model
+                let created_submitter = db_submitter.clone();
+                let submitter_api = Submitter {
+                    id: Some(db_submitter.id),
+                    template_id: Some(db_submitter.template_id),
+                    submission_id: db_submitter.submission_id,
+                    signing_order: Some(db_submitter.signing_order),
+                    role_id: db_submitter.role_id,
+                    locale: db_submitter.locale.clone(),
+                    user_id: Some(db_submitter.user_id),
+                    name: db_submitter.name,
+                    email: db_submitter.email,
+                    status: db_submitter.status,
+                    signed_at: db_submitter.signed_at,
+                    token: db_submitter.token,
+                    bulk_signatures: db_submitter.bulk_signatures,
+                    reminder_config,
+                    last_reminder_sent_at: db_submitter.last_reminder_sent_at,
+                    reminder_count: db_submitter.reminder_count,
+                    created_at: db_submitter.created_at,
+                    updated_at: db_submitter.updated_at,
+                    session_id: db_submitter.session_id,
+                    template_name: None,
+                    decline_reason: db_submitter.decline_reason,
+                    can_download: None,
+                    global_settings: None,
                 };
+                created_submitters.push(submitter_api.clone());
 
-                match SubmitterQueries::create_submitter(pool, create_submitter).await {
-                    Ok(db_submitter) => {
-                        let reminder_config = db_submitter.reminder_config.as_ref()
-                            .and_then(|v| serde_json::from_value(v.clone()).ok());
-                            
-                        let submitter_api = Submitter {
-                            id: Some(db_submitter.id),
-                            template_id: Some(db_submitter.template_id),
-                            user_id: Some(db_submitter.user_id),
-                            name: db_submitter.name,
-                            email: db_submitter.email,
-                            status: db_submitter.status,
-                            signed_at: db_submitter.signed_at,
-                            token: db_submitter.token,
-                            bulk_signatures: db_submitter.bulk_signatures,
-                            reminder_config,
-                            last_reminder_sent_at: db_submitter.last_reminder_sent_at,
-                            reminder_count: db_submitter.reminder_count,
-                            created_at: db_submitter.created_at,
-                            updated_at: db_submitter.updated_at,
-                            session_id: db_submitter.session_id,
-                            template_name: None,
-                            decline_reason: db_submitter.decline_reason,
-                            can_download: None,
-                            global_settings: None,
-                        };
-                        created_submitters.push(submitter_api.clone());
-
-                        // Copy template fields to submission fields for this submitter
-                        match crate::database::queries::TemplateFieldQueries::get_template_fields(pool, payload.template_id).await {
-                            Ok(template_fields) => {
-                                for db_field in template_fields {
-                                    let create_field = CreateSubmissionField {
-                                        submitter_id: db_submitter.id,
-                                        template_field_id: db_field.id,
-                                        name: db_field.name,
-                                        field_type: db_field.field_type,
-                                        required: db_field.required,
-                                        display_order: db_field.display_order,
-                                        position: db_field.position,
-                                        options: db_field.options,
-                                        metadata: db_field.metadata,
-                                        partner: db_field.partner,
-                                    };
-                                    if let Err(e) = SubmissionFieldQueries::create_submission_field(pool, create_field).await {
-                                        eprintln!("Failed to create submission field for submitter {}: {}", db_submitter.id, e);
-                                        // Continue with other fields, don't fail the whole submission
-                                    }
-                                }
-                            }
-                            Err(e) => {
-                                eprintln!("Failed to get template fields for submission copy: {}", e);
-                                // Continue, don't fail the submission
-                            }
-                        }
-
-                        // Send email to submitter using Email Templates
-                        let template = convert_db_template_to_template(db_template.clone());
-                        if let Ok(email_service) = EmailService::new() {
-                            // Try to get user's default invitation template
-                            let email_template_result = EmailTemplateQueries::get_default_template_by_type(
-                                pool, user_id, "invitation"
-                            ).await;
-
-                            match email_template_result {
-                                Ok(Some(email_template)) => {
-                                    // Use custom email template
-                                    let base_url = std::env::var("BASE_URL").unwrap_or_else(|_| "http://localhost:8081".to_string());
-                                    let signature_link = format!("{}/templates/{}/edit", base_url, token);
-                                    let mut variables = std::collections::HashMap::new();
-                                    variables.insert("submitter.name", submitter.name.as_str());
-                                    variables.insert("template.name", template.name.as_str());
-                                    variables.insert("submitter.link", &signature_link);
-                                    variables.insert("account.name", "DocuSeal Pro");
-
-                                    let subject = replace_template_variables(&email_template.subject, &variables);
-                                    let mut body = replace_template_variables(&email_template.body, &variables);
-
-                                    // If the template doesn't contain {submitter.link}, append the link by default
-                                    if !email_template.body.contains("{submitter.link}") {
-                                        if email_template.body_format == "html" {
-                                            let link_html = format!("<br><br><strong></strong> <a href=\"{}\">{}</a>", signature_link, signature_link);
-                                            body.push_str(&link_html);
-                                        } else {
-                                            let link_text = format!("\n\n{}", signature_link);
-                                            body.push_str(&link_text);
-                                        }
-                                    }
-
-                                    // Generate attachments if needed
-                                    let mut document_path = None;
-
-                                    if email_template.attach_documents {
-                                        // Generate original PDF for invitation
-                                        if let Ok(storage_service) = crate::services::storage::StorageService::new().await {
-                                            if let Some(documents) = &db_template.documents {
-                                                if let Ok(docs) = serde_json::from_value::<Vec<crate::models::template::Document>>(documents.clone()) {
-                                                    if let Some(first_doc) = docs.first() {
-                                                        if let Ok(pdf_bytes) = storage_service.download_file(&first_doc.url).await {
-                                                            let temp_file = std::env::temp_dir().join(format!("original_document_{}.pdf", db_template.id));
-                                                            if let Ok(_) = tokio::fs::write(&temp_file, pdf_bytes).await {
-                                                                document_path = Some(temp_file.to_string_lossy().to_string());
-                                                            }
-                                                        }
-                                                    }
-                                                }
-                                            }
-                                        }
-                                    }
-
-                                    if let Err(e) = email_service.send_template_email(
-                                        &submitter.email,
-                                        &submitter.name,
-                                        &subject,
-                                        &body,
-                                        &email_template.body_format,
-                                        email_template.attach_documents,
-                                        email_template.attach_audit_log,
-                                        document_path.as_deref(),
-                                        None, // No audit log for invitation
-                                    ).await {
-                                        eprintln!("Failed to send template email to {}: {}", submitter.email, e);
-                                    } else {
-                                        emails_sent_count += 1;
-                                    }
-
-                                    // Clean up temporary file
-                                    if let Some(path) = document_path {
-                                        let _ = tokio::fs::remove_file(path).await;
-                                    }
-                                },
-                                _ => {
-                                    // No email template found, skip sending email
-                                    eprintln!("No email template found for user {}, skipping email send", user_id);
-                                }
-                            }
-                        }
+                // Copy template fields to submission fields for this submitter
+                for db_field in template_fields.iter().cloned() {
+                    let prefilled = prepared.prefilled_values.get(&db_field.id);
+                    let create_field = CreateSubmissionField {
+                        submitter_id: db_submitter.id,
+                        template_field_id: db_field.id,
+                        name: db_field.name,
+                        field_type: db_field.field_type,
+                        required: db_field.required,
+                        display_order: db_field.display_order,
+                        position: db_field.position,
+                        options: db_field.options,
+                        metadata: db_field.metadata,
+                        partner: db_field.partner,
+                        conditions: db_field.conditions,
+                        validation: db_field.validation,
+                        role_id: db_field.role_id,
+                        prefilled_value: prefilled.map(|(value, _)| value.clone()),
+                        readonly: prefilled.map(|(_, readonly)| *readonly).unwrap_or(false),
+                    };
+                    if let Err(e) = SubmissionFieldQueries::create_submission_field(pool, create_field).await {
+                        eprintln!("Failed to create submission field for submitter {}: {}", db_submitter.id, e);
+                        // Continue with other fields, don't fail the whole submission
                     }
-                    Err(e) => {
-                        return ApiResponse::internal_error(format!("Failed to create submitter: {}", e));
+                }
+
+                // Waiting submitters are invited when the previous signing group completes
+                if status != "waiting" {
+                    let email_sent = send_invitation_email(pool, db_template, &created_submitter).await;
+                    if email_sent {
+                        emails_sent_count += 1;
                     }
+                    record_sent_event(pool, &created_submitter, email_sent).await;
+                    crate::services::reminder_queue::schedule_next_reminder(pool, created_submitter.id).await;
                 }
             }
+            Err(e) => {
+                return Err(format!("Failed to create submitter: {}", e));
+            }
+        }
+    }
+
+    if let Ok(db_submitters) = SubmitterQueries::get_submitters_by_submission_id(pool, db_submission.id).await {
+        crate::services::webhooks::dispatch_event(
+            pool,
+            user_id,
+            crate::services::webhooks::EVENT_SUBMISSION_CREATED,
+            crate::services::webhooks::submission_event_data(&db_submission, &db_submitters),
+        ).await;
+    }
+
+    let submission = convert_db_submission_to_submission(db_submission, Some(created_submitters));
+
+    Ok((submission, emails_sent_count))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/submissions",
+    tag = "submissions",
+    request_body = CreateSubmissionRequest,
+    responses(
+        (status = 201, description = "Submission created successfully", body = ApiResponse<Submission>),
+        (status = 400, description = "Bad request", body = ApiResponse<Submission>),
+        (status = 404, description = "Template not found", body = ApiResponse<Submission>)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn create_submission(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<i64>,
+    Json(payload): Json<CreateSubmissionRequest>,
+) -> (StatusCode, Json<ApiResponse<Submission>>) {
+    if let Err(e) = validate_submission_request(&payload) {
+        return ApiResponse::bad_request(e);
+    }
 
-            // Create synthetic submission response
-            let submission = Submission {
-                id: payload.template_id, // Use template_id as submission id
-                template_id: payload.template_id,
-                user_id: user_id,
-                status: "active".to_string(),
-                documents: None,
-                submitters: Some(created_submitters),
-                created_at: chrono::Utc::now(),
-                updated_at: chrono::Utc::now(),
-                expires_at: payload.expires_at,
+    let pool = &state.lock().await.db_pool;
+
+    // Check usage limits considering the number of emails being sent
+    let account_id = match check_send_allowance(pool, user_id, payload.submitters.len() as i32).await {
+        Ok(account_id) => account_id,
+        Err(e) => return ApiResponse::forbidden(e),
+    };
+
+    // Check if template exists
+    match TemplateQueries::get_template_by_id(pool, payload.template_id).await {
+        Ok(Some(db_template)) => {
+            match can_send_template(pool, &db_template, user_id).await {
+                Ok(true) => {}
+                Ok(false) => return ApiResponse::forbidden("You do not have access to this form".to_string()),
+                Err(e) => return ApiResponse::internal_error(format!("Database error: {}", e)),
+            }
+
+            let template_fields = match crate::database::queries::TemplateFieldQueries::get_template_fields(pool, payload.template_id).await {
+                Ok(fields) => fields,
+                Err(e) => return ApiResponse::internal_error(format!("Failed to get template fields: {}", e)),
+            };
+
+            let roles = match TemplateRoleQueries::get_roles_by_template(pool, payload.template_id).await {
+                Ok(roles) => roles,
+                Err(e) => return ApiResponse::internal_error(format!("Failed to get template roles: {}", e)),
+            };
+
+            let prepared = match prepare_submitters(&template_fields, &roles, &payload) {
+                Ok(prepared) => prepared,
+                Err(e) => return ApiResponse::bad_request(e),
             };
 
-            // Increment usage count cho số email đã gửi thành công
-            if emails_sent_count > 0 {
-                if let Err(e) = increment_usage_count_by(pool, user_id, emails_sent_count).await {
-                    eprintln!("Warning: Failed to increment usage count for user {} by {}: {}", user_id, emails_sent_count, e);
-                    // Don't fail the request, just log the warning
+            match create_envelope(pool, user_id, account_id, &db_template, &template_fields, &payload, &prepared).await {
+                Ok((submission, emails_sent_count)) => {
+                    // Increment usage count cho số email đã gửi thành công
+                    if emails_sent_count > 0 {
+                        if let Err(e) = increment_usage_count_by(pool, user_id, emails_sent_count).await {
+                            eprintln!("Warning: Failed to increment usage count for user {} by {}: {}", user_id, emails_sent_count, e);
+                            // Don't fail the request, just log the warning
+                        }
+                    }
+                    ApiResponse::success(submission, "Submission created successfully".to_string())
                 }
+                Err(e) => ApiResponse::internal_error(e),
             }
-
-            ApiResponse::success(submission, "Submission created successfully".to_string())
         }
         Ok(None) => ApiResponse::not_found("Template not found".to_string()),
         Err(e) => ApiResponse::internal_error(format!("Database error: {}", e)),
     }
 }
 
+pub fn convert_db_bulk_send_to_bulk_send(db_bulk_send: DbBulkSend) -> BulkSend {
+    BulkSend {
+        id: db_bulk_send.id,
+        template_id: db_bulk_send.template_id,
+        filename: db_bulk_send.filename,
+        status: db_bulk_send.status,
+        total_rows: db_bulk_send.total_rows,
+        processed_rows: db_bulk_send.processed_rows,
+        succeeded_rows: db_bulk_send.succeeded_rows,
+        failed_rows: db_bulk_send.failed_rows,
+        results: serde_json::from_value(db_bulk_send.results).unwrap_or_default(),
+        created_at: db_bulk_send.created_at,
+        updated_at: db_bulk_send.updated_at,
+        completed_at: db_bulk_send.completed_at,
+    }
+}
+
+// One validated spreadsheet row, ready to become an envelope
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+struct BulkSendRow {
+    row: i32,
+    emails: Vec<String>,
+    request: CreateSubmissionRequest,
+}
+
+// Payload of a `bulk_send` job. Plan usage for every row's first invitations is reserved
+// when the job is queued; each row then gives back whatever it did not send.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+struct BulkSendJob {
+    bulk_send_id: i64,
+    user_id: i64,
+    account_id: Option<i64>,
+    rows: Vec<BulkSendRow>,
+}
+
+// Rows per job run, so a run finishes well within the job lease
+const BULK_SEND_BATCH_ROWS: usize = 25;
+
+// Give back plan usage that was reserved but not spent
+async fn release_usage(pool: &PgPool, user_id: i64, count: i32) {
+    if count > 0 {
+        if let Err(e) = increment_usage_count_by(pool, user_id, -count).await {
+            eprintln!("Warning: Failed to release {} reserved send(s) for user {}: {}", count, user_id, e);
+        }
+    }
+}
+
+/// Job handler for `bulk_send`: creates the envelopes of a bulk send a batch of rows at a
+/// time, recording progress as it goes. Rows already in the report are skipped, so a run
+/// cut short by a restart picks up where it stopped.
+pub fn bulk_send_job(pool: PgPool, payload: serde_json::Value) -> BoxFuture<'static, Result<JobOutcome, String>> {
+    Box::pin(async move {
+        let job: BulkSendJob = serde_json::from_value(payload)
+            .map_err(|e| format!("Invalid bulk send payload: {}", e))?;
+        let Some(db_bulk_send) = BulkSendQueries::get_bulk_send_by_id(&pool, job.bulk_send_id).await
+            .map_err(|e| format!("Failed to get bulk send: {}", e))? else {
+            return Ok(JobOutcome::Done);
+        };
+        if db_bulk_send.status == "completed" || db_bulk_send.status == "failed" {
+            return Ok(JobOutcome::Done);
+        }
+        if db_bulk_send.status != "processing" {
+            BulkSendQueries::update_status(&pool, job.bulk_send_id, "processing").await
+                .map_err(|e| format!("Failed to start bulk send: {}", e))?;
+        }
+
+        let processed: std::collections::HashSet<i64> = db_bulk_send.results.as_array()
+            .map(|results| results.iter().filter_map(|result| result.get("row").and_then(|row| row.as_i64())).collect())
+            .unwrap_or_default();
+        let remaining: Vec<&BulkSendRow> = job.rows.iter().filter(|row| !processed.contains(&(row.row as i64))).collect();
+
+        let db_template = TemplateQueries::get_template_by_id(&pool, db_bulk_send.template_id).await
+            .map_err(|e| format!("Failed to get template: {}", e))?;
+        let template_fields = crate::database::queries::TemplateFieldQueries::get_template_fields(&pool, db_bulk_send.template_id).await
+            .map_err(|e| format!("Failed to get template fields: {}", e))?;
+        let roles = TemplateRoleQueries::get_roles_by_template(&pool, db_bulk_send.template_id).await
+            .map_err(|e| format!("Failed to get template roles: {}", e))?;
+
+        for row in remaining.iter().take(BULK_SEND_BATCH_ROWS) {
+            let reserved = initial_invites(&row.request);
+            // Rows were validated on upload; the template may still have changed since
+            let created = match &db_template {
+                Some(db_template) => match prepare_submitters(&template_fields, &roles, &row.request) {
+                    Ok(prepared) => create_envelope(&pool, job.user_id, job.account_id, db_template, &template_fields, &row.request, &prepared).await,
+                    Err(e) => Err(e),
+                },
+                None => Err("Template not found".to_string()),
+            };
+            let result = match created {
+                Ok((submission, emails_sent_count)) => {
+                    release_usage(&pool, job.user_id, reserved - emails_sent_count).await;
+                    BulkSendRowResult {
+                        row: row.row,
+                        status: "created".to_string(),
+                        emails: row.emails.clone(),
+                        submission_id: Some(submission.id),
+                        error: None,
+                    }
+                }
+                Err(e) => {
+                    release_usage(&pool, job.user_id, reserved).await;
+                    BulkSendRowResult {
+                        row: row.row,
+                        status: "failed".to_string(),
+                        emails: row.emails.clone(),
+                        submission_id: None,
+                        error: Some(e),
+                    }
+                }
+            };
+            let succeeded = result.submission_id.is_some();
+            let result = serde_json::to_value(&result).unwrap_or_default();
+            if let Err(e) = BulkSendQueries::record_row_result(&pool, job.bulk_send_id, result, succeeded).await {
+                eprintln!("Failed to record row {} of bulk send {}: {}", row.row, job.bulk_send_id, e);
+            }
+        }
+
+        if remaining.len() > BULK_SEND_BATCH_ROWS {
+            return Ok(JobOutcome::RunAt(Utc::now()));
+        }
+        BulkSendQueries::update_status(&pool, job.bulk_send_id, "completed").await
+            .map_err(|e| format!("Failed to complete bulk send: {}", e))?;
+        Ok(JobOutcome::Done)
+    })
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/submissions/bulk",
+    tag = "submissions",
+    request_body(content = crate::models::submission::BulkSendUpload, content_type = "multipart/form-data"),
+    responses(
+        (status = 201, description = "Bulk send started", body = ApiResponse<BulkSend>),
+        (status = 400, description = "Bad request", body = ApiResponse<BulkSend>),
+        (status = 403, description = "Access denied or sending limit reached", body = ApiResponse<BulkSend>),
+        (status = 404, description = "Template not found", body = ApiResponse<BulkSend>),
+        (status = 422, description = "Some rows are invalid; nothing was sent", body = ApiResponse<Vec<BulkSendRowResult>>)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn create_bulk_send(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<i64>,
+    mut multipart: Multipart,
+) -> Response {
+    let mut file: Option<(Option<String>, Vec<u8>)> = None;
+    let mut template_id: Option<i64> = None;
+    let mut mapping: Option<Vec<BulkSendColumn>> = None;
+    let mut options = bulk_send::BulkSendOptions::default();
+
+    loop {
+        let field = match multipart.next_field().await {
+            Ok(Some(field)) => field,
+            Ok(None) => break,
+            Err(e) => return ApiResponse::<BulkSend>::bad_request(format!("Invalid multipart body: {}", e)).into_response(),
+        };
+        let name = field.name().unwrap_or("").to_string();
+        let filename = field.file_name().map(|s| s.to_string());
+        let data = match field.bytes().await {
+            Ok(data) => data.to_vec(),
+            Err(e) => return ApiResponse::<BulkSend>::bad_request(format!("Failed to read '{}': {}", name, e)).into_response(),
+        };
+        let text = String::from_utf8_lossy(&data).trim().to_string();
+
+        match name.as_str() {
+            "file" => file = Some((filename, data)),
+            "template_id" => match text.parse() {
+                Ok(id) => template_id = Some(id),
+                Err(_) => return ApiResponse::<BulkSend>::bad_request("template_id must be a number".to_string()).into_response(),
+            },
+            "mapping" if !text.is_empty() => match serde_json::from_str(&text) {
+                Ok(columns) => mapping = Some(columns),
+                Err(e) => return ApiResponse::<BulkSend>::bad_request(format!("Invalid mapping: {}", e)).into_response(),
+            },
+            "name" if !text.is_empty() => options.name = Some(text),
+            "signing_mode" if !text.is_empty() => options.signing_mode = Some(text),
+            "expires_at" if !text.is_empty() => match DateTime::parse_from_rfc3339(&text) {
+                Ok(expires_at) => options.expires_at = Some(expires_at.with_timezone(&Utc)),
+                Err(_) => return ApiResponse::<BulkSend>::bad_request("expires_at must be an RFC 3339 date-time".to_string()).into_response(),
+            },
+            _ => {}
+        }
+    }
+
+    let Some((filename, data)) = file else {
+        return ApiResponse::<BulkSend>::bad_request("No file provided".to_string()).into_response();
+    };
+    let Some(template_id) = template_id else {
+        return ApiResponse::<BulkSend>::bad_request("template_id is required".to_string()).into_response();
+    };
+    options.template_id = template_id;
+
+    let mut rows = match spreadsheet::read_rows(filename.as_deref(), &data) {
+        Ok(rows) => rows.into_iter(),
+        Err(e) => return ApiResponse::<BulkSend>::bad_request(e).into_response(),
+    };
+    let headers = rows.next().unwrap_or_default();
+    let rows: Vec<Vec<String>> = rows.collect();
+    if rows.is_empty() {
+        return ApiResponse::<BulkSend>::bad_request("The file has no rows below the header".to_string()).into_response();
+    }
+    if rows.len() > bulk_send::MAX_ROWS {
+        return ApiResponse::<BulkSend>::bad_request(format!("A bulk send is limited to {} rows", bulk_send::MAX_ROWS)).into_response();
+    }
+
+    let pool = state.lock().await.db_pool.clone();
+
+    let db_template = match TemplateQueries::get_template_by_id(&pool, template_id).await {
+        Ok(Some(db_template)) => db_template,
+        Ok(None) => return ApiResponse::<BulkSend>::not_found("Template not found".to_string()).into_response(),
+        Err(e) => return ApiResponse::<BulkSend>::internal_error(format!("Database error: {}", e)).into_response(),
+    };
+    match can_send_template(&pool, &db_template, user_id).await {
+        Ok(true) => {}
+        Ok(false) => return ApiResponse::<BulkSend>::forbidden("You do not have access to this form".to_string()).into_response(),
+        Err(e) => return ApiResponse::<BulkSend>::internal_error(format!("Database error: {}", e)).into_response(),
+    }
+
+    let template_fields = match crate::database::queries::TemplateFieldQueries::get_template_fields(&pool, template_id).await {
+        Ok(fields) => fields,
+        Err(e) => return ApiResponse::<BulkSend>::internal_error(format!("Failed to get template fields: {}", e)).into_response(),
+    };
+    let roles = match TemplateRoleQueries::get_roles_by_template(&pool, template_id).await {
+        Ok(roles) => roles,
+        Err(e) => return ApiResponse::<BulkSend>::internal_error(format!("Failed to get template roles: {}", e)).into_response(),
+    };
+    let mapping = mapping.unwrap_or_else(|| bulk_send::default_mapping(&headers, &template_fields, &roles));
+    let row_mapping = match bulk_send::resolve_mapping(&headers, &mapping, &template_fields, &roles) {
+        Ok(row_mapping) => row_mapping,
+        Err(e) => return ApiResponse::<BulkSend>::bad_request(e).into_response(),
+    };
+
+    // Validate every row before anything is sent, so a bad row never leaves a half-sent batch
+    let mut valid_rows = Vec::with_capacity(rows.len());
+    let mut invalid_rows = Vec::new();
+    for (index, row) in rows.iter().enumerate() {
+        let row_number = index as i32 + 2;
+        let emails = row_mapping.emails(row);
+        let checked = row_mapping.build_request(row, &options).and_then(|request| {
+            validate_submission_request(&request)?;
+            prepare_submitters(&template_fields, &roles, &request)?;
+            Ok(request)
+        });
+        match checked {
+            Ok(request) => valid_rows.push(BulkSendRow { row: row_number, emails, request }),
+            Err(e) => invalid_rows.push(BulkSendRowResult {
+                row: row_number,
+                status: "invalid".to_string(),
+                emails,
+                submission_id: None,
+                error: Some(e),
+            }),
+        }
+    }
+    if !invalid_rows.is_empty() {
+        let summary = format!("{} of {} row(s) are invalid; nothing was sent", invalid_rows.len(), rows.len());
+        return ApiResponse::unprocessable(invalid_rows, summary).into_response();
+    }
+
+    // Plan limits apply to the whole batch, not row by row
+    let emails_to_send: usize = valid_rows.iter().map(|row| row.request.submitters.len()).sum();
+    let account_id = match check_send_allowance(&pool, user_id, emails_to_send as i32).await {
+        Ok(account_id) => account_id,
+        Err(e) => return ApiResponse::<BulkSend>::forbidden(e).into_response(),
+    };
+
+    // Reserve the first invitations of every row now, so concurrent sends cannot overdraw the plan
+    let reserved: i32 = valid_rows.iter().map(|row| initial_invites(&row.request)).sum();
+    if let Err(e) = increment_usage_count_by(&pool, user_id, reserved).await {
+        return ApiResponse::<BulkSend>::internal_error(format!("Failed to reserve sends: {}", e)).into_response();
+    }
+
+    let db_bulk_send = match BulkSendQueries::create_bulk_send(&pool, template_id, user_id, account_id, filename, valid_rows.len() as i32).await {
+        Ok(db_bulk_send) => db_bulk_send,
+        Err(e) => {
+            release_usage(&pool, user_id, reserved).await;
+            return ApiResponse::<BulkSend>::internal_error(format!("Failed to create bulk send: {}", e)).into_response();
+        }
+    };
+
+    // Runs on the job queue, so a restart resumes the batch instead of leaving it half sent
+    let job = BulkSendJob { bulk_send_id: db_bulk_send.id, user_id, account_id, rows: valid_rows };
+    let payload = serde_json::to_value(&job).unwrap_or_default();
+    let key = format!("{}:{}", jobs::JOB_BULK_SEND, db_bulk_send.id);
+    if let Err(e) = jobs::enqueue(&pool, jobs::JOB_BULK_SEND, payload, None, Some(&key)).await {
+        release_usage(&pool, user_id, reserved).await;
+        if let Err(e) = BulkSendQueries::update_status(&pool, db_bulk_send.id, "failed").await {
+            eprintln!("Failed to mark bulk send {} as failed: {}", db_bulk_send.id, e);
+        }
+        return ApiResponse::<BulkSend>::internal_error(format!("Failed to queue bulk send: {}", e)).into_response();
+    }
+
+    ApiResponse::created(convert_db_bulk_send_to_bulk_send(db_bulk_send), "Bulk send started".to_string()).into_response()
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/submissions/bulk/{id}",
+    tag = "submissions",
+    params(
+        ("id" = i64, Path, description = "Bulk send ID")
+    ),
+    responses(
+        (status = 200, description = "Bulk send progress and per-row report", body = ApiResponse<BulkSend>),
+        (status = 403, description = "Access denied", body = ApiResponse<BulkSend>),
+        (status = 404, description = "Bulk send not found", body = ApiResponse<BulkSend>)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_bulk_send(
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+    Extension(user_id): Extension<i64>,
+) -> (StatusCode, Json<ApiResponse<BulkSend>>) {
+    let pool = &state.lock().await.db_pool;
+
+    let db_bulk_send = match BulkSendQueries::get_bulk_send_by_id(pool, id).await {
+        Ok(Some(db_bulk_send)) => db_bulk_send,
+        Ok(None) => return ApiResponse::not_found("Bulk send not found".to_string()),
+        Err(e) => return ApiResponse::internal_error(format!("Failed to get bulk send: {}", e)),
+    };
+
+    match can_access_owned(pool, db_bulk_send.user_id, db_bulk_send.account_id, user_id).await {
+        Ok(true) => {}
+        Ok(false) => return ApiResponse::forbidden("Access denied: You do not have permission to view this bulk send".to_string()),
+        Err(e) => return ApiResponse::internal_error(format!("Failed to check permissions: {}", e)),
+    }
+
+    ApiResponse::success(convert_db_bulk_send_to_bulk_send(db_bulk_send), "Bulk send retrieved successfully".to_string())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/submissions",
+    tag = "submissions",
+    params(
+        ("page" = Option<i64>, Query, description = "Page number (default 1)"),
+        ("limit" = Option<i64>, Query, description = "Items per page (default 20, max 100)"),
+        ("status" = Option<String>, Query, description = "Filter by status: pending, completed, expired, declined, voided"),
+        ("include_archived" = Option<bool>, Query, description = "Include archived submissions")
+    ),
+    responses(
+        (status = 200, description = "Submissions retrieved successfully", body = ApiResponse<Vec<Submission>>),
+        (status = 500, description = "Internal server error", body = ApiResponse<Vec<Submission>>)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_submissions(
+    State(state): State<AppState>,
+    Query(params): Query<GetSubmissionsQuery>,
+    Extension(user_id): Extension<i64>,
+) -> (StatusCode, Json<ApiResponse<Vec<Submission>>>) {
+    let pool = &state.lock().await.db_pool;
+
+    let page = params.page.unwrap_or(1).max(1);
+    let limit = params.limit.unwrap_or(20).clamp(1, 100);
+    let offset = (page - 1) * limit;
+
+    let db_submissions = match SubmissionQueries::get_team_submissions(
+        pool,
+        user_id,
+        params.status.as_deref(),
+        params.include_archived.unwrap_or(false),
+        offset,
+        limit,
+    ).await {
+        Ok(submissions) => submissions,
+        Err(e) => return ApiResponse::internal_error(format!("Failed to get submissions: {}", e)),
+    };
+
+    let mut submissions = Vec::new();
+    for db_submission in db_submissions {
+        let submitters = match SubmitterQueries::get_submitters_by_submission_id(pool, db_submission.id).await {
+            Ok(db_submitters) => db_submitters.into_iter().map(convert_db_submitter_to_submitter).collect(),
+            Err(e) => return ApiResponse::internal_error(format!("Failed to get submitters: {}", e)),
+        };
+        submissions.push(convert_db_submission_to_submission(db_submission, Some(submitters)));
+    }
+
+    ApiResponse::success(submissions, "Submissions retrieved successfully".to_string())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/submissions/{id}",
+    tag = "submissions",
+    params(
+        ("id" = i64, Path, description = "Submission ID")
+    ),
+    responses(
+        (status = 200, description = "Submission retrieved successfully", body = ApiResponse<Submission>),
+        (status = 403, description = "Access denied", body = ApiResponse<Submission>),
+        (status = 404, description = "Submission not found", body = ApiResponse<Submission>)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_submission(
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+    Extension(user_id): Extension<i64>,
+) -> (StatusCode, Json<ApiResponse<Submission>>) {
+    let pool = &state.lock().await.db_pool;
+
+    let db_submission = match SubmissionQueries::get_submission_by_id(pool, id).await {
+        Ok(Some(submission)) => submission,
+        Ok(None) => return ApiResponse::not_found("Submission not found".to_string()),
+        Err(e) => return ApiResponse::internal_error(format!("Failed to get submission: {}", e)),
+    };
+
+    match can_access_submission(pool, &db_submission, user_id).await {
+        Ok(true) => {}
+        Ok(false) => return ApiResponse::forbidden("Access denied: You do not have permission to view this submission".to_string()),
+        Err(e) => return ApiResponse::internal_error(format!("Failed to check permissions: {}", e)),
+    }
+
+    let submitters = match SubmitterQueries::get_submitters_by_submission_id(pool, db_submission.id).await {
+        Ok(db_submitters) => db_submitters.into_iter().map(convert_db_submitter_to_submitter).collect(),
+        Err(e) => return ApiResponse::internal_error(format!("Failed to get submitters: {}", e)),
+    };
+
+    ApiResponse::success(
+        convert_db_submission_to_submission(db_submission, Some(submitters)),
+        "Submission retrieved successfully".to_string(),
+    )
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/submissions/{id}",
+    tag = "submissions",
+    params(
+        ("id" = i64, Path, description = "Submission ID")
+    ),
+    responses(
+        (status = 200, description = "Submission archived successfully", body = ApiResponse<Submission>),
+        (status = 403, description = "Access denied", body = ApiResponse<Submission>),
+        (status = 404, description = "Submission not found", body = ApiResponse<Submission>)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn archive_submission(
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+    Extension(user_id): Extension<i64>,
+) -> (StatusCode, Json<ApiResponse<Submission>>) {
+    let pool = &state.lock().await.db_pool;
+
+    let db_submission = match SubmissionQueries::get_submission_by_id(pool, id).await {
+        Ok(Some(submission)) => submission,
+        Ok(None) => return ApiResponse::not_found("Submission not found".to_string()),
+        Err(e) => return ApiResponse::internal_error(format!("Failed to get submission: {}", e)),
+    };
+
+    match can_access_submission(pool, &db_submission, user_id).await {
+        Ok(true) => {}
+        Ok(false) => return ApiResponse::forbidden("Access denied: You do not have permission to archive this submission".to_string()),
+        Err(e) => return ApiResponse::internal_error(format!("Failed to check permissions: {}", e)),
+    }
+
+    match SubmissionQueries::archive_submission(pool, id).await {
+        Ok(Some(archived)) => ApiResponse::success(
+            convert_db_submission_to_submission(archived, None),
+            "Submission archived successfully".to_string(),
+        ),
+        Ok(None) => ApiResponse::not_found("Submission not found".to_string()),
+        Err(e) => ApiResponse::internal_error(format!("Failed to archive submission: {}", e)),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/submissions/{id}/void",
+    tag = "submissions",
+    params(
+        ("id" = i64, Path, description = "Submission ID")
+    ),
+    request_body = VoidSubmissionRequest,
+    responses(
+        (status = 200, description = "Submission voided; signing links stop working and invited recipients are notified", body = ApiResponse<Submission>),
+        (status = 400, description = "Missing reason, or the submission is already completed, declined or voided", body = ApiResponse<Submission>),
+        (status = 403, description = "Access denied", body = ApiResponse<Submission>),
+        (status = 404, description = "Submission not found", body = ApiResponse<Submission>)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn void_submission(
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+    Extension(user_id): Extension<i64>,
+    Json(payload): Json<VoidSubmissionRequest>,
+) -> (StatusCode, Json<ApiResponse<Submission>>) {
+    let pool = &state.lock().await.db_pool;
+
+    let reason = payload.reason.trim();
+    if reason.is_empty() {
+        return ApiResponse::bad_request("A reason is required to void a submission".to_string());
+    }
+
+    let db_submission = match SubmissionQueries::get_submission_by_id(pool, id).await {
+        Ok(Some(submission)) => submission,
+        Ok(None) => return ApiResponse::not_found("Submission not found".to_string()),
+        Err(e) => return ApiResponse::internal_error(format!("Failed to get submission: {}", e)),
+    };
+
+    match can_access_submission(pool, &db_submission, user_id).await {
+        Ok(true) => {}
+        Ok(false) => return ApiResponse::forbidden("Access denied: You do not have permission to void this submission".to_string()),
+        Err(e) => return ApiResponse::internal_error(format!("Failed to check permissions: {}", e)),
+    }
+
+    // Statuses before voiding decide who was ever invited and gets the notice
+    let submitters_before = match SubmitterQueries::get_submitters_by_submission_id(pool, id).await {
+        Ok(submitters) => submitters,
+        Err(e) => return ApiResponse::internal_error(format!("Failed to get submitters: {}", e)),
+    };
+
+    let voided = match SubmissionQueries::void_submission(pool, id, reason).await {
+        Ok(Some(submission)) => submission,
+        Ok(None) => return ApiResponse::bad_request(format!("Only pending or expired submissions can be voided; this one is {}", db_submission.status)),
+        Err(e) => return ApiResponse::internal_error(format!("Failed to void submission: {}", e)),
+    };
+
+    let actor = match crate::database::queries::UserQueries::get_user_by_id(pool, user_id).await {
+        Ok(Some(user)) => user.email,
+        _ => "System".to_string(),
+    };
+    let template_name = match TemplateQueries::get_template_by_id(pool, voided.template_id).await {
+        Ok(Some(template)) => template.name,
+        _ => voided.name.clone().unwrap_or_default(),
+    };
+
+    // Sequential signers still waiting for their turn never received a link
+    for submitter in &submitters_before {
+        let email_sent = submitter.status != "waiting"
+            && send_cancellation_email(pool, voided.user_id, &template_name, submitter, reason).await;
+        crate::services::audit::record_event(
+            pool,
+            submitter,
+            crate::services::audit::EVENT_VOIDED,
+            &actor,
+            format!("Submission voided: {}", reason),
+            crate::services::audit::AuditContext::system(),
+            Some(serde_json::json!({ "reason": reason, "email_sent": email_sent })),
+        ).await;
+    }
+
+    let db_submitters = match SubmitterQueries::get_submitters_by_submission_id(pool, id).await {
+        Ok(submitters) => submitters,
+        Err(e) => return ApiResponse::internal_error(format!("Failed to get submitters: {}", e)),
+    };
+    crate::services::webhooks::dispatch_event(
+        pool,
+        voided.user_id,
+        crate::services::webhooks::EVENT_SUBMISSION_VOIDED,
+        crate::services::webhooks::submission_event_data(&voided, &db_submitters),
+    ).await;
+
+    let submitters = db_submitters.into_iter().map(convert_db_submitter_to_submitter).collect();
+    ApiResponse::success(
+        convert_db_submission_to_submission(voided, Some(submitters)),
+        "Submission voided successfully".to_string(),
+    )
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/submissions/{id}/extend",
+    tag = "submissions",
+    params(
+        ("id" = i64, Path, description = "Submission ID")
+    ),
+    request_body = ExtendSubmissionRequest,
+    responses(
+        (status = 200, description = "Expiry moved; an expired submission can be signed again", body = ApiResponse<Submission>),
+        (status = 400, description = "Submission is completed or declined, or the new date is not in the future", body = ApiResponse<Submission>),
+        (status = 403, description = "Access denied", body = ApiResponse<Submission>),
+        (status = 404, description = "Submission not found", body = ApiResponse<Submission>)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn extend_submission(
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+    Extension(user_id): Extension<i64>,
+    Json(payload): Json<ExtendSubmissionRequest>,
+) -> (StatusCode, Json<ApiResponse<Submission>>) {
+    let pool = &state.lock().await.db_pool;
+
+    let db_submission = match SubmissionQueries::get_submission_by_id(pool, id).await {
+        Ok(Some(submission)) => submission,
+        Ok(None) => return ApiResponse::not_found("Submission not found".to_string()),
+        Err(e) => return ApiResponse::internal_error(format!("Failed to get submission: {}", e)),
+    };
+
+    match can_access_submission(pool, &db_submission, user_id).await {
+        Ok(true) => {}
+        Ok(false) => return ApiResponse::forbidden("Access denied: You do not have permission to modify this submission".to_string()),
+        Err(e) => return ApiResponse::internal_error(format!("Failed to check permissions: {}", e)),
+    }
+
+    if let Err(e) = expiry::validate_extension(&db_submission.status, payload.expires_at, Utc::now()) {
+        return ApiResponse::bad_request(e);
+    }
+
+    let extended = match SubmissionQueries::extend_submission(pool, id, payload.expires_at).await {
+        Ok(Some(submission)) => submission,
+        Ok(None) => return ApiResponse::bad_request("Only pending or expired submissions can be extended".to_string()),
+        Err(e) => return ApiResponse::internal_error(format!("Failed to extend submission: {}", e)),
+    };
+
+    let db_submitters = match SubmitterQueries::get_submitters_by_submission_id(pool, id).await {
+        Ok(submitters) => submitters,
+        Err(e) => return ApiResponse::internal_error(format!("Failed to get submitters: {}", e)),
+    };
+    for submitter in db_submitters.iter().filter(|s| s.status != "signed" && s.status != "completed") {
+        crate::services::audit::record_event(
+            pool,
+            submitter,
+            crate::services::audit::EVENT_EXPIRY_EXTENDED,
+            "System",
+            format!("Expiration extended to {}", payload.expires_at.to_rfc3339()),
+            crate::services::audit::AuditContext::system(),
+            Some(serde_json::json!({
+                "previous_expires_at": db_submission.expires_at,
+                "expires_at": payload.expires_at,
+                "extended_by_user_id": user_id
+            })),
+        ).await;
+        // Reminders stop while a submission is expired; the new expiry date starts them again
+        crate::services::reminder_queue::schedule_next_reminder(pool, submitter.id).await;
+    }
+
+    let submitters = db_submitters.into_iter().map(convert_db_submitter_to_submitter).collect();
+    ApiResponse::success(
+        convert_db_submission_to_submission(extended, Some(submitters)),
+        "Submission expiry extended successfully".to_string(),
+    )
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/submissions/{id}/document",
+    tag = "submissions",
+    params(
+        ("id" = i64, Path, description = "Submission ID")
+    ),
+    responses(
+        (status = 200, description = "Final signed PDF", content_type = "application/pdf"),
+        (status = 403, description = "Access denied"),
+        (status = 404, description = "Submission or signed document not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn download_signed_document(
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+    Extension(user_id): Extension<i64>,
+    headers: HeaderMap,
+) -> Response<Body> {
+    let pool = state.lock().await.db_pool.clone();
+
+    let error_response = |status: StatusCode, message: String| {
+        Response::builder()
+            .status(status)
+            .header(header::CONTENT_TYPE, "text/plain")
+            .body(Body::from(message))
+            .unwrap()
+    };
+
+    let db_submission = match SubmissionQueries::get_submission_by_id(&pool, id).await {
+        Ok(Some(submission)) => submission,
+        Ok(None) => return error_response(StatusCode::NOT_FOUND, "Submission not found".to_string()),
+        Err(e) => return error_response(StatusCode::INTERNAL_SERVER_ERROR, format!("Failed to get submission: {}", e)),
+    };
+
+    match can_access_submission(&pool, &db_submission, user_id).await {
+        Ok(true) => {}
+        Ok(false) => return error_response(StatusCode::FORBIDDEN, "Access denied: You do not have permission to download this document".to_string()),
+        Err(e) => return error_response(StatusCode::INTERNAL_SERVER_ERROR, format!("Failed to check permissions: {}", e)),
+    }
+
+    let pdf_bytes = match load_signed_document(&pool, id).await {
+        Ok(Some(bytes)) => bytes,
+        Ok(None) => return error_response(StatusCode::NOT_FOUND, "Signed document is not available yet".to_string()),
+        Err(e) => return error_response(StatusCode::INTERNAL_SERVER_ERROR, e),
+    };
+
+    // Use the account's filename format for the download name
+    let document_name = db_submission.name.clone().unwrap_or_else(|| format!("submission_{}", id));
+    let submitters = SubmitterQueries::get_submitters_by_submission_id(&pool, id).await.unwrap_or_default();
+    let submitter_emails = submitters.iter().map(|s| s.email.clone()).collect();
+    let filename_format = get_user_pdf_settings(&pool, db_submission.user_id, db_submission.account_id).await
+        .unwrap_or_else(|_| "{document.name}".to_string());
+    let loc = Localizer::for_user(&pool, db_submission.user_id).await;
+    let filename = generate_download_filename(
+        &filename_format,
+        &document_name,
+        &db_submission.status,
+        submitter_emails,
+        db_submission.completed_at.map(|dt| dt.with_timezone(&loc.utc_offset).naive_local()),
+        loc.locale,
+    );
+
+    // The download shows up in every signer's audit trail
+    let downloaded_by = match crate::database::queries::UserQueries::get_user_by_id(&pool, user_id).await {
+        Ok(Some(user)) => user.email,
+        _ => format!("user #{}", user_id),
+    };
+    for submitter in &submitters {
+        crate::services::audit::record_event(
+            &pool,
+            submitter,
+            crate::services::audit::EVENT_DOWNLOADED,
+            &downloaded_by,
+            format!("Signed document downloaded by {}", downloaded_by),
+            crate::services::audit::AuditContext::from_request(&headers, None, None),
+            db_submission.signed_document_sha256.as_ref().map(|sha256| serde_json::json!({ "sha256": sha256 })),
+        ).await;
+    }
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, "application/pdf")
+        .header(header::CONTENT_DISPOSITION, crate::services::pdf_preferences::content_disposition("attachment", &filename))
+        .header("X-Content-SHA256", db_submission.signed_document_sha256.unwrap_or_default())
+        .header("Content-Length", pdf_bytes.len().to_string())
+        .body(Body::from(pdf_bytes))
+        .unwrap_or_else(|e| error_response(StatusCode::INTERNAL_SERVER_ERROR, format!("Failed to build response: {}", e)))
+}
+
 pub fn create_submission_router() -> Router<AppState> {
     Router::new()
-        .route("/submissions", post(create_submission))
+        .route("/submissions", post(create_submission).get(get_submissions))
+        .route("/submissions/:id", get(get_submission).delete(archive_submission))
+        .route("/submissions/:id/document", get(download_signed_document))
+        .route("/submissions/:id/extend", post(extend_submission))
+        .route("/submissions/:id/void", post(void_submission))
+        .route("/submissions/bulk", post(create_bulk_send))
+        .route("/submissions/bulk/:id", get(get_bulk_send))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::models::DbTemplateField;
+
+    fn field(id: i64, name: &str, role_id: Option<i64>) -> DbTemplateField {
+        DbTemplateField {
+            id,
+            template_id: 1,
+            name: name.to_string(),
+            field_type: "text".to_string(),
+            required: false,
+            display_order: id as i32,
+            position: None,
+            options: None,
+            metadata: None,
+            partner: None,
+            conditions: None,
+            validation: None,
+            role_id,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+        }
+    }
+
+    fn value(field_id: Option<i64>, field_name: Option<&str>, value: &str) -> PrefilledFieldValue {
+        PrefilledFieldValue { field_id, field_name: field_name.map(str::to_string), value: value.to_string(), readonly: false }
+    }
+
+    #[test]
+    fn test_prefilled_values_resolve_to_own_role() {
+        let fields = vec![field(1, "Name", Some(10)), field(2, "Name", Some(20)), field(3, "Notes", None)];
+
+        let resolved = resolve_prefilled_values(&fields, Some(20), &[
+            value(None, Some("Name"), "Bob"),
+            value(Some(3), None, "shared"),
+        ]).unwrap();
+        assert_eq!(resolved.get(&2), Some(&("Bob".to_string(), false)));
+        assert_eq!(resolved.get(&3), Some(&("shared".to_string(), false)));
+        assert!(!resolved.contains_key(&1));
+    }
+
+    #[test]
+    fn test_prefilled_values_for_another_role_are_rejected() {
+        let fields = vec![field(1, "Name", Some(10)), field(2, "Company", Some(10))];
+
+        let by_id = resolve_prefilled_values(&fields, Some(20), &[value(Some(1), None, "Alice")]);
+        assert_eq!(by_id.unwrap_err(), "field 'Name' belongs to another role");
+        let by_name = resolve_prefilled_values(&fields, Some(20), &[value(None, Some("Company"), "Acme")]);
+        assert_eq!(by_name.unwrap_err(), "field 'Company' belongs to another role");
+    }
+
+    #[test]
+    fn test_prefilled_values_are_checked() {
+        let mut formula_field = field(2, "Total", None);
+        formula_field.field_type = formula::FIELD_TYPE_FORMULA.to_string();
+        let fields = vec![field(1, "Name", None), formula_field];
+
+        assert!(resolve_prefilled_values(&fields, None, &[value(Some(9), None, "x")]).is_err());
+        assert!(resolve_prefilled_values(&fields, None, &[value(None, None, "x")]).is_err());
+        assert!(resolve_prefilled_values(&fields, None, &[value(Some(2), None, "1")]).is_err());
+        assert!(resolve_prefilled_values(&fields, None, &[value(Some(1), None, "a"), value(None, Some("Name"), "b")]).is_err());
+    }
+
+    #[test]
+    fn test_initial_invites_count_the_first_signing_group() {
+        let submitter = |email: &str, order: Option<i32>| crate::models::submitter::CreateSubmitterRequest {
+            name: email.to_string(),
+            email: email.to_string(),
+            order,
+            role_id: None,
+            role: None,
+            locale: None,
+            reminder_config: None,
+            values: Vec::new(),
+        };
+        let mut request = CreateSubmissionRequest {
+            template_id: 1,
+            name: None,
+            submitters: vec![submitter("a@example.com", Some(1)), submitter("b@example.com", Some(1)), submitter("c@example.com", Some(2))],
+            expires_at: None,
+            signing_mode: None,
+        };
+        assert_eq!(initial_invites(&request), 3);
+        request.signing_mode = Some("sequential".to_string());
+        assert_eq!(initial_invites(&request), 2);
+    }
 }
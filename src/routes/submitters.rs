@@ -2,14 +2,14 @@ use axum::{
     extract::{Path, State, Extension, ConnectInfo},
     http::{StatusCode, header, HeaderMap},
     response::{Json, Response, IntoResponse},
-    routing::{get, put, delete},
+    routing::{get, put, post, delete},
     Router,
     middleware,
     body::Body,
 };
 use std::net::SocketAddr;
 use crate::common::responses::ApiResponse;
-use crate::database::queries::{SubmitterQueries, UserQueries, SubmissionFieldQueries, GlobalSettingsQueries, TemplateQueries, EmailTemplateQueries, TemplateFieldQueries};
+use crate::database::queries::{SubmitterQueries, SubmissionQueries, UserQueries, SubmissionFieldQueries, GlobalSettingsQueries, TemplateQueries, EmailTemplateQueries, TemplateFieldQueries, AuditEventQueries, EmailOutboxQueries};
 use crate::common::jwt::{auth_middleware, combined_auth_middleware};
 use crate::common::authorization::require_admin_or_team_member;
 use crate::services::storage::StorageService;
@@ -21,7 +21,24 @@ use sqlx::PgPool;
 
 use crate::routes::web::AppState;
 
-use crate::common::utils::{replace_template_variables, generate_api_key};
+use crate::common::utils::generate_api_key;
+use crate::services::email_branding::EmailBranding;
+use crate::services::email_template_engine;
+use crate::services::audit::{self, AuditContext};
+use crate::services::webhooks;
+use crate::services::pdf_fonts::{self, DocumentFonts, FONT_NOTO_SANS};
+use crate::services::field_conditions::{self, parse_conditions, ConditionalField, RoleField};
+use crate::services::field_validation::{self, parse_validation, FieldViolation};
+use crate::services::formula;
+use crate::services::template_roles;
+use crate::services::expiry;
+use crate::services::i18n::{self, Localizer};
+use crate::services::reassignment;
+use crate::services::jobs::{self, JobOutcome};
+use crate::services::reminder_queue;
+use futures::future::BoxFuture;
+use crate::common::token::generate_token;
+use crate::models::signature::FieldValidationError;
 
 
 #[utoipa::path(
@@ -51,6 +68,10 @@ pub async fn get_submitters(
                 let submitter = crate::models::submitter::Submitter {
                     id: Some(db_submitter.id),
                     template_id: Some(db_submitter.template_id),
+                    submission_id: db_submitter.submission_id,
+                    signing_order: Some(db_submitter.signing_order),
+                    role_id: db_submitter.role_id,
+                    locale: db_submitter.locale.clone(),
                     user_id: Some(db_submitter.user_id),
                     name: db_submitter.name,
                     email: db_submitter.email,
@@ -118,6 +139,10 @@ pub async fn get_submitter(
             let submitter = crate::models::submitter::Submitter {
                 id: Some(db_submitter.id),
                 template_id: Some(db_submitter.template_id),
+                submission_id: db_submitter.submission_id,
+                signing_order: Some(db_submitter.signing_order),
+                role_id: db_submitter.role_id,
+                locale: db_submitter.locale.clone(),
                 user_id: Some(db_submitter.user_id),
                 name: db_submitter.name,
                 email: db_submitter.email,
@@ -281,6 +306,10 @@ pub async fn update_submitter(
                     let submitter = crate::models::submitter::Submitter {
                         id: Some(db_submitter.id),
                         template_id: Some(db_submitter.template_id),
+                        submission_id: db_submitter.submission_id,
+                        signing_order: Some(db_submitter.signing_order),
+                        role_id: db_submitter.role_id,
+                        locale: db_submitter.locale.clone(),
                         user_id: Some(db_submitter.user_id),
                         name: db_submitter.name,
                         email: db_submitter.email,
@@ -362,6 +391,119 @@ pub async fn delete_submitter(
         Err(e) => ApiResponse::internal_error(format!("Failed to get submitter: {}", e)),
     }
 }
+#[utoipa::path(
+    post,
+    path = "/api/submitters/{id}/reassign",
+    params(
+        ("id" = i64, Path, description = "Submitter ID")
+    ),
+    request_body = crate::models::submitter::ReassignSubmitterRequest,
+    responses(
+        (status = 200, description = "Submitter reassigned; the previous signing link no longer works", body = ApiResponse<crate::models::submitter::Submitter>),
+        (status = 400, description = "Submitter already acted, the submission is closed or the new signer is invalid", body = ApiResponse<crate::models::submitter::Submitter>),
+        (status = 403, description = "Access denied", body = ApiResponse<crate::models::submitter::Submitter>),
+        (status = 404, description = "Submitter not found", body = ApiResponse<crate::models::submitter::Submitter>)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn reassign_submitter(
+    State(state): State<AppState>,
+    Path(submitter_id): Path<i64>,
+    Extension(user_id): Extension<i64>,
+    Json(payload): Json<crate::models::submitter::ReassignSubmitterRequest>,
+) -> (StatusCode, Json<ApiResponse<crate::models::submitter::Submitter>>) {
+    let pool = &state.lock().await.db_pool;
+
+    let db_submitter = match SubmitterQueries::get_submitter_by_id(pool, submitter_id).await {
+        Ok(Some(submitter)) => submitter,
+        Ok(None) => return ApiResponse::not_found("Submitter not found".to_string()),
+        Err(e) => return ApiResponse::internal_error(format!("Failed to get submitter: {}", e)),
+    };
+
+    // The owner, or a team member of the envelope's account
+    match crate::routes::submissions::can_access_submitter(pool, &db_submitter, user_id).await {
+        Ok(true) => {}
+        Ok(false) => return ApiResponse::forbidden("Access denied".to_string()),
+        Err(e) => return ApiResponse::internal_error(format!("Failed to check permissions: {}", e)),
+    }
+    let actor = match UserQueries::get_user_by_id(pool, user_id).await {
+        Ok(Some(user)) => user.email,
+        _ => return ApiResponse::forbidden("User not found".to_string()),
+    };
+
+    match expiry::closed_link_reason(pool, &db_submitter).await {
+        Ok(None) => {}
+        Ok(Some(_)) => return ApiResponse::bad_request("The submission has expired or was voided; its submitters cannot be reassigned".to_string()),
+        Err(e) => return ApiResponse::internal_error(format!("Failed to check submission: {}", e)),
+    }
+
+    match reassign_signer(pool, &db_submitter, &payload, &actor, false, AuditContext::system()).await {
+        Ok(updated) => ApiResponse::success(
+            crate::routes::submissions::convert_db_submitter_to_submitter(updated),
+            "Submitter reassigned successfully".to_string(),
+        ),
+        Err(err) => err,
+    }
+}
+
+// Hand a submitter's fields and signing turn to another person. The new signer gets a
+// fresh token, so the old link stops working, and is invited unless still waiting for their turn.
+async fn reassign_signer<T>(
+    pool: &PgPool,
+    db_submitter: &crate::database::models::DbSubmitter,
+    request: &crate::models::submitter::ReassignSubmitterRequest,
+    actor: &str,
+    delegated: bool,
+    context: AuditContext,
+) -> Result<crate::database::models::DbSubmitter, (StatusCode, Json<ApiResponse<T>>)> {
+    let envelope = get_envelope_submitters(pool, db_submitter.template_id, db_submitter.submission_id).await
+        .map_err(|e| ApiResponse::internal_error(format!("Failed to get envelope submitters: {}", e)))?;
+    let envelope_emails: Vec<&str> = envelope.iter().map(|s| s.email.as_str()).collect();
+    reassignment::validate_reassignment(&db_submitter.status, &request.name, &request.email, &envelope_emails)
+        .map_err(ApiResponse::bad_request)?;
+
+    let updated = SubmitterQueries::reassign_submitter(pool, db_submitter.id, request.name.trim(), request.email.trim(), &generate_token()).await
+        .map_err(|e| ApiResponse::internal_error(format!("Failed to reassign submitter: {}", e)))?
+        .ok_or_else(|| ApiResponse::bad_request("Only submitters who have not signed yet can be reassigned".to_string()))?;
+
+    // Sequential signers still waiting for their turn are invited when it comes
+    let mut email_sent = false;
+    if updated.status != "waiting" {
+        if let Ok(Some(db_template)) = TemplateQueries::get_template_by_id(pool, updated.template_id).await {
+            email_sent = crate::routes::submissions::send_invitation_email(pool, &db_template, &updated).await;
+        }
+        if email_sent {
+            if let Err(e) = crate::routes::subscription::increment_usage_count_by(pool, updated.user_id, 1).await {
+                eprintln!("Warning: Failed to increment usage count for user {}: {}", updated.user_id, e);
+            }
+        }
+    }
+
+    reminder_queue::schedule_next_reminder(pool, updated.id).await;
+
+    let reason = request.reason.as_deref().map(str::trim).filter(|r| !r.is_empty());
+    let verb = if delegated { "delegated" } else { "reassigned" };
+    audit::record_event(
+        pool,
+        &updated,
+        audit::EVENT_REASSIGNED,
+        actor,
+        format!("Signing {} from {} to {}", verb, db_submitter.email, updated.email),
+        context,
+        Some(serde_json::json!({
+            "from_name": db_submitter.name,
+            "from_email": db_submitter.email,
+            "to_name": updated.name,
+            "to_email": updated.email,
+            "delegated": delegated,
+            "reason": reason,
+            "email_sent": email_sent,
+        })),
+    ).await;
+
+    Ok(updated)
+}
+
 #[utoipa::path(
     put,
     path = "/public/submissions/{token}",
@@ -371,18 +513,29 @@ pub async fn delete_submitter(
     request_body = crate::models::submitter::PublicUpdateSubmitterRequest,
     responses(
         (status = 200, description = "Submitter updated successfully", body = ApiResponse<crate::models::submitter::Submitter>),
-        (status = 404, description = "Submitter not found", body = ApiResponse<crate::models::submitter::Submitter>)
+        (status = 404, description = "Submitter not found", body = ApiResponse<crate::models::submitter::Submitter>),
+        (status = 410, description = "The submission has expired or was voided", body = ApiResponse<crate::models::submitter::Submitter>)
     )
 )]
 pub async fn update_public_submitter(
     State(state): State<AppState>,
     Path(token): Path<String>,
+    headers: HeaderMap,
     Json(payload): Json<crate::models::submitter::PublicUpdateSubmitterRequest>,
 ) -> (StatusCode, Json<ApiResponse<crate::models::submitter::Submitter>>) {
     let pool = &state.lock().await.db_pool;
 
     match SubmitterQueries::get_submitter_by_token(pool, &token).await {
         Ok(Some(db_submitter)) => {
+            match expiry::closed_link_reason(pool, &db_submitter).await {
+                Ok(None) => {}
+                Ok(Some(reason)) => {
+                    let loc = Localizer::for_signer(pool, &db_submitter, &headers).await;
+                    return ApiResponse::gone(reason.message(loc.locale));
+                }
+                Err(e) => return ApiResponse::internal_error(format!("Failed to check signing link: {}", e)),
+            }
+
             match SubmitterQueries::update_submitter(pool, db_submitter.id, None).await {
                 Ok(Some(updated_submitter)) => {
                     let reminder_config = updated_submitter.reminder_config.as_ref()
@@ -391,6 +544,10 @@ pub async fn update_public_submitter(
                     let submitter = crate::models::submitter::Submitter {
                         id: Some(updated_submitter.id),
                         template_id: Some(updated_submitter.template_id),
+                        submission_id: updated_submitter.submission_id,
+                        signing_order: Some(updated_submitter.signing_order),
+                        role_id: updated_submitter.role_id,
+                        locale: updated_submitter.locale.clone(),
                         user_id: Some(updated_submitter.user_id),
                         name: updated_submitter.name,
                         email: updated_submitter.email,
@@ -428,17 +585,43 @@ pub async fn update_public_submitter(
     ),
     responses(
         (status = 200, description = "Submitter retrieved successfully", body = ApiResponse<crate::models::submitter::Submitter>),
-        (status = 404, description = "Submitter not found", body = ApiResponse<crate::models::submitter::Submitter>)
+        (status = 404, description = "Submitter not found", body = ApiResponse<crate::models::submitter::Submitter>),
+        (status = 410, description = "The submission has expired or was voided", body = ApiResponse<crate::models::submitter::Submitter>)
     )
 )]
 pub async fn get_public_submitter(
     State(state): State<AppState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
     Path(token): Path<String>,
     headers: HeaderMap,
 ) -> (StatusCode, Json<ApiResponse<crate::models::submitter::Submitter>>) {
     let pool = &state.lock().await.db_pool;
     match SubmitterQueries::get_submitter_by_token(pool, &token).await {
         Ok(Some(db_submitter)) => {
+            match expiry::closed_link_reason(pool, &db_submitter).await {
+                Ok(None) => {}
+                Ok(Some(reason)) => {
+                    let loc = Localizer::for_signer(pool, &db_submitter, &headers).await;
+                    return ApiResponse::gone(reason.message(loc.locale));
+                }
+                Err(e) => return ApiResponse::internal_error(format!("Failed to check signing link: {}", e)),
+            }
+
+            // Every view goes to the audit trail; viewed_at keeps the first one
+            if let Err(e) = SubmitterQueries::mark_submitter_viewed(pool, db_submitter.id).await {
+                eprintln!("Failed to mark submitter {} as viewed: {}", db_submitter.id, e);
+            }
+            audit::record_event(
+                pool,
+                &db_submitter,
+                audit::EVENT_VIEWED,
+                &db_submitter.email,
+                format!("Form opened and viewed by {}", db_submitter.email),
+                AuditContext::from_request(&headers, Some(addr.ip().to_string()), None),
+                None,
+            ).await;
+            webhooks::dispatch_event(pool, db_submitter.user_id, webhooks::EVENT_SUBMISSION_VIEWED, webhooks::submitter_event_data(&db_submitter)).await;
+
             let reminder_config = db_submitter.reminder_config.as_ref()
                 .and_then(|v| serde_json::from_value(v.clone()).ok());
             
@@ -462,6 +645,7 @@ pub async fn get_public_submitter(
                     "allow_typed_text_signatures": settings.allow_typed_text_signatures,
                     "allow_to_resubmit_completed_forms": settings.allow_to_resubmit_completed_forms,
                     "allow_to_decline_documents": settings.allow_to_decline_documents,
+                    "allow_to_delegate_signing": settings.allow_to_delegate_signing,
                     "remember_and_pre_fill_signatures": settings.remember_and_pre_fill_signatures,
                     "require_authentication_for_file_download_links": settings.require_authentication_for_file_download_links,
                     "combine_completed_documents_and_audit_log": settings.combine_completed_documents_and_audit_log,
@@ -483,6 +667,7 @@ pub async fn get_public_submitter(
                             "allow_typed_text_signatures": settings.allow_typed_text_signatures,
                             "allow_to_resubmit_completed_forms": settings.allow_to_resubmit_completed_forms,
                             "allow_to_decline_documents": settings.allow_to_decline_documents,
+                            "allow_to_delegate_signing": settings.allow_to_delegate_signing,
                             "remember_and_pre_fill_signatures": settings.remember_and_pre_fill_signatures,
                             "require_authentication_for_file_download_links": settings.require_authentication_for_file_download_links,
                             "combine_completed_documents_and_audit_log": settings.combine_completed_documents_and_audit_log,
@@ -525,6 +710,10 @@ pub async fn get_public_submitter(
             let submitter = crate::models::submitter::Submitter {
                 id: Some(db_submitter.id),
                 template_id: Some(db_submitter.template_id),
+                submission_id: db_submitter.submission_id,
+                signing_order: Some(db_submitter.signing_order),
+                role_id: db_submitter.role_id,
+                locale: db_submitter.locale.clone(),
                 user_id: Some(db_submitter.user_id),
                 name: db_submitter.name,
                 email: db_submitter.email,
@@ -550,6 +739,54 @@ pub async fn get_public_submitter(
     }
 }
 
+// Transparent 1x1 GIF served by the invitation email tracking pixel
+const TRACKING_PIXEL_GIF: [u8; 43] = [
+    0x47, 0x49, 0x46, 0x38, 0x39, 0x61, 0x01, 0x00, 0x01, 0x00, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0xff, 0xff, 0xff, 0x21, 0xf9, 0x04, 0x01, 0x00, 0x00, 0x00, 0x00, 0x2c, 0x00, 0x00, 0x00, 0x00,
+    0x01, 0x00, 0x01, 0x00, 0x00, 0x02, 0x02, 0x44, 0x01, 0x00, 0x3b,
+];
+
+#[utoipa::path(
+    get,
+    path = "/public/submissions/{token}/open.gif",
+    params(
+        ("token" = String, Path, description = "Submitter token")
+    ),
+    responses(
+        (status = 200, description = "Tracking pixel (always returned, even for unknown tokens)", content_type = "image/gif")
+    )
+)]
+pub async fn track_email_open(
+    State(state): State<AppState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    Path(token): Path<String>,
+    headers: HeaderMap,
+) -> Response<Body> {
+    let pool = state.lock().await.db_pool.clone();
+
+    if let Ok(Some(db_submitter)) = SubmitterQueries::get_submitter_by_token(&pool, &token).await {
+        audit::record_event(
+            &pool,
+            &db_submitter,
+            audit::EVENT_EMAIL_OPENED,
+            &db_submitter.email,
+            format!("Invitation email opened by {}", db_submitter.email),
+            AuditContext::from_request(&headers, Some(addr.ip().to_string()), None),
+            None,
+        ).await;
+    }
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, "image/gif")
+        .header(header::CACHE_CONTROL, "no-store, no-cache, must-revalidate")
+        .body(Body::from(TRACKING_PIXEL_GIF.to_vec()))
+        .unwrap()
+}
+
+// Signing handlers return plain responses so field validation errors can carry their own payload
+type SubmitterResponse = ApiResponse<crate::models::submitter::Submitter>;
+
 #[utoipa::path(
     post,
     path = "/public/signatures/bulk/{token}",
@@ -559,15 +796,18 @@ pub async fn get_public_submitter(
     request_body = crate::models::signature::BulkSignatureRequest,
     responses(
         (status = 200, description = "Bulk signatures submitted successfully", body = ApiResponse<crate::models::submitter::Submitter>),
-        (status = 404, description = "Submitter not found", body = ApiResponse<crate::models::submitter::Submitter>)
+        (status = 404, description = "Submitter not found", body = ApiResponse<crate::models::submitter::Submitter>),
+        (status = 410, description = "The submission has expired or was voided", body = ApiResponse<crate::models::submitter::Submitter>),
+        (status = 422, description = "Some fields are missing or invalid", body = ApiResponse<Vec<crate::models::signature::FieldValidationError>>)
     )
 )]
 pub async fn submit_bulk_signatures(
     State(state): State<AppState>,
     ConnectInfo(addr): ConnectInfo<SocketAddr>,
     Path(token): Path<String>,
+    headers: HeaderMap,
     Json(payload): Json<crate::models::signature::BulkSignatureRequest>,
-) -> (StatusCode, Json<ApiResponse<crate::models::submitter::Submitter>>) {
+) -> Response {
     // Clone pool to release lock early
     let pool = state.lock().await.db_pool.clone();
 
@@ -578,27 +818,58 @@ pub async fn submit_bulk_signatures(
     // Get submitter
     let db_submitter = match SubmitterQueries::get_submitter_by_token(&pool, &token).await {
         Ok(Some(submitter)) => submitter,
-        Ok(None) => return ApiResponse::not_found("Invalid token".to_string()),
-        Err(e) => return ApiResponse::internal_error(format!("Database error: {}", e)),
+        Ok(None) => {
+            let locale = i18n::resolve_locale([i18n::accept_language(&headers)]);
+            return SubmitterResponse::not_found(i18n::t(locale, "error.link_invalid")).into_response();
+        }
+        Err(e) => return SubmitterResponse::internal_error(format!("Database error: {}", e)).into_response(),
     };
 
+    // In sequential submissions, later signers have to wait for their turn
+    if db_submitter.status == "waiting" {
+        let loc = Localizer::for_signer(&pool, &db_submitter, &headers).await;
+        return SubmitterResponse::forbidden(loc.t("error.not_your_turn")).into_response();
+    }
+
+    match expiry::closed_link_reason(&pool, &db_submitter).await {
+        Ok(None) => {}
+        Ok(Some(reason)) => {
+            let loc = Localizer::for_signer(&pool, &db_submitter, &headers).await;
+            return SubmitterResponse::gone(reason.message(loc.locale)).into_response();
+        }
+        Err(e) => return SubmitterResponse::internal_error(format!("Failed to check signing link: {}", e)).into_response(),
+    }
+
     // Handle decline action
     if let Some(action) = &payload.action {
         if action == "decline" {
             return handle_decline_action(&pool, db_submitter, payload, real_ip).await;
         } else if action != "sign" {
-            return ApiResponse::bad_request("Invalid action. Must be 'sign' or 'decline'".to_string());
+            return SubmitterResponse::bad_request("Invalid action. Must be 'sign' or 'decline'".to_string()).into_response();
         }
     }
     
     // Get submission fields for validation
     let submission_fields = match SubmissionFieldQueries::get_submission_fields_by_submitter_id(&pool, db_submitter.id).await {
         Ok(fields) => fields,
-        Err(e) => return ApiResponse::internal_error(format!("Failed to get submission fields: {}", e)),
+        Err(e) => return SubmitterResponse::internal_error(format!("Failed to get submission fields: {}", e)).into_response(),
+    };
+
+    // Values other signers of the envelope already filled in, for conditional fields
+    let envelope_values = match get_envelope_submitters(&pool, db_submitter.template_id, db_submitter.submission_id).await {
+        Ok(submitters) => {
+            let role_fields: Vec<RoleField> = submission_fields.iter().map(|f| RoleField {
+                template_field_id: f.template_field_id,
+                name: &f.name,
+                role_id: f.role_id,
+            }).collect();
+            collect_field_values(submitters.iter().filter(|s| s.id != db_submitter.id), &role_fields)
+        }
+        Err(e) => return SubmitterResponse::internal_error(format!("Failed to get envelope submitters: {}", e)).into_response(),
     };
 
     // Validate and create signatures array (extracted to helper)
-    let bulk_signatures = match validate_and_create_signatures(&db_submitter, &payload.signatures, &submission_fields) {
+    let bulk_signatures = match validate_and_create_signatures(&db_submitter, &payload.signatures, &submission_fields, &envelope_values, true) {
         Ok(sigs) => sigs,
         Err(err_response) => return err_response,
     };
@@ -613,27 +884,77 @@ pub async fn submit_bulk_signatures(
         payload.timezone.as_deref(),
     ).await {
         Ok(Some(submitter)) => submitter,
-        Ok(None) => return ApiResponse::not_found("Submitter not found".to_string()),
-        Err(e) => return ApiResponse::internal_error(format!("Failed to save bulk signatures: {}", e)),
+        Ok(None) => return SubmitterResponse::not_found("Submitter not found".to_string()).into_response(),
+        Err(e) => return SubmitterResponse::internal_error(format!("Failed to save bulk signatures: {}", e)).into_response(),
     };
     
-    // Spawn background task for auto-sign and email notifications (non-blocking)
+    // One audit event per filled field, then the signature itself
+    let audit_context = AuditContext::from_request(&headers, Some(real_ip.clone()), payload.timezone.clone());
+    let audit_context = AuditContext {
+        user_agent: payload.user_agent.clone().or(audit_context.user_agent),
+        ..audit_context
+    };
+    for signature_item in &payload.signatures {
+        let field_name = submission_fields.iter()
+            .find(|f| f.id == signature_item.field_id)
+            .map(|f| f.name.clone())
+            .unwrap_or_else(|| format!("field_{}", signature_item.field_id));
+        audit::record_event(
+            &pool,
+            &updated_submitter,
+            audit::EVENT_FIELD_FILLED,
+            &updated_submitter.email,
+            format!("Field '{}' filled by {}", field_name, updated_submitter.email),
+            audit_context.clone(),
+            Some(serde_json::json!({ "field_id": signature_item.field_id, "field_name": field_name })),
+        ).await;
+    }
+    audit::record_event(
+        &pool,
+        &updated_submitter,
+        audit::EVENT_SIGNED,
+        &updated_submitter.email,
+        format!("Document signed and submitted by {}", updated_submitter.email),
+        audit_context,
+        Some(serde_json::json!({ "status": updated_submitter.status })),
+    ).await;
+
+    // The final PDF and completion emails run on the job queue, so they survive restarts and are retried
+    let signed_job = SignedSubmitterJob::from_submitter(&updated_submitter);
+    let first_job = if updated_submitter.status == "completed" || updated_submitter.status == "signed" {
+        jobs::JOB_AUTO_SIGN
+    } else {
+        jobs::JOB_COMPLETION_EMAILS
+    };
+    signed_job.enqueue(&pool, first_job).await;
+
+    // Spawn background task for webhooks and the next signing group (non-blocking)
     let pool_clone = pool.clone();
-    let submitter_id = db_submitter.id;
-    let template_id = db_submitter.template_id;
     let user_id = db_submitter.user_id;
-    let submitter_status = updated_submitter.status.clone();
+    let submission_id = db_submitter.submission_id;
+    let signed_submitter = updated_submitter.clone();
     tokio::spawn(async move {
-        // Auto-sign PDF if submitter completed
-        if submitter_status == "completed" || submitter_status == "signed" {
-            if let Err(e) = auto_sign_completed_submission(&pool_clone, submitter_id, template_id, user_id).await {
-                eprintln!("⚠️  Auto-sign skipped for submission {}: {}", submitter_id, e);
+        webhooks::dispatch_event(&pool_clone, user_id, webhooks::EVENT_SUBMISSION_SIGNED, webhooks::submitter_event_data(&signed_submitter)).await;
+
+        // Keep the envelope status in sync with its submitters, then invite the next signing group
+        if let Some(submission_id) = submission_id {
+            let previous_status = SubmissionQueries::get_submission_by_id(&pool_clone, submission_id).await
+                .ok()
+                .flatten()
+                .map(|submission| submission.status);
+            match SubmissionQueries::refresh_submission_status(&pool_clone, submission_id).await {
+                Ok(Some(submission)) => {
+                    if submission.status == "completed" && previous_status.as_deref() != Some("completed") {
+                        let submitters = SubmitterQueries::get_submitters_by_submission_id(&pool_clone, submission_id).await.unwrap_or_default();
+                        webhooks::dispatch_event(&pool_clone, user_id, webhooks::EVENT_SUBMISSION_COMPLETED, webhooks::submission_event_data(&submission, &submitters)).await;
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => eprintln!("Failed to refresh status for submission {}: {}", submission_id, e),
+            }
+            if let Err(e) = crate::routes::submissions::invite_next_signing_group(&pool_clone, submission_id).await {
+                eprintln!("Failed to invite next signers for submission {}: {}", submission_id, e);
             }
-        }
-        
-        // Send email notifications
-        if let Err(e) = send_completion_notifications(&pool_clone, submitter_id, template_id, user_id).await {
-            eprintln!("Background email notification error: {}", e);
         }
     });
     
@@ -644,6 +965,10 @@ pub async fn submit_bulk_signatures(
     let submitter = crate::models::submitter::Submitter {
         id: Some(updated_submitter.id),
         template_id: Some(updated_submitter.template_id),
+        submission_id: updated_submitter.submission_id,
+        signing_order: Some(updated_submitter.signing_order),
+        role_id: updated_submitter.role_id,
+        locale: updated_submitter.locale.clone(),
         user_id: Some(updated_submitter.user_id),
         name: updated_submitter.name,
         email: updated_submitter.email,
@@ -662,34 +987,145 @@ pub async fn submit_bulk_signatures(
         can_download: None,
         global_settings: None,
     };
-    ApiResponse::success(submitter, "Bulk signatures submitted successfully".to_string())
+    ApiResponse::success(submitter, "Bulk signatures submitted successfully".to_string()).into_response()
+}
+
+// Check if submitter is allowed to sign a field based on its role
+fn is_assigned_to_submitter(field: &crate::database::models::DbSubmissionField, db_submitter: &crate::database::models::DbSubmitter) -> bool {
+    template_roles::can_fill(field.role_id, db_submitter.role_id)
+}
+
+// Field values stored in the bulk signatures of the given submitters, by template field id.
+// A stored value belongs to the field of that name the submitter's role fills.
+fn collect_field_values<'a>(
+    submitters: impl Iterator<Item = &'a crate::database::models::DbSubmitter>,
+    fields: &[RoleField],
+) -> std::collections::HashMap<i64, String> {
+    let mut values = std::collections::HashMap::new();
+    for submitter in submitters {
+        let Some(serde_json::Value::Array(signatures)) = &submitter.bulk_signatures else { continue };
+        for sig in signatures {
+            if let (Some(field_name), Some(signature_value)) = (
+                sig.get("field_name").and_then(|v| v.as_str()),
+                sig.get("signature_value").and_then(|v| v.as_str()),
+            ) {
+                let field = fields.iter()
+                    .find(|f| f.name == field_name && template_roles::can_fill(f.role_id, submitter.role_id));
+                if let Some(field) = field {
+                    values.insert(field.template_field_id, signature_value.to_string());
+                }
+            }
+        }
+    }
+    values
 }
 
 // Helper function to validate signatures and create array
+// envelope_values holds what other signers filled in, so conditions on their fields can be evaluated.
+// With validate_values, required fields and validation rules are enforced and every failing
+// field is reported at once.
 fn validate_and_create_signatures(
     db_submitter: &crate::database::models::DbSubmitter,
     signatures: &[crate::models::signature::BulkSignatureItem],
     submission_fields: &[crate::database::models::DbSubmissionField],
-) -> Result<serde_json::Value, (StatusCode, Json<ApiResponse<crate::models::submitter::Submitter>>)> {
+    envelope_values: &std::collections::HashMap<i64, String>,
+    validate_values: bool,
+) -> Result<serde_json::Value, Response> {
     // Validate that all field_ids belong to this submitter's submission fields
     for signature_item in signatures {
         if let Some(field) = submission_fields.iter().find(|f| f.id == signature_item.field_id) {
-            // Check if submitter is allowed to sign this field based on partner
-            if let Some(ref partner) = field.partner {
-                let allowed = partner == &db_submitter.name || 
-                             partner == &db_submitter.email || 
-                             db_submitter.name.contains(&format!("({})", partner));
-                if !allowed {
-                    return Err(ApiResponse::bad_request(format!("Field {} is not assigned to this submitter", signature_item.field_id)));
-                }
+            if !is_assigned_to_submitter(field, db_submitter) {
+                return Err(SubmitterResponse::bad_request(format!("Field {} is not assigned to this submitter", signature_item.field_id)).into_response());
+            }
+            if field.readonly && field.prefilled_value.as_deref() != Some(signature_item.signature_value.as_str()) {
+                return Err(SubmitterResponse::bad_request(format!("Field '{}' is read-only", field.name)).into_response());
             }
         } else {
-            return Err(ApiResponse::bad_request(format!("Field {} not found in submission", signature_item.field_id)));
+            return Err(SubmitterResponse::bad_request(format!("Field {} not found in submission", signature_item.field_id)).into_response());
+        }
+    }
+
+    // Evaluate field conditions against the submitted values, falling back to what the sender prefilled
+    let mut field_values = envelope_values.clone();
+    for field in submission_fields {
+        if let Some(prefilled) = &field.prefilled_value {
+            field_values.entry(field.template_field_id).or_insert_with(|| prefilled.clone());
+        }
+    }
+    for signature_item in signatures {
+        if let Some(field) = submission_fields.iter().find(|f| f.id == signature_item.field_id) {
+            field_values.insert(field.template_field_id, signature_item.signature_value.clone());
+        }
+    }
+    // Names resolve to this submitter's own fields before same-named fields of other roles
+    let role_fields: Vec<RoleField> = submission_fields.iter().map(|f| RoleField {
+        template_field_id: f.template_field_id,
+        name: &f.name,
+        role_id: f.role_id,
+    }).collect();
+    let mut values = field_conditions::role_scoped_values(&role_fields, &field_values, db_submitter.role_id);
+    let mut ordered_fields: Vec<&crate::database::models::DbSubmissionField> = submission_fields.iter().collect();
+    ordered_fields.sort_by_key(|f| is_assigned_to_submitter(f, db_submitter));
+
+    // Formula fields are always computed here; whatever the client sent for them is discarded
+    let formula_sources: std::collections::HashMap<String, String> = ordered_fields.iter()
+        .filter(|f| f.field_type == formula::FIELD_TYPE_FORMULA)
+        .filter_map(|f| formula::formula_of(f.options.as_ref()).map(|source| (f.name.clone(), source.to_string())))
+        .collect();
+    let formula_results = formula::compute_all(&formula_sources, &values);
+    for (name, result) in &formula_results {
+        values.insert(name.clone(), result.clone().unwrap_or_default());
+    }
+    let conditional_fields: Vec<ConditionalField> = ordered_fields.iter().map(|f| ConditionalField {
+        name: &f.name,
+        required: f.required,
+        conditions: parse_conditions(f.conditions.as_ref()),
+    }).collect();
+    let states = field_conditions::evaluate(&conditional_fields, &values);
+
+    if validate_values {
+        let mut errors = Vec::new();
+        for field in submission_fields.iter().filter(|f| is_assigned_to_submitter(f, db_submitter)) {
+            let state = states.get(&field.name);
+            if !state.map(|s| s.visible).unwrap_or(true) {
+                continue;
+            }
+            let value = values.get(&field.name).map(String::as_str).unwrap_or("");
+            let violation = if let Some(Err(e)) = formula_results.get(&field.name) {
+                Some(FieldViolation { code: "formula", message: e.clone() })
+            } else if state.map(|s| s.required).unwrap_or(field.required) && value.trim().is_empty() {
+                Some(FieldViolation { code: "required", message: "This field is required".to_string() })
+            } else {
+                field_validation::check_value(&field.field_type, parse_validation(field.validation.as_ref()).as_ref(), value).err()
+            };
+            if let Some(violation) = violation {
+                errors.push(FieldValidationError {
+                    field_id: field.id,
+                    field_name: field.name.clone(),
+                    code: violation.code.to_string(),
+                    message: violation.message,
+                });
+            }
+        }
+        if !errors.is_empty() {
+            let summary = format!("{} field(s) failed validation", errors.len());
+            return Err(ApiResponse::unprocessable(errors, summary).into_response());
         }
     }
 
+    // Hidden fields are not kept, so stale values never reach the document
+    let is_visible = |field: &crate::database::models::DbSubmissionField| {
+        states.get(&field.name).map(|s| s.visible).unwrap_or(true)
+    };
+    let visible_signatures = signatures.iter().filter(|signature_item| {
+        submission_fields.iter()
+            .find(|f| f.id == signature_item.field_id)
+            .map(|f| is_visible(f) && f.field_type != formula::FIELD_TYPE_FORMULA && !f.readonly)
+            .unwrap_or(true)
+    });
+
     // Create signatures array with field details
-    let signatures_array: Vec<serde_json::Value> = signatures.iter().map(|signature_item| {
+    let mut signatures_array: Vec<serde_json::Value> = visible_signatures.map(|signature_item| {
         let field_id = signature_item.field_id;
         let field_name = submission_fields.iter()
             .find(|f| f.id == field_id)
@@ -703,6 +1139,35 @@ fn validate_and_create_signatures(
         })
     }).collect();
 
+    // Prefilled values the signer kept (always the case for read-only fields)
+    for field in submission_fields.iter().filter(|f| f.prefilled_value.is_some()) {
+        let submitted = signatures.iter().any(|item| item.field_id == field.id);
+        if !is_assigned_to_submitter(field, db_submitter) || !is_visible(field) || (submitted && !field.readonly) {
+            continue;
+        }
+        signatures_array.push(serde_json::json!({
+            "field_id": field.id,
+            "field_name": field.name,
+            "signature_value": field.prefilled_value,
+            "reason": null
+        }));
+    }
+
+    // Then this submitter's formula fields with their server-side values
+    for field in submission_fields.iter().filter(|f| f.field_type == formula::FIELD_TYPE_FORMULA) {
+        if !is_assigned_to_submitter(field, db_submitter) || !is_visible(field) {
+            continue;
+        }
+        if let Some(Ok(value)) = formula_results.get(&field.name) {
+            signatures_array.push(serde_json::json!({
+                "field_id": field.id,
+                "field_name": field.name,
+                "signature_value": value,
+                "reason": null
+            }));
+        }
+    }
+
     Ok(serde_json::Value::Array(signatures_array))
 }
 
@@ -712,32 +1177,32 @@ async fn handle_decline_action(
     db_submitter: crate::database::models::DbSubmitter,
     payload: crate::models::signature::BulkSignatureRequest,
     real_ip: String,
-) -> (StatusCode, Json<ApiResponse<crate::models::submitter::Submitter>>) {
+) -> Response {
     // Check global settings
     let user_settings = match GlobalSettingsQueries::get_user_settings(pool, db_submitter.user_id as i32).await {
         Ok(Some(settings)) => settings,
-        Ok(None) => return ApiResponse::internal_error("Global settings not found".to_string()),
-        Err(e) => return ApiResponse::internal_error(format!("Failed to get global settings: {}", e)),
+        Ok(None) => return SubmitterResponse::internal_error("Global settings not found".to_string()).into_response(),
+        Err(e) => return SubmitterResponse::internal_error(format!("Failed to get global settings: {}", e)).into_response(),
     };
     
     if !user_settings.allow_to_decline_documents {
-        return ApiResponse::bad_request("Declining documents is not allowed".to_string());
+        return SubmitterResponse::bad_request("Declining documents is not allowed".to_string()).into_response();
     }
     
     // Validate decline reason
     let decline_reason = match payload.decline_reason.as_ref() {
         Some(reason) if !reason.trim().is_empty() => reason,
-        _ => return ApiResponse::bad_request("Decline reason is required and cannot be empty".to_string()),
+        _ => return SubmitterResponse::bad_request("Decline reason is required and cannot be empty".to_string()).into_response(),
     };
 
     // Get submission fields
     let submission_fields = match SubmissionFieldQueries::get_submission_fields_by_submitter_id(pool, db_submitter.id).await {
         Ok(fields) => fields,
-        Err(e) => return ApiResponse::internal_error(format!("Failed to get submission fields: {}", e)),
+        Err(e) => return SubmitterResponse::internal_error(format!("Failed to get submission fields: {}", e)).into_response(),
     };
 
-    // Validate and create signatures
-    let bulk_signatures = match validate_and_create_signatures(&db_submitter, &payload.signatures, &submission_fields) {
+    // Validate and create signatures; a decline does not have to fill required fields
+    let bulk_signatures = match validate_and_create_signatures(&db_submitter, &payload.signatures, &submission_fields, &std::collections::HashMap::new(), false) {
         Ok(sigs) => sigs,
         Err(err_response) => return err_response,
     };
@@ -753,12 +1218,37 @@ async fn handle_decline_action(
         payload.timezone.as_deref(),
     ).await {
         Ok(Some(updated_submitter)) => {
+            audit::record_event(
+                pool,
+                &updated_submitter,
+                audit::EVENT_DECLINED,
+                &updated_submitter.email,
+                format!("Document declined by {}", updated_submitter.email),
+                AuditContext {
+                    ip_address: Some(real_ip.clone()),
+                    user_agent: payload.user_agent.clone(),
+                    timezone: payload.timezone.clone(),
+                },
+                Some(serde_json::json!({ "reason": decline_reason })),
+            ).await;
+            webhooks::dispatch_event(pool, updated_submitter.user_id, webhooks::EVENT_SUBMISSION_DECLINED, webhooks::submitter_event_data(&updated_submitter)).await;
+
+            if let Some(submission_id) = updated_submitter.submission_id {
+                if let Err(e) = SubmissionQueries::update_submission_status(pool, submission_id, "declined").await {
+                    eprintln!("Failed to mark submission {} as declined: {}", submission_id, e);
+                }
+            }
+
             let reminder_config = updated_submitter.reminder_config.as_ref()
                 .and_then(|v| serde_json::from_value(v.clone()).ok());
                 
             let submitter = crate::models::submitter::Submitter {
                 id: Some(updated_submitter.id),
                 template_id: Some(updated_submitter.template_id),
+                submission_id: updated_submitter.submission_id,
+                signing_order: Some(updated_submitter.signing_order),
+                role_id: updated_submitter.role_id,
+                locale: updated_submitter.locale.clone(),
                 user_id: Some(updated_submitter.user_id),
                 name: updated_submitter.name,
                 email: updated_submitter.email,
@@ -777,10 +1267,22 @@ async fn handle_decline_action(
                 can_download: None,
                 global_settings: None,
             };
-            ApiResponse::success(submitter, "Document declined successfully".to_string())
+            ApiResponse::success(submitter, "Document declined successfully".to_string()).into_response()
         }
-        Ok(None) => ApiResponse::not_found("Submitter not found".to_string()),
-        Err(e) => ApiResponse::internal_error(format!("Failed to decline document: {}", e)),
+        Ok(None) => SubmitterResponse::not_found("Submitter not found".to_string()).into_response(),
+        Err(e) => SubmitterResponse::internal_error(format!("Failed to decline document: {}", e)).into_response(),
+    }
+}
+
+// Submitters of the same envelope; falls back to the template for submitters created before submissions existed
+async fn get_envelope_submitters(
+    pool: &PgPool,
+    template_id: i64,
+    submission_id: Option<i64>,
+) -> Result<Vec<crate::database::models::DbSubmitter>, sqlx::Error> {
+    match submission_id {
+        Some(id) => SubmitterQueries::get_submitters_by_submission_id(pool, id).await,
+        None => SubmitterQueries::get_submitters_by_template_id(pool, template_id).await,
     }
 }
 
@@ -789,14 +1291,15 @@ async fn auto_sign_completed_submission(
     pool: &PgPool,
     submitter_id: i64,
     template_id: i64,
+    submission_id: Option<i64>,
     user_id: i64,
 ) -> Result<(), String> {
     use crate::routes::pdf_signature::auto_sign_submission_pdf;
     
     eprintln!("🔄 Auto-sign: Checking submission {} for auto-sign eligibility", submitter_id);
     
-    // Get all submitters for this template
-    let all_submitters = SubmitterQueries::get_submitters_by_template_id(pool, template_id)
+    // Get all submitters of this envelope
+    let all_submitters = get_envelope_submitters(pool, template_id, submission_id)
         .await
         .map_err(|e| format!("Failed to get submitters: {}", e))?;
     
@@ -820,31 +1323,209 @@ async fn auto_sign_completed_submission(
         pool,
         template_id,
         &storage_service,
-        None, // Include all submitters
+        None, // Include all submitters of the envelope
+        submission_id,
     )
     .await
     .map_err(|e| format!("Failed to generate PDF: {}", e))?;
     
     eprintln!("📄 Auto-sign: PDF generated ({} bytes). Attempting to sign...", pdf_bytes.len());
     
-    // Auto-sign the PDF
-    match auto_sign_submission_pdf(pool, user_id, &pdf_bytes).await {
+    // The widget sits on the signature field of the submitter who completed the envelope
+    let appearance = match all_submitters.iter().find(|s| s.id == submitter_id) {
+        Some(submitter) => build_signature_appearance(pool, &storage_service, submitter, &pdf_bytes).await,
+        None => None,
+    };
+
+    // Auto-sign the PDF; without a usable certificate the flattened PDF is still kept as the final document
+    let (final_pdf_bytes, digitally_signed, sign_result) = match auto_sign_submission_pdf(pool, user_id, &pdf_bytes, appearance.as_ref()).await {
         Ok(signed_pdf_bytes) => {
             eprintln!("✅ Auto-sign: PDF signed successfully ({} bytes)", signed_pdf_bytes.len());
-            
-            // TODO: Save signed PDF to storage
-            // This would require updating the submission or template with the signed PDF
-            // For now, just log success
-            eprintln!("ℹ️  Auto-sign: Signed PDF ready. Storage integration pending.");
-            
-            Ok(())
+            (signed_pdf_bytes, true, Ok(()))
         },
         Err(e) => {
             // Don't fail the submission, just log the error
             eprintln!("⚠️  Auto-sign failed: {}", e);
-            Err(e)
+            (pdf_bytes, false, Err(e))
+        }
+    };
+
+    match submission_id {
+        Some(submission_id) => {
+            let stored = crate::routes::submissions::store_signed_document(
+                pool,
+                &storage_service,
+                submission_id,
+                final_pdf_bytes,
+                digitally_signed,
+            ).await?;
+            eprintln!(
+                "💾 Auto-sign: Final PDF stored for submission {} (sha256={})",
+                submission_id,
+                stored.signed_document_sha256.unwrap_or_default()
+            );
+        }
+        None => eprintln!("ℹ️  Auto-sign: Submitter {} has no submission, final PDF not stored", submitter_id),
+    }
+
+    // The flattened PDF is kept as the final document; retrying would not find a certificate either
+    if let Err(e) = sign_result {
+        eprintln!("⚠️  Auto-sign: final PDF stored without a digital signature: {}", e);
+    }
+    Ok(())
+}
+
+/// Payload of the `auto_sign` and `completion_emails` jobs queued when a submitter signs
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+struct SignedSubmitterJob {
+    submitter_id: i64,
+    template_id: i64,
+    submission_id: Option<i64>,
+    user_id: i64,
+    signed_at: Option<i64>,
+}
+
+impl SignedSubmitterJob {
+    fn from_submitter(submitter: &crate::database::models::DbSubmitter) -> Self {
+        Self {
+            submitter_id: submitter.id,
+            template_id: submitter.template_id,
+            submission_id: submitter.submission_id,
+            user_id: submitter.user_id,
+            signed_at: submitter.signed_at.map(|at| at.timestamp()),
+        }
+    }
+
+    // Keyed by signing time so a resubmitted form gets its own jobs
+    async fn enqueue(&self, pool: &PgPool, kind: &str) {
+        let key = format!("{}:{}:{}", kind, self.submitter_id, self.signed_at.unwrap_or(0));
+        let payload = serde_json::to_value(self).unwrap_or_default();
+        if let Err(e) = jobs::enqueue(pool, kind, payload, None, Some(&key)).await {
+            eprintln!("❌ Failed to queue {} job for submitter {}: {}", kind, self.submitter_id, e);
+        }
+    }
+}
+
+/// Job handler for `auto_sign`: stores the final PDF once every signer is done, then
+/// queues the completion emails so they can attach it
+pub fn auto_sign_job(pool: PgPool, payload: serde_json::Value) -> BoxFuture<'static, Result<JobOutcome, String>> {
+    Box::pin(async move {
+        let job: SignedSubmitterJob = serde_json::from_value(payload)
+            .map_err(|e| format!("Invalid auto-sign payload: {}", e))?;
+        auto_sign_completed_submission(&pool, job.submitter_id, job.template_id, job.submission_id, job.user_id).await?;
+        job.enqueue(&pool, jobs::JOB_COMPLETION_EMAILS).await;
+        Ok(JobOutcome::Done)
+    })
+}
+
+/// Job handler for `completion_emails`
+pub fn completion_emails_job(pool: PgPool, payload: serde_json::Value) -> BoxFuture<'static, Result<JobOutcome, String>> {
+    Box::pin(async move {
+        let job: SignedSubmitterJob = serde_json::from_value(payload)
+            .map_err(|e| format!("Invalid completion email payload: {}", e))?;
+        send_completion_notifications(&pool, job.submitter_id, job.template_id, job.submission_id, job.user_id).await
+            .map_err(|e| format!("Failed to send completion emails: {}", e))?;
+        Ok(JobOutcome::Done)
+    })
+}
+
+// Visible widget for the digital signature: the submitter's first signature field,
+// showing their drawn signature, name, signature ID and signing date
+pub async fn build_signature_appearance(
+    pool: &PgPool,
+    storage_service: &StorageService,
+    submitter: &crate::database::models::DbSubmitter,
+    pdf_bytes: &[u8],
+) -> Option<crate::services::pades::SignatureAppearance> {
+    let template_fields = TemplateFieldQueries::get_template_fields(pool, submitter.template_id).await.ok()?;
+    let signatures: Vec<serde_json::Value> = serde_json::from_value(submitter.bulk_signatures.clone()?).ok()?;
+
+    let (sig, position) = signatures.iter().find_map(|sig| {
+        let field_name = sig.get("field_name")?.as_str()?;
+        let field = template_fields.iter().find(|f| f.name == field_name && f.field_type == "signature")?;
+        let position = serde_json::from_value::<crate::models::template::FieldPosition>(field.position.clone()?).ok()?;
+        Some((sig, position))
+    })?;
+
+    let doc = lopdf::Document::load_mem(pdf_bytes).ok()?;
+    let page_index = (position.page - 1).max(0) as usize;
+    let page_id = doc.get_pages().values().nth(page_index).copied()?;
+    let (page_width, page_height) = page_dimensions(&doc, page_id);
+
+    // Same placement rules as render_signatures_on_pdf
+    let (x, y, width, height) = match (
+        sig.get("abs_x").and_then(|v| v.as_f64()),
+        sig.get("abs_y").and_then(|v| v.as_f64()),
+        sig.get("abs_w").and_then(|v| v.as_f64()),
+        sig.get("abs_h").and_then(|v| v.as_f64()),
+    ) {
+        (Some(x), Some(y), Some(w), Some(h)) => (x, y, w, h),
+        _ => {
+            let (x, y, w, h) = normalize_position(position.x, position.y, position.width, position.height);
+            (x * page_width, y * page_height, w * page_width, h * page_height)
         }
+    };
+
+    let signature_value = sig.get("signature_value").and_then(|v| v.as_str()).unwrap_or("");
+    let image = if signature_value.starts_with("/api/files/") || signature_value.starts_with("http://") || signature_value.starts_with("https://") {
+        load_signature_image(signature_value, storage_service).await
+    } else {
+        None
+    };
+
+    let loc = Localizer::for_user(pool, submitter.user_id).await;
+    let date = loc.datetime(submitter.signed_at.unwrap_or_else(Utc::now));
+
+    let mut lines = vec![if submitter.name.is_empty() { submitter.email.clone() } else { submitter.name.clone() }];
+    if let Some(reason) = sig.get("reason").and_then(|r| r.as_str()).filter(|r| !r.is_empty()) {
+        lines.push(loc.t_args("signature.reason", &[("reason", reason)]));
     }
+    lines.push(format!("ID: {}", hash_id(submitter.id + 1)));
+    lines.push(date);
+
+    let account_id = UserQueries::get_user_by_id(pool, submitter.user_id).await.ok().flatten().and_then(|user| user.account_id);
+    let font = pdf_fonts::load_field_font(pool, submitter.user_id, account_id).await
+        .unwrap_or_else(|_| FONT_NOTO_SANS.to_string());
+
+    Some(crate::services::pades::SignatureAppearance {
+        page_index,
+        rect: [x, page_height - y - height, x + width, page_height - y],
+        image,
+        lines,
+        font,
+    })
+}
+
+// Write the document to attach for a submitter to a temp file.
+// Uses the stored final PDF once the submission is completed, otherwise renders only this submitter's signatures.
+async fn prepare_signed_document_attachment(
+    pool: &PgPool,
+    template_id: i64,
+    submission_id: Option<i64>,
+    submitter_id: i64,
+) -> Option<String> {
+    let stored_pdf = match submission_id {
+        Some(submission_id) => match crate::routes::submissions::load_signed_document(pool, submission_id).await {
+            Ok(stored) => stored,
+            Err(e) => {
+                eprintln!("⚠️  Failed to load stored signed PDF for submission {}: {}", submission_id, e);
+                None
+            }
+        },
+        None => None,
+    };
+
+    let pdf_bytes = match stored_pdf {
+        Some(bytes) => bytes,
+        None => {
+            let storage_service = StorageService::new().await.ok()?;
+            generate_signed_pdf_for_template_with_filter(pool, template_id, &storage_service, Some(submitter_id), None).await.ok()?
+        }
+    };
+
+    let temp_file = std::env::temp_dir().join(format!("signed_document_{}.pdf", submitter_id));
+    tokio::fs::write(&temp_file, pdf_bytes).await.ok()?;
+    Some(temp_file.to_string_lossy().to_string())
 }
 
 // Background task for sending completion notifications
@@ -852,6 +1533,7 @@ async fn send_completion_notifications(
     pool: &PgPool,
     submitter_id: i64,
     template_id: i64,
+    submission_id: Option<i64>,
     user_id: i64,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     // Get reminder settings
@@ -869,7 +1551,7 @@ async fn send_completion_notifications(
     let template = TemplateQueries::get_template_by_id(pool, template_id).await?
         .ok_or("Template not found")?;
     
-    let all_submitters = SubmitterQueries::get_submitters_by_template_id(pool, template_id).await?;
+    let all_submitters = get_envelope_submitters(pool, template_id, submission_id).await?;
     let completed_count = all_submitters.iter().filter(|s| s.status == "signed" || s.status == "completed").count();
     let total_count = all_submitters.len();
 
@@ -880,77 +1562,56 @@ async fn send_completion_notifications(
 
     println!("All submitters completed for template {}. Sending notifications...", template_id);
 
-    let email_service = crate::services::email::EmailService::new()?;
-    let email_template = EmailTemplateQueries::get_default_template_by_type(pool, user_id, "completion").await.ok().flatten();
-    
-    use std::collections::HashSet;
-    let mut notified_emails: HashSet<String> = HashSet::new();
-    
-    // DO NOT generate combined PDF - each submitter should get their own signatures only
-    // Generate combined PDF once if needed
-    // let combined_document_path = if email_template.as_ref().map(|t| t.attach_documents).unwrap_or(false) {
-    //     if let Ok(storage_service) = StorageService::new().await {
-    //         if let Ok(signed_pdf_bytes) = generate_signed_pdf_for_template_with_filter(pool, template_id, &storage_service, None).await {
-    //             let temp_file = std::env::temp_dir().join(format!("signed_document_all_{}.pdf", template_id));
-    //             tokio::fs::write(&temp_file, signed_pdf_bytes).await.ok();
-    //             Some(temp_file.to_string_lossy().to_string())
-    //         } else { None }
-    //     } else { None }
-    // } else { None };
+    let email_service = crate::services::email::EmailService::new(pool)?;
+    let email_template = match EmailTemplateQueries::get_default_template_by_type(pool, user_id, "completion").await.ok().flatten() {
+        Some(email_template) => email_template,
+        None => return Ok(()),
+    };
+
+    // A retried job only sends to the recipients that haven't received this round's email yet
+    let submitter_ids: Vec<i64> = all_submitters.iter().map(|s| s.id).collect();
+    let since = all_submitters.iter().filter_map(|s| s.signed_at).max();
+    let mut notified_emails = EmailOutboxQueries::get_sent_recipients(pool, "completion", &submitter_ids, since).await?;
     let combined_document_path: Option<String> = None;
 
-    // Send to completion_email first
-    if let Some(ref email_tmpl) = email_template {
-        let db_submitter = SubmitterQueries::get_submitter_by_id(pool, submitter_id).await?.ok_or("Submitter not found")?;
-        send_single_completion_email(
+    let db_submitter = SubmitterQueries::get_submitter_by_id(pool, submitter_id).await?.ok_or("Submitter not found")?;
+    let mut recipients = vec![(completion_email.clone(), db_submitter.name.clone(), db_submitter.token.clone(), submitter_id)];
+    // Send to all submitters if multiple
+    if total_count > 1 {
+        recipients.extend(all_submitters.iter()
+            .filter(|s| s.status == "signed" || s.status == "completed")
+            .map(|s| (s.email.clone(), s.name.clone(), s.token.clone(), s.id)));
+    }
+
+    let mut failed = Vec::new();
+    for (email, name, token, recipient_submitter_id) in recipients {
+        if !notified_emails.insert(email.to_lowercase()) {
+            continue;
+        }
+        if let Err(e) = send_single_completion_email(
             &email_service,
             pool,
-            &completion_email,
-            &db_submitter.name,
-            &db_submitter.token,
+            &email,
+            &name,
+            &token,
             &template,
-            email_tmpl,
+            &email_template,
             &all_submitters,
             completed_count,
             total_count,
             combined_document_path.as_deref(),
             template_id,
-            Some(submitter_id),
-        ).await?;
-        notified_emails.insert(completion_email.clone());
-    }
-
-    // Send to all submitters if multiple
-    if total_count > 1 {
-        for submitter_info in &all_submitters {
-            if (submitter_info.status == "signed" || submitter_info.status == "completed") 
-                && !notified_emails.contains(&submitter_info.email) {
-                if let Some(ref email_tmpl) = email_template {
-                    let _ = send_single_completion_email(
-                        &email_service,
-                        pool,
-                        &submitter_info.email,
-                        &submitter_info.name,
-                        &submitter_info.token,
-                        &template,
-                        email_tmpl,
-                        &all_submitters,
-                        completed_count,
-                        total_count,
-                        combined_document_path.as_deref(),
-                        template_id,
-                        Some(submitter_info.id),
-                    ).await;
-                    notified_emails.insert(submitter_info.email.clone());
-                }
-            }
+            submission_id,
+            Some(recipient_submitter_id),
+        ).await {
+            eprintln!("⚠️  Failed to send completion email to {}: {}", email, e);
+            failed.push(email);
         }
     }
 
-    // Cleanup - no combined document path anymore
-    // if let Some(path) = combined_document_path {
-    //     let _ = tokio::fs::remove_file(path).await;
-    // }
+    if !failed.is_empty() {
+        return Err(format!("completion email not sent to {}", failed.join(", ")).into());
+    }
 
     Ok(())
 }
@@ -969,8 +1630,19 @@ async fn send_single_completion_email(
     total_count: usize,
     combined_document_path: Option<&str>,
     template_id: i64,
+    submission_id: Option<i64>,
     submitter_id: Option<i64>,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let mut branding = EmailBranding::for_user(pool, template.user_id).await;
+    // The owner reads the account's language, a submitter the one they were sent the submission in
+    if let Some(recipient) = all_submitters.iter().find(|s| s.email.eq_ignore_ascii_case(to_email)) {
+        branding = branding.for_recipient(recipient);
+    }
+    let email_service = email_service.clone().with_context(crate::services::email::EmailContext {
+        user_id: Some(template.user_id),
+        submitter_id,
+        kind: Some("completion".to_string()),
+    }).with_branding(&branding);
     let completed_signers = all_submitters.iter()
         .filter(|s| s.status == "signed" || s.status == "completed")
         .map(|s| s.name.clone())
@@ -980,54 +1652,40 @@ async fn send_single_completion_email(
     let base_url = std::env::var("BASE_URL").unwrap_or_else(|_| "http://localhost:8081".to_string());
     let submitter_link = format!("{}/templates/{}/edit", base_url, token);
     let signed_submission_link = format!("{}/signed-submission/{}", base_url, token);
-    let template_name_html = format!(r#"<a href="{}">{}</a>"#, signed_submission_link, template.name);
+    let template_name_html = format!(r#"<a href="{}">{}</a>"#, signed_submission_link, email_template_engine::escape_html(&template.name));
     let progress = format!("{} of {} completed", completed_count, total_count);
-    
-    let mut subject_variables = std::collections::HashMap::new();
-    subject_variables.insert("submitter.name", to_name);
-    subject_variables.insert("template.name", template.name.as_str());
-    subject_variables.insert("submitter.link", submitter_link.as_str());
-    subject_variables.insert("account.name", "DocuSeal Pro");
-    subject_variables.insert("completed.signers", completed_signers.as_str());
-    subject_variables.insert("progress", progress.as_str());
-
-    let mut body_variables = std::collections::HashMap::new();
-    body_variables.insert("submitter.name", to_name);
-    
-    if email_template.body_format == "html" {
-        body_variables.insert("template.name", template_name_html.as_str());
-    } else {
-        body_variables.insert("template.name", template.name.as_str());
-    }
 
-    body_variables.insert("submitter.link", submitter_link.as_str());
-    body_variables.insert("account.name", "DocuSeal Pro");
-    body_variables.insert("completed.signers", completed_signers.as_str());
-    body_variables.insert("progress", progress.as_str());
+    // The recipient may be the owner rather than a submitter; the envelope is the same either way
+    let envelope_submitter = all_submitters.iter()
+        .find(|s| Some(s.id) == submitter_id)
+        .or_else(|| all_submitters.first());
+    let mut context = branding.context();
+    if let Some(submitter) = envelope_submitter {
+        email_template_engine::insert_submitter_variables(pool, &mut context, submitter, &template.name).await;
+    }
+    context
+        .insert("submitter.name", to_name)
+        .insert("submitter.email", to_email)
+        .insert("submitter.link", submitter_link.as_str())
+        .insert("completed.signers", completed_signers.as_str())
+        .insert("progress", progress.as_str())
+        .insert_html("template.name", &template.name, template_name_html);
 
-    let subject = replace_template_variables(&email_template.subject, &subject_variables);
-    let body = replace_template_variables(&email_template.body, &body_variables);
+    let (subject, body) = branding.render(email_template, &context)?;
 
     let mut document_path = combined_document_path.map(|s| s.to_string());
     let mut audit_log_path = None;
 
-    // Generate per-submitter PDF if no combined and attach_documents is true
+    // Attach the stored final PDF (or a per-submitter render) if no combined and attach_documents is true
     if email_template.attach_documents && document_path.is_none() {
         if let Some(sid) = submitter_id {
-            if let Ok(storage_service) = StorageService::new().await {
-                if let Ok(signed_pdf_bytes) = generate_signed_pdf_for_template_with_filter(pool, template_id, &storage_service, Some(sid)).await {
-                    let temp_file = std::env::temp_dir().join(format!("signed_document_{}.pdf", sid));
-                    if tokio::fs::write(&temp_file, signed_pdf_bytes).await.is_ok() {
-                        document_path = Some(temp_file.to_string_lossy().to_string());
-                    }
-                }
-            }
+            document_path = prepare_signed_document_attachment(pool, template_id, submission_id, sid).await;
         }
     }
 
     if email_template.attach_audit_log {
-        if let Ok(audit_pdf_bytes) = generate_template_audit_log_pdf(pool, template_id).await {
-            let temp_file = std::env::temp_dir().join(format!("audit_log_template_{}.pdf", template_id));
+        if let Ok(audit_pdf_bytes) = generate_envelope_audit_log_pdf(pool, template_id, submission_id).await {
+            let temp_file = std::env::temp_dir().join(format!("audit_log_{}_{}.pdf", template_id, submission_id.unwrap_or(0)));
             if tokio::fs::write(&temp_file, audit_pdf_bytes).await.is_ok() {
                 audit_log_path = Some(temp_file.to_string_lossy().to_string());
             }
@@ -1067,17 +1725,28 @@ async fn send_single_completion_email(
     ),
     responses(
         (status = 200, description = "Template fields retrieved successfully", body = ApiResponse<crate::models::submitter::PublicSubmitterFieldsResponse>),
-        (status = 404, description = "Submitter not found", body = ApiResponse<crate::models::submitter::PublicSubmitterFieldsResponse>)
+        (status = 404, description = "Submitter not found", body = ApiResponse<crate::models::submitter::PublicSubmitterFieldsResponse>),
+        (status = 410, description = "The submission has expired or was voided", body = ApiResponse<crate::models::submitter::PublicSubmitterFieldsResponse>)
     )
 )]
 pub async fn get_public_submitter_fields(
     State(state): State<AppState>,
     Path(token): Path<String>,
+    headers: HeaderMap,
 ) -> (StatusCode, Json<ApiResponse<crate::models::submitter::PublicSubmitterFieldsResponse>>) {
     let pool = &state.lock().await.db_pool;
 
     match SubmitterQueries::get_submitter_by_token(pool, &token).await {
         Ok(Some(db_submitter)) => {
+            match expiry::closed_link_reason(pool, &db_submitter).await {
+                Ok(None) => {}
+                Ok(Some(reason)) => {
+                    let loc = Localizer::for_signer(pool, &db_submitter, &headers).await;
+                    return ApiResponse::gone(reason.message(loc.locale));
+                }
+                Err(e) => return ApiResponse::internal_error(format!("Failed to check signing link: {}", e)),
+            }
+
             // Get the template for basic info
             let template_id = db_submitter.template_id;
             match crate::database::queries::TemplateQueries::get_template_by_id(pool, template_id).await {
@@ -1096,12 +1765,21 @@ pub async fn get_public_submitter_fields(
                                     display_order: sf.display_order,
                                     position: sf.position.map(|pos| {
                                         // Parse position JSON to FieldPosition
-                                        serde_json::from_value(pos).unwrap_or_else(|_| crate::models::template::FieldPosition {
+                                        let mut position: crate::models::template::FieldPosition = serde_json::from_value(pos).unwrap_or_else(|_| crate::models::template::FieldPosition {
                                             x: 0.0, y: 0.0, width: 100.0, height: 20.0, page: 1, default_value: None
-                                        })
+                                        });
+                                        // A value prefilled by the sender replaces the template default
+                                        if sf.prefilled_value.is_some() {
+                                            position.default_value = sf.prefilled_value.clone();
+                                        }
+                                        position
                                     }),
                                     options: sf.options,
                                     partner: sf.partner,
+                                    role_id: sf.role_id,
+                                    conditions: parse_conditions(sf.conditions.as_ref()),
+                                    validation: parse_validation(sf.validation.as_ref()),
+                                    readonly: sf.readonly,
                                     created_at: sf.created_at,
                                     updated_at: sf.updated_at,
                                 }
@@ -1125,21 +1803,10 @@ pub async fn get_public_submitter_fields(
                                 document,
                             };
 
-                            // Filter fields based on partner matching submitter's name or email
-                            println!("DEBUG: Submitter name: {}, email: {}", db_submitter.name, db_submitter.email);
+                            // Only the fields of the submitter's role (and fields without a role)
                             let filtered_fields: Vec<crate::models::template::TemplateField> = template_fields.into_iter()
-                                .filter(|field| {
-                                    if let Some(ref partner) = field.partner {
-                                        let matches = partner == &db_submitter.name || partner == &db_submitter.email;
-                                        println!("DEBUG: Field {} partner '{}' matches: {}", field.name, partner, matches);
-                                        matches
-                                    } else {
-                                        println!("DEBUG: Field {} has no partner, allowing", field.name);
-                                        true // Allow fields without partner for all submitters
-                                    }
-                                })
+                                .filter(|field| template_roles::can_fill(field.role_id, db_submitter.role_id))
                                 .collect();
-                            println!("DEBUG: Filtered fields count: {}", filtered_fields.len());
 
                             let response = crate::models::submitter::PublicSubmitterFieldsResponse {
                                 template_info,
@@ -1171,17 +1838,28 @@ pub async fn get_public_submitter_fields(
     ),
     responses(
         (status = 200, description = "Signatures retrieved successfully", body = ApiResponse<crate::models::submitter::PublicSubmitterSignaturesResponse>),
-        (status = 404, description = "Submitter not found", body = ApiResponse<crate::models::submitter::PublicSubmitterSignaturesResponse>)
+        (status = 404, description = "Submitter not found", body = ApiResponse<crate::models::submitter::PublicSubmitterSignaturesResponse>),
+        (status = 410, description = "The submission has expired or was voided", body = ApiResponse<crate::models::submitter::PublicSubmitterSignaturesResponse>)
     )
 )]
 pub async fn get_public_submitter_signatures(
     State(state): State<AppState>,
     Path(token): Path<String>,
+    headers: HeaderMap,
 ) -> (StatusCode, Json<ApiResponse<crate::models::submitter::PublicSubmitterSignaturesResponse>>) {
     let pool = &state.lock().await.db_pool;
 
     match SubmitterQueries::get_submitter_by_token(pool, &token).await {
         Ok(Some(db_submitter)) => {
+            match expiry::closed_link_reason(pool, &db_submitter).await {
+                Ok(None) => {}
+                Ok(Some(reason)) => {
+                    let loc = Localizer::for_signer(pool, &db_submitter, &headers).await;
+                    return ApiResponse::gone(reason.message(loc.locale));
+                }
+                Err(e) => return ApiResponse::internal_error(format!("Failed to check signing link: {}", e)),
+            }
+
             // Get the template
             let template_id = db_submitter.template_id;
             match crate::database::queries::TemplateQueries::get_template_by_id(pool, template_id).await {
@@ -1199,25 +1877,19 @@ pub async fn get_public_submitter_signatures(
                                 document,
                             };
                             
-                            // Get all submitters for this template
-                            match SubmitterQueries::get_submitters_by_template(pool, template_id).await {
-                                Ok(all_submitters) => {
-                                    // Group submitters by creation time proximity (within 1 minute)
-                                    let mut time_groups: std::collections::HashMap<String, Vec<crate::database::models::DbSubmitter>> = std::collections::HashMap::new();
-
-                                    for submitter in &all_submitters {
-                                        // Group by minute timestamp (floor to nearest minute)
-                                        let timestamp = submitter.created_at.timestamp();
-                                        let minute_key = (timestamp / 60).to_string(); // Group by minute
-                                        time_groups.entry(minute_key).or_insert_with(Vec::new).push(submitter.clone());
-                                    }
-
-                                    // Find the group that contains the current submitter
-                                    let current_group = time_groups.into_iter()
-                                        .find(|(_, group)| group.iter().any(|s| s.id == db_submitter.id))
-                                        .map(|(_, group)| group)
-                                        .unwrap_or_else(|| vec![db_submitter.clone()]);
-
+                            // Get all submitters in the same envelope
+                            let envelope_submitters = match db_submitter.submission_id {
+                                Some(submission_id) => SubmitterQueries::get_submitters_by_submission_id(pool, submission_id).await,
+                                None => SubmitterQueries::get_submitters_by_template(pool, template_id).await.map(|all_submitters| {
+                                    // Legacy submitters without a submission: group by creation time proximity (within 1 minute)
+                                    let minute_key = db_submitter.created_at.timestamp() / 60;
+                                    all_submitters.into_iter()
+                                        .filter(|s| s.created_at.timestamp() / 60 == minute_key)
+                                        .collect()
+                                }),
+                            };
+                            match envelope_submitters {
+                                Ok(current_group) => {
                                     // Collect all bulk_signatures from submitters in the same group
                                     let mut all_signatures = Vec::new();
                                     
@@ -1253,6 +1925,7 @@ pub async fn get_public_submitter_signatures(
                                                                     "position": field.position,
                                                                     "options": field.options,
                                                                     "partner": field.partner,
+                                                                    "role_id": field.role_id,
                                                                     "created_at": field.created_at,
                                                                     "updated_at": field.updated_at
                                                                 });
@@ -1314,13 +1987,31 @@ fn normalize_position(x: f64, y: f64, width: f64, height: f64) -> (f64, f64, f64
     }
 }
 
+// Page width and height from the MediaBox, defaulting to US Letter
+fn page_dimensions(doc: &lopdf::Document, page_id: lopdf::ObjectId) -> (f64, f64) {
+    let number = |value: &lopdf::Object| value.as_f32().map(|v| v as f64).or_else(|_| value.as_i64().map(|v| v as f64));
+
+    doc.get_dictionary(page_id)
+        .and_then(|page| page.get(b"MediaBox"))
+        .and_then(|mediabox| mediabox.as_array())
+        .ok()
+        .filter(|mediabox| mediabox.len() >= 4)
+        .map(|mediabox| (
+            number(&mediabox[2]).unwrap_or(612.0),
+            number(&mediabox[3]).unwrap_or(792.0),
+        ))
+        .unwrap_or((612.0, 792.0))
+}
+
 /// Helper function to render signatures on PDF using the position formula
+#[allow(clippy::too_many_arguments)]
 async fn render_signatures_on_pdf(
     pdf_bytes: &[u8],
     signatures: &[(String, String, String, f64, f64, f64, f64, i32, serde_json::Value)], // (field_name, field_type, signature_value, x, y, w, h, page, signature_json)
     user_settings: &crate::database::models::DbGlobalSettings,
     submitter: &crate::database::models::DbSubmitter,
     storage_service: &crate::services::storage::StorageService,
+    field_font: &str,
 ) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync + 'static>> {
     println!("=== RENDER_SIGNATURES_ON_PDF CALLED (submitters.rs) ===");
     use lopdf::{Document, Object, Stream, Dictionary};
@@ -1328,6 +2019,9 @@ async fn render_signatures_on_pdf(
     
     // Load the PDF document
     let mut doc = Document::load_mem(pdf_bytes)?;
+    let mut fonts = DocumentFonts::new(field_font);
+    // The document belongs to the account, so every signer's values read the same way
+    let loc = Localizer::from_settings(Some(user_settings), None);
     
     // Get all page IDs first
     let page_ids: Vec<_> = doc.get_pages()
@@ -1358,39 +2052,7 @@ async fn render_signatures_on_pdf(
         let page_id = page_ids[page_index];
         
         // Get page dimensions (MediaBox)
-        let (page_width, page_height) = {
-            let page_obj = doc.get_object(page_id)?;
-            let page_dict = page_obj.as_dict()?;
-            
-            if let Ok(mediabox) = page_dict.get(b"MediaBox") {
-                if let Ok(mediabox_array) = mediabox.as_array() {
-                    if mediabox_array.len() >= 4 {
-                        let width = if let Ok(w) = mediabox_array[2].as_f32() {
-                            w as f64
-                        } else if let Ok(w) = mediabox_array[2].as_i64() {
-                            w as f64
-                        } else {
-                            612.0
-                        };
-                        
-                        let height = if let Ok(h) = mediabox_array[3].as_f32() {
-                            h as f64
-                        } else if let Ok(h) = mediabox_array[3].as_i64() {
-                            h as f64
-                        } else {
-                            792.0
-                        };
-                        (width, height)
-                    } else {
-                        (612.0, 792.0)
-                    }
-                } else {
-                    (612.0, 792.0)
-                }
-            } else {
-                (612.0, 792.0)
-            }
-        };
+        let (page_width, page_height) = page_dimensions(&doc, page_id);
         
         // Try to get absolute coordinates from signature_json first, fallback to calculation
         let (x_pos, y_pos, field_width, field_height) = if let (Some(abs_x), Some(abs_y), Some(abs_w), Some(abs_h)) = (
@@ -1450,20 +2112,20 @@ async fn render_signatures_on_pdf(
             "multiple" => {
                 // Chia giá trị theo dấu phẩy và nối chúng bằng dấu cách
                 let display_value = signature_value.split(',').collect::<Vec<&str>>().join(" ");
-                render_text_field(&mut doc, page_id, &display_value, x_pos, pdf_y, field_width, field_height)?;
+                render_text_field(&mut doc, page_id, &mut fonts, &display_value, x_pos, pdf_y, field_width, field_height)?;
             },
             "cells" => {
                 // Hiển thị trong bố cục lưới với mỗi ký tự trong một ô riêng biệt
-                render_cells_field(&mut doc, page_id, &signature_value, x_pos, pdf_y, field_width, field_height)?;
+                render_cells_field(&mut doc, page_id, &mut fonts, &signature_value, x_pos, pdf_y, field_width, field_height)?;
             },
             "radio" => {
                 // Hiển thị giá trị đã chọn hoặc chỗ giữ chỗ
                 let display_value = if signature_value.is_empty() {
-                    format!("Chọn {}", field_name)
+                    loc.t_args("field.radio_placeholder", &[("field", field_name)])
                 } else {
                     signature_value.to_string()
                 };
-                render_text_field(&mut doc, page_id, &display_value, x_pos, pdf_y, field_width, field_height)?;
+                render_text_field(&mut doc, page_id, &mut fonts, &display_value, x_pos, pdf_y, field_width, field_height)?;
             },
             "initials" => {
                 // Calculate text height dynamically (matching SignatureRenderer.tsx)
@@ -1534,22 +2196,22 @@ async fn render_signatures_on_pdf(
                     // JSON object - có thể có text hoặc vector
                     if let Ok(json_value) = serde_json::from_str::<serde_json::Value>(signature_value) {
                         if let Some(text) = json_value.get("text").and_then(|t| t.as_str()) {
-                            render_initials_field(&mut doc, page_id, text, x_pos, sig_y, field_width, sig_height)?;
+                            render_initials_field(&mut doc, page_id, &mut fonts, text, x_pos, sig_y, field_width, sig_height)?;
                         } else if let Some(initials) = json_value.get("initials").and_then(|i| i.as_str()) {
-                            render_initials_field(&mut doc, page_id, initials, x_pos, sig_y, field_width, sig_height)?;
+                            render_initials_field(&mut doc, page_id, &mut fonts, initials, x_pos, sig_y, field_width, sig_height)?;
                         } else {
-                            render_initials_field(&mut doc, page_id, "[SIGNATURE]", x_pos, sig_y, field_width, sig_height)?;
+                            render_initials_field(&mut doc, page_id, &mut fonts, "[SIGNATURE]", x_pos, sig_y, field_width, sig_height)?;
                         }
                     } else {
-                        render_initials_field(&mut doc, page_id, &signature_value, x_pos, sig_y, field_width, sig_height)?;
+                        render_initials_field(&mut doc, page_id, &mut fonts, &signature_value, x_pos, sig_y, field_width, sig_height)?;
                     }
                 } else {
                     // Plain text
-                    render_initials_field(&mut doc, page_id, &signature_value, x_pos, sig_y, field_width, sig_height)?;
+                    render_initials_field(&mut doc, page_id, &mut fonts, &signature_value, x_pos, sig_y, field_width, sig_height)?;
                 }
                 
                 // Add signature ID information below the signature (always show for downloaded PDFs)
-                render_signature_id_info(&mut doc, page_id, submitter, &signature_json, x_pos, pdf_y, field_width, field_height, user_settings)?;
+                render_signature_id_info(&mut doc, page_id, &mut fonts, submitter, &signature_json, x_pos, pdf_y, field_width, field_height, user_settings)?;
             },
             "image" => {
                 // Render image from URL
@@ -1558,14 +2220,23 @@ async fn render_signatures_on_pdf(
                 } else {
                     // Fallback for invalid paths
                     let display_value = format!("[IMAGE: {}]", signature_value);
-                    render_text_field(&mut doc, page_id, &display_value, x_pos, pdf_y, field_width, field_height)?;
+                    render_text_field(&mut doc, page_id, &mut fonts, &display_value, x_pos, pdf_y, field_width, field_height)?;
                 }
             },
             "file" => {
                 // Hiển thị liên kết tải xuống có thể nhấp với tên tệp được trích xuất từ URL
                 let filename = extract_filename_from_url(&signature_value);
                 let display_value = format!("[DOWNLOAD: {}]", filename);
-                render_text_field(&mut doc, page_id, &display_value, x_pos, pdf_y, field_width, field_height)?;
+                render_text_field(&mut doc, page_id, &mut fonts, &display_value, x_pos, pdf_y, field_width, field_height)?;
+            },
+            "formula" => {
+                // Value was computed on the server when the submitter signed
+                render_text_field(&mut doc, page_id, &mut fonts, signature_value, x_pos, pdf_y, field_width, field_height)?;
+            },
+            "date" => {
+                // Dates are written the way the account's locale and timezone write them
+                let display_value = loc.date_value(signature_value);
+                render_text_field(&mut doc, page_id, &mut fonts, &display_value, x_pos, pdf_y, field_width, field_height)?;
             },
             "text" => {
                 // Pure text field - use full field dimensions without subtracting text height
@@ -1574,7 +2245,7 @@ async fn render_signatures_on_pdf(
                 } else {
                     signature_value.to_string()
                 };
-                render_text_field(&mut doc, page_id, &display_value, x_pos, pdf_y, field_width, field_height)?;
+                render_text_field(&mut doc, page_id, &mut fonts, &display_value, x_pos, pdf_y, field_width, field_height)?;
             },
             _ => {
                 // Calculate text height dynamically (matching SignatureRenderer.tsx)
@@ -1653,16 +2324,16 @@ async fn render_signatures_on_pdf(
                     // JSON object - có thể có text hoặc vector
                     if let Ok(json_value) = serde_json::from_str::<serde_json::Value>(signature_value) {
                         if let Some(text) = json_value.get("text").and_then(|t| t.as_str()) {
-                            render_text_field(&mut doc, page_id, text, x_pos, sig_y, field_width, sig_height)?;
+                            render_text_field(&mut doc, page_id, &mut fonts, text, x_pos, sig_y, field_width, sig_height)?;
                         } else if let Some(sig_text) = json_value.get("signature").and_then(|s| s.as_str()) {
-                            render_text_field(&mut doc, page_id, sig_text, x_pos, sig_y, field_width, sig_height)?;
+                            render_text_field(&mut doc, page_id, &mut fonts, sig_text, x_pos, sig_y, field_width, sig_height)?;
                         } else {
                             let display_value = if signature_value.is_empty() {
                                 field_name.clone()
                             } else {
                                 signature_value.to_string()
                             };
-                            render_text_field(&mut doc, page_id, &display_value, x_pos, sig_y, field_width, sig_height)?;
+                            render_text_field(&mut doc, page_id, &mut fonts, &display_value, x_pos, sig_y, field_width, sig_height)?;
                         }
                     } else {
                         let display_value = if signature_value.is_empty() {
@@ -1670,7 +2341,7 @@ async fn render_signatures_on_pdf(
                         } else {
                             signature_value.to_string()
                         };
-                        render_text_field(&mut doc, page_id, &display_value, x_pos, sig_y, field_width, sig_height)?;
+                        render_text_field(&mut doc, page_id, &mut fonts, &display_value, x_pos, sig_y, field_width, sig_height)?;
                     }
                 } else {
                     // Plain text
@@ -1680,17 +2351,20 @@ async fn render_signatures_on_pdf(
                         signature_value.to_string()
                     };
                     println!("DEBUG: Plain text signature - field_name={}, signature_value='{}', display_value='{}'", field_name, signature_value, display_value);
-                    render_text_field(&mut doc, page_id, &display_value, x_pos, sig_y, field_width, sig_height)?;
+                    render_text_field(&mut doc, page_id, &mut fonts, &display_value, x_pos, sig_y, field_width, sig_height)?;
                 }
                 
                 // Add signature ID information below signatures (always show for downloaded PDFs)
                 if field_type == "signature" {
-                    render_signature_id_info(&mut doc, page_id, submitter, &signature_json, x_pos, pdf_y, field_width, field_height, user_settings)?;
+                    render_signature_id_info(&mut doc, page_id, &mut fonts, submitter, &signature_json, x_pos, pdf_y, field_width, field_height, user_settings)?;
                 }
             }
         }
     }
     
+    // Embed the glyphs drawn above
+    fonts.finish(&mut doc)?;
+
     // Save modified PDF to bytes
     let mut output = Vec::new();
     doc.save_to(&mut output)?;
@@ -1762,9 +2436,11 @@ fn calculate_signature_text_height(
 }
 
 // Render signature ID information below the signature
+#[allow(clippy::too_many_arguments)]
 fn render_signature_id_info(
     doc: &mut lopdf::Document,
     page_id: lopdf::ObjectId,
+    fonts: &mut DocumentFonts,
     submitter: &crate::database::models::DbSubmitter,
     signature_data: &serde_json::Value,
     x_pos: f64,
@@ -1788,73 +2464,15 @@ fn render_signature_id_info(
     let signer_email = submitter.email.clone();
     let signed_at = submitter.signed_at.unwrap_or(chrono::Utc::now());
     
-    // Parse timezone from global settings or use default GMT+7
-    let timezone_str = user_settings.timezone.as_deref().unwrap_or("Asia/Ho_Chi_Minh");
-    
-    // Map common timezone names to IANA identifiers (matching SignatureRenderer)
-    let timezone_mapped = match timezone_str {
-        "Midway Island" => "Pacific/Midway",
-        "Hawaii" => "Pacific/Honolulu",
-        "Alaska" => "America/Anchorage",
-        "Pacific" => "America/Los_Angeles",
-        "Mountain" => "America/Denver",
-        "Central" => "America/Chicago",
-        "Eastern" => "America/New_York",
-        "Atlantic" => "America/Halifax",
-        "Newfoundland" => "America/St_Johns",
-        "London" => "Europe/London",
-        "Berlin" => "Europe/Berlin",
-        "Paris" => "Europe/Paris",
-        "Rome" => "Europe/Rome",
-        "Moscow" => "Europe/Moscow",
-        "Tokyo" => "Asia/Tokyo",
-        "Shanghai" => "Asia/Shanghai",
-        "Hong Kong" => "Asia/Hong_Kong",
-        "Singapore" => "Asia/Singapore",
-        "Sydney" => "Australia/Sydney",
-        "UTC" => "UTC",
-        _ => timezone_str,
-    };
-    
-    // Parse timezone offset (simplified approach for common timezones)
-    let timezone_offset_hours = match timezone_mapped {
-        "Asia/Ho_Chi_Minh" => 7,
-        "Pacific/Midway" => -11,
-        "Pacific/Honolulu" => -10,
-        "America/Anchorage" => -9,
-        "America/Los_Angeles" => -8,
-        "America/Denver" => -7,
-        "America/Chicago" => -6,
-        "America/New_York" => -5,
-        "America/Halifax" => -4,
-        "Europe/London" => 0,
-        "Europe/Berlin" | "Europe/Paris" | "Europe/Rome" => 1,
-        "Europe/Moscow" => 3,
-        "Asia/Tokyo" => 9,
-        "Asia/Shanghai" | "Asia/Hong_Kong" | "Asia/Singapore" => 8,
-        "Australia/Sydney" => 10,
-        "UTC" => 0,
-        _ => 7, // Default to GMT+7
-    };
-    
-    let timezone_offset = chrono::FixedOffset::east_opt(timezone_offset_hours * 3600).unwrap();
-    let signed_at_formatted = signed_at.with_timezone(&timezone_offset);
-    
-    // Format date according to locale (simplified)
-    let locale = user_settings.locale.as_deref().unwrap_or("vi-VN");
-    let date_str = if locale.starts_with("vi") {
-        // Vietnamese format: DD/MM/YYYY, HH:MM:SS
-        signed_at_formatted.format("%d/%m/%Y, %H:%M:%S").to_string()
-    } else {
-        // English/Default format: MM/DD/YYYY, HH:MM:SS
-        signed_at_formatted.format("%m/%d/%Y, %H:%M:%S").to_string()
-    };
+    // Signing time in the account's timezone and locale (matching SignatureRenderer.tsx)
+    let loc = Localizer::from_settings(Some(user_settings), None);
+    let date_str = loc.datetime(signed_at);
     
     let mut signature_info_parts = Vec::new();
     
     // Always show reason first if require_signing_reason is enabled and reason exists
     if user_settings.require_signing_reason && !reason.is_empty() {
-        signature_info_parts.push(format!("Reason: {}", reason));
+        signature_info_parts.push(loc.t_args("signature.reason", &[("reason", reason)]));
     }
     
     // Show ID, email, and date if add_signature_id_to_the_documents is enabled
@@ -1941,10 +2559,6 @@ fn render_signature_id_info(
     // Create text content stream for signature info with multiple lines
     let mut text_operations = vec![
         Operation::new("BT", vec![]), // Begin text
-        Operation::new("Tf", vec![
-            Object::Name(b"F1".to_vec()), // Use Arial (F1) instead of Helvetica
-            Object::Real(font_size as f32),
-        ]), // Set font
         Operation::new("rg", vec![
             Object::Real(0.0),
             Object::Real(0.0),
@@ -1970,12 +2584,12 @@ fn render_signature_id_info(
             Object::Real(line_y as f32),  // f: vertical position
         ]));
         
-        text_operations.push(Operation::new("Tj", vec![
-            Object::string_literal(line.clone()),
-        ])); // Show text
+        text_operations.extend(fonts.text_operations(line, font_size)); // Show text
     }
     
     text_operations.push(Operation::new("ET", vec![])); // End text
+
+    fonts.register_on_page(doc, page_id)?;
     
     let content = Content { operations: text_operations };
     let content_data = content.encode()?;
@@ -2014,19 +2628,19 @@ fn render_signature_id_info(
 
 
 // Render text field (default)
+#[allow(clippy::too_many_arguments)]
 fn render_text_field(
     doc: &mut lopdf::Document,
     page_id: lopdf::ObjectId,
+    fonts: &mut DocumentFonts,
     text: &str,
     x_pos: f64,
     pdf_y: f64,
     field_width: f64,
     field_height: f64,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
-    use lopdf::{Object, Stream, Dictionary, StringFormat};
+    use lopdf::{Object, Stream, Dictionary};
     use lopdf::content::{Content, Operation};
-    use std::fs;
-    use std::collections::BTreeMap;
 
     // Use CSS-like font size to match frontend (16px -> 12pt)
     let font_size = 12.0; // Fixed size to match frontend
@@ -2037,163 +2651,17 @@ fn render_text_field(
     // Debug: Print the original text and display text
     println!("DEBUG render_text_field: original text = '{}', display_text = '{}'", text, display_text);
 
-    // Load Noto Sans Bold font for Unicode support with bold weight
-    let font_data = fs::read("/home/giap/LamViec/Letmesign/NotoSans-Bold.ttf")
-        .map_err(|e| format!("Failed to load font file: {}", e))?;
-
-    // Create embedded TrueType font with CID encoding for Unicode support
-    let font_name = b"F1".to_vec();
-
-    // Create font file stream
-    let mut font_file_dict = Dictionary::new();
-    font_file_dict.set("Length1", Object::Integer(font_data.len() as i64));
-    let font_file_stream = Stream::new(font_file_dict, font_data);
-    let font_file_id = doc.add_object(font_file_stream);
-
-    // Create ToUnicode CMap for proper character mapping
-    let unicode_chars: Vec<u32> = display_text.chars().map(|c| c as u32).collect();
-    let mut cmap_content = String::from(
-        "/CIDInit /ProcSet findresource begin\n\
-         12 dict begin\n\
-         begincmap\n\
-         /CIDSystemInfo << /Registry (Adobe) /Ordering (UCS) /Supplement 0 >> def\n\
-         /CMapName /Adobe-Identity-UCS def\n\
-         /CMapType 2 def\n\
-         1 begincodespacerange\n\
-         <0000> <FFFF>\n\
-         endcodespacerange\n"
-    );
-    
-    // Add character mappings
-    let mut unique_chars: BTreeMap<u32, ()> = BTreeMap::new();
-    for &code in &unicode_chars {
-        unique_chars.insert(code, ());
-    }
-    
-    cmap_content.push_str(&format!("{} beginbfchar\n", unique_chars.len()));
-    for &code in unique_chars.keys() {
-        cmap_content.push_str(&format!("<{:04X}> <{:04X}>\n", code, code));
-    }
-    cmap_content.push_str(
-        "endbfchar\n\
-         endcmap\n\
-         CMapName currentdict /CMap defineresource pop\n\
-         end\n\
-         end\n"
-    );
-    
-    let cmap_bytes = cmap_content.into_bytes();
-    let mut cmap_stream_dict = Dictionary::new();
-    cmap_stream_dict.set("Length", Object::Integer(cmap_bytes.len() as i64));
-    let cmap_stream = Stream::new(cmap_stream_dict, cmap_bytes);
-    let to_unicode_id = doc.add_object(cmap_stream);
-
-    // Create font descriptor
-    let mut font_descriptor = Dictionary::new();
-    font_descriptor.set("Type", Object::Name(b"FontDescriptor".to_vec()));
-    font_descriptor.set("FontName", Object::Name(b"NotoSans-Bold".to_vec()));
-    font_descriptor.set("Flags", Object::Integer(32)); // Symbolic font
-    font_descriptor.set("FontBBox", Object::Array(vec![
-        Object::Integer(-511), Object::Integer(-250), Object::Integer(1399), Object::Integer(1000)
-    ]));
-    font_descriptor.set("ItalicAngle", Object::Integer(0));
-    font_descriptor.set("Ascent", Object::Integer(1000));
-    font_descriptor.set("Descent", Object::Integer(-250));
-    font_descriptor.set("CapHeight", Object::Integer(700));
-    font_descriptor.set("StemV", Object::Integer(120)); // Increased for bold weight
-    font_descriptor.set("FontFile2", Object::Reference(font_file_id));
-    let font_descriptor_id = doc.add_object(Object::Dictionary(font_descriptor));
-
-    // Create CID font dictionary with proper width mapping
-    let mut cid_font_dict = Dictionary::new();
-    cid_font_dict.set("Type", Object::Name(b"Font".to_vec()));
-    cid_font_dict.set("Subtype", Object::Name(b"CIDFontType2".to_vec()));
-    cid_font_dict.set("BaseFont", Object::Name(b"NotoSans-Bold".to_vec()));
-    cid_font_dict.set("CIDSystemInfo", Object::Dictionary({
-        let mut cid_system_info = Dictionary::new();
-        cid_system_info.set("Registry", Object::String(b"Adobe".to_vec(), StringFormat::Literal));
-        cid_system_info.set("Ordering", Object::String(b"Identity".to_vec(), StringFormat::Literal));
-        cid_system_info.set("Supplement", Object::Integer(0));
-        cid_system_info
-    }));
-    cid_font_dict.set("FontDescriptor", Object::Reference(font_descriptor_id));
-    
-    // Set proper default width for better spacing (600 units = balanced spacing)
-    cid_font_dict.set("DW", Object::Integer(600));
-    
-    // Add width array for common characters to ensure consistent spacing
-    let w_array = vec![
-        Object::Integer(0),     // Start CID
-        Object::Array(vec![Object::Integer(600); 256]),  // 256 characters with width 600
-    ];
-    cid_font_dict.set("W", Object::Array(w_array));
-    
-    let cid_font_id = doc.add_object(Object::Dictionary(cid_font_dict));
-
-    // Create Type0 font dictionary with ToUnicode CMap
-    let mut type0_font_dict = Dictionary::new();
-    type0_font_dict.set("Type", Object::Name(b"Font".to_vec()));
-    type0_font_dict.set("Subtype", Object::Name(b"Type0".to_vec()));
-    type0_font_dict.set("BaseFont", Object::Name(b"NotoSans-Bold".to_vec()));
-    type0_font_dict.set("Encoding", Object::Name(b"Identity-H".to_vec()));
-    type0_font_dict.set("DescendantFonts", Object::Array(vec![Object::Reference(cid_font_id)]));
-    type0_font_dict.set("ToUnicode", Object::Reference(to_unicode_id));
-    let font_dict_id = doc.add_object(Object::Dictionary(type0_font_dict));
-
-    // Add font to page resources
-    {
-        let page_obj = doc.get_object_mut(page_id)?;
-        let page_dict = page_obj.as_dict_mut()?;
-
-        // Get or create Resources
-        if !page_dict.has(b"Resources") {
-            page_dict.set("Resources", Object::Dictionary(Dictionary::new()));
-        }
-    }
-
-    // Update the resources (separate borrow)
-    {
-        let page_obj = doc.get_object_mut(page_id)?;
-        let page_dict = page_obj.as_dict_mut()?;
-
-        if let Ok(resources_obj) = page_dict.get_mut(b"Resources") {
-            if let Ok(resources) = resources_obj.as_dict_mut() {
-                // Get or create Font dictionary
-                if !resources.has(b"Font") {
-                    let mut font_dict = Dictionary::new();
-                    font_dict.set(font_name.clone(), Object::Reference(font_dict_id));
-                    resources.set("Font", Object::Dictionary(font_dict));
-                } else if let Ok(font_obj) = resources.get_mut(b"Font") {
-                    if let Ok(fonts) = font_obj.as_dict_mut() {
-                        fonts.set(font_name.clone(), Object::Reference(font_dict_id));
-                    }
-                }
-            }
-        }
-    }
-
     // Position text to the left
     let text_y = pdf_y + field_height / 2.0 + 5.0;
 
     // Left align horizontally
     let text_x = x_pos + 5.0; // Small padding from left
 
-    // Convert text to UTF-16BE bytes for Identity-H encoding
-    let utf16_bytes: Vec<u8> = display_text.encode_utf16()
-        .flat_map(|c| c.to_be_bytes())
-        .collect();
-
     // Create text content stream with proper character spacing
-    let operations = vec![
+    let mut operations = vec![
         // Begin text object
         Operation::new("BT", vec![]),
 
-        // Set font and size
-        Operation::new("Tf", vec![
-            Object::Name(font_name),
-            Object::Real(font_size as f32),
-        ]),
-
         // Set text color to black
         Operation::new("rg", vec![
             Object::Real(0.0),
@@ -2216,15 +2684,15 @@ fn render_text_field(
             Object::Real(text_x as f32),
             Object::Real(text_y as f32),
         ]),
+    ];
 
-        // Show text using Identity-H encoding with embedded font (use Literal format for better spacing)
-        Operation::new("Tj", vec![
-            Object::String(utf16_bytes, StringFormat::Literal),
-        ]),
+    // Show text as glyph ids of the embedded font subset
+    operations.extend(fonts.text_operations(&display_text, font_size));
 
-        // End text object
-        Operation::new("ET", vec![]),
-    ];
+    // End text object
+    operations.push(Operation::new("ET", vec![]));
+
+    fonts.register_on_page(doc, page_id)?;
 
     let content = Content { operations };
     let content_data = content.encode()?;
@@ -2385,9 +2853,11 @@ fn render_empty_checkbox(
 }
 
 // Render cells field (grid layout)
+#[allow(clippy::too_many_arguments)]
 fn render_cells_field(
     doc: &mut lopdf::Document,
     page_id: lopdf::ObjectId,
+    fonts: &mut DocumentFonts,
     text: &str,
     x_pos: f64,
     pdf_y: f64,
@@ -2401,49 +2871,6 @@ fn render_cells_field(
     let cell_width = field_width / chars.len() as f64;
     let font_size = (field_height * 0.8).min(cell_width * 0.8);
 
-    // Create Arial font if not exists
-    let font_name = b"F1".to_vec();
-    let font_dict_id = {
-        let mut arial_dict = Dictionary::new();
-        arial_dict.set("Type", Object::Name(b"Font".to_vec()));
-        arial_dict.set("Subtype", Object::Name(b"Type1".to_vec()));
-        arial_dict.set("BaseFont", Object::Name(b"Helvetica".to_vec()));
-        arial_dict.set("Encoding", Object::Name(b"Identity-H".to_vec()));
-        doc.add_object(Object::Dictionary(arial_dict))
-    };
-
-    // Add font to page resources
-    {
-        let page_obj = doc.get_object_mut(page_id)?;
-        let page_dict = page_obj.as_dict_mut()?;
-
-        // Get or create Resources
-        if !page_dict.has(b"Resources") {
-            page_dict.set("Resources", Object::Dictionary(Dictionary::new()));
-        }
-    }
-
-    // Update the resources (separate borrow)
-    {
-        let page_obj = doc.get_object_mut(page_id)?;
-        let page_dict = page_obj.as_dict_mut()?;
-
-        if let Ok(resources_obj) = page_dict.get_mut(b"Resources") {
-            if let Ok(resources) = resources_obj.as_dict_mut() {
-                // Get or create Font dictionary
-                if !resources.has(b"Font") {
-                    let mut font_dict = Dictionary::new();
-                    font_dict.set(font_name.clone(), Object::Reference(font_dict_id));
-                    resources.set("Font", Object::Dictionary(font_dict));
-                } else if let Ok(font_obj) = resources.get_mut(b"Font") {
-                    if let Ok(fonts) = font_obj.as_dict_mut() {
-                        fonts.set(font_name.clone(), Object::Reference(font_dict_id));
-                    }
-                }
-            }
-        }
-    }
-
     let mut operations = Vec::new();
 
     // Draw grid lines
@@ -2467,27 +2894,30 @@ fn render_cells_field(
     operations.push(Operation::new("l", vec![Object::Real((x_pos + field_width) as f32), Object::Real((pdf_y + field_height) as f32)]));
     operations.push(Operation::new("S", vec![]));
 
-    // Draw characters
+    // Draw characters, each centered in its cell
     operations.push(Operation::new("BT", vec![]));
-    operations.push(Operation::new("Tf", vec![
-        Object::Name(font_name),
-        Object::Real(font_size as f32),
-    ]));
     operations.push(Operation::new("rg", vec![Object::Real(0.0), Object::Real(0.0), Object::Real(0.0)]));
 
     for (i, ch) in chars.iter().enumerate() {
-        let cell_x = x_pos + i as f64 * cell_width + cell_width * 0.1;
-        let baseline_y = pdf_y + field_height * 0.8;
+        let ch = ch.to_string();
+        let cell_x = x_pos + i as f64 * cell_width + (cell_width - fonts.text_width(&ch, font_size)) / 2.0;
+        let baseline_y = pdf_y + (field_height - font_size) / 2.0 + font_size * 0.25;
 
-        operations.push(Operation::new("Td", vec![
+        operations.push(Operation::new("Tm", vec![
+            Object::Real(1.0),
+            Object::Real(0.0),
+            Object::Real(0.0),
+            Object::Real(1.0),
             Object::Real(cell_x as f32),
             Object::Real(baseline_y as f32),
         ]));
-        operations.push(Operation::new("Tj", vec![Object::string_literal(ch.to_string())]));
+        operations.extend(fonts.text_operations(&ch, font_size));
     }
 
     operations.push(Operation::new("ET", vec![]));
 
+    fonts.register_on_page(doc, page_id)?;
+
     let content = Content { operations };
     let content_data = content.encode()?;
 
@@ -2529,13 +2959,15 @@ fn render_cells_field(
 }
 
 // Render initials field with special positioning
+#[allow(clippy::too_many_arguments)]
 fn render_initials_field(
     doc: &mut lopdf::Document,
     page_id: lopdf::ObjectId,
+    fonts: &mut DocumentFonts,
     text: &str,
     x_pos: f64,
     pdf_y: f64,
-    _field_width: f64,
+    field_width: f64,
     field_height: f64,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
     use lopdf::{Object, Stream, Dictionary};
@@ -2544,68 +2976,6 @@ fn render_initials_field(
     // Special font size for initials (smaller, more condensed)
     let font_size = (field_height * 0.6).max(10.0).min(18.0);
 
-    // Helper: approximate text width (average width per character groups)
-    let approx_text_width = |text: &str, font_size_pt: f64| -> f64 {
-        // Rough per-character width factors for Latin alphabet (approximation)
-        // narrow: i, l; mid: a-z; wide: m, w, W, M; space smaller
-        let mut width = 0.0_f64;
-        for ch in text.chars() {
-            let factor = match ch {
-                'i' | 'I' | 'l' | 'j' | 't' | '\'' | '|' => 0.28,
-                ' ' => 0.28,
-                'f' | 'r' | 's' | ',' | ':' | ';' | '.' => 0.35,
-                'a'..='z' | 'A'..='Z' | '0'..='9' => 0.55,
-                'm' | 'w' | 'M' | 'W' => 0.85,
-                _ => 0.6,
-            };
-            width += factor * font_size_pt;
-        }
-        width
-    };
-
-    // Create Arial font if not exists
-    let font_name = b"F1".to_vec();
-    let font_dict_id = {
-        let mut arial_dict = Dictionary::new();
-        arial_dict.set("Type", Object::Name(b"Font".to_vec()));
-        arial_dict.set("Subtype", Object::Name(b"Type1".to_vec()));
-        arial_dict.set("BaseFont", Object::Name(b"Helvetica".to_vec()));
-        arial_dict.set("Encoding", Object::Name(b"Identity-H".to_vec()));
-        doc.add_object(Object::Dictionary(arial_dict))
-    };
-
-    // Add font to page resources
-    {
-        let page_obj = doc.get_object_mut(page_id)?;
-        let page_dict = page_obj.as_dict_mut()?;
-
-        // Get or create Resources
-        if !page_dict.has(b"Resources") {
-            page_dict.set("Resources", Object::Dictionary(Dictionary::new()));
-        }
-    }
-
-    // Update the resources (separate borrow)
-    {
-        let page_obj = doc.get_object_mut(page_id)?;
-        let page_dict = page_obj.as_dict_mut()?;
-
-        if let Ok(resources_obj) = page_dict.get_mut(b"Resources") {
-            if let Ok(resources) = resources_obj.as_dict_mut() {
-                // Get or create Font dictionary
-                if !resources.has(b"Font") {
-                    let mut font_dict = Dictionary::new();
-                    font_dict.set(font_name.clone(), Object::Reference(font_dict_id));
-                    resources.set("Font", Object::Dictionary(font_dict));
-                } else if let Ok(font_obj) = resources.get_mut(b"Font") {
-                    if let Ok(fonts) = font_obj.as_dict_mut() {
-                        fonts.set(font_name.clone(), Object::Reference(font_dict_id));
-                    }
-                }
-            }
-        }
-    }
-
     // Calculate positioning for initials (matching frontend centering logic)
     // Center in available space, similar to frontend
     // Note: field_height here is already sig_height (field_height - text_height) from caller
@@ -2617,12 +2987,6 @@ fn render_initials_field(
         // Begin text object
         Operation::new("BT", vec![]),
 
-        // Set font and size (bold for initials)
-        Operation::new("Tf", vec![
-            Object::Name(font_name),
-            Object::Real(font_size as f32),
-        ]),
-
         // Set text color to black
         Operation::new("rg", vec![
             Object::Real(0.0),
@@ -2632,18 +2996,18 @@ fn render_initials_field(
 
         // Position text at baseline (centered horizontally)
         Operation::new("Td", vec![
-            Object::Real((x_pos + _field_width / 2.0 - approx_text_width(text, font_size) / 2.0) as f32),
+            Object::Real((x_pos + field_width / 2.0 - fonts.text_width(text, font_size) / 2.0) as f32),
             Object::Real(baseline_y as f32),
         ]),
+    ];
 
-        // Show text
-        Operation::new("Tj", vec![
-            Object::string_literal(text.to_string()),
-        ]),
+    // Show text
+    text_operations.extend(fonts.text_operations(text, font_size));
 
-        // End text object
-        Operation::new("ET", vec![]),
-    ];
+    // End text object
+    text_operations.push(Operation::new("ET", vec![]));
+
+    fonts.register_on_page(doc, page_id)?;
 
     let content = Content { operations: text_operations };
     let content_data = content.encode()?;
@@ -2829,19 +3193,11 @@ fn render_vector_signature(
 }
 
 // Render image from file path or URL into PDF
-async fn render_image_field(
-    doc: &mut lopdf::Document,
-    page_id: lopdf::ObjectId,
+// Load a signature/image field value from storage (/api/files/...) or an http(s) URL
+async fn load_signature_image(
     image_path: &str,
-    x_pos: f64,
-    pdf_y: f64,
-    field_width: f64,
-    field_height: f64,
     storage_service: &crate::services::storage::StorageService,
-) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
-    use lopdf::{Object, Stream, Dictionary};
-    use lopdf::content::{Content, Operation};
-    
+) -> Option<image::RgbaImage> {
     // Load image from storage or URL
     let image_bytes = if image_path.starts_with("/api/files/") {
         // Extract filename from path
@@ -2852,7 +3208,7 @@ async fn render_image_field(
             Ok(bytes) => bytes,
             Err(e) => {
                 eprintln!("Failed to download image {}: {}", filename, e);
-                return Ok(()); // Skip rendering this image
+                return None;
             }
         }
     } else if image_path.starts_with("http://") || image_path.starts_with("https://") {
@@ -2862,26 +3218,45 @@ async fn render_image_field(
                 Ok(bytes) => bytes.to_vec(),
                 Err(e) => {
                     eprintln!("Failed to download image from URL {}: {}", image_path, e);
-                    return Ok(());
+                    return None;
                 }
             },
             Err(e) => {
                 eprintln!("Failed to fetch image from URL {}: {}", image_path, e);
-                return Ok(());
+                return None;
             }
         }
     } else {
         eprintln!("Invalid image path: {}", image_path);
-        return Ok(());
+        return None;
     };
     
     // Load image using the image crate
-    let img = match image::load_from_memory(&image_bytes) {
-        Ok(img) => img.to_rgba8(),
+    match image::load_from_memory(&image_bytes) {
+        Ok(img) => Some(img.to_rgba8()),
         Err(e) => {
             eprintln!("Failed to load image: {}", e);
-            return Ok(());
+            None
         }
+    }
+}
+
+async fn render_image_field(
+    doc: &mut lopdf::Document,
+    page_id: lopdf::ObjectId,
+    image_path: &str,
+    x_pos: f64,
+    pdf_y: f64,
+    field_width: f64,
+    field_height: f64,
+    storage_service: &crate::services::storage::StorageService,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+    use lopdf::{Object, Stream, Dictionary};
+    use lopdf::content::{Content, Operation};
+    
+    let img = match load_signature_image(image_path, storage_service).await {
+        Some(img) => img,
+        None => return Ok(()), // Skip rendering this image
     };
     
     let (img_width, img_height) = img.dimensions();
@@ -3011,65 +3386,6 @@ async fn render_image_field(
     Ok(())
 }
 
-/// Merge multiple PDFs into one
-fn merge_pdfs(pdf_bytes_list: Vec<Vec<u8>>) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
-    use lopdf::{Document, Object, Dictionary};
-    
-    if pdf_bytes_list.is_empty() {
-        return Err("No PDFs to merge".into());
-    }
-    
-    if pdf_bytes_list.len() == 1 {
-        return Ok(pdf_bytes_list[0].clone());
-    }
-    
-    // Load all documents
-    let mut documents: Vec<Document> = Vec::new();
-    for pdf_bytes in &pdf_bytes_list {
-        documents.push(Document::load_mem(pdf_bytes)?);
-    }
-    
-    // Create a new merged document by combining pages manually
-    let mut merged_doc = Document::with_version("1.5");
-    let mut max_id = 1;
-    
-    for doc in &documents {
-        // Get all pages from this document
-        let pages: Vec<_> = doc.get_pages().into_iter().collect();
-        
-        for (_page_num, page_id) in pages {
-            // Get the page object
-            if let Ok(page_obj) = doc.get_object(page_id) {
-                if let Ok(_page_dict) = page_obj.as_dict() {
-                    // Clone the page and all its resources
-                    // Simple approach: add all objects from source doc
-                    for (obj_id, obj) in doc.objects.iter() {
-                        if obj_id.0 > max_id {
-                            max_id = obj_id.0;
-                        }
-                        merged_doc.objects.insert((obj_id.0 + max_id, obj_id.1), obj.clone());
-                    }
-                    max_id += max_id;
-                }
-            }
-        }
-    }
-    
-    // Rebuild page tree
-    merged_doc.renumber_objects();
-    merged_doc.compress();
-    
-    // Alternative simpler approach: concatenate pages
-    // Since lopdf doesn't have add_page_from, we'll use a simpler concatenation
-    // For now, return the first document with signatures as fallback
-    // In production, you might want to use a different library or implement proper merging
-    
-    // Simple fallback: return all PDFs concatenated (not truly merged)
-    // For a production solution, consider using pdfium-render or pdf_writer
-    Ok(pdf_bytes_list.concat())
-}
-
-
 #[utoipa::path(
     put,
     path = "/public/submissions/{token}/resubmit",
@@ -3079,12 +3395,15 @@ fn merge_pdfs(pdf_bytes_list: Vec<Vec<u8>>) -> Result<Vec<u8>, Box<dyn std::erro
     responses(
         (status = 200, description = "Submitter resubmitted successfully", body = ApiResponse<crate::models::submitter::Submitter>),
         (status = 404, description = "Submitter not found", body = ApiResponse<crate::models::submitter::Submitter>),
-        (status = 400, description = "Cannot resubmit if not completed", body = ApiResponse<crate::models::submitter::Submitter>)
+        (status = 400, description = "Cannot resubmit if not completed", body = ApiResponse<crate::models::submitter::Submitter>),
+        (status = 410, description = "The submission has expired or was voided", body = ApiResponse<crate::models::submitter::Submitter>)
     )
 )]
 pub async fn resubmit_submitter(
     State(state): State<AppState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
     Path(token): Path<String>,
+    headers: HeaderMap,
 ) -> (StatusCode, Json<ApiResponse<crate::models::submitter::Submitter>>) {
     let pool = &state.lock().await.db_pool;
 
@@ -3109,8 +3428,33 @@ pub async fn resubmit_submitter(
                 return ApiResponse::bad_request("Cannot resubmit: submission is not completed".to_string());
             }
 
+            // A voided or expired envelope is not reopened
+            match expiry::closed_for_resubmission(pool, &db_submitter).await {
+                Ok(None) => {}
+                Ok(Some(reason)) => {
+                    let loc = Localizer::for_signer(pool, &db_submitter, &headers).await;
+                    return ApiResponse::gone(reason.message(loc.locale));
+                }
+                Err(e) => return ApiResponse::internal_error(format!("Failed to check signing link: {}", e)),
+            }
+
             match SubmitterQueries::resubmit_submitter(pool, db_submitter.id).await {
                 Ok(()) => {
+                    audit::record_event(
+                        pool,
+                        &db_submitter,
+                        audit::EVENT_RESUBMITTED,
+                        &db_submitter.email,
+                        format!("Form reopened for resubmission by {}", db_submitter.email),
+                        AuditContext::from_request(&headers, Some(addr.ip().to_string()), None),
+                        None,
+                    ).await;
+
+                    // The envelope is open again until this submitter signs
+                    if let Some(submission_id) = db_submitter.submission_id {
+                        let _ = SubmissionQueries::refresh_submission_status(pool, submission_id).await;
+                    }
+
                     // Fetch the updated submitter
                     match SubmitterQueries::get_submitter_by_id(pool, db_submitter.id).await {
                         Ok(Some(updated_submitter)) => {
@@ -3120,6 +3464,10 @@ pub async fn resubmit_submitter(
                             let submitter = crate::models::submitter::Submitter {
                                 id: Some(updated_submitter.id),
                                 template_id: Some(updated_submitter.template_id),
+                                submission_id: updated_submitter.submission_id,
+                                signing_order: Some(updated_submitter.signing_order),
+                                role_id: updated_submitter.role_id,
+                                locale: updated_submitter.locale.clone(),
                                 user_id: Some(updated_submitter.user_id),
                                 name: updated_submitter.name,
                                 email: updated_submitter.email,
@@ -3165,7 +3513,9 @@ pub async fn resubmit_submitter(
 )]
 pub async fn send_copy_email(
     State(state): State<AppState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
     Path(token): Path<String>,
+    headers: HeaderMap,
 ) -> (StatusCode, Json<ApiResponse<String>>) {
     let state_lock = state.lock().await;
     let pool = &state_lock.db_pool;
@@ -3181,8 +3531,13 @@ pub async fn send_copy_email(
             match TemplateQueries::get_template_by_id(pool, db_submitter.template_id).await {
                 Ok(Some(template)) => {
                     // Create email service
-                    let email_service = match crate::services::email::EmailService::new() {
-                        Ok(service) => service,
+                    let branding = EmailBranding::for_user(pool, db_submitter.user_id).await.for_recipient(&db_submitter);
+                    let email_service = match crate::services::email::EmailService::new(pool) {
+                        Ok(service) => service.with_context(crate::services::email::EmailContext {
+                            user_id: Some(db_submitter.user_id),
+                            submitter_id: Some(db_submitter.id),
+                            kind: Some("copy".to_string()),
+                        }).with_branding(&branding),
                         Err(e) => return ApiResponse::internal_error(format!("Failed to initialize email service: {}", e)),
                     };
 
@@ -3194,48 +3549,37 @@ pub async fn send_copy_email(
                     match email_template_result {
                         Ok(Some(email_template)) => {
                             // Use custom email template
-                            let mut subject_variables = std::collections::HashMap::new();
-                            subject_variables.insert("submitter.name", db_submitter.name.as_str());
-                            subject_variables.insert("template.name", template.name.as_str());
-                            subject_variables.insert("account.name", "DocuSeal Pro");
-
-                            let mut body_variables = std::collections::HashMap::new();
-                            body_variables.insert("submitter.name", db_submitter.name.as_str());
-                            body_variables.insert("account.name", "DocuSeal Pro");
-                            
                             let base_url = std::env::var("BASE_URL").unwrap_or_else(|_| "http://localhost:8081".to_string());
                             let signed_submission_link = format!("{}/signed-submission/{}", base_url, token);
-                            let template_name_html = format!(r#"<a href="{}">{}</a>"#, signed_submission_link, template.name);
+                            let template_name_html = format!(r#"<a href="{}">{}</a>"#, signed_submission_link, email_template_engine::escape_html(&template.name));
 
-                            if email_template.body_format == "html" {
-                                body_variables.insert("template.name", template_name_html.as_str());
-                            } else {
-                                body_variables.insert("template.name", template.name.as_str());
-                            }
-                            
-                            let subject = replace_template_variables(&email_template.subject, &subject_variables);
-                            let body = replace_template_variables(&email_template.body, &body_variables);
+                            let mut context = branding.context();
+                            email_template_engine::insert_submitter_variables(pool, &mut context, &db_submitter, &template.name).await;
+                            context.insert_html("template.name", &template.name, template_name_html);
+
+                            let (subject, body) = match branding.render(&email_template, &context) {
+                                Ok(rendered) => rendered,
+                                Err(e) => return ApiResponse::internal_error(format!("Failed to render copy email: {}", e)),
+                            };
 
                             // Generate attachments if needed
                             let mut document_path = None;
                             let mut audit_log_path = None;
 
                             if email_template.attach_documents {
-                                // Generate signed PDF (only include signatures from this submitter for their email)
-                                if let Ok(storage_service) = StorageService::new().await {
-                                    if let Ok(signed_pdf_bytes) = generate_signed_pdf_for_template_with_filter(pool, db_submitter.template_id, &storage_service, Some(db_submitter.id)).await {
-                                        let temp_file = std::env::temp_dir().join(format!("signed_document_{}.pdf", db_submitter.id));
-                                        if let Ok(_) = tokio::fs::write(&temp_file, signed_pdf_bytes).await {
-                                            document_path = Some(temp_file.to_string_lossy().to_string());
-                                        }
-                                    }
-                                }
+                                // Stored final PDF when the submission is completed, otherwise this submitter's signatures only
+                                document_path = prepare_signed_document_attachment(
+                                    pool,
+                                    db_submitter.template_id,
+                                    db_submitter.submission_id,
+                                    db_submitter.id,
+                                ).await;
                             }
 
                             if email_template.attach_audit_log {
                                 // Generate audit log PDF
-                                if let Ok(audit_pdf_bytes) = generate_template_audit_log_pdf(pool, db_submitter.template_id).await {
-                                    let temp_file = std::env::temp_dir().join(format!("audit_log_template_{}.pdf", db_submitter.template_id));
+                                if let Ok(audit_pdf_bytes) = generate_envelope_audit_log_pdf(pool, db_submitter.template_id, db_submitter.submission_id).await {
+                                    let temp_file = std::env::temp_dir().join(format!("audit_log_{}_{}.pdf", db_submitter.template_id, db_submitter.submission_id.unwrap_or(0)));
                                     if let Ok(_) = tokio::fs::write(&temp_file, audit_pdf_bytes).await {
                                         audit_log_path = Some(temp_file.to_string_lossy().to_string());
                                     }
@@ -3261,6 +3605,7 @@ pub async fn send_copy_email(
                                     if let Some(path) = audit_log_path {
                                         let _ = tokio::fs::remove_file(path).await;
                                     }
+                                    record_copy_sent_event(pool, &db_submitter, AuditContext::from_request(&headers, Some(addr.ip().to_string()), None)).await;
                                     ApiResponse::success("Email sent successfully".to_string(), "Email sent successfully".to_string())
                                 },
                                 Err(e) => ApiResponse::internal_error(format!("Failed to send email: {}", e)),
@@ -3275,7 +3620,10 @@ pub async fn send_copy_email(
                                 &db_submitter.name,
                                 &db_submitter.token,
                             ).await {
-                                Ok(_) => ApiResponse::success("Email sent successfully".to_string(), "Email sent successfully".to_string()),
+                                Ok(_) => {
+                                    record_copy_sent_event(pool, &db_submitter, AuditContext::from_request(&headers, Some(addr.ip().to_string()), None)).await;
+                                    ApiResponse::success("Email sent successfully".to_string(), "Email sent successfully".to_string())
+                                },
                                 Err(e) => ApiResponse::internal_error(format!("Failed to send email: {}", e)),
                             }
                         }
@@ -3290,6 +3638,78 @@ pub async fn send_copy_email(
     }
 }
 
+async fn record_copy_sent_event(pool: &PgPool, db_submitter: &crate::database::models::DbSubmitter, context: AuditContext) {
+    audit::record_event(
+        pool,
+        db_submitter,
+        audit::EVENT_COPY_SENT,
+        &db_submitter.email,
+        format!("Copy of the signed document emailed to {}", db_submitter.email),
+        context,
+        None,
+    ).await;
+}
+
+#[utoipa::path(
+    post,
+    path = "/public/submissions/{token}/delegate",
+    params(
+        ("token" = String, Path, description = "Submitter token")
+    ),
+    request_body = crate::models::submitter::ReassignSubmitterRequest,
+    responses(
+        (status = 200, description = "Signing delegated; this link no longer works", body = ApiResponse<serde_json::Value>),
+        (status = 400, description = "Already signed or the new signer is invalid", body = ApiResponse<serde_json::Value>),
+        (status = 403, description = "The sender does not allow delegation", body = ApiResponse<serde_json::Value>),
+        (status = 404, description = "Submitter not found", body = ApiResponse<serde_json::Value>),
+        (status = 410, description = "The submission has expired or was voided", body = ApiResponse<serde_json::Value>)
+    )
+)]
+pub async fn delegate_submitter(
+    State(state): State<AppState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    Path(token): Path<String>,
+    headers: HeaderMap,
+    Json(payload): Json<crate::models::submitter::ReassignSubmitterRequest>,
+) -> (StatusCode, Json<ApiResponse<serde_json::Value>>) {
+    let pool = &state.lock().await.db_pool;
+
+    let db_submitter = match SubmitterQueries::get_submitter_by_token(pool, &token).await {
+        Ok(Some(submitter)) => submitter,
+        Ok(None) => {
+            let locale = i18n::resolve_locale([i18n::accept_language(&headers)]);
+            return ApiResponse::not_found(i18n::t(locale, "error.link_invalid"));
+        }
+        Err(e) => return ApiResponse::internal_error(format!("Database error: {}", e)),
+    };
+
+    let settings = match GlobalSettingsQueries::get_user_settings(pool, db_submitter.user_id as i32).await {
+        Ok(settings) => settings,
+        Err(e) => return ApiResponse::internal_error(format!("Failed to get global settings: {}", e)),
+    };
+    let loc = Localizer::from_settings(settings.as_ref(), i18n::accept_language(&headers))
+        .preferring(db_submitter.locale.as_deref());
+    if !settings.is_some_and(|s| s.allow_to_delegate_signing) {
+        return ApiResponse::forbidden(loc.t("error.delegation_not_allowed"));
+    }
+
+    match expiry::closed_link_reason(pool, &db_submitter).await {
+        Ok(None) => {}
+        Ok(Some(reason)) => return ApiResponse::gone(reason.message(loc.locale)),
+        Err(e) => return ApiResponse::internal_error(format!("Failed to check signing link: {}", e)),
+    }
+
+    let context = AuditContext::from_request(&headers, Some(addr.ip().to_string()), None);
+    match reassign_signer(pool, &db_submitter, &payload, &db_submitter.email, true, context).await {
+        // The new token goes to the new signer by email only
+        Ok(updated) => ApiResponse::success(
+            serde_json::json!({ "delegated": true, "name": updated.name, "email": updated.email }),
+            format!("Signing delegated to {}", updated.email),
+        ),
+        Err(err) => err,
+    }
+}
+
 // Get audit log for a submitter
 #[utoipa::path(
     get,
@@ -3312,110 +3732,211 @@ pub async fn get_submitter_audit_log(
     // Get submitter info
     match SubmitterQueries::get_submitter_by_token(pool, &token).await {
         Ok(Some(submitter)) => {
-            // Build detailed audit log entries from database
-            let mut audit_entries = Vec::new();
-
-            // Get template for document info
-            let template = TemplateQueries::get_template_by_id(pool, submitter.template_id).await;
-
-            // Add header information (Envelope ID, Document ID, etc.)
-            let envelope_info = serde_json::json!({
-                "type": "envelope_info",
-                "envelope_id": submitter.id,
-                "document_id": submitter.template_id,
-                "token": submitter.token,
-                "status": submitter.status,
-                "template_name": template.as_ref().ok().and_then(|t| t.as_ref().map(|t| t.name.clone())).unwrap_or_else(|| "Unknown".to_string())
-            });
-            audit_entries.push(envelope_info);
-
-            // 1. Document Created event
-            if let Ok(Some(template)) = template {
-                audit_entries.push(serde_json::json!({
-                    "timestamp": template.created_at.format("%d/%m/%Y %H:%M:%S").to_string(),
-                    "action": "Document Created",
-                    "user": submitter.email.clone(),
-                    "details": format!("Template '{}' was uploaded and configured", template.name),
-                    "ip": submitter.ip_address.clone().unwrap_or_else(|| "N/A".to_string()),
-                    "user_agent": submitter.user_agent.clone().unwrap_or_else(|| "N/A".to_string()),
-                    "session_id": submitter.session_id.clone().unwrap_or_else(|| "N/A".to_string()),
-                    "timezone": submitter.timezone.clone().unwrap_or_else(|| "N/A".to_string())
-                }));
+            let loc = Localizer::for_user(pool, submitter.user_id).await;
+            match build_submitter_audit_entries(pool, &submitter, &loc).await {
+                Ok(audit_entries) => ApiResponse::success(audit_entries, "Audit log retrieved successfully".to_string()),
+                Err(e) => ApiResponse::internal_error(format!("Failed to get audit log: {}", e)),
             }
+        },
+        Ok(None) => ApiResponse::not_found("Submitter not found".to_string()),
+        Err(e) => ApiResponse::internal_error(format!("Failed to get audit log: {}", e)),
+    }
+}
 
-            // 2. Document Sent event (when submitter was created)
-            audit_entries.push(serde_json::json!({
-                "timestamp": submitter.created_at.format("%d/%m/%Y %H:%M:%S").to_string(),
-                "action": "Document Sent",
-                "user": "System",
-                "details": format!("Document sent to {} for signature", submitter.email),
-                "ip": "System",
-                "user_agent": "System",
-                "session_id": "N/A",
-                "timezone": "UTC"
-            }));
+// Build audit log entries for one submitter from its audit_events trail, with actions
+// and times written for `loc`. The first entry is the envelope header, including
+// whether the hash chain verified.
+async fn build_submitter_audit_entries(
+    pool: &PgPool,
+    submitter: &crate::database::models::DbSubmitter,
+    loc: &Localizer,
+) -> Result<Vec<serde_json::Value>, sqlx::Error> {
+    let template = TemplateQueries::get_template_by_id(pool, submitter.template_id).await?;
+    let trail = submitter_audit_trail(pool, submitter, loc).await?;
 
-            // 3. Form Viewed event (if submitter accessed it)
-            if let Some(viewed_at) = submitter.viewed_at {
-                audit_entries.push(serde_json::json!({
-                    "timestamp": viewed_at.format("%d/%m/%Y %H:%M:%S").to_string(),
-                    "action": "Form Viewed",
-                    "user": submitter.email.clone(),
-                    "details": format!("Form opened and viewed by {}", submitter.email),
-                    "ip": submitter.ip_address.clone().unwrap_or_else(|| "N/A".to_string()),
-                    "user_agent": submitter.user_agent.clone().unwrap_or_else(|| "N/A".to_string()),
-                    "session_id": submitter.session_id.clone().unwrap_or_else(|| "N/A".to_string()),
-                    "timezone": submitter.timezone.clone().unwrap_or_else(|| "N/A".to_string())
-                }));
-            } else if submitter.ip_address.is_some() {
-                // Fallback if viewed_at not set but IP exists
-                audit_entries.push(serde_json::json!({
-                    "timestamp": submitter.updated_at.format("%d/%m/%Y %H:%M:%S").to_string(),
-                    "action": "Form Viewed",
-                    "user": submitter.email.clone(),
-                    "details": format!("Form accessed by {}", submitter.email),
-                    "ip": submitter.ip_address.clone().unwrap_or_else(|| "N/A".to_string()),
-                    "user_agent": submitter.user_agent.clone().unwrap_or_else(|| "N/A".to_string()),
-                    "session_id": submitter.session_id.clone().unwrap_or_else(|| "N/A".to_string()),
-                    "timezone": submitter.timezone.clone().unwrap_or_else(|| "N/A".to_string())
-                }));
-            }
+    let mut audit_entries = Vec::new();
 
-            // 4. Document Signed event (if completed)
-            if submitter.status == "signed" || submitter.status == "completed" {
-                if let Some(signed_at) = submitter.signed_at {
-                    audit_entries.push(serde_json::json!({
-                        "timestamp": signed_at.format("%d/%m/%Y %H:%M:%S").to_string(),
-                        "action": "Document Signed",
-                        "user": submitter.email.clone(),
-                        "details": format!("Document signed and submitted by {}", submitter.email),
-                        "ip": submitter.ip_address.clone().unwrap_or_else(|| "N/A".to_string()),
-                        "user_agent": submitter.user_agent.clone().unwrap_or_else(|| "N/A".to_string()),
-                        "session_id": submitter.session_id.clone().unwrap_or_else(|| "N/A".to_string()),
-                        "timezone": submitter.timezone.clone().unwrap_or_else(|| "N/A".to_string())
-                    }));
-                }
-            }
+    // Add header information (Envelope ID, Document ID, etc.)
+    audit_entries.push(serde_json::json!({
+        "type": "envelope_info",
+        "envelope_id": submitter.id,
+        "submission_id": submitter.submission_id,
+        "document_id": submitter.template_id,
+        "token": submitter.token,
+        "status": submitter.status,
+        "template_name": template.as_ref().map(|t| t.name.clone()).unwrap_or_else(|| "Unknown".to_string()),
+        "chain_status": trail.chain_status,
+        "broken_event_id": trail.broken_event_id,
+        "last_hash": trail.last_hash
+    }));
 
-            // 5. Submission Completed event
-            if submitter.status == "completed" {
-                audit_entries.push(serde_json::json!({
-                    "timestamp": submitter.updated_at.format("%d/%m/%Y %H:%M:%S").to_string(),
-                    "action": "Submission Completed",
-                    "user": submitter.email.clone(),
-                    "details": "All required fields completed and document submitted successfully",
-                    "ip": submitter.ip_address.clone().unwrap_or_else(|| "N/A".to_string()),
-                    "user_agent": submitter.user_agent.clone().unwrap_or_else(|| "N/A".to_string()),
-                    "session_id": submitter.session_id.clone().unwrap_or_else(|| "N/A".to_string()),
-                    "timezone": submitter.timezone.clone().unwrap_or_else(|| "N/A".to_string())
-                }));
-            }
+    // 1. Document Created event
+    if let Some(template) = &template {
+        audit_entries.push(template_created_entry(template, loc));
+    }
 
-            ApiResponse::success(audit_entries, "Audit log retrieved successfully".to_string())
-        },
-        Ok(None) => ApiResponse::not_found("Submitter not found".to_string()),
-        Err(e) => ApiResponse::internal_error(format!("Failed to get audit log: {}", e)),
+    audit_entries.extend(trail.entries);
+    Ok(audit_entries)
+}
+
+/// A submitter's audit events, checked against their hash chain
+struct SubmitterAuditTrail {
+    chain_status: &'static str,
+    broken_event_id: Option<i64>,
+    last_hash: Option<String>,
+    entries: Vec<serde_json::Value>,
+}
+
+async fn submitter_audit_trail(
+    pool: &PgPool,
+    submitter: &crate::database::models::DbSubmitter,
+    loc: &Localizer,
+) -> Result<SubmitterAuditTrail, sqlx::Error> {
+    let events = AuditEventQueries::get_events_by_submitter(pool, submitter.id).await?;
+
+    // Submitters created before the audit trail existed have no events to verify
+    if events.is_empty() {
+        return Ok(SubmitterAuditTrail {
+            chain_status: "unavailable",
+            broken_event_id: None,
+            last_hash: None,
+            entries: legacy_audit_entries(submitter, loc),
+        });
+    }
+
+    let (chain_status, broken_event_id) = match audit::verify_chain(&events) {
+        Ok(()) => ("verified", None),
+        Err(event_id) => {
+            eprintln!("⚠️  Audit chain of submitter {} is broken at event {}", submitter.id, event_id);
+            ("broken", Some(event_id))
+        }
+    };
+    let last_hash = events.last().map(|e| e.hash.clone());
+
+    let entries = events.into_iter().map(|event| serde_json::json!({
+        "timestamp": loc.datetime(event.created_at),
+        "occurred_at": event.created_at.to_rfc3339(),
+        "action": audit::event_label(&event.event_type, loc.locale),
+        "event_type": event.event_type,
+        "user": event.actor,
+        "details": event.details,
+        "ip": event.ip_address.unwrap_or_else(|| "N/A".to_string()),
+        "user_agent": event.user_agent.unwrap_or_else(|| "N/A".to_string()),
+        "session_id": submitter.session_id.clone().unwrap_or_else(|| "N/A".to_string()),
+        "timezone": event.timezone.unwrap_or_else(|| "N/A".to_string()),
+        "data": event.data,
+        "hash": event.hash
+    })).collect();
+
+    Ok(SubmitterAuditTrail { chain_status, broken_event_id, last_hash, entries })
+}
+
+fn template_created_entry(template: &crate::database::models::DbTemplate, loc: &Localizer) -> serde_json::Value {
+    serde_json::json!({
+        "timestamp": loc.datetime(template.created_at),
+        "occurred_at": template.created_at.to_rfc3339(),
+        "action": loc.t("audit.event.created"),
+        "user": "System",
+        "details": format!("Template '{}' was uploaded and configured", template.name),
+        "ip": "System",
+        "user_agent": "System",
+        "session_id": "N/A",
+        "timezone": "UTC"
+    })
+}
+
+// Reconstruct events from the submitter's timestamp columns (submitters from before audit_events)
+fn legacy_audit_entries(submitter: &crate::database::models::DbSubmitter, loc: &Localizer) -> Vec<serde_json::Value> {
+    let mut audit_entries = Vec::new();
+
+    // 2. Document Sent event (when the invitation went out), or waiting for earlier signers
+    if let Some(invited_at) = submitter.invited_at {
+        audit_entries.push(serde_json::json!({
+            "timestamp": loc.datetime(invited_at),
+            "occurred_at": invited_at.to_rfc3339(),
+            "action": loc.t("audit.event.sent"),
+            "user": "System",
+            "details": format!("Document sent to {} for signature", submitter.email),
+            "ip": "System",
+            "user_agent": "System",
+            "session_id": "N/A",
+            "timezone": "UTC"
+        }));
+    } else if submitter.status == "waiting" {
+        audit_entries.push(serde_json::json!({
+            "timestamp": loc.datetime(submitter.created_at),
+            "occurred_at": submitter.created_at.to_rfc3339(),
+            "action": loc.t("audit.event.waiting"),
+            "user": "System",
+            "details": format!("{} is waiting for previous signers (signing order {})", submitter.email, submitter.signing_order),
+            "ip": "System",
+            "user_agent": "System",
+            "session_id": "N/A",
+            "timezone": "UTC"
+        }));
+    }
+
+    // 3. Form Viewed event (if submitter accessed it)
+    if let Some(viewed_at) = submitter.viewed_at {
+        audit_entries.push(serde_json::json!({
+            "timestamp": loc.datetime(viewed_at),
+            "occurred_at": viewed_at.to_rfc3339(),
+            "action": loc.t("audit.event.viewed"),
+            "user": submitter.email.clone(),
+            "details": format!("Form opened and viewed by {}", submitter.email),
+            "ip": submitter.ip_address.clone().unwrap_or_else(|| "N/A".to_string()),
+            "user_agent": submitter.user_agent.clone().unwrap_or_else(|| "N/A".to_string()),
+            "session_id": submitter.session_id.clone().unwrap_or_else(|| "N/A".to_string()),
+            "timezone": submitter.timezone.clone().unwrap_or_else(|| "N/A".to_string())
+        }));
+    } else if submitter.ip_address.is_some() {
+        // Fallback if viewed_at not set but IP exists
+        audit_entries.push(serde_json::json!({
+            "timestamp": loc.datetime(submitter.updated_at),
+            "occurred_at": submitter.updated_at.to_rfc3339(),
+            "action": loc.t("audit.event.viewed"),
+            "user": submitter.email.clone(),
+            "details": format!("Form accessed by {}", submitter.email),
+            "ip": submitter.ip_address.clone().unwrap_or_else(|| "N/A".to_string()),
+            "user_agent": submitter.user_agent.clone().unwrap_or_else(|| "N/A".to_string()),
+            "session_id": submitter.session_id.clone().unwrap_or_else(|| "N/A".to_string()),
+            "timezone": submitter.timezone.clone().unwrap_or_else(|| "N/A".to_string())
+        }));
+    }
+
+    // 4. Document Signed event (if completed)
+    if submitter.status == "signed" || submitter.status == "completed" {
+        if let Some(signed_at) = submitter.signed_at {
+            audit_entries.push(serde_json::json!({
+                "timestamp": loc.datetime(signed_at),
+                "occurred_at": signed_at.to_rfc3339(),
+                "action": loc.t("audit.event.signed"),
+                "user": submitter.email.clone(),
+                "details": format!("Document signed and submitted by {}", submitter.email),
+                "ip": submitter.ip_address.clone().unwrap_or_else(|| "N/A".to_string()),
+                "user_agent": submitter.user_agent.clone().unwrap_or_else(|| "N/A".to_string()),
+                "session_id": submitter.session_id.clone().unwrap_or_else(|| "N/A".to_string()),
+                "timezone": submitter.timezone.clone().unwrap_or_else(|| "N/A".to_string())
+            }));
+        }
     }
+
+    // 5. Submission Completed event
+    if submitter.status == "completed" {
+        audit_entries.push(serde_json::json!({
+            "timestamp": loc.datetime(submitter.updated_at),
+            "occurred_at": submitter.updated_at.to_rfc3339(),
+            "action": loc.t("audit.event.completed"),
+            "user": submitter.email.clone(),
+            "details": "All required fields completed and document submitted successfully",
+            "ip": submitter.ip_address.clone().unwrap_or_else(|| "N/A".to_string()),
+            "user_agent": submitter.user_agent.clone().unwrap_or_else(|| "N/A".to_string()),
+            "session_id": submitter.session_id.clone().unwrap_or_else(|| "N/A".to_string()),
+            "timezone": submitter.timezone.clone().unwrap_or_else(|| "N/A".to_string())
+        }));
+    }
+
+    audit_entries
 }
 
 async fn generate_signed_pdf_for_template(
@@ -3423,17 +3944,19 @@ async fn generate_signed_pdf_for_template(
     template_id: i64,
     storage_service: &StorageService,
 ) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync + 'static>> {
-    generate_signed_pdf_for_template_with_filter(pool, template_id, storage_service, None).await
+    generate_signed_pdf_for_template_with_filter(pool, template_id, storage_service, None, None).await
 }
 
 // Generate signed PDF with optional submitter filter
 // If submitter_id is Some, only include signatures from that submitter
 // If submitter_id is None, include all signatures from all submitters
+// If submission_id is Some, only submitters of that envelope are considered
 async fn generate_signed_pdf_for_template_with_filter(
     pool: &PgPool,
     template_id: i64,
     storage_service: &StorageService,
     submitter_id: Option<i64>,
+    submission_id: Option<i64>,
 ) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync + 'static>> {
     // Get template
     let template = TemplateQueries::get_template_by_id(pool, template_id).await?
@@ -3455,12 +3978,36 @@ async fn generate_signed_pdf_for_template_with_filter(
         return Err("Template has no documents".into());
     };
 
-    // Get all submitters for this template
-    let submitters = SubmitterQueries::get_submitters_by_template_id(pool, template_id).await?;
+    // Get all submitters for this envelope (or template for legacy submitters)
+    let submitters = get_envelope_submitters(pool, template_id, submission_id).await?;
 
     // Get template fields for position information
     let template_fields = TemplateFieldQueries::get_template_fields(pool, template_id).await?;
 
+    // Conditions are evaluated against every signer's values, even when only one signer is rendered,
+    // with field names resolved within each signer's own role
+    let role_fields: Vec<RoleField> = template_fields.iter().map(|f| RoleField {
+        template_field_id: f.id,
+        name: &f.name,
+        role_id: f.role_id,
+    }).collect();
+    let field_values = collect_field_values(submitters.iter(), &role_fields);
+    let hidden_for_role = |role_id: Option<i64>| -> std::collections::HashSet<String> {
+        let values = field_conditions::role_scoped_values(&role_fields, &field_values, role_id);
+        let mut ordered_fields: Vec<_> = template_fields.iter().collect();
+        ordered_fields.sort_by_key(|f| template_roles::can_fill(f.role_id, role_id));
+        let conditional_fields: Vec<ConditionalField> = ordered_fields.iter().map(|f| ConditionalField {
+            name: &f.name,
+            required: f.required,
+            conditions: parse_conditions(f.conditions.as_ref()),
+        }).collect();
+        field_conditions::evaluate(&conditional_fields, &values)
+            .into_iter()
+            .filter(|(_, state)| !state.visible)
+            .map(|(name, _)| name)
+            .collect()
+    };
+
     // Collect all signatures with position information
     let mut all_signatures = Vec::new();
     for submitter in &submitters {
@@ -3476,6 +4023,9 @@ async fn generate_signed_pdf_for_template_with_filter(
             println!("DEBUG: Including submitter {} (no filter)", submitter.id);
         }
         
+        // Fields hidden by their conditions are not rendered
+        let hidden_fields = hidden_for_role(submitter.role_id);
+
         if let Some(bulk_signatures) = &submitter.bulk_signatures {
             if let Ok(signatures) = serde_json::from_value::<Vec<serde_json::Value>>(bulk_signatures.clone()) {
                 for sig in signatures {
@@ -3484,8 +4034,13 @@ async fn generate_signed_pdf_for_template_with_filter(
                         sig.get("signature_value").and_then(|v| v.as_str()),
                     ) {
                         println!("DEBUG: Retrieved signature_value from DB: '{}'", signature_value);
-                        // Find the corresponding template field for position information
-                        if let Some(template_field) = template_fields.iter().find(|f| f.name == field_name) {
+                        if hidden_fields.contains(field_name) {
+                            continue;
+                        }
+                        // Find the corresponding template field of this signer's role for position information
+                        let template_field = template_fields.iter()
+                            .find(|f| f.name == field_name && template_roles::can_fill(f.role_id, submitter.role_id));
+                        if let Some(template_field) = template_field {
                             // Parse position from JSON
                             if let Some(position_json) = &template_field.position {
                                 if let Ok(position) = serde_json::from_value::<crate::models::template::FieldPosition>(position_json.clone()) {
@@ -3569,6 +4124,10 @@ async fn generate_signed_pdf_for_template_with_filter(
             completion_body: None,
             redirect_title: None,
             redirect_url: None,
+            allow_to_delegate_signing: false,
+            email_from_name: None,
+            email_reply_to: None,
+            email_layout: None,
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
         });
@@ -3576,6 +4135,8 @@ async fn generate_signed_pdf_for_template_with_filter(
     // Create a dummy submitter for rendering (we need this for the function signature)
     let dummy_submitter = submitters.first().ok_or("No submitters found")?;
 
+    let field_font = pdf_fonts::load_field_font(pool, template.user_id, template.account_id).await?;
+
     // Render signatures on PDF
     let signed_pdf = render_signatures_on_pdf(
         &pdf_bytes,
@@ -3583,171 +4144,39 @@ async fn generate_signed_pdf_for_template_with_filter(
         &user_settings,
         dummy_submitter,
         storage_service,
+        &field_font,
     ).await?;
 
     Ok(signed_pdf)
 }
 
-async fn generate_template_audit_log_pdf(
+// Audit log attached to emails: the audit_events of every signer of this envelope,
+// in the order they happened, with the state of each signer's hash chain
+async fn generate_envelope_audit_log_pdf(
     pool: &PgPool,
     template_id: i64,
+    submission_id: Option<i64>,
 ) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync + 'static>> {
-    // Get all submitters for this template
-    let submitters = SubmitterQueries::get_submitters_by_template_id(pool, template_id).await?;
-
-    // Get template for document info
     let template = TemplateQueries::get_template_by_id(pool, template_id).await?
         .ok_or("Template not found")?;
+    let submitters = get_envelope_submitters(pool, template_id, submission_id).await?;
+    let loc = Localizer::for_user(pool, template.user_id).await;
 
-    // Collect all signature values from all signed submitters
-    let mut all_signature_values = Vec::new();
-    for submitter in &submitters {
-        if submitter.status == "signed" || submitter.status == "completed" {
-            if let Some(bulk_signatures) = &submitter.bulk_signatures {
-                if let Ok(signatures) = serde_json::from_value::<Vec<serde_json::Value>>(bulk_signatures.clone()) {
-                    for signature in signatures {
-                        if let Some(sig_value) = signature.get("signature_value").and_then(|v| v.as_str()) {
-                            if sig_value != "N/A" {
-                                all_signature_values.push(sig_value.to_string());
-                            }
-                        }
-                    }
-                }
-            }
-        }
-    }
-
-    // Build comprehensive audit log entries from all submitters
-    let mut audit_entries = Vec::new();
-
-    // Add header information
-    let envelope_info = serde_json::json!({
-        "type": "envelope_info",
-        "document_id": template.id,
-        "template_name": template.name,
-        "total_submitters": submitters.len(),
-        "total_signatures": all_signature_values.len(),
-        "created_at": template.created_at.format("%d/%m/%Y %H:%M:%S").to_string()
-    });
-    audit_entries.push(envelope_info);
-
-    // 1. Document Created event
-    audit_entries.push(serde_json::json!({
-        "timestamp": template.created_at.format("%d/%m/%Y %H:%M:%S").to_string(),
-        "action": "Document Created",
-        "user": "System",
-        "details": format!("Template '{}' was uploaded and configured", template.name),
-        "ip": "System",
-        "user_agent": "System",
-        "session_id": "N/A",
-        "timezone": "UTC"
-    }));
-
-    // 2. Document Sent events for all submitters
-    for submitter in &submitters {
-        audit_entries.push(serde_json::json!({
-            "timestamp": submitter.created_at.format("%d/%m/%Y %H:%M:%S").to_string(),
-            "action": "Document Sent",
-            "user": "System",
-            "details": format!("Document sent to {} for signature", submitter.email),
-            "ip": "System",
-            "user_agent": "System",
-            "session_id": "N/A",
-            "timezone": "UTC",
-            "submitter_email": submitter.email.clone(),
-            "submitter_role": "Signer".to_string()
-        }));
-    }
-
-    // 3. Form Viewed events for all submitters
-    for submitter in &submitters {
-        if let Some(viewed_at) = submitter.viewed_at {
-            audit_entries.push(serde_json::json!({
-                "timestamp": viewed_at.format("%d/%m/%Y %H:%M:%S").to_string(),
-                "action": "Form Viewed",
-                "user": submitter.email.clone(),
-                "details": format!("Form opened and viewed by {}", submitter.email),
-                "ip": submitter.ip_address.clone().unwrap_or_else(|| "N/A".to_string()),
-                "user_agent": submitter.user_agent.clone().unwrap_or_else(|| "N/A".to_string()),
-                "session_id": submitter.session_id.clone().unwrap_or_else(|| "N/A".to_string()),
-                "timezone": submitter.timezone.clone().unwrap_or_else(|| "N/A".to_string()),
-                "submitter_role": "Signer".to_string()
-            }));
-        } else if submitter.ip_address.is_some() {
-            audit_entries.push(serde_json::json!({
-                "timestamp": submitter.updated_at.format("%d/%m/%Y %H:%M:%S").to_string(),
-                "action": "Form Viewed",
-                "user": submitter.email.clone(),
-                "details": format!("Form accessed by {}", submitter.email),
-                "ip": submitter.ip_address.clone().unwrap_or_else(|| "N/A".to_string()),
-                "user_agent": submitter.user_agent.clone().unwrap_or_else(|| "N/A".to_string()),
-                "session_id": submitter.session_id.clone().unwrap_or_else(|| "N/A".to_string()),
-                "timezone": submitter.timezone.clone().unwrap_or_else(|| "N/A".to_string()),
-                "submitter_role": "Signer".to_string()
-            }));
-        }
-    }
-
-    // 4. Document Signed events for all submitters
+    let mut chain_lines = Vec::new();
+    let mut audit_entries = vec![template_created_entry(&template, &loc)];
     for submitter in &submitters {
-        if submitter.status == "signed" || submitter.status == "completed" {
-            if let Some(signed_at) = submitter.signed_at {
-                // Collect signature values for this specific submitter
-                let mut submitter_signature_values = Vec::new();
-                if let Some(bulk_signatures) = &submitter.bulk_signatures {
-                    if let Ok(signatures) = serde_json::from_value::<Vec<serde_json::Value>>(bulk_signatures.clone()) {
-                        for signature in signatures {
-                            if let Some(sig_value) = signature.get("signature_value").and_then(|v| v.as_str()) {
-                                if sig_value != "N/A" {
-                                    submitter_signature_values.push(sig_value.to_string());
-                                }
-                            }
-                        }
-                    }
-                }
-
-                audit_entries.push(serde_json::json!({
-                    "timestamp": signed_at.format("%d/%m/%Y %H:%M:%S").to_string(),
-                    "action": "Document Signed",
-                    "user": submitter.email.clone(),
-                    "details": format!("Document signed and submitted by {}", submitter.email),
-                    "ip": submitter.ip_address.clone().unwrap_or_else(|| "N/A".to_string()),
-                    "user_agent": submitter.user_agent.clone().unwrap_or_else(|| "N/A".to_string()),
-                    "session_id": submitter.session_id.clone().unwrap_or_else(|| "N/A".to_string()),
-                    "timezone": submitter.timezone.clone().unwrap_or_else(|| "N/A".to_string()),
-                    "submitter_role": "Signer".to_string(),
-                    "signature_values": submitter_signature_values
-                }));
-            }
+        let trail = submitter_audit_trail(pool, submitter, &loc).await?;
+        let status = loc.t_args("audit.hash_chain", &[("status", &loc.t(&format!("audit.chain.{}", trail.chain_status)))]);
+        chain_lines.push(format!("{}: {} - {}", loc.t("audit.signer"), submitter.email, status));
+        for mut entry in trail.entries {
+            entry["submitter_email"] = serde_json::json!(submitter.email);
+            audit_entries.push(entry);
         }
     }
 
-    // 5. Template Completion event (when all submitters have completed)
-    let all_completed = submitters.iter().all(|s| s.status == "completed");
-    if all_completed && !submitters.is_empty() {
-        // Find the latest completion time
-        let latest_completion = submitters.iter()
-            .filter_map(|s| Some(s.updated_at))
-            .max()
-            .unwrap_or_else(|| template.created_at);
-
-        audit_entries.push(serde_json::json!({
-            "timestamp": latest_completion.format("%d/%m/%Y %H:%M:%S").to_string(),
-            "action": "Template Completed",
-            "user": "System",
-            "details": format!("All {} submitters have completed signing the document", submitters.len()),
-            "ip": "System",
-            "user_agent": "System",
-            "session_id": "N/A",
-            "timezone": "UTC"
-        }));
-    }
-
-    // Sort audit entries by timestamp
-    audit_entries.sort_by(|a, b| {
-        let a_timestamp = a.get("timestamp").and_then(|v| v.as_str()).unwrap_or("");
-        let b_timestamp = b.get("timestamp").and_then(|v| v.as_str()).unwrap_or("");
-        a_timestamp.cmp(b_timestamp)
+    // Sort by time; the displayed timestamp is localized and does not sort
+    audit_entries.sort_by_key(|entry| {
+        entry.get("occurred_at").and_then(|v| v.as_str()).and_then(|v| chrono::DateTime::parse_from_rfc3339(v).ok())
     });
 
     // Generate PDF from audit entries
@@ -3757,21 +4186,7 @@ async fn generate_template_audit_log_pdf(
     let mut doc = Document::new();
     let pages_id = doc.new_object_id();
 
-    // Create Arial font
-    let font_dict_id = {
-        let mut arial_dict = Dictionary::new();
-        arial_dict.set("Type", Object::Name(b"Font".to_vec()));
-        arial_dict.set("Subtype", Object::Name(b"Type1".to_vec()));
-        arial_dict.set("BaseFont", Object::Name(b"Arial".to_vec()));
-        arial_dict.set("Encoding", Object::Name(b"Identity-H".to_vec()));
-        doc.add_object(Object::Dictionary(arial_dict))
-    };
-
-    let resources_id = doc.add_object(Object::Dictionary(Dictionary::from_iter(vec![
-        ("Font", Object::Dictionary(Dictionary::from_iter(vec![
-            ("F1", Object::Reference(font_dict_id)),
-        ]))),
-    ])));
+    let mut fonts = DocumentFonts::new(FONT_NOTO_SANS);
 
     // Create content with audit log text
     let mut content = Content { operations: vec![] };
@@ -3779,12 +4194,6 @@ async fn generate_template_audit_log_pdf(
     // Begin text object
     content.operations.push(Operation::new("BT", vec![]));
 
-    // Set font and size
-    content.operations.push(Operation::new("Tf", vec![
-        Object::Name(b"F1".to_vec()),
-        Object::Real(10.0),
-    ]));
-
     // Set text color to black
     content.operations.push(Operation::new("rg", vec![
         Object::Real(0.0),
@@ -3799,14 +4208,7 @@ async fn generate_template_audit_log_pdf(
     ]));
 
     // Add title
-    content.operations.push(Operation::new("Tj", vec![
-        Object::string_literal("AUDIT LOG - TEMPLATE AUDIT TRAIL".to_string()),
-    ]));
-
-    // Add separator
-    content.operations.push(Operation::new("Tj", vec![
-        Object::string_literal("========================================".to_string()),
-    ]));
+    content.operations.extend(fonts.text_operations(&loc.t("audit.title"), 10.0));
 
     // Move to next line
     content.operations.push(Operation::new("Td", vec![
@@ -3814,52 +4216,38 @@ async fn generate_template_audit_log_pdf(
         Object::Real(-20.0),
     ]));
 
-    // Add template info
-    content.operations.push(Operation::new("Tj", vec![
-        Object::string_literal(format!("Template: {}", template.name)),
-    ]));
-    content.operations.push(Operation::new("Td", vec![
-        Object::Real(0.0),
-        Object::Real(-12.0),
-    ]));
+    // Add separator
+    content.operations.extend(fonts.text_operations("=========", 10.0));
 
-    content.operations.push(Operation::new("Tj", vec![
-        Object::string_literal(format!("Total Submitters: {}", submitters.len())),
-    ]));
+    // Move to next line
     content.operations.push(Operation::new("Td", vec![
         Object::Real(0.0),
-        Object::Real(-12.0),
+        Object::Real(-20.0),
     ]));
 
-    content.operations.push(Operation::new("Tj", vec![
-        Object::string_literal(format!("Created: {}", template.created_at.format("%d/%m/%Y %H:%M:%S"))),
-    ]));
+    content.operations.extend(fonts.text_operations(&format!("{}: {}", loc.t("audit.template"), template.name), 10.0));
     content.operations.push(Operation::new("Td", vec![
         Object::Real(0.0),
         Object::Real(-12.0),
     ]));
 
-    content.operations.push(Operation::new("Tj", vec![
-        Object::string_literal(format!("Total Signatures: {}", all_signature_values.len())),
-    ]));
-    content.operations.push(Operation::new("Td", vec![
-        Object::Real(0.0),
-        Object::Real(-20.0),
-    ]));
-
-    // Add separator
-    content.operations.push(Operation::new("Tj", vec![
-        Object::string_literal("----------------------------------------".to_string()),
-    ]));
-
-    // Move to next line
+    // Integrity of the hash chain each signer's entries were rendered from
+    let mut y_pos = 678.0;
+    for line in &chain_lines {
+        content.operations.extend(fonts.text_operations(line, 10.0));
+        content.operations.push(Operation::new("Td", vec![
+            Object::Real(0.0),
+            Object::Real(-12.0),
+        ]));
+        y_pos -= 12.0;
+    }
     content.operations.push(Operation::new("Td", vec![
         Object::Real(0.0),
-        Object::Real(-20.0),
+        Object::Real(-8.0),
     ]));
+    y_pos -= 8.0;
 
     // Add audit entries
-    let mut y_pos = 650.0;
     for entry in audit_entries {
         if y_pos < 50.0 {
             // Would need new page, but for now we'll truncate
@@ -3867,9 +4255,7 @@ async fn generate_template_audit_log_pdf(
         }
 
         if let Some(action) = entry.get("action").and_then(|v| v.as_str()) {
-            content.operations.push(Operation::new("Tj", vec![
-                Object::string_literal(format!("Action: {}", action)),
-            ]));
+            content.operations.extend(fonts.text_operations(&format!("{}: {}", loc.t("audit.action"), action), 10.0));
             content.operations.push(Operation::new("Td", vec![
                 Object::Real(0.0),
                 Object::Real(-12.0),
@@ -3878,9 +4264,7 @@ async fn generate_template_audit_log_pdf(
         }
 
         if let Some(timestamp) = entry.get("timestamp").and_then(|v| v.as_str()) {
-            content.operations.push(Operation::new("Tj", vec![
-                Object::string_literal(format!("Time: {}", timestamp)),
-            ]));
+            content.operations.extend(fonts.text_operations(&format!("{}: {}", loc.t("audit.timestamp"), timestamp), 10.0));
             content.operations.push(Operation::new("Td", vec![
                 Object::Real(0.0),
                 Object::Real(-12.0),
@@ -3889,9 +4273,7 @@ async fn generate_template_audit_log_pdf(
         }
 
         if let Some(user) = entry.get("user").and_then(|v| v.as_str()) {
-            content.operations.push(Operation::new("Tj", vec![
-                Object::string_literal(format!("User: {}", user)),
-            ]));
+            content.operations.extend(fonts.text_operations(&format!("{}: {}", loc.t("audit.user"), user), 10.0));
             content.operations.push(Operation::new("Td", vec![
                 Object::Real(0.0),
                 Object::Real(-12.0),
@@ -3900,20 +4282,7 @@ async fn generate_template_audit_log_pdf(
         }
 
         if let Some(submitter_email) = entry.get("submitter_email").and_then(|v| v.as_str()) {
-            content.operations.push(Operation::new("Tj", vec![
-                Object::string_literal(format!("Signer: {}", submitter_email)),
-            ]));
-            content.operations.push(Operation::new("Td", vec![
-                Object::Real(0.0),
-                Object::Real(-12.0),
-            ]));
-            y_pos -= 15.0;
-        }
-
-        if let Some(submitter_role) = entry.get("submitter_role").and_then(|v| v.as_str()) {
-            content.operations.push(Operation::new("Tj", vec![
-                Object::string_literal(format!("Role: {}", submitter_role)),
-            ]));
+            content.operations.extend(fonts.text_operations(&format!("{}: {}", loc.t("audit.signer"), submitter_email), 10.0));
             content.operations.push(Operation::new("Td", vec![
                 Object::Real(0.0),
                 Object::Real(-12.0),
@@ -3922,9 +4291,7 @@ async fn generate_template_audit_log_pdf(
         }
 
         if let Some(details) = entry.get("details").and_then(|v| v.as_str()) {
-            content.operations.push(Operation::new("Tj", vec![
-                Object::string_literal(format!("Details: {}", details)),
-            ]));
+            content.operations.extend(fonts.text_operations(&format!("{}: {}", loc.t("audit.details"), details), 10.0));
             content.operations.push(Operation::new("Td", vec![
                 Object::Real(0.0),
                 Object::Real(-12.0),
@@ -3932,333 +4299,8 @@ async fn generate_template_audit_log_pdf(
             y_pos -= 15.0;
         }
 
-        if let Some(signature_values) = entry.get("signature_values").and_then(|v| v.as_array()) {
-            // Add extra spacing before signature values
-            content.operations.push(Operation::new("Td", vec![
-                Object::Real(0.0),
-                Object::Real(-8.0),  // Extra space before signature
-            ]));
-            y_pos -= 8.0;
-            
-            for (i, sig_value) in signature_values.iter().enumerate() {
-                if let Some(sig_str) = sig_value.as_str() {
-                    if sig_str != "N/A" {
-                        let truncated_value = if sig_str.len() > 100 {
-                            format!("{}... [TRUNCATED]", &sig_str[..100])
-                        } else {
-                            sig_str.to_string()
-                        };
-                        let label = if signature_values.len() > 1 {
-                            format!("Signature Values {}: {}", i + 1, truncated_value)
-                        } else {
-                            format!("Signature Values: {}", truncated_value)
-                        };
-                        content.operations.push(Operation::new("Tj", vec![
-                            Object::string_literal(label),
-                        ]));
-                        content.operations.push(Operation::new("Td", vec![
-                            Object::Real(0.0),
-                            Object::Real(-20.0),  // Increased spacing after signature
-                        ]));
-                        y_pos -= 20.0;
-                    }
-                }
-            }
-        }
-
         if let Some(ip) = entry.get("ip").and_then(|v| v.as_str()) {
-            if ip != "System" {
-                content.operations.push(Operation::new("Tj", vec![
-                    Object::string_literal(format!("IP: {}", ip)),
-                ]));
-                content.operations.push(Operation::new("Td", vec![
-                    Object::Real(0.0),
-                    Object::Real(-12.0),
-                ]));
-                y_pos -= 15.0;
-            }
-        }
-
-        // Add spacing between entries
-        content.operations.push(Operation::new("Td", vec![
-            Object::Real(0.0),
-            Object::Real(-10.0),
-        ]));
-        y_pos -= 10.0;
-    }
-
-    // End text object
-    content.operations.push(Operation::new("ET", vec![]));
-
-    let content_id = doc.add_object(Object::Stream(Stream::new(Dictionary::new(), content.encode()?)));
-
-    let page_id = doc.add_object(Object::Dictionary(Dictionary::from_iter(vec![
-        ("Type", Object::Name("Page".into())),
-        ("Parent", pages_id.into()),
-        ("Contents", content_id.into()),
-        ("Resources", resources_id.into()),
-        ("MediaBox", Object::Array(vec![
-            Object::Integer(0),
-            Object::Integer(0),
-            Object::Integer(612),
-            Object::Integer(792),
-        ])),
-    ])));
-
-    let pages = Object::Dictionary(Dictionary::from_iter(vec![
-        ("Type", Object::Name("Pages".into())),
-        ("Kids", Object::Array(vec![page_id.into()])),
-        ("Count", Object::Integer(1)),
-    ]));
-
-    doc.objects.insert(pages_id, pages);
-
-    let catalog_id = doc.add_object(Object::Dictionary(Dictionary::from_iter(vec![
-        ("Type", Object::Name("Catalog".into())),
-        ("Pages", pages_id.into()),
-    ])));
-
-    doc.trailer.set("Root", catalog_id);
-
-    // Save PDF to bytes
-    let mut buffer = Vec::new();
-    doc.save_to(&mut buffer)?;
-
-    Ok(buffer)
-}
-
-async fn generate_audit_log_pdf(
-    pool: &PgPool,
-    submitter_id: i64,
-) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync + 'static>> {
-    // Get submitter
-    let submitter = SubmitterQueries::get_submitter_by_id(pool, submitter_id).await?
-        .ok_or("Submitter not found")?;
-
-    // Get audit log data (reuse the logic from get_submitter_audit_log)
-    let mut audit_entries = Vec::new();
-
-    // Get template for document info
-    let template = TemplateQueries::get_template_by_id(pool, submitter.template_id).await;
-
-    // Add header information
-    let envelope_info = serde_json::json!({
-        "type": "envelope_info",
-        "envelope_id": submitter.id,
-        "document_id": submitter.template_id,
-        "token": submitter.token,
-        "status": submitter.status,
-        "template_name": template.as_ref().ok().and_then(|t| t.as_ref().map(|t| t.name.clone())).unwrap_or_else(|| "Unknown".to_string())
-    });
-    audit_entries.push(envelope_info);
-
-    // 1. Document Created event
-    if let Ok(Some(template)) = template {
-        audit_entries.push(serde_json::json!({
-            "timestamp": template.created_at.format("%d/%m/%Y %H:%M:%S").to_string(),
-            "action": "Document Created",
-            "user": submitter.email.clone(),
-            "details": format!("Template '{}' was uploaded and configured", template.name),
-            "ip": submitter.ip_address.clone().unwrap_or_else(|| "N/A".to_string()),
-            "user_agent": submitter.user_agent.clone().unwrap_or_else(|| "N/A".to_string()),
-            "session_id": submitter.session_id.clone().unwrap_or_else(|| "N/A".to_string()),
-            "timezone": submitter.timezone.clone().unwrap_or_else(|| "N/A".to_string())
-        }));
-    }
-
-    // 2. Document Sent event
-    audit_entries.push(serde_json::json!({
-        "timestamp": submitter.created_at.format("%d/%m/%Y %H:%M:%S").to_string(),
-        "action": "Document Sent",
-        "user": "System",
-        "details": format!("Document sent to {} for signature", submitter.email),
-        "ip": "System",
-        "user_agent": "System",
-        "session_id": "N/A",
-        "timezone": "UTC"
-    }));
-
-    // 3. Form Viewed event
-    if let Some(viewed_at) = submitter.viewed_at {
-        audit_entries.push(serde_json::json!({
-            "timestamp": viewed_at.format("%d/%m/%Y %H:%M:%S").to_string(),
-            "action": "Form Viewed",
-            "user": submitter.email.clone(),
-            "details": format!("Form opened and viewed by {}", submitter.email),
-            "ip": submitter.ip_address.clone().unwrap_or_else(|| "N/A".to_string()),
-            "user_agent": submitter.user_agent.clone().unwrap_or_else(|| "N/A".to_string()),
-            "session_id": submitter.session_id.clone().unwrap_or_else(|| "N/A".to_string()),
-            "timezone": submitter.timezone.clone().unwrap_or_else(|| "N/A".to_string())
-        }));
-    } else if submitter.ip_address.is_some() {
-        audit_entries.push(serde_json::json!({
-            "timestamp": submitter.updated_at.format("%d/%m/%Y %H:%M:%S").to_string(),
-            "action": "Form Viewed",
-            "user": submitter.email.clone(),
-            "details": format!("Form accessed by {}", submitter.email),
-            "ip": submitter.ip_address.clone().unwrap_or_else(|| "N/A".to_string()),
-            "user_agent": submitter.user_agent.clone().unwrap_or_else(|| "N/A".to_string()),
-            "session_id": submitter.session_id.clone().unwrap_or_else(|| "N/A".to_string()),
-            "timezone": submitter.timezone.clone().unwrap_or_else(|| "N/A".to_string())
-        }));
-    }
-
-    // 4. Document Signed event
-    if submitter.status == "signed" || submitter.status == "completed" {
-        if let Some(signed_at) = submitter.signed_at {
-            audit_entries.push(serde_json::json!({
-                "timestamp": signed_at.format("%d/%m/%Y %H:%M:%S").to_string(),
-                "action": "Document Signed",
-                "user": submitter.email.clone(),
-                "details": format!("Document signed and submitted by {}", submitter.email),
-                "ip": submitter.ip_address.clone().unwrap_or_else(|| "N/A".to_string()),
-                "user_agent": submitter.user_agent.clone().unwrap_or_else(|| "N/A".to_string()),
-                "session_id": submitter.session_id.clone().unwrap_or_else(|| "N/A".to_string()),
-                "timezone": submitter.timezone.clone().unwrap_or_else(|| "N/A".to_string())
-            }));
-        }
-    }
-
-    // 5. Submission Completed event
-    if submitter.status == "completed" {
-        audit_entries.push(serde_json::json!({
-            "timestamp": submitter.updated_at.format("%d/%m/%Y %H:%M:%S").to_string(),
-            "action": "Submission Completed",
-            "user": submitter.email.clone(),
-            "details": "All required fields completed and document submitted successfully",
-            "ip": submitter.ip_address.clone().unwrap_or_else(|| "N/A".to_string()),
-            "user_agent": submitter.user_agent.clone().unwrap_or_else(|| "N/A".to_string()),
-            "session_id": submitter.session_id.clone().unwrap_or_else(|| "N/A".to_string()),
-            "timezone": submitter.timezone.clone().unwrap_or_else(|| "N/A".to_string())
-        }));
-    }
-
-    // Generate PDF from audit entries
-    use lopdf::{Document, Object, Stream, Dictionary};
-    use lopdf::content::{Content, Operation};
-
-    let mut doc = Document::new();
-    let pages_id = doc.new_object_id();
-
-    // Create Arial font
-    let font_dict_id = {
-        let mut arial_dict = Dictionary::new();
-        arial_dict.set("Type", Object::Name(b"Font".to_vec()));
-        arial_dict.set("Subtype", Object::Name(b"Type1".to_vec()));
-        arial_dict.set("BaseFont", Object::Name(b"Arial".to_vec()));
-        arial_dict.set("Encoding", Object::Name(b"Identity-H".to_vec()));
-        doc.add_object(Object::Dictionary(arial_dict))
-    };
-
-    let resources_id = doc.add_object(Object::Dictionary(Dictionary::from_iter(vec![
-        ("Font", Object::Dictionary(Dictionary::from_iter(vec![
-            ("F1", Object::Reference(font_dict_id)),
-        ]))),
-    ])));
-
-    // Create content with audit log text
-    let mut content = Content { operations: vec![] };
-
-    // Begin text object
-    content.operations.push(Operation::new("BT", vec![]));
-
-    // Set font and size
-    content.operations.push(Operation::new("Tf", vec![
-        Object::Name(b"F1".to_vec()),
-        Object::Real(10.0),
-    ]));
-
-    // Set text color to black
-    content.operations.push(Operation::new("rg", vec![
-        Object::Real(0.0),
-        Object::Real(0.0),
-        Object::Real(0.0),
-    ]));
-
-    // Position text at top
-    content.operations.push(Operation::new("Td", vec![
-        Object::Real(50.0),
-        Object::Real(750.0),
-    ]));
-
-    // Add title
-    content.operations.push(Operation::new("Tj", vec![
-        Object::string_literal("AUDIT LOG".to_string()),
-    ]));
-
-    // Move to next line
-    content.operations.push(Operation::new("Td", vec![
-        Object::Real(0.0),
-        Object::Real(-20.0),
-    ]));
-
-    // Add separator
-    content.operations.push(Operation::new("Tj", vec![
-        Object::string_literal("=========".to_string()),
-    ]));
-
-    // Move to next line
-    content.operations.push(Operation::new("Td", vec![
-        Object::Real(0.0),
-        Object::Real(-20.0),
-    ]));
-
-    // Add audit entries
-    let mut y_pos = 710.0;
-    for entry in audit_entries {
-        if y_pos < 50.0 {
-            // Would need new page, but for now we'll truncate
-            break;
-        }
-
-        if let Some(action) = entry.get("action").and_then(|v| v.as_str()) {
-            content.operations.push(Operation::new("Tj", vec![
-                Object::string_literal(format!("Action: {}", action)),
-            ]));
-            content.operations.push(Operation::new("Td", vec![
-                Object::Real(0.0),
-                Object::Real(-12.0),
-            ]));
-            y_pos -= 15.0;
-        }
-
-        if let Some(timestamp) = entry.get("timestamp").and_then(|v| v.as_str()) {
-            content.operations.push(Operation::new("Tj", vec![
-                Object::string_literal(format!("Timestamp: {}", timestamp)),
-            ]));
-            content.operations.push(Operation::new("Td", vec![
-                Object::Real(0.0),
-                Object::Real(-12.0),
-            ]));
-            y_pos -= 15.0;
-        }
-
-        if let Some(user) = entry.get("user").and_then(|v| v.as_str()) {
-            content.operations.push(Operation::new("Tj", vec![
-                Object::string_literal(format!("User: {}", user)),
-            ]));
-            content.operations.push(Operation::new("Td", vec![
-                Object::Real(0.0),
-                Object::Real(-12.0),
-            ]));
-            y_pos -= 15.0;
-        }
-
-        if let Some(details) = entry.get("details").and_then(|v| v.as_str()) {
-            content.operations.push(Operation::new("Tj", vec![
-                Object::string_literal(format!("Details: {}", details)),
-            ]));
-            content.operations.push(Operation::new("Td", vec![
-                Object::Real(0.0),
-                Object::Real(-12.0),
-            ]));
-            y_pos -= 15.0;
-        }
-
-        if let Some(ip) = entry.get("ip").and_then(|v| v.as_str()) {
-            content.operations.push(Operation::new("Tj", vec![
-                Object::string_literal(format!("IP: {}", ip)),
-            ]));
+            content.operations.extend(fonts.text_operations(&format!("{}: {}", loc.t("audit.ip"), ip), 10.0));
             content.operations.push(Operation::new("Td", vec![
                 Object::Real(0.0),
                 Object::Real(-12.0),
@@ -4278,6 +4320,10 @@ async fn generate_audit_log_pdf(
     content.operations.push(Operation::new("ET", vec![]));
 
     let content_id = doc.add_object(Object::Stream(Stream::new(Dictionary::new(), content.encode()?)));
+    let font_resources = fonts.resources(&mut doc);
+    let resources_id = doc.add_object(Object::Dictionary(Dictionary::from_iter(vec![
+        ("Font", Object::Dictionary(font_resources)),
+    ])));
 
     let page_id = doc.add_object(Object::Dictionary(Dictionary::from_iter(vec![
         ("Type", Object::Name("Page".into())),
@@ -4306,6 +4352,7 @@ async fn generate_audit_log_pdf(
     ])));
 
     doc.trailer.set("Root", catalog_id);
+    fonts.finish(&mut doc)?;
 
     // Save PDF to bytes
     let mut buffer = Vec::new();
@@ -4322,6 +4369,7 @@ pub fn create_submitter_router() -> Router<AppState> {
         .route("/submitters/:id", get(get_submitter))
         .route("/submitters/:id", put(update_submitter))
         .route("/submitters/:id", delete(delete_submitter))
+        .route("/submitters/:id/reassign", post(reassign_submitter))
         .layer(middleware::from_fn(combined_auth_middleware))
         .layer(middleware::from_fn(require_admin_or_team_member))
 }
\ No newline at end of file
@@ -16,7 +16,8 @@ use crate::database::queries::AccountQueries;
 use crate::database::models::{CreateUser, DbUser};
 use crate::models::user::User;
 use crate::models::role::Role;
-use crate::services::email::EmailService;
+use crate::services::email::{EmailContext, EmailService};
+use crate::services::i18n::Localizer;
 
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct CreateTeamMemberRequest {
@@ -185,8 +186,11 @@ pub async fn create_team_member(
     let user: User = new_user.into();
 
     // Send invitation email asynchronously (deliver_later equivalent)
-    let email_service = EmailService::new();
+    let email_service = EmailService::new(pool);
     if let Ok(service) = email_service {
+        let service = service
+            .with_context(EmailContext { user_id: Some(user_id), ..Default::default() })
+            .with_locale(Localizer::for_user(pool, user_id).await.locale);
         let base_url = std::env::var("BASE_URL").unwrap_or_else(|_| "http://localhost:8081".to_string());
         let invitation_link = format!("{}/set-password?token={}", base_url, activation_token);
         
@@ -65,13 +65,17 @@ use crate::models::template::{
     CreateTemplateFieldRequest, UpdateTemplateFieldRequest,
     FileUploadResponse, CreateTemplateFromFileRequest, CreateTemplateRequest,
     TemplateFolder, CreateFolderRequest, UpdateFolderRequest,
-    CreateTemplateFromGoogleDriveRequest
+    CreateTemplateFromGoogleDriveRequest,
+    TemplateRole, CreateTemplateRoleRequest, UpdateTemplateRoleRequest
 };
 use rand::Rng;
 use crate::database::connection::DbPool;
-use crate::database::models::{CreateTemplate, CreateTemplateField, CreateTemplateFolder};
-use crate::database::queries::{TemplateQueries, TemplateFolderQueries, TemplateFieldQueries};
+use crate::database::models::{CreateTemplate, CreateTemplateField, CreateTemplateFolder, DbTemplateRole};
+use crate::database::queries::{TemplateQueries, TemplateFolderQueries, TemplateFieldQueries, TemplateRoleQueries};
 use crate::services::storage::StorageService;
+use crate::services::field_conditions::{parse_conditions, validate_conditions};
+use crate::services::field_validation::{parse_validation, validate_rules};
+use crate::services::formula::validate_formula_field;
 use crate::common::jwt::auth_middleware;
 
 use crate::routes::web::AppState;
@@ -148,6 +152,10 @@ pub async fn get_template_full_info(
                         crate::models::submitter::Submitter {
                             id: Some(db_sub.id),
                             template_id: Some(db_sub.template_id),
+                            submission_id: db_sub.submission_id,
+                            signing_order: Some(db_sub.signing_order),
+                            role_id: db_sub.role_id,
+                            locale: db_sub.locale.clone(),
                             user_id: Some(db_sub.user_id),
                             name: db_sub.name,
                             email: db_sub.email,
@@ -401,6 +409,11 @@ pub fn create_template_router() -> Router<AppState> {
         .route("/templates/:template_id/fields", post(create_template_field))
         .route("/templates/:template_id/fields/:field_id", put(update_template_field))
         .route("/templates/:template_id/fields/:field_id", delete(delete_template_field))
+    // Template Roles routes
+        .route("/templates/:template_id/roles", get(get_template_roles))
+        .route("/templates/:template_id/roles", post(create_template_role))
+        .route("/templates/:template_id/roles/:role_id", put(update_template_role))
+        .route("/templates/:template_id/roles/:role_id", delete(delete_template_role))
 
         .layer(middleware::from_fn(crate::common::jwt::combined_auth_middleware));
     // Merge public and authenticated routes
@@ -2084,6 +2097,8 @@ pub async fn create_template_from_docx(
     request_body = MergeTemplatesRequest,
     responses(
         (status = 201, description = "Templates merged successfully", body = ApiResponse<Template>),
+        (status = 400, description = "Invalid merge request", body = ApiResponse<Template>),
+        (status = 404, description = "Template not found", body = ApiResponse<Template>),
         (status = 500, description = "Internal server error", body = ApiResponse<Template>)
     ),
     security(("bearer_auth" = [])),
@@ -2092,10 +2107,179 @@ pub async fn create_template_from_docx(
 pub async fn merge_templates(
     State(state): State<AppState>,
     Extension(user_id): Extension<i64>,
-    Json(_payload): Json<MergeTemplatesRequest>,
+    Json(payload): Json<MergeTemplatesRequest>,
 ) -> (StatusCode, Json<ApiResponse<Template>>) {
-    // TODO: Implement template merging logic
-    ApiResponse::internal_error("Template merging not yet implemented".to_string())
+    let pool = &state.lock().await.db_pool;
+
+    if payload.template_ids.len() < 2 {
+        return ApiResponse::bad_request("At least two templates are required to merge".to_string());
+    }
+    if payload.name.trim().is_empty() {
+        return ApiResponse::bad_request("Template name is required".to_string());
+    }
+
+    let user = match crate::database::queries::UserQueries::get_user_by_id(pool, user_id).await {
+        Ok(Some(user)) => user,
+        Ok(None) => return ApiResponse::forbidden("User not found".to_string()),
+        Err(e) => return ApiResponse::internal_error(format!("Failed to get user: {}", e)),
+    };
+
+    let storage = match StorageService::new().await {
+        Ok(storage) => storage,
+        Err(e) => return ApiResponse::internal_error(format!("Failed to initialize storage: {}", e)),
+    };
+
+    // Load every document of every source template, in request order
+    let mut source_templates = Vec::new();
+    let mut pdf_bytes_list = Vec::new();
+    let mut document_counts = Vec::new();
+    for template_id in &payload.template_ids {
+        if let Err(response) = check_template_access(pool, *template_id, user_id, false).await {
+            return response;
+        }
+        let db_template = match TemplateQueries::get_template_by_id(pool, *template_id).await {
+            Ok(Some(template)) => template,
+            Ok(None) => return ApiResponse::not_found(format!("Template {} not found", template_id)),
+            Err(e) => return ApiResponse::internal_error(format!("Failed to get template: {}", e)),
+        };
+
+        let documents = db_template.documents.as_ref()
+            .and_then(|documents| serde_json::from_value::<Vec<crate::models::template::Document>>(documents.clone()).ok())
+            .unwrap_or_default();
+        if documents.is_empty() {
+            return ApiResponse::bad_request(format!("Template {} has no document", template_id));
+        }
+
+        for document in &documents {
+            match storage.download_file(&document.url).await {
+                Ok(bytes) => pdf_bytes_list.push(bytes),
+                Err(e) => return ApiResponse::internal_error(format!("Failed to download document for template {}: {}", template_id, e)),
+            }
+        }
+        document_counts.push(documents.len());
+        source_templates.push(db_template);
+    }
+
+    // A template's field pages run across its documents in order, so each template
+    // starts at the offset of its first document inside the merged document
+    let mut page_offsets = Vec::new();
+    let mut total_pages = 0;
+    let mut documents = pdf_bytes_list.iter();
+    for (db_template, document_count) in source_templates.iter().zip(&document_counts) {
+        page_offsets.push(total_pages);
+        for pdf_bytes in documents.by_ref().take(*document_count) {
+            match crate::services::pdf_merge::count_pages(pdf_bytes) {
+                Ok(count) => total_pages += count as i64,
+                Err(e) => return ApiResponse::bad_request(format!("Template {} document is not a valid PDF: {}", db_template.id, e)),
+            }
+        }
+    }
+
+    let merged_pdf = match crate::services::pdf_merge::merge_pdfs(pdf_bytes_list) {
+        Ok(bytes) => bytes,
+        Err(e) => return ApiResponse::internal_error(format!("Failed to merge documents: {}", e)),
+    };
+
+    let filename = format!("{}.pdf", payload.name.trim());
+    let file_size = merged_pdf.len();
+    let file_key = match storage.upload_file(merged_pdf, &filename, "application/pdf").await {
+        Ok(key) => key,
+        Err(e) => return ApiResponse::internal_error(format!("Failed to upload merged document: {}", e)),
+    };
+
+    let slug_base = payload.name.trim().to_lowercase().replace(" ", "-");
+    let random_num: u32 = rand::thread_rng().gen_range(1000..9999);
+    let slug = format!("merged-{}-{}-{}", slug_base, chrono::Utc::now().timestamp(), random_num);
+
+    let create_template = CreateTemplate {
+        name: payload.name.trim().to_string(),
+        slug,
+        user_id,
+        account_id: user.account_id,
+        folder_id: payload.folder_id,
+        documents: Some(serde_json::json!([{
+            "filename": filename,
+            "content_type": "application/pdf",
+            "size": file_size,
+            "url": file_key
+        }])),
+    };
+
+    let db_template = match TemplateQueries::create_template(pool, create_template).await {
+        Ok(template) => template,
+        Err(e) => {
+            let _ = storage.delete_file(&file_key).await;
+            return ApiResponse::internal_error(format!("Failed to create merged template: {}", e));
+        }
+    };
+
+    // Don't leave a half-copied template behind
+    if let Err(e) = copy_merged_fields(pool, db_template.id, &source_templates, &page_offsets).await {
+        if let Err(delete_error) = TemplateQueries::delete_template(pool, db_template.id).await {
+            eprintln!("Failed to delete incomplete merged template {}: {}", db_template.id, delete_error);
+        }
+        let _ = storage.delete_file(&file_key).await;
+        return ApiResponse::internal_error(e);
+    }
+
+    match convert_db_template_to_template_with_fields(db_template, pool).await {
+        Ok(template) => ApiResponse::created(template, "Templates merged successfully".to_string()),
+        Err(e) => ApiResponse::internal_error(format!("Failed to load template fields: {}", e)),
+    }
+}
+
+/// Copy the fields of the source templates onto the merged template, shifting their
+/// page numbers onto the merged document
+async fn copy_merged_fields(
+    pool: &sqlx::PgPool,
+    template_id: i64,
+    source_templates: &[crate::database::models::DbTemplate],
+    page_offsets: &[i64],
+) -> Result<(), String> {
+    let mut display_order = 0;
+    for (source, page_offset) in source_templates.iter().zip(page_offsets) {
+        let fields = TemplateFieldQueries::get_template_fields(pool, source.id).await
+            .map_err(|e| format!("Failed to get fields for template {}: {}", source.id, e))?;
+        let source_roles = TemplateRoleQueries::get_roles_by_template(pool, source.id).await
+            .map_err(|e| format!("Failed to get roles for template {}: {}", source.id, e))?;
+
+        for field in fields {
+            let position = field.position.map(|position| rebase_field_page(position, *page_offset));
+            // Roles with the same name in different source templates become one role
+            let role = match source_roles.iter().find(|r| Some(r.id) == field.role_id) {
+                Some(source_role) => Some(TemplateRoleQueries::find_or_create_role(pool, template_id, &source_role.name).await
+                    .map_err(|e| format!("Failed to copy template role: {}", e))?),
+                None => None,
+            };
+            let create_field = CreateTemplateField {
+                template_id,
+                name: field.name,
+                field_type: field.field_type,
+                required: field.required,
+                display_order,
+                position,
+                options: field.options,
+                metadata: field.metadata,
+                partner: role.as_ref().map(|r| r.name.clone()),
+                conditions: field.conditions,
+                validation: field.validation,
+                role_id: role.map(|r| r.id),
+            };
+            display_order += 1;
+
+            TemplateFieldQueries::create_template_field(pool, create_field).await
+                .map_err(|e| format!("Failed to copy template field: {}", e))?;
+        }
+    }
+    Ok(())
+}
+
+/// Shift a field position's page number by the page offset of its source document
+fn rebase_field_page(mut position: serde_json::Value, page_offset: i64) -> serde_json::Value {
+    if let Some(page) = position.get("page").and_then(|p| p.as_i64()) {
+        position["page"] = serde_json::json!(page + page_offset);
+    }
+    position
 }
 
 // Helper function to create template without storage (for testing)
@@ -2148,6 +2332,7 @@ pub fn convert_db_template_to_template(db_template: crate::database::models::DbT
         user_name: None, // Will be set by caller if needed
         folder_id: db_template.folder_id,
         template_fields: None, // Will be loaded separately if needed
+        roles: None,
         submitters: None, // No longer stored in templates
         documents: db_template.documents.and_then(|v| serde_json::from_value(v).ok()),
         created_at: db_template.created_at,
@@ -2167,6 +2352,7 @@ pub fn convert_db_template_to_template_without_fields(
         user_name: None, // Will be set by caller if needed
         folder_id: db_template.folder_id,
         template_fields: None,
+        roles: None,
         submitters: None,
         documents: db_template.documents.and_then(|v| serde_json::from_value(v).ok()),
         created_at: db_template.created_at,
@@ -2192,11 +2378,20 @@ pub async fn convert_db_template_to_template_with_fields(
             position: db_field.position.and_then(|v| serde_json::from_value(v).ok()),
             options: db_field.options,
             partner: db_field.partner,
+            role_id: db_field.role_id,
+            conditions: parse_conditions(db_field.conditions.as_ref()),
+            validation: parse_validation(db_field.validation.as_ref()),
+            readonly: false,
             created_at: db_field.created_at,
             updated_at: db_field.updated_at,
         })
         .collect::<Vec<_>>();
 
+    let roles = TemplateRoleQueries::get_roles_by_template(pool, db_template.id).await?
+        .into_iter()
+        .map(convert_db_role_to_role)
+        .collect();
+
     Ok(Template {
         id: db_template.id,
         name: db_template.name,
@@ -2205,6 +2400,7 @@ pub async fn convert_db_template_to_template_with_fields(
         user_name: None, // Will be set by caller if needed
         folder_id: db_template.folder_id,
         template_fields: Some(template_fields),
+        roles: Some(roles),
         submitters: None, // No longer stored in templates
         documents: db_template.documents.and_then(|v| serde_json::from_value(v).ok()),
         created_at: db_template.created_at,
@@ -2212,6 +2408,41 @@ pub async fn convert_db_template_to_template_with_fields(
     })
 }
 
+pub fn convert_db_role_to_role(db_role: DbTemplateRole) -> TemplateRole {
+    TemplateRole {
+        id: db_role.id,
+        template_id: db_role.template_id,
+        name: db_role.name,
+        display_order: db_role.display_order,
+        created_at: db_role.created_at,
+        updated_at: db_role.updated_at,
+    }
+}
+
+// Role of a field being saved: `role_id` when given, otherwise the role named by the
+// legacy `partner` string (created on first use). Returns the role id and its name.
+async fn resolve_field_role<T>(
+    pool: &sqlx::PgPool,
+    template_id: i64,
+    role_id: Option<i64>,
+    partner: Option<String>,
+) -> Result<(Option<i64>, Option<String>), (StatusCode, Json<ApiResponse<T>>)> {
+    if let Some(role_id) = role_id {
+        return match TemplateRoleQueries::get_role_by_id(pool, role_id).await {
+            Ok(Some(role)) if role.template_id == template_id => Ok((Some(role.id), Some(role.name))),
+            Ok(_) => Err(ApiResponse::bad_request(format!("Role {} does not exist in this template", role_id))),
+            Err(e) => Err(ApiResponse::internal_error(format!("Failed to get role: {}", e))),
+        };
+    }
+    match partner.map(|p| p.trim().to_string()).filter(|p| !p.is_empty()) {
+        Some(name) => match TemplateRoleQueries::find_or_create_role(pool, template_id, &name).await {
+            Ok(role) => Ok((Some(role.id), Some(role.name))),
+            Err(e) => Err(ApiResponse::internal_error(format!("Failed to create role: {}", e))),
+        },
+        None => Ok((None, None)),
+    }
+}
+
 #[utoipa::path(
     get,
     path = "/api/files/{key}",
@@ -2850,6 +3081,10 @@ pub async fn get_template_fields(
                     position: db_field.position.and_then(|v| serde_json::from_value(v).ok()),
                     options: db_field.options,
                     partner: db_field.partner,
+                    role_id: db_field.role_id,
+                    conditions: parse_conditions(db_field.conditions.as_ref()),
+                    validation: parse_validation(db_field.validation.as_ref()),
+                    readonly: false,
                     created_at: db_field.created_at,
                     updated_at: db_field.updated_at,
                 })
@@ -2926,9 +3161,25 @@ pub async fn create_template_field(
         return ApiResponse::bad_request("No fields provided".to_string());
     }
 
+    for field_req in &field_requests {
+        if let Err(e) = validate_conditions(&field_req.name, field_req.conditions.as_deref().unwrap_or_default()) {
+            return ApiResponse::bad_request(e);
+        }
+        if let Some(Err(e)) = field_req.validation.as_ref().map(|rules| validate_rules(&field_req.name, rules)) {
+            return ApiResponse::bad_request(e);
+        }
+        if let Err(e) = validate_formula_field(&field_req.name, &field_req.field_type, field_req.options.as_ref()) {
+            return ApiResponse::bad_request(e);
+        }
+    }
+
     let mut created_fields = Vec::new();
 
     for field_req in field_requests {
+        let (role_id, partner) = match resolve_field_role(pool, template_id, field_req.role_id, field_req.partner).await {
+            Ok(role) => role,
+            Err(response) => return response,
+        };
         let create_field = CreateTemplateField {
             template_id,
             name: field_req.name,
@@ -2938,7 +3189,10 @@ pub async fn create_template_field(
             position: field_req.position.map(|p| serde_json::to_value(p).unwrap_or(serde_json::Value::Null)),
             options: field_req.options,
             metadata: None,
-            partner: field_req.partner,
+            partner,
+            conditions: field_req.conditions.map(|c| serde_json::to_value(c).unwrap_or(serde_json::Value::Null)),
+            validation: field_req.validation.map(|v| serde_json::to_value(v).unwrap_or(serde_json::Value::Null)),
+            role_id,
         };
 
         match crate::database::queries::TemplateFieldQueries::create_template_field(pool, create_field).await {
@@ -2953,6 +3207,10 @@ pub async fn create_template_field(
                     position: db_field.position.and_then(|v| serde_json::from_value(v).ok()),
                     options: db_field.options,
                     partner: db_field.partner,
+                    role_id: db_field.role_id,
+                    conditions: parse_conditions(db_field.conditions.as_ref()),
+                    validation: parse_validation(db_field.validation.as_ref()),
+                    readonly: false,
                     created_at: db_field.created_at,
                     updated_at: db_field.updated_at,
                 };
@@ -3088,6 +3346,9 @@ pub async fn upload_template_field_file(
                 options: Some(options_value),
                 metadata: None,
                 partner: None, // No partner specified in file upload
+                conditions: None,
+                validation: None,
+                role_id: None,
             };
 
             match crate::database::queries::TemplateFieldQueries::create_template_field(pool, create_field).await {
@@ -3102,6 +3363,10 @@ pub async fn upload_template_field_file(
                         position: db_field.position.and_then(|v| serde_json::from_value(v).ok()),
                         options: db_field.options,
                         partner: db_field.partner,
+                        role_id: db_field.role_id,
+                        conditions: parse_conditions(db_field.conditions.as_ref()),
+                        validation: parse_validation(db_field.validation.as_ref()),
+                        readonly: false,
                         created_at: db_field.created_at,
                         updated_at: db_field.updated_at,
                     };
@@ -3159,6 +3424,29 @@ pub async fn update_template_field(
         Err(e) => return ApiResponse::internal_error(format!("Failed to verify template: {}", e)),
     }
 
+    if let Some(conditions) = &payload.conditions {
+        if let Err(e) = validate_conditions(payload.name.as_deref().unwrap_or_default(), conditions) {
+            return ApiResponse::bad_request(e);
+        }
+    }
+    if let Some(rules) = &payload.validation {
+        if let Err(e) = validate_rules(payload.name.as_deref().unwrap_or_default(), rules) {
+            return ApiResponse::bad_request(e);
+        }
+    }
+    if let Err(e) = validate_formula_field(
+        payload.name.as_deref().unwrap_or_default(),
+        payload.field_type.as_deref().unwrap_or_default(),
+        payload.options.as_ref(),
+    ) {
+        return ApiResponse::bad_request(e);
+    }
+
+    let (role_id, partner) = match resolve_field_role(pool, template_id, payload.role_id, payload.partner).await {
+        Ok(role) => role,
+        Err(response) => return response,
+    };
+
     let update_field = CreateTemplateField {
         template_id,
         name: payload.name.unwrap_or_else(|| "temp".to_string()),
@@ -3168,7 +3456,10 @@ pub async fn update_template_field(
         position: payload.position.map(|p| serde_json::to_value(p).unwrap_or(serde_json::Value::Null)),
         options: payload.options,
         metadata: None,
-        partner: payload.partner,
+        partner,
+        conditions: payload.conditions.map(|c| serde_json::to_value(c).unwrap_or(serde_json::Value::Null)),
+        validation: payload.validation.map(|v| serde_json::to_value(v).unwrap_or(serde_json::Value::Null)),
+        role_id,
     };
 
     match crate::database::queries::TemplateFieldQueries::update_template_field(pool, field_id, update_field).await {
@@ -3183,6 +3474,10 @@ pub async fn update_template_field(
                 position: db_field.position.and_then(|v| serde_json::from_value(v).ok()),
                 options: db_field.options,
                 partner: db_field.partner,
+                role_id: db_field.role_id,
+                conditions: parse_conditions(db_field.conditions.as_ref()),
+                validation: parse_validation(db_field.validation.as_ref()),
+                readonly: false,
                 created_at: db_field.created_at,
                 updated_at: db_field.updated_at,
             };
@@ -3244,6 +3539,227 @@ pub async fn delete_template_field(
     }
 }
 
+// ===== TEMPLATE ROLE ENDPOINTS =====
+
+// Owner always has access; within the owner's account Editors/Admins may also modify,
+// Members may only read
+async fn check_template_access<T>(
+    pool: &sqlx::PgPool,
+    template_id: i64,
+    user_id: i64,
+    modify: bool,
+) -> Result<(), (StatusCode, Json<ApiResponse<T>>)> {
+    let db_template = match TemplateQueries::get_template_by_id(pool, template_id).await {
+        Ok(Some(db_template)) => db_template,
+        Ok(None) => return Err(ApiResponse::not_found("Template not found".to_string())),
+        Err(e) => return Err(ApiResponse::internal_error(format!("Failed to verify template: {}", e))),
+    };
+    let user = match crate::database::queries::UserQueries::get_user_by_id(pool, user_id).await {
+        Ok(Some(user)) => user,
+        _ => return Err(ApiResponse::not_found("User not found".to_string())),
+    };
+    if db_template.user_id == user_id {
+        return Ok(());
+    }
+    // Team roles only reach templates of the user's own account
+    let template_account_id = match db_template.account_id {
+        Some(account_id) => Some(account_id),
+        None => match crate::database::queries::UserQueries::get_user_by_id(pool, db_template.user_id).await {
+            Ok(owner) => owner.and_then(|owner| owner.account_id),
+            Err(e) => return Err(ApiResponse::internal_error(format!("Failed to verify template: {}", e))),
+        },
+    };
+    if user.account_id.is_none() || user.account_id != template_account_id {
+        return Err(ApiResponse::not_found("Template not found".to_string()));
+    }
+    match user.role {
+        crate::models::role::Role::Editor | crate::models::role::Role::Admin => Ok(()),
+        crate::models::role::Role::Member if !modify => Ok(()),
+        _ if modify => Err(ApiResponse::forbidden("Access denied: You do not have permission to modify this template".to_string())),
+        _ => Err(ApiResponse::not_found("Template not found".to_string())),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/templates/{template_id}/roles",
+    params(
+        ("template_id" = i64, Path, description = "Template ID")
+    ),
+    responses(
+        (status = 200, description = "Template roles retrieved successfully", body = ApiResponse<Vec<TemplateRole>>),
+        (status = 404, description = "Template not found", body = ApiResponse<Vec<TemplateRole>>),
+        (status = 500, description = "Internal server error", body = ApiResponse<Vec<TemplateRole>>)
+    ),
+    security(("bearer_auth" = [])),
+    tag = "template_fields"
+)]
+pub async fn get_template_roles(
+    State(state): State<AppState>,
+    Path(template_id): Path<i64>,
+    Extension(user_id): Extension<i64>,
+) -> (StatusCode, Json<ApiResponse<Vec<TemplateRole>>>) {
+    let pool = &state.lock().await.db_pool;
+
+    if let Err(response) = check_template_access(pool, template_id, user_id, false).await {
+        return response;
+    }
+
+    match TemplateRoleQueries::get_roles_by_template(pool, template_id).await {
+        Ok(roles) => {
+            let roles = roles.into_iter().map(convert_db_role_to_role).collect();
+            ApiResponse::success(roles, "Template roles retrieved successfully".to_string())
+        }
+        Err(e) => ApiResponse::internal_error(format!("Failed to retrieve template roles: {}", e)),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/templates/{template_id}/roles",
+    params(
+        ("template_id" = i64, Path, description = "Template ID")
+    ),
+    request_body = CreateTemplateRoleRequest,
+    responses(
+        (status = 201, description = "Template role created successfully", body = ApiResponse<TemplateRole>),
+        (status = 400, description = "Invalid or duplicate role name", body = ApiResponse<TemplateRole>),
+        (status = 404, description = "Template not found", body = ApiResponse<TemplateRole>),
+        (status = 500, description = "Internal server error", body = ApiResponse<TemplateRole>)
+    ),
+    security(("bearer_auth" = [])),
+    tag = "template_fields"
+)]
+pub async fn create_template_role(
+    State(state): State<AppState>,
+    Path(template_id): Path<i64>,
+    Extension(user_id): Extension<i64>,
+    Json(payload): Json<CreateTemplateRoleRequest>,
+) -> (StatusCode, Json<ApiResponse<TemplateRole>>) {
+    let pool = &state.lock().await.db_pool;
+
+    if let Err(response) = check_template_access(pool, template_id, user_id, true).await {
+        return response;
+    }
+
+    let name = payload.name.trim();
+    if name.is_empty() {
+        return ApiResponse::bad_request("Role name is required".to_string());
+    }
+    match TemplateRoleQueries::get_roles_by_template(pool, template_id).await {
+        Ok(roles) if roles.iter().any(|r| r.name == name) => {
+            return ApiResponse::bad_request(format!("Role '{}' already exists in this template", name));
+        }
+        Ok(_) => {}
+        Err(e) => return ApiResponse::internal_error(format!("Failed to retrieve template roles: {}", e)),
+    }
+
+    match TemplateRoleQueries::create_role(pool, template_id, name, payload.display_order).await {
+        Ok(role) => ApiResponse::created(convert_db_role_to_role(role), "Template role created successfully".to_string()),
+        Err(e) => ApiResponse::internal_error(format!("Failed to create template role: {}", e)),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/templates/{template_id}/roles/{role_id}",
+    params(
+        ("template_id" = i64, Path, description = "Template ID"),
+        ("role_id" = i64, Path, description = "Role ID")
+    ),
+    request_body = UpdateTemplateRoleRequest,
+    responses(
+        (status = 200, description = "Template role updated successfully; fields keep their assignment", body = ApiResponse<TemplateRole>),
+        (status = 400, description = "Invalid or duplicate role name", body = ApiResponse<TemplateRole>),
+        (status = 404, description = "Template role not found", body = ApiResponse<TemplateRole>),
+        (status = 500, description = "Internal server error", body = ApiResponse<TemplateRole>)
+    ),
+    security(("bearer_auth" = [])),
+    tag = "template_fields"
+)]
+pub async fn update_template_role(
+    State(state): State<AppState>,
+    Path((template_id, role_id)): Path<(i64, i64)>,
+    Extension(user_id): Extension<i64>,
+    Json(payload): Json<UpdateTemplateRoleRequest>,
+) -> (StatusCode, Json<ApiResponse<TemplateRole>>) {
+    let pool = &state.lock().await.db_pool;
+
+    if let Err(response) = check_template_access(pool, template_id, user_id, true).await {
+        return response;
+    }
+
+    let roles = match TemplateRoleQueries::get_roles_by_template(pool, template_id).await {
+        Ok(roles) => roles,
+        Err(e) => return ApiResponse::internal_error(format!("Failed to retrieve template roles: {}", e)),
+    };
+    let Some(current) = roles.iter().find(|r| r.id == role_id) else {
+        return ApiResponse::not_found("Template role not found".to_string());
+    };
+
+    let name = payload.name.as_deref().map(str::trim).unwrap_or(&current.name);
+    if name.is_empty() {
+        return ApiResponse::bad_request("Role name is required".to_string());
+    }
+    if roles.iter().any(|r| r.id != role_id && r.name == name) {
+        return ApiResponse::bad_request(format!("Role '{}' already exists in this template", name));
+    }
+    let display_order = payload.display_order.unwrap_or(current.display_order);
+
+    match TemplateRoleQueries::update_role(pool, role_id, name, display_order).await {
+        Ok(Some(role)) => ApiResponse::success(convert_db_role_to_role(role), "Template role updated successfully".to_string()),
+        Ok(None) => ApiResponse::not_found("Template role not found".to_string()),
+        Err(e) => ApiResponse::internal_error(format!("Failed to update template role: {}", e)),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/templates/{template_id}/roles/{role_id}",
+    params(
+        ("template_id" = i64, Path, description = "Template ID"),
+        ("role_id" = i64, Path, description = "Role ID")
+    ),
+    responses(
+        (status = 200, description = "Template role deleted; its fields become unassigned", body = ApiResponse<serde_json::Value>),
+        (status = 400, description = "The role has already been sent to submitters", body = ApiResponse<serde_json::Value>),
+        (status = 404, description = "Template role not found", body = ApiResponse<serde_json::Value>),
+        (status = 500, description = "Internal server error", body = ApiResponse<serde_json::Value>)
+    ),
+    security(("bearer_auth" = [])),
+    tag = "template_fields"
+)]
+pub async fn delete_template_role(
+    State(state): State<AppState>,
+    Path((template_id, role_id)): Path<(i64, i64)>,
+    Extension(user_id): Extension<i64>,
+) -> (StatusCode, Json<ApiResponse<serde_json::Value>>) {
+    let pool = &state.lock().await.db_pool;
+
+    if let Err(response) = check_template_access(pool, template_id, user_id, true).await {
+        return response;
+    }
+
+    match TemplateRoleQueries::get_role_by_id(pool, role_id).await {
+        Ok(Some(role)) if role.template_id == template_id => {}
+        Ok(_) => return ApiResponse::not_found("Template role not found".to_string()),
+        Err(e) => return ApiResponse::internal_error(format!("Failed to get role: {}", e)),
+    }
+
+    // Sent envelopes keep their roles, otherwise their fields would be open to every signer
+    match TemplateRoleQueries::is_role_in_use(pool, role_id).await {
+        Ok(false) => {}
+        Ok(true) => return ApiResponse::bad_request("This role has already been sent to submitters and cannot be deleted".to_string()),
+        Err(e) => return ApiResponse::internal_error(format!("Failed to check role usage: {}", e)),
+    }
+
+    match TemplateRoleQueries::delete_role(pool, role_id).await {
+        Ok(true) => ApiResponse::success(serde_json::json!({"deleted": true}), "Template role deleted successfully".to_string()),
+        Ok(false) => ApiResponse::not_found("Template role not found".to_string()),
+        Err(e) => ApiResponse::internal_error(format!("Failed to delete template role: {}", e)),
+    }
+}
+
 // ===== PUBLIC FILE UPLOAD ENDPOINT (for signing) =====
 
 #[utoipa::path(
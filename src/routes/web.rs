@@ -9,7 +9,8 @@ use axum::{
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use serde::Deserialize;
-use crate::services::email::EmailService;
+use crate::services::email::{EmailContext, EmailService};
+use crate::services::i18n::Localizer;
 use bcrypt::{hash, verify, DEFAULT_COST};
 use crate::common::requests::{RegisterRequest, LoginRequest};
 use crate::common::responses::{ApiResponse, LoginResponse, TwoFactorRequiredResponse};
@@ -49,6 +50,8 @@ use crate::routes::global_settings;
 use crate::routes::email_templates;
 use crate::routes::team;
 use crate::routes::pdf_signature;
+use crate::routes::webhooks;
+use crate::routes::emails;
 use crate::common::jwt::{generate_jwt, generate_temp_2fa_token, auth_middleware, combined_auth_middleware};
 
 pub fn create_router() -> Router<AppState> {
@@ -69,6 +72,7 @@ pub fn create_router() -> Router<AppState> {
         .route("/submitters/:id", get(submitters::get_submitter))
         .route("/submitters/:id", put(submitters::update_submitter))
         .route("/submitters/:id", delete(submitters::delete_submitter))
+        .route("/submitters/:id/reassign", post(submitters::reassign_submitter))
         // .route("/subscription/status", get(subscription::get_subscription_status))
         // .route("/subscription/payment-link", get(subscription::get_payment_link))
         .route("/auth/2fa/setup", get(setup_2fa_handler))
@@ -85,6 +89,8 @@ pub fn create_router() -> Router<AppState> {
         .merge(email_templates::create_router())
         .merge(team::create_router())
         .merge(pdf_signature::create_router())
+        .merge(webhooks::create_router())
+        .merge(emails::create_router())
         .layer(middleware::from_fn(combined_auth_middleware));
 
     let public_routes = Router::new()
@@ -116,6 +122,8 @@ pub fn create_router() -> Router<AppState> {
         .route("/public/signatures/bulk/:token", post(submitters::submit_bulk_signatures))
         .route("/public/submissions/:token/resubmit", put(submitters::resubmit_submitter))
         .route("/public/submissions/:token/send-copy", post(submitters::send_copy_email))
+        .route("/public/submissions/:token/delegate", post(submitters::delegate_submitter))
+        .route("/public/submissions/:token/open.gif", get(submitters::track_email_open))
         .route("/api/submitters/:token/audit-log", get(submitters::get_submitter_audit_log));
     
     println!("Final router created");
@@ -1025,8 +1033,10 @@ pub async fn forgot_password_handler(
             match state_data.otp_cache.store_otp(&payload.email, &otp_string, 900).await {
                 Ok(_) => {
                     // Send email with OTP
-                    let email_service = match EmailService::new() {
-                        Ok(service) => service,
+                    let email_service = match EmailService::new(&state_data.db_pool) {
+                        Ok(service) => service
+                            .with_context(EmailContext { user_id: Some(db_user.id), ..Default::default() })
+                            .with_locale(Localizer::for_user(&state_data.db_pool, db_user.id).await.locale),
                         Err(e) => {
                             eprintln!("Failed to initialize email service: {:?}", e);
                             return (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiResponse {
@@ -1340,8 +1350,10 @@ pub async fn invite_user_handler(
     };
 
     // Send invitation email with JWT token link
-    let email_service = match EmailService::new() {
-        Ok(service) => service,
+    let email_service = match EmailService::new(pool) {
+        Ok(service) => service
+            .with_context(EmailContext { user_id: Some(user_id), ..Default::default() })
+            .with_locale(Localizer::for_user(pool, user_id).await.locale),
         Err(e) => {
             eprintln!("Failed to initialize email service: {}", e);
             return ApiResponse::internal_error("Email service unavailable".to_string());
@@ -1515,8 +1527,10 @@ pub async fn update_user_invitation_handler(
                 }
 
                 // Send invitation email
-                let email_service = match EmailService::new() {
-                    Ok(service) => service,
+                let email_service = match EmailService::new(pool) {
+                    Ok(service) => service
+                        .with_context(EmailContext { user_id: Some(user_id), ..Default::default() })
+                        .with_locale(Localizer::for_user(pool, user_id).await.locale),
                     Err(e) => {
                         eprintln!("Failed to initialize email service: {}", e);
                         return ApiResponse::internal_error("Email service unavailable".to_string());
@@ -2060,6 +2074,10 @@ pub struct UpdateBasicSettingsRequest {
     pub completion_body: Option<String>,
     pub redirect_title: Option<String>,
     pub redirect_url: Option<String>,
+    pub allow_to_delegate_signing: Option<bool>,
+    pub email_from_name: Option<String>,
+    pub email_reply_to: Option<String>,
+    pub email_layout: Option<String>,
 }
 
 // Get basic settings handler
@@ -2115,6 +2133,14 @@ pub async fn update_basic_settings_handler(
 ) -> (StatusCode, Json<ApiResponse<String>>) {
     let pool = &state.lock().await.db_pool;
 
+    if let Err(e) = crate::services::email_branding::validate_settings(
+        payload.email_from_name.as_deref(),
+        payload.email_reply_to.as_deref(),
+        payload.email_layout.as_deref(),
+    ) {
+        return ApiResponse::bad_request(e);
+    }
+
     // Get current settings
     let current_settings = match GlobalSettingsQueries::get_global_settings(pool).await {
         Ok(Some(settings)) => settings,
@@ -2153,6 +2179,10 @@ pub async fn update_basic_settings_handler(
         completion_body: payload.completion_body.or_else(|| current_settings.completion_body.clone()),
         redirect_title: payload.redirect_title.or_else(|| current_settings.redirect_title.clone()),
         redirect_url: payload.redirect_url.or_else(|| current_settings.redirect_url.clone()),
+        allow_to_delegate_signing: payload.allow_to_delegate_signing.or(Some(current_settings.allow_to_delegate_signing)),
+        email_from_name: payload.email_from_name,
+        email_reply_to: payload.email_reply_to,
+        email_layout: payload.email_layout,
     };
 
     match GlobalSettingsQueries::update_global_settings(pool, update_data).await {
@@ -0,0 +1,399 @@
+use axum::{
+    extract::{Path, Query, State, Extension},
+    http::StatusCode,
+    response::Json,
+    routing::{get, post, put},
+    Router,
+    middleware,
+};
+use serde::Deserialize;
+
+use crate::common::responses::ApiResponse;
+use crate::common::authorization::require_admin_or_team_member;
+use crate::database::connection::DbPool;
+use crate::database::models::{CreateWebhookEndpoint, DbUser, DbWebhookEndpoint};
+use crate::database::queries::{UserQueries, WebhookQueries};
+use crate::models::webhook::{WebhookEndpoint, CreateWebhookRequest, UpdateWebhookRequest, WebhookDelivery};
+use crate::routes::web::AppState;
+use crate::services::webhooks::{self, generate_secret, SUPPORTED_EVENTS};
+
+#[derive(Deserialize)]
+pub struct GetDeliveriesQuery {
+    pub page: Option<i64>,
+    pub limit: Option<i64>,
+}
+
+// Endpoints belong to the caller's account, or to the caller when they have no account
+fn is_endpoint_owner(endpoint: &DbWebhookEndpoint, user: &DbUser) -> bool {
+    match user.account_id {
+        Some(account_id) => endpoint.account_id == Some(account_id),
+        None => endpoint.account_id.is_none() && endpoint.user_id == user.id,
+    }
+}
+
+async fn get_owned_endpoint(pool: &DbPool, id: i64, user: &DbUser) -> Result<Option<DbWebhookEndpoint>, sqlx::Error> {
+    Ok(WebhookQueries::get_endpoint_by_id(pool, id).await?
+        .filter(|endpoint| is_endpoint_owner(endpoint, user)))
+}
+
+// Deliveries check the URL again, since where a host resolves to can change
+async fn validate_url(url: &str) -> Result<(), String> {
+    webhooks::resolve_target(url).await.map(|_| ())
+}
+
+fn validate_events(events: &[String]) -> Result<(), String> {
+    if events.is_empty() {
+        return Err("At least one event type is required".to_string());
+    }
+    for event in events {
+        if !SUPPORTED_EVENTS.contains(&event.as_str()) {
+            return Err(format!("Unsupported event type '{}'. Supported: {}", event, SUPPORTED_EVENTS.join(", ")));
+        }
+    }
+    Ok(())
+}
+
+/// List the webhook endpoints of the current account
+#[utoipa::path(
+    get,
+    path = "/api/webhooks",
+    responses(
+        (status = 200, description = "Webhook endpoints retrieved successfully", body = ApiResponse<Vec<WebhookEndpoint>>),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "webhooks"
+)]
+pub async fn get_webhooks(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<i64>,
+) -> (StatusCode, Json<ApiResponse<Vec<WebhookEndpoint>>>) {
+    let pool = &state.lock().await.db_pool;
+
+    let user = match UserQueries::get_user_by_id(pool, user_id).await {
+        Ok(Some(user)) => user,
+        Ok(None) => return ApiResponse::not_found("User not found".to_string()),
+        Err(e) => return ApiResponse::internal_error(format!("Failed to get user: {}", e)),
+    };
+
+    match WebhookQueries::get_endpoints_by_owner(pool, user.id, user.account_id).await {
+        Ok(endpoints) => {
+            let response: Vec<WebhookEndpoint> = endpoints.into_iter().map(WebhookEndpoint::from).collect();
+            ApiResponse::success(response, "Webhook endpoints retrieved successfully".to_string())
+        }
+        Err(e) => ApiResponse::internal_error(format!("Failed to get webhook endpoints: {}", e)),
+    }
+}
+
+/// Create a webhook endpoint; the signing secret is only returned here
+#[utoipa::path(
+    post,
+    path = "/api/webhooks",
+    request_body = CreateWebhookRequest,
+    responses(
+        (status = 201, description = "Webhook endpoint created successfully", body = ApiResponse<WebhookEndpoint>),
+        (status = 400, description = "Invalid URL or event types"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "webhooks"
+)]
+pub async fn create_webhook(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<i64>,
+    Json(payload): Json<CreateWebhookRequest>,
+) -> (StatusCode, Json<ApiResponse<WebhookEndpoint>>) {
+    let pool = &state.lock().await.db_pool;
+
+    if let Err(e) = validate_events(&payload.events) {
+        return ApiResponse::bad_request(e);
+    }
+    if let Err(e) = validate_url(&payload.url).await {
+        return ApiResponse::bad_request(e);
+    }
+
+    let user = match UserQueries::get_user_by_id(pool, user_id).await {
+        Ok(Some(user)) => user,
+        Ok(None) => return ApiResponse::not_found("User not found".to_string()),
+        Err(e) => return ApiResponse::internal_error(format!("Failed to get user: {}", e)),
+    };
+
+    let create_endpoint = CreateWebhookEndpoint {
+        user_id: user.id,
+        account_id: user.account_id,
+        url: payload.url,
+        secret: generate_secret(),
+        events: serde_json::json!(payload.events),
+    };
+
+    match WebhookQueries::create_endpoint(pool, create_endpoint).await {
+        Ok(db_endpoint) => {
+            let secret = db_endpoint.secret.clone();
+            let mut endpoint = WebhookEndpoint::from(db_endpoint);
+            endpoint.secret = Some(secret);
+            ApiResponse::created(endpoint, "Webhook endpoint created successfully".to_string())
+        }
+        Err(e) => ApiResponse::internal_error(format!("Failed to create webhook endpoint: {}", e)),
+    }
+}
+
+/// Update a webhook endpoint's URL, events or active flag
+#[utoipa::path(
+    put,
+    path = "/api/webhooks/{id}",
+    params(
+        ("id" = i64, Path, description = "Webhook endpoint ID")
+    ),
+    request_body = UpdateWebhookRequest,
+    responses(
+        (status = 200, description = "Webhook endpoint updated successfully", body = ApiResponse<WebhookEndpoint>),
+        (status = 400, description = "Invalid URL or event types"),
+        (status = 404, description = "Webhook endpoint not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "webhooks"
+)]
+pub async fn update_webhook(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<i64>,
+    Path(id): Path<i64>,
+    Json(payload): Json<UpdateWebhookRequest>,
+) -> (StatusCode, Json<ApiResponse<WebhookEndpoint>>) {
+    let pool = &state.lock().await.db_pool;
+
+    if let Some(url) = &payload.url {
+        if let Err(e) = validate_url(url).await {
+            return ApiResponse::bad_request(e);
+        }
+    }
+    if let Some(events) = &payload.events {
+        if let Err(e) = validate_events(events) {
+            return ApiResponse::bad_request(e);
+        }
+    }
+
+    let user = match UserQueries::get_user_by_id(pool, user_id).await {
+        Ok(Some(user)) => user,
+        Ok(None) => return ApiResponse::not_found("User not found".to_string()),
+        Err(e) => return ApiResponse::internal_error(format!("Failed to get user: {}", e)),
+    };
+
+    match get_owned_endpoint(pool, id, &user).await {
+        Ok(Some(_)) => {}
+        Ok(None) => return ApiResponse::not_found("Webhook endpoint not found".to_string()),
+        Err(e) => return ApiResponse::internal_error(format!("Failed to get webhook endpoint: {}", e)),
+    }
+
+    match WebhookQueries::update_endpoint(
+        pool,
+        id,
+        payload.url.as_deref(),
+        payload.events.map(|events| serde_json::json!(events)),
+        payload.is_active,
+    ).await {
+        Ok(Some(db_endpoint)) => ApiResponse::success(WebhookEndpoint::from(db_endpoint), "Webhook endpoint updated successfully".to_string()),
+        Ok(None) => ApiResponse::not_found("Webhook endpoint not found".to_string()),
+        Err(e) => ApiResponse::internal_error(format!("Failed to update webhook endpoint: {}", e)),
+    }
+}
+
+/// Delete a webhook endpoint together with its delivery log
+#[utoipa::path(
+    delete,
+    path = "/api/webhooks/{id}",
+    params(
+        ("id" = i64, Path, description = "Webhook endpoint ID")
+    ),
+    responses(
+        (status = 200, description = "Webhook endpoint deleted successfully", body = ApiResponse<String>),
+        (status = 404, description = "Webhook endpoint not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "webhooks"
+)]
+pub async fn delete_webhook(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<i64>,
+    Path(id): Path<i64>,
+) -> (StatusCode, Json<ApiResponse<String>>) {
+    let pool = &state.lock().await.db_pool;
+
+    let user = match UserQueries::get_user_by_id(pool, user_id).await {
+        Ok(Some(user)) => user,
+        Ok(None) => return ApiResponse::not_found("User not found".to_string()),
+        Err(e) => return ApiResponse::internal_error(format!("Failed to get user: {}", e)),
+    };
+
+    match get_owned_endpoint(pool, id, &user).await {
+        Ok(Some(_)) => {}
+        Ok(None) => return ApiResponse::not_found("Webhook endpoint not found".to_string()),
+        Err(e) => return ApiResponse::internal_error(format!("Failed to get webhook endpoint: {}", e)),
+    }
+
+    match WebhookQueries::delete_endpoint(pool, id).await {
+        Ok(true) => ApiResponse::success("Webhook endpoint deleted".to_string(), "Webhook endpoint deleted successfully".to_string()),
+        Ok(false) => ApiResponse::not_found("Webhook endpoint not found".to_string()),
+        Err(e) => ApiResponse::internal_error(format!("Failed to delete webhook endpoint: {}", e)),
+    }
+}
+
+/// Delivery log of a webhook endpoint, newest first
+#[utoipa::path(
+    get,
+    path = "/api/webhooks/{id}/deliveries",
+    params(
+        ("id" = i64, Path, description = "Webhook endpoint ID"),
+        ("page" = Option<i64>, Query, description = "Page number (default 1)"),
+        ("limit" = Option<i64>, Query, description = "Items per page (default 20, max 100)")
+    ),
+    responses(
+        (status = 200, description = "Webhook deliveries retrieved successfully", body = ApiResponse<Vec<WebhookDelivery>>),
+        (status = 404, description = "Webhook endpoint not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "webhooks"
+)]
+pub async fn get_webhook_deliveries(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<i64>,
+    Path(id): Path<i64>,
+    Query(params): Query<GetDeliveriesQuery>,
+) -> (StatusCode, Json<ApiResponse<Vec<WebhookDelivery>>>) {
+    let pool = &state.lock().await.db_pool;
+
+    let user = match UserQueries::get_user_by_id(pool, user_id).await {
+        Ok(Some(user)) => user,
+        Ok(None) => return ApiResponse::not_found("User not found".to_string()),
+        Err(e) => return ApiResponse::internal_error(format!("Failed to get user: {}", e)),
+    };
+
+    match get_owned_endpoint(pool, id, &user).await {
+        Ok(Some(_)) => {}
+        Ok(None) => return ApiResponse::not_found("Webhook endpoint not found".to_string()),
+        Err(e) => return ApiResponse::internal_error(format!("Failed to get webhook endpoint: {}", e)),
+    }
+
+    let page = params.page.unwrap_or(1).max(1);
+    let limit = params.limit.unwrap_or(20).clamp(1, 100);
+    let offset = (page - 1) * limit;
+
+    match WebhookQueries::get_deliveries_by_endpoint(pool, id, offset, limit).await {
+        Ok(deliveries) => {
+            let response: Vec<WebhookDelivery> = deliveries.into_iter().map(WebhookDelivery::from).collect();
+            ApiResponse::success(response, "Webhook deliveries retrieved successfully".to_string())
+        }
+        Err(e) => ApiResponse::internal_error(format!("Failed to get webhook deliveries: {}", e)),
+    }
+}
+
+/// A single delivery with the log of every attempt
+#[utoipa::path(
+    get,
+    path = "/api/webhooks/deliveries/{id}",
+    params(
+        ("id" = i64, Path, description = "Webhook delivery ID")
+    ),
+    responses(
+        (status = 200, description = "Webhook delivery retrieved successfully", body = ApiResponse<WebhookDelivery>),
+        (status = 404, description = "Webhook delivery not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "webhooks"
+)]
+pub async fn get_webhook_delivery(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<i64>,
+    Path(id): Path<i64>,
+) -> (StatusCode, Json<ApiResponse<WebhookDelivery>>) {
+    let pool = &state.lock().await.db_pool;
+
+    let user = match UserQueries::get_user_by_id(pool, user_id).await {
+        Ok(Some(user)) => user,
+        Ok(None) => return ApiResponse::not_found("User not found".to_string()),
+        Err(e) => return ApiResponse::internal_error(format!("Failed to get user: {}", e)),
+    };
+
+    let db_delivery = match WebhookQueries::get_delivery_by_id(pool, id).await {
+        Ok(Some(delivery)) => delivery,
+        Ok(None) => return ApiResponse::not_found("Webhook delivery not found".to_string()),
+        Err(e) => return ApiResponse::internal_error(format!("Failed to get webhook delivery: {}", e)),
+    };
+
+    match get_owned_endpoint(pool, db_delivery.endpoint_id, &user).await {
+        Ok(Some(_)) => {}
+        Ok(None) => return ApiResponse::not_found("Webhook delivery not found".to_string()),
+        Err(e) => return ApiResponse::internal_error(format!("Failed to get webhook endpoint: {}", e)),
+    }
+
+    match WebhookQueries::get_delivery_attempts(pool, id).await {
+        Ok(attempts) => {
+            let mut delivery = WebhookDelivery::from(db_delivery);
+            delivery.attempt_log = Some(attempts.into_iter().map(Into::into).collect());
+            ApiResponse::success(delivery, "Webhook delivery retrieved successfully".to_string())
+        }
+        Err(e) => ApiResponse::internal_error(format!("Failed to get delivery attempts: {}", e)),
+    }
+}
+
+/// Queue a delivery for an immediate new attempt
+#[utoipa::path(
+    post,
+    path = "/api/webhooks/deliveries/{id}/redeliver",
+    params(
+        ("id" = i64, Path, description = "Webhook delivery ID")
+    ),
+    responses(
+        (status = 200, description = "Webhook delivery queued for redelivery", body = ApiResponse<WebhookDelivery>),
+        (status = 400, description = "The delivery is being attempted right now"),
+        (status = 404, description = "Webhook delivery not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "webhooks"
+)]
+pub async fn redeliver_webhook(
+    State(state): State<AppState>,
+    Extension(user_id): Extension<i64>,
+    Path(id): Path<i64>,
+) -> (StatusCode, Json<ApiResponse<WebhookDelivery>>) {
+    let pool = &state.lock().await.db_pool;
+
+    let user = match UserQueries::get_user_by_id(pool, user_id).await {
+        Ok(Some(user)) => user,
+        Ok(None) => return ApiResponse::not_found("User not found".to_string()),
+        Err(e) => return ApiResponse::internal_error(format!("Failed to get user: {}", e)),
+    };
+
+    let db_delivery = match WebhookQueries::get_delivery_by_id(pool, id).await {
+        Ok(Some(delivery)) => delivery,
+        Ok(None) => return ApiResponse::not_found("Webhook delivery not found".to_string()),
+        Err(e) => return ApiResponse::internal_error(format!("Failed to get webhook delivery: {}", e)),
+    };
+
+    match get_owned_endpoint(pool, db_delivery.endpoint_id, &user).await {
+        Ok(Some(_)) => {}
+        Ok(None) => return ApiResponse::not_found("Webhook delivery not found".to_string()),
+        Err(e) => return ApiResponse::internal_error(format!("Failed to get webhook endpoint: {}", e)),
+    }
+
+    match WebhookQueries::requeue_delivery(pool, id).await {
+        Ok(Some(delivery)) => ApiResponse::success(WebhookDelivery::from(delivery), "Webhook delivery queued for redelivery".to_string()),
+        // The delivery was found above, so it is leased to a worker
+        Ok(None) => ApiResponse::bad_request("This delivery is being attempted right now; try again once it finishes".to_string()),
+        Err(e) => ApiResponse::internal_error(format!("Failed to queue redelivery: {}", e)),
+    }
+}
+
+pub fn create_router() -> Router<AppState> {
+    Router::new()
+        .route("/webhooks", get(get_webhooks).post(create_webhook))
+        .route("/webhooks/:id", put(update_webhook).delete(delete_webhook))
+        .route("/webhooks/:id/deliveries", get(get_webhook_deliveries))
+        .route("/webhooks/deliveries/:id", get(get_webhook_delivery))
+        .route("/webhooks/deliveries/:id/redeliver", post(redeliver_webhook))
+        .layer(middleware::from_fn(require_admin_or_team_member))
+}
@@ -0,0 +1,221 @@
+use axum::http::{header, HeaderMap};
+use chrono::{DateTime, SecondsFormat, Utc};
+use sha2::{Digest, Sha256};
+
+use crate::database::connection::DbPool;
+use crate::database::models::{CreateAuditEvent, DbAuditEvent, DbSubmitter};
+use crate::database::queries::AuditEventQueries;
+use crate::services::i18n;
+
+pub const EVENT_SENT: &str = "sent";
+pub const EVENT_EMAIL_OPENED: &str = "email_opened";
+pub const EVENT_VIEWED: &str = "viewed";
+pub const EVENT_FIELD_FILLED: &str = "field_filled";
+pub const EVENT_SIGNED: &str = "signed";
+pub const EVENT_DECLINED: &str = "declined";
+pub const EVENT_REMINDED: &str = "reminded";
+pub const EVENT_RESUBMITTED: &str = "resubmitted";
+pub const EVENT_COPY_SENT: &str = "copy_sent";
+pub const EVENT_DOWNLOADED: &str = "downloaded";
+pub const EVENT_EXPIRED: &str = "expired";
+pub const EVENT_EXPIRY_EXTENDED: &str = "expiry_extended";
+pub const EVENT_VOIDED: &str = "voided";
+pub const EVENT_REASSIGNED: &str = "reassigned";
+
+/// Request metadata captured with an event
+#[derive(Debug, Clone, Default)]
+pub struct AuditContext {
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub timezone: Option<String>,
+}
+
+impl AuditContext {
+    pub fn system() -> Self {
+        Self::default()
+    }
+
+    /// Build a context from request headers; proxies' X-Forwarded-For wins over the socket address
+    pub fn from_request(headers: &HeaderMap, socket_ip: Option<String>, timezone: Option<String>) -> Self {
+        let forwarded_ip = headers.get("x-forwarded-for")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.split(',').next())
+            .map(|ip| ip.trim().to_string())
+            .filter(|ip| !ip.is_empty());
+        let user_agent = headers.get(header::USER_AGENT)
+            .and_then(|v| v.to_str().ok())
+            .map(|ua| ua.to_string());
+
+        Self {
+            ip_address: forwarded_ip.or(socket_ip),
+            user_agent,
+            timezone,
+        }
+    }
+}
+
+/// Hash an event together with the hash of the event before it.
+///
+/// Fields are serialized as a JSON array so values containing separators
+/// cannot collide; `created_at` must already be truncated to microseconds
+/// (the precision Postgres stores).
+pub fn compute_event_hash(prev_hash: Option<&str>, event: &CreateAuditEvent, created_at: DateTime<Utc>) -> String {
+    let canonical = serde_json::json!([
+        prev_hash,
+        event.submitter_id,
+        event.submission_id,
+        event.template_id,
+        event.event_type,
+        event.actor,
+        event.details,
+        event.data,
+        event.ip_address,
+        event.user_agent,
+        event.timezone,
+        created_at.to_rfc3339_opts(SecondsFormat::Micros, true),
+    ]);
+
+    hex::encode(Sha256::digest(canonical.to_string().as_bytes()))
+}
+
+/// Check that every event links to its predecessor and that its hash matches its content.
+/// Returns the id of the first event that fails verification.
+pub fn verify_chain(events: &[DbAuditEvent]) -> Result<(), i64> {
+    let mut prev_hash: Option<&str> = None;
+
+    for event in events {
+        if event.prev_hash.as_deref() != prev_hash {
+            return Err(event.id);
+        }
+
+        let content = CreateAuditEvent {
+            submitter_id: event.submitter_id,
+            submission_id: event.submission_id,
+            template_id: event.template_id,
+            event_type: event.event_type.clone(),
+            actor: event.actor.clone(),
+            details: event.details.clone(),
+            data: event.data.clone(),
+            ip_address: event.ip_address.clone(),
+            user_agent: event.user_agent.clone(),
+            timezone: event.timezone.clone(),
+        };
+        if compute_event_hash(prev_hash, &content, event.created_at) != event.hash {
+            return Err(event.id);
+        }
+
+        prev_hash = Some(event.hash.as_str());
+    }
+
+    Ok(())
+}
+
+/// Human readable label used in audit log responses and PDFs, in the given locale
+pub fn event_label(event_type: &str, locale: &str) -> String {
+    let known = [
+        EVENT_SENT, EVENT_EMAIL_OPENED, EVENT_VIEWED, EVENT_FIELD_FILLED, EVENT_SIGNED,
+        EVENT_DECLINED, EVENT_REMINDED, EVENT_RESUBMITTED, EVENT_COPY_SENT, EVENT_DOWNLOADED,
+        EVENT_EXPIRED, EVENT_EXPIRY_EXTENDED, EVENT_VOIDED, EVENT_REASSIGNED,
+    ];
+    let event_type = if known.contains(&event_type) { event_type } else { "other" };
+    i18n::t(locale, &format!("audit.event.{}", event_type))
+}
+
+/// Append an event to a submitter's audit trail.
+/// Failures are logged and never abort the request that triggered the event.
+pub async fn record_event(
+    pool: &DbPool,
+    submitter: &DbSubmitter,
+    event_type: &str,
+    actor: &str,
+    details: String,
+    context: AuditContext,
+    data: Option<serde_json::Value>,
+) {
+    let event = CreateAuditEvent {
+        submitter_id: submitter.id,
+        submission_id: submitter.submission_id,
+        template_id: submitter.template_id,
+        event_type: event_type.to_string(),
+        actor: actor.to_string(),
+        details,
+        data,
+        ip_address: context.ip_address,
+        user_agent: context.user_agent,
+        timezone: context.timezone,
+    };
+
+    if let Err(e) = AuditEventQueries::append_event(pool, event).await {
+        eprintln!("❌ Failed to record '{}' audit event for submitter {}: {}", event_type, submitter.id, e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::SubsecRound;
+
+    fn build_chain(count: usize) -> Vec<DbAuditEvent> {
+        let mut events: Vec<DbAuditEvent> = Vec::new();
+        for i in 0..count {
+            let content = CreateAuditEvent {
+                submitter_id: 7,
+                submission_id: Some(3),
+                template_id: 1,
+                event_type: EVENT_VIEWED.to_string(),
+                actor: "signer@example.com".to_string(),
+                details: format!("View #{}", i + 1),
+                data: Some(serde_json::json!({ "page": i })),
+                ip_address: Some("203.0.113.5".to_string()),
+                user_agent: Some("Mozilla/5.0".to_string()),
+                timezone: Some("Europe/Berlin".to_string()),
+            };
+            let created_at = Utc::now().trunc_subsecs(6);
+            let prev_hash = events.last().map(|e| e.hash.clone());
+            let hash = compute_event_hash(prev_hash.as_deref(), &content, created_at);
+            events.push(DbAuditEvent {
+                id: i as i64 + 1,
+                submitter_id: content.submitter_id,
+                submission_id: content.submission_id,
+                template_id: content.template_id,
+                event_type: content.event_type,
+                actor: content.actor,
+                details: content.details,
+                data: content.data,
+                ip_address: content.ip_address,
+                user_agent: content.user_agent,
+                timezone: content.timezone,
+                prev_hash,
+                hash,
+                created_at,
+            });
+        }
+        events
+    }
+
+    #[test]
+    fn test_verify_chain_accepts_untouched_chain() {
+        assert_eq!(verify_chain(&build_chain(4)), Ok(()));
+    }
+
+    #[test]
+    fn test_verify_chain_detects_edited_event() {
+        let mut events = build_chain(4);
+        events[2].ip_address = Some("198.51.100.1".to_string());
+        assert_eq!(verify_chain(&events), Err(3));
+    }
+
+    #[test]
+    fn test_verify_chain_detects_removed_event() {
+        let mut events = build_chain(4);
+        events.remove(1);
+        assert_eq!(verify_chain(&events), Err(3));
+    }
+
+    #[test]
+    fn test_event_label() {
+        assert_eq!(event_label(EVENT_SIGNED, "en"), "Document Signed");
+        assert_eq!(event_label(EVENT_SIGNED, "fr"), "Document signé");
+        assert_eq!(event_label("something_new", "en"), "Event");
+    }
+}
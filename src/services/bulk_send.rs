@@ -0,0 +1,307 @@
+use chrono::{DateTime, Utc};
+
+use crate::database::models::{DbTemplateField, DbTemplateRole};
+use crate::models::submission::{BulkSendColumn, CreateSubmissionRequest};
+use crate::models::submitter::{CreateSubmitterRequest, PrefilledFieldValue};
+use crate::services::field_validation::is_email;
+use crate::services::formula;
+
+pub const TARGET_NAME: &str = "name";
+pub const TARGET_EMAIL: &str = "email";
+pub const TARGET_FIELD: &str = "field";
+
+/// Largest number of rows accepted in one bulk send
+pub const MAX_ROWS: usize = 1000;
+
+fn role_names(roles: &[DbTemplateRole]) -> Vec<String> {
+    let mut ordered: Vec<&DbTemplateRole> = roles.iter().collect();
+    ordered.sort_by_key(|r| (r.display_order, r.id));
+    ordered.into_iter().map(|r| r.name.clone()).collect()
+}
+
+/// Mapping used when the sender does not give one.
+///
+/// `Email` and `Name` belong to the first role, `<Role> Email` and `<Role> Name`
+/// to that role, and a header equal to a template field name fills that field.
+/// Headers are compared case-insensitively; other columns are ignored.
+pub fn default_mapping(headers: &[String], fields: &[DbTemplateField], roles: &[DbTemplateRole]) -> Vec<BulkSendColumn> {
+    let roles = role_names(roles);
+    let mut mapping = Vec::new();
+    for header in headers {
+        let key = header.trim().to_lowercase();
+        let column = |role: Option<String>, target: &str, field_name: Option<String>| BulkSendColumn {
+            column: header.clone(),
+            role,
+            target: target.to_string(),
+            field_name,
+            readonly: false,
+        };
+        if key == TARGET_EMAIL || key == TARGET_NAME {
+            mapping.push(column(None, &key, None));
+            continue;
+        }
+        let role_target = roles.iter().find_map(|role| {
+            let rest = key.strip_prefix(&role.to_lowercase())?.trim();
+            (rest == TARGET_EMAIL || rest == TARGET_NAME).then(|| (role.clone(), rest.to_string()))
+        });
+        if let Some((role, target)) = role_target {
+            mapping.push(column(Some(role), &target, None));
+        } else if let Some(field) = fields.iter().find(|f| f.name.trim().to_lowercase() == key) {
+            mapping.push(column(None, TARGET_FIELD, Some(field.name.clone())));
+        }
+    }
+    mapping
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Target {
+    Name,
+    Email,
+    Field { id: i64, readonly: bool },
+}
+
+#[derive(Debug, Clone)]
+struct MappedColumn {
+    index: usize,
+    role: String,
+    target: Target,
+}
+
+/// Column mapping checked against the header row and the template
+#[derive(Debug, Clone)]
+pub struct RowMapping {
+    columns: Vec<MappedColumn>,
+    /// Roles that get a submitter, in signing order
+    roles: Vec<String>,
+}
+
+/// Check a column mapping against the header row and the template fields
+pub fn resolve_mapping(
+    headers: &[String],
+    mapping: &[BulkSendColumn],
+    fields: &[DbTemplateField],
+    roles: &[DbTemplateRole],
+) -> Result<RowMapping, String> {
+    let template_roles = role_names(roles);
+    let default_role = template_roles.first().cloned().unwrap_or_default();
+
+    let mut columns: Vec<MappedColumn> = Vec::new();
+    for entry in mapping {
+        let index = headers.iter().position(|h| h.trim() == entry.column.trim())
+            .ok_or_else(|| format!("Column '{}' is not in the file", entry.column))?;
+        if let Some(role) = &entry.role {
+            if !template_roles.is_empty() && !template_roles.contains(role) {
+                return Err(format!("Column '{}' refers to unknown role '{}'", entry.column, role));
+            }
+        }
+
+        let (target, role) = match entry.target.as_str() {
+            TARGET_NAME => (Target::Name, entry.role.clone()),
+            TARGET_EMAIL => (Target::Email, entry.role.clone()),
+            TARGET_FIELD => {
+                let field_name = entry.field_name.as_deref().unwrap_or(&entry.column);
+                let field = fields.iter().find(|f| f.name == field_name)
+                    .ok_or_else(|| format!("Column '{}' refers to field '{}', which does not exist in this template", entry.column, field_name))?;
+                if field.field_type == formula::FIELD_TYPE_FORMULA {
+                    return Err(format!("Column '{}' refers to calculated field '{}', which cannot be prefilled", entry.column, field.name));
+                }
+                let role = entry.role.clone()
+                    .or_else(|| roles.iter().find(|r| Some(r.id) == field.role_id).map(|r| r.name.clone()));
+                (Target::Field { id: field.id, readonly: entry.readonly }, role)
+            }
+            other => return Err(format!("Column '{}' has invalid target '{}'. Must be 'name', 'email' or 'field'", entry.column, other)),
+        };
+        let role = role.unwrap_or_else(|| default_role.clone());
+
+        let duplicate = columns.iter().any(|c| c.role == role && match (&c.target, &target) {
+            (Target::Field { id: a, .. }, Target::Field { id: b, .. }) => a == b,
+            (a, b) => a == b,
+        });
+        if duplicate {
+            return Err(format!("Column '{}' maps to a value that another column already fills", entry.column));
+        }
+        columns.push(MappedColumn { index, role, target });
+    }
+
+    let has_email = |role: &str| columns.iter().any(|c| c.role == role && c.target == Target::Email);
+    let mut roles: Vec<String> = template_roles.iter().filter(|r| has_email(r)).cloned().collect();
+    if template_roles.is_empty() && has_email("") {
+        roles.push(String::new());
+    }
+    if roles.is_empty() {
+        return Err("No email column found. Add an 'Email' column or map one explicitly".to_string());
+    }
+    if let Some(orphan) = columns.iter().find(|c| !roles.contains(&c.role)) {
+        return Err(format!("Column '{}' belongs to role '{}', which has no email column", headers[orphan.index], orphan.role));
+    }
+
+    Ok(RowMapping { columns, roles })
+}
+
+/// Sending options shared by every row of a bulk send
+#[derive(Debug, Clone, Default)]
+pub struct BulkSendOptions {
+    pub template_id: i64,
+    pub name: Option<String>,
+    pub signing_mode: Option<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl RowMapping {
+    /// Email addresses of a row, used in the per-row report
+    pub fn emails(&self, row: &[String]) -> Vec<String> {
+        self.columns.iter()
+            .filter(|c| c.target == Target::Email)
+            .map(|c| cell(row, c.index).to_string())
+            .filter(|email| !email.is_empty())
+            .collect()
+    }
+
+    /// Build the submission request for one data row; blank field cells are left unfilled
+    pub fn build_request(&self, row: &[String], options: &BulkSendOptions) -> Result<CreateSubmissionRequest, String> {
+        let mut submitters = Vec::with_capacity(self.roles.len());
+        for role in &self.roles {
+            let label = if role.is_empty() { "submitter".to_string() } else { format!("role '{}'", role) };
+            let mut email = "";
+            let mut name = "";
+            let mut values = Vec::new();
+            for column in self.columns.iter().filter(|c| &c.role == role) {
+                let value = cell(row, column.index);
+                match column.target {
+                    Target::Email => email = value,
+                    Target::Name => name = value,
+                    Target::Field { id, readonly } if !value.is_empty() => values.push(PrefilledFieldValue {
+                        field_id: Some(id),
+                        field_name: None,
+                        value: value.to_string(),
+                        readonly,
+                    }),
+                    Target::Field { .. } => {}
+                }
+            }
+            if email.is_empty() {
+                return Err(format!("Missing email for {}", label));
+            }
+            if !is_email(email) {
+                return Err(format!("Invalid email '{}' for {}", email, label));
+            }
+            submitters.push(CreateSubmitterRequest {
+                name: if name.is_empty() { email.to_string() } else { name.to_string() },
+                email: email.to_string(),
+                order: None,
+                role_id: None,
+                role: (!role.is_empty()).then(|| role.clone()),
+                locale: None,
+                reminder_config: None,
+                values,
+            });
+        }
+
+        Ok(CreateSubmissionRequest {
+            template_id: options.template_id,
+            name: options.name.clone(),
+            submitters,
+            expires_at: options.expires_at,
+            signing_mode: options.signing_mode.clone(),
+        })
+    }
+}
+
+fn cell(row: &[String], index: usize) -> &str {
+    row.get(index).map(|value| value.trim()).unwrap_or("")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(id: i64, name: &str, role_id: i64, display_order: i32) -> DbTemplateField {
+        DbTemplateField {
+            id,
+            template_id: 1,
+            name: name.to_string(),
+            field_type: "text".to_string(),
+            required: false,
+            display_order,
+            position: None,
+            options: None,
+            metadata: None,
+            partner: None,
+            conditions: None,
+            validation: None,
+            role_id: Some(role_id),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+        }
+    }
+
+    fn roles() -> Vec<DbTemplateRole> {
+        ["Client", "Manager"].iter().enumerate().map(|(index, name)| DbTemplateRole {
+            id: index as i64 + 1,
+            template_id: 1,
+            name: name.to_string(),
+            display_order: index as i32,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }).collect()
+    }
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn test_default_mapping_by_role_and_field() {
+        let fields = vec![field(10, "Company", 1, 1), field(11, "Approved by", 2, 2)];
+        let roles = roles();
+        let headers = strings(&["Name", "Email", "Manager Email", "company", "Notes"]);
+        let mapping = default_mapping(&headers, &fields, &roles);
+        let resolved = resolve_mapping(&headers, &mapping, &fields, &roles).unwrap();
+        assert_eq!(resolved.roles, vec!["Client", "Manager"]);
+
+        let options = BulkSendOptions { template_id: 1, ..Default::default() };
+        let row = strings(&["Jane", "jane@example.com", "boss@example.com", " Acme ", "ignored"]);
+        let request = resolved.build_request(&row, &options).unwrap();
+        assert_eq!(request.submitters.len(), 2);
+        assert_eq!(request.submitters[0].name, "Jane");
+        assert_eq!(request.submitters[1].role.as_deref(), Some("Manager"));
+        assert_eq!(request.submitters[0].values[0].field_id, Some(10));
+        assert_eq!(request.submitters[0].values[0].value, "Acme");
+        assert_eq!(request.submitters[1].name, "boss@example.com");
+        assert!(request.submitters[1].values.is_empty());
+        assert_eq!(resolved.emails(&row), vec!["jane@example.com", "boss@example.com"]);
+
+        let bad_row = strings(&["Jane", "not-an-email", "boss@example.com", "", ""]);
+        assert!(resolved.build_request(&bad_row, &options).unwrap_err().contains("Invalid email"));
+        let short_row = strings(&["Jane", "jane@example.com"]);
+        assert!(resolved.build_request(&short_row, &options).unwrap_err().contains("Manager"));
+    }
+
+    #[test]
+    fn test_explicit_mapping_errors() {
+        let fields = vec![field(10, "Company", 1, 1), field(11, "Approved by", 2, 2)];
+        let roles = roles();
+        let headers = strings(&["Client mail", "Approver"]);
+        let column = |column: &str, role: Option<&str>, target: &str, field_name: Option<&str>| BulkSendColumn {
+            column: column.to_string(),
+            role: role.map(str::to_string),
+            target: target.to_string(),
+            field_name: field_name.map(str::to_string),
+            readonly: true,
+        };
+
+        let ok = vec![column("Client mail", Some("Client"), "email", None)];
+        assert_eq!(resolve_mapping(&headers, &ok, &fields, &roles).unwrap().roles, vec!["Client"]);
+
+        let orphan = vec![ok[0].clone(), column("Approver", None, "field", Some("Approved by"))];
+        assert!(resolve_mapping(&headers, &orphan, &fields, &roles).unwrap_err().contains("no email column"));
+        let missing = vec![column("Phone", None, "email", None)];
+        assert!(resolve_mapping(&headers, &missing, &fields, &roles).unwrap_err().contains("not in the file"));
+        let unknown_role = vec![column("Client mail", Some("Witness"), "email", None)];
+        assert!(resolve_mapping(&headers, &unknown_role, &fields, &roles).unwrap_err().contains("unknown role"));
+        let twice = vec![ok[0].clone(), column("Approver", Some("Client"), "email", None)];
+        assert!(resolve_mapping(&headers, &twice, &fields, &roles).is_err());
+        assert!(resolve_mapping(&headers, &[], &fields, &roles).unwrap_err().contains("No email column"));
+    }
+}
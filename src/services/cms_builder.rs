@@ -0,0 +1,143 @@
+use anyhow::{anyhow, Result};
+use cms::cert::{CertificateChoices, IssuerAndSerialNumber};
+use cms::content_info::{CmsVersion, ContentInfo};
+use cms::signed_data::{CertificateSet, EncapsulatedContentInfo, SignedData, SignerIdentifier, SignerInfo, SignerInfos};
+use der::asn1::{ObjectIdentifier, OctetString, SetOfVec};
+use der::{Any, Decode, Encode, Sequence};
+use openssl::hash::{hash, MessageDigest};
+use openssl::pkey::{Id, PKey, Private};
+use openssl::sign::Signer;
+use openssl::x509::X509;
+use spki::AlgorithmIdentifierOwned;
+use x509_cert::attr::Attribute;
+use x509_cert::ext::pkix::name::{GeneralName, GeneralNames};
+use x509_cert::serial_number::SerialNumber;
+use x509_cert::Certificate;
+
+pub const ID_DATA: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.7.1");
+pub const ID_SIGNED_DATA: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.7.2");
+pub const ID_CONTENT_TYPE: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.9.3");
+pub const ID_MESSAGE_DIGEST: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.9.4");
+pub const ID_AA_SIGNING_CERTIFICATE_V2: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.9.16.2.47");
+pub const ID_AA_TIME_STAMP_TOKEN: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.9.16.2.14");
+pub const ID_SHA1: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.14.3.2.26");
+pub const ID_SHA256: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.2.1");
+pub const ID_SHA384: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.2.2");
+pub const ID_SHA512: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.2.3");
+const ID_SHA256_WITH_RSA: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.1.11");
+const ID_ECDSA_WITH_SHA256: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.4.3.2");
+
+/// RFC 5035 `IssuerSerial`
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+struct IssuerSerial {
+    issuer: GeneralNames,
+    serial_number: SerialNumber,
+}
+
+/// RFC 5035 `ESSCertIDv2`; the hash algorithm is left at its SHA-256 default
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+struct EssCertIdV2 {
+    cert_hash: OctetString,
+    issuer_serial: Option<IssuerSerial>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+struct SigningCertificateV2 {
+    certs: Vec<EssCertIdV2>,
+}
+
+/// Build a CMS SignedData over `content` with the signed attributes CAdES and
+/// RFC 3161 both require: content type, message digest and signing-certificate-v2.
+///
+/// `embed_content` selects an attached (timestamp tokens) or detached (PDF) signature.
+pub fn build_signed_data(
+    content_type: ObjectIdentifier,
+    content: &[u8],
+    embed_content: bool,
+    certificate: &X509,
+    key: &PKey<Private>,
+    chain: &[X509],
+) -> Result<ContentInfo> {
+    let certificate_der = certificate.to_der()?;
+    let signer_certificate = Certificate::from_der(&certificate_der)?;
+    let issuer = signer_certificate.tbs_certificate.issuer.clone();
+    let serial_number = signer_certificate.tbs_certificate.serial_number.clone();
+
+    let signing_certificate = SigningCertificateV2 {
+        certs: vec![EssCertIdV2 {
+            cert_hash: OctetString::new(hash(MessageDigest::sha256(), &certificate_der)?.to_vec())?,
+            issuer_serial: Some(IssuerSerial {
+                issuer: vec![GeneralName::DirectoryName(issuer.clone())],
+                serial_number: serial_number.clone(),
+            }),
+        }],
+    };
+    let signed_attrs = SetOfVec::try_from(vec![
+        attribute(ID_CONTENT_TYPE, Any::encode_from(&content_type)?)?,
+        attribute(ID_MESSAGE_DIGEST, Any::encode_from(&OctetString::new(hash(MessageDigest::sha256(), content)?.to_vec())?)?)?,
+        attribute(ID_AA_SIGNING_CERTIFICATE_V2, Any::encode_from(&signing_certificate)?)?,
+    ])?;
+
+    // The signature covers the DER SET OF encoding of the signed attributes
+    let mut signer = Signer::new(MessageDigest::sha256(), key)?;
+    signer.update(&signed_attrs.to_der()?)?;
+    let signature = signer.sign_to_vec()?;
+
+    let signature_algorithm = match key.id() {
+        Id::RSA => AlgorithmIdentifierOwned { oid: ID_SHA256_WITH_RSA, parameters: Some(Any::null()) },
+        Id::EC => AlgorithmIdentifierOwned { oid: ID_ECDSA_WITH_SHA256, parameters: None },
+        other => return Err(anyhow!("Unsupported signing key type {:?}", other)),
+    };
+
+    let signer_info = SignerInfo {
+        version: CmsVersion::V1,
+        sid: SignerIdentifier::IssuerAndSerialNumber(IssuerAndSerialNumber { issuer, serial_number }),
+        digest_alg: sha256_algorithm(),
+        signed_attrs: Some(signed_attrs),
+        signature_algorithm,
+        signature: OctetString::new(signature)?,
+        unsigned_attrs: None,
+    };
+
+    let mut certificates = vec![CertificateChoices::Certificate(signer_certificate)];
+    for cert in chain {
+        certificates.push(CertificateChoices::Certificate(Certificate::from_der(&cert.to_der()?)?));
+    }
+
+    let signed_data = SignedData {
+        // RFC 5652: version 3 whenever the content is not id-data
+        version: if content_type == ID_DATA { CmsVersion::V1 } else { CmsVersion::V3 },
+        digest_algorithms: SetOfVec::try_from(vec![sha256_algorithm()])?,
+        encap_content_info: EncapsulatedContentInfo {
+            econtent_type: content_type,
+            econtent: if embed_content {
+                Some(Any::encode_from(&OctetString::new(content.to_vec())?)?)
+            } else {
+                None
+            },
+        },
+        certificates: Some(CertificateSet(SetOfVec::try_from(certificates)?)),
+        crls: None,
+        signer_infos: SignerInfos(SetOfVec::try_from(vec![signer_info])?),
+    };
+
+    Ok(ContentInfo { content_type: ID_SIGNED_DATA, content: Any::encode_from(&signed_data)? })
+}
+
+pub fn attribute(oid: ObjectIdentifier, value: Any) -> Result<Attribute> {
+    Ok(Attribute { oid, values: SetOfVec::try_from(vec![value])? })
+}
+
+pub fn sha256_algorithm() -> AlgorithmIdentifierOwned {
+    AlgorithmIdentifierOwned { oid: ID_SHA256, parameters: None }
+}
+
+pub fn digest_for(oid: &ObjectIdentifier) -> Option<MessageDigest> {
+    match *oid {
+        ID_SHA1 => Some(MessageDigest::sha1()),
+        ID_SHA256 => Some(MessageDigest::sha256()),
+        ID_SHA384 => Some(MessageDigest::sha384()),
+        ID_SHA512 => Some(MessageDigest::sha512()),
+        _ => None,
+    }
+}
@@ -355,26 +355,6 @@ pub fn decrypt_password_aes(encrypted_data: &[u8]) -> Result<String> {
         .context("Decrypted data is not valid UTF-8")
 }
 
-/// Calculate ByteRange for PDF signature
-/// Returns [0, offset_before_contents, offset_after_contents, length_after_contents]
-pub fn calculate_byte_range(pdf_size: usize, signature_size: usize) -> [u32; 4] {
-    // Reserve space for signature hex string + delimiters
-    let contents_placeholder_size = signature_size * 2; // Hex encoding
-    
-    // Find /Contents position (simplified - in real implementation, parse PDF)
-    // For now, estimate signature will be at 80% of file
-    let sig_dict_offset = (pdf_size as f64 * 0.8) as u32;
-    let contents_start = sig_dict_offset + 100; // After /Contents <
-    let contents_end = contents_start + contents_placeholder_size as u32;
-    
-    [
-        0,
-        contents_start,
-        contents_end,
-        (pdf_size as u32).saturating_sub(contents_end),
-    ]
-}
-
 /// Store CA certificates in database
 pub async fn store_ca_certificates(
     pool: &PgPool,
@@ -668,67 +648,3 @@ pub fn extract_certificate_info(cert: &X509) -> Result<(String, String, String,
 
     Ok((issuer, subject, serial, valid_from, valid_to))
 }
-
-/// Create PKCS#7 signature for PDF using uploaded certificate
-pub fn create_pkcs7_signature_with_cert(
-    pdf_data: &[u8],
-    byte_range: &[u32; 4],
-    signing_cert: &X509,
-    signing_keypair: &PKey<Private>,
-    cert_chain: Option<&[X509]>,
-) -> Result<Vec<u8>> {
-    use openssl::pkcs7::{Pkcs7, Pkcs7Flags};
-    use openssl::stack::Stack;
-    
-    // Extract content to sign (exclude signature placeholder)
-    let [offset1, len1, offset2, len2] = *byte_range;
-    let pdf_len = pdf_data.len();
-    
-    // Validate byte ranges to prevent panic
-    let end1 = (offset1 + len1) as usize;
-    let end2 = (offset2 + len2) as usize;
-    
-    if offset1 as usize >= pdf_len || end1 > pdf_len {
-        return Err(anyhow::anyhow!(
-            "Invalid byte range: offset1={} len1={} end={} exceeds PDF size {}",
-            offset1, len1, end1, pdf_len
-        ));
-    }
-    
-    // Only validate offset2 if len2 > 0
-    if len2 > 0 && (offset2 as usize >= pdf_len || end2 > pdf_len) {
-        return Err(anyhow::anyhow!(
-            "Invalid byte range: offset2={} len2={} end={} exceeds PDF size {}",
-            offset2, len2, end2, pdf_len
-        ));
-    }
-    
-    let mut content_to_sign = Vec::new();
-    content_to_sign.extend_from_slice(&pdf_data[offset1 as usize..end1]);
-    if len2 > 0 {
-        content_to_sign.extend_from_slice(&pdf_data[offset2 as usize..end2]);
-    }
-    
-    // Create certificate chain stack
-    let mut cert_stack = Stack::new()?;
-    if let Some(chain) = cert_chain {
-        for cert in chain {
-            cert_stack.push(cert.clone())?;
-        }
-    }
-    
-    // Create PKCS#7 signature
-    let flags = Pkcs7Flags::DETACHED | Pkcs7Flags::BINARY;
-    let pkcs7 = Pkcs7::sign(
-        signing_cert,
-        signing_keypair,
-        &cert_stack,
-        &content_to_sign,
-        flags,
-    )?;
-    
-    // Convert to DER format
-    let pkcs7_der = pkcs7.to_der()?;
-    
-    Ok(pkcs7_der)
-}
@@ -1,51 +1,151 @@
-use lettre::transport::smtp::authentication::Credentials;
-use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
 use lettre::message::{Attachment, MultiPart, SinglePart, header::ContentDisposition};
+use lettre::message::header::{Subject, To};
+use lettre::message::{Mailbox, Mailboxes, MessageBuilder};
+use lettre::address::Envelope;
+use lettre::Message;
 use std::env;
+use std::sync::Arc;
+use chrono::Utc;
+use futures::future::BoxFuture;
+
+use crate::database::connection::DbPool;
+use crate::database::models::{CreateOutboxEmail, DbOutboxEmail};
+use crate::database::queries::EmailOutboxQueries;
+use crate::services::email_branding::EmailBranding;
+use crate::services::email_template_engine::escape_html;
+use crate::services::email_transport::{self, EmailTransport};
+use crate::services::i18n;
+use crate::services::jobs::JobOutcome;
+
+// Notice boxes of the reminder email, the last reminder stands out
+const NOTICE_STYLE: &str = "background-color: #fff3cd; border: 1px solid #ffeaa7; color: #856404; padding: 15px; border-radius: 5px; margin: 20px 0;";
+const NOTICE_URGENT_STYLE: &str = "background-color: #f8d7da; border: 1px solid #f5c6cb; color: #721c24; padding: 15px; border-radius: 5px; margin: 20px 0;";
+
+/// What an email is about, stored with it in the outbox
+#[derive(Debug, Clone, Default)]
+pub struct EmailContext {
+    pub user_id: Option<i64>,
+    pub submitter_id: Option<i64>,
+    /// Overrides the kind the `send_*` method would record, e.g. "invitation" for a template email
+    pub kind: Option<String>,
+}
 
 #[derive(Clone)]
 pub struct EmailService {
-    smtp_host: String,
-    smtp_port: u16,
-    smtp_username: String,
-    smtp_password: String,
     from_email: String,
     from_name: String,
-    use_tls: bool,
-    test_mode: bool,
+    reply_to: Option<Mailbox>,
+    transport: Arc<dyn EmailTransport>,
+    /// Every message is recorded in this pool's outbox
+    outbox: (DbPool, EmailContext),
+    /// Layout and language of the built-in emails
+    branding: EmailBranding,
 }
 
 impl EmailService {
-    pub fn new() -> Result<Self, Box<dyn std::error::Error + Send + Sync + 'static>> {
-        let smtp_host = env::var("SMTP_HOST").unwrap_or_else(|_| "smtp.gmail.com".to_string());
-        let smtp_port: u16 = env::var("SMTP_PORT")
-            .unwrap_or_else(|_| "587".to_string())
-            .parse()
-            .unwrap_or(587);
-        let smtp_username = env::var("SMTP_USERNAME")?;
-        let smtp_password = env::var("SMTP_PASSWORD")?;
+    pub fn new(pool: &DbPool) -> Result<Self, Box<dyn std::error::Error + Send + Sync + 'static>> {
         let from_email = env::var("FROM_EMAIL")?;
         let from_name = env::var("FROM_NAME").unwrap_or_else(|_| "DocuSeal Pro".to_string());
-        let use_tls: bool = env::var("SMTP_USE_TLS")
-            .unwrap_or_else(|_| "true".to_string())
-            .parse()
-            .unwrap_or(true);
-        let test_mode: bool = env::var("EMAIL_TEST_MODE")
-            .unwrap_or_else(|_| "false".to_string())
-            .parse()
-            .unwrap_or(false);
-
-        println!("EmailService initialized with test_mode: {}", test_mode);
+        let transport = email_transport::default_transport()?;
+        let branding = EmailBranding { account_name: from_name.clone(), ..EmailBranding::default() };
 
         Ok(Self {
-            smtp_host,
-            smtp_port,
-            smtp_username,
-            smtp_password,
             from_email,
             from_name,
-            use_tls,
-            test_mode,
+            reply_to: None,
+            transport,
+            outbox: (pool.clone(), EmailContext::default()),
+            branding,
+        })
+    }
+
+    /// Record every message sent by this service in the outbox with `context`
+    pub fn with_context(mut self, context: EmailContext) -> Self {
+        self.outbox.1 = context;
+        self
+    }
+
+    /// Send as the account: its sender name (the address stays FROM_EMAIL) and Reply-To
+    pub fn with_branding(mut self, branding: &EmailBranding) -> Self {
+        if let Some(from_name) = &branding.from_name {
+            self.from_name = from_name.clone();
+        }
+        self.reply_to = branding.reply_to.clone();
+        self.branding = branding.clone();
+        self
+    }
+
+    /// Write the built-in emails in `locale`, for recipients without an account branding
+    pub fn with_locale(mut self, locale: &'static str) -> Self {
+        self.branding.locale = locale;
+        self
+    }
+
+    // Builder with the sender and Reply-To of this service set
+    fn message_builder(&self) -> Result<MessageBuilder, Box<dyn std::error::Error + Send + Sync + 'static>> {
+        let builder = Message::builder().from(Mailbox::new(Some(self.from_name.clone()), self.from_email.parse()?));
+        Ok(match &self.reply_to {
+            Some(reply_to) => builder.reply_to(reply_to.clone()),
+            None => builder,
+        })
+    }
+
+    // Hand a message to the transport, recording it and the outcome in the outbox.
+    // A failure to write the outbox is logged and does not stop the email.
+    async fn deliver(&self, kind: &str, email: Message) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+        let raw = email.formatted();
+
+        let (pool, context) = &self.outbox;
+        let recipient = email.headers().get::<To>()
+            .and_then(|to| Mailboxes::from(to).into_iter().next());
+        let kind = context.kind.as_deref().unwrap_or(kind);
+        let data = CreateOutboxEmail {
+            user_id: context.user_id,
+            submitter_id: context.submitter_id,
+            kind: kind.to_string(),
+            transport: self.transport.name().to_string(),
+            from_email: self.from_email.clone(),
+            to_email: recipient.as_ref().map(|r| r.email.to_string()).unwrap_or_default(),
+            to_name: recipient.and_then(|r| r.name),
+            subject: email.headers().get::<Subject>().map(|s| s.as_ref().to_string()).unwrap_or_default(),
+            // Reset codes and activation links stay usable; don't keep a copy of them
+            raw_message: (!is_credential_kind(kind)).then(|| raw.clone()),
+        };
+        let outbox_id = match EmailOutboxQueries::create_email(pool, data).await {
+            Ok(row) => Some(row.id),
+            Err(e) => {
+                eprintln!("Failed to record outgoing {} email in the outbox: {}", kind, e);
+                None
+            }
+        };
+
+        let result = self.transport.send(email.envelope(), &raw).await;
+
+        if let Some(id) = outbox_id {
+            if let Err(e) = EmailOutboxQueries::record_attempt(pool, id, self.transport.name(), result.as_ref().err().map(String::as_str)).await {
+                eprintln!("Failed to record delivery of outbox email {}: {}", id, e);
+            }
+        }
+        result.map_err(Into::into)
+    }
+
+    fn t(&self, key: &str) -> String {
+        i18n::t(self.branding.locale, key)
+    }
+
+    fn t_args(&self, key: &str, args: &[(&str, &str)]) -> String {
+        i18n::t_args(self.branding.locale, key, args)
+    }
+
+    // Wrap the html body of a built-in email in the account layout. An account layout
+    // that no longer parses is logged and the default layout used instead.
+    fn with_layout(&self, content: String) -> String {
+        let context = self.branding.context();
+        self.branding.apply_layout(content.clone(), "html", &context).unwrap_or_else(|e| {
+            eprintln!("{}; sending with the default layout", e);
+            EmailBranding { layout: None, ..self.branding.clone() }
+                .apply_layout(content, "html", &context)
+                .expect("the default layout parses")
         })
     }
 
@@ -57,194 +157,65 @@ impl EmailService {
         signature_link: &str,
         reminder_number: i32,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
-        let subject = format!("Document Signature Reminder (Attempt {}): {}", reminder_number, submission_name);
+        let number = reminder_number.to_string();
+        let subject = self.t_args("email.reminder.subject", &[("number", &number), ("document", submission_name)]);
         println!("🎯 EMAIL SUBJECT: {}", subject);
-        println!("send_signature_reminder called with test_mode: {}, reminder_number: {}", self.test_mode, reminder_number);
         println!("📧 Email details: to={}, name={}, submission={}, link={}", to_email, to_name, submission_name, signature_link);
-        
-        if self.test_mode {
-            println!("TEST MODE: Would send reminder #{} to {} ({}) with link: {}", reminder_number, to_email, to_name, signature_link);
-            return Ok(());
-        }
 
-        let html_body = format!(
-            r#"
-<!DOCTYPE html>
-<html lang="en">
-<head>
-    <meta charset="UTF-8">
-    <meta name="viewport" content="width=device-width, initial-scale=1.0">
-    <title>Document Signature Reminder</title>
-    <style>
-        body {{
-            font-family: 'Segoe UI', Tahoma, Geneva, Verdana, sans-serif;
-            line-height: 1.6;
-            color: #333;
-            max-width: 600px;
-            margin: 0 auto;
-            background-color: #f8f9fa;
-            padding: 20px;
-        }}
-        .container {{
-            background: white;
-            padding: 30px;
-            border-radius: 10px;
-            box-shadow: 0 2px 10px rgba(0,0,0,0.1);
-        }}
-        .header {{
-            text-align: center;
-            margin-bottom: 30px;
-        }}
-        .header h1 {{
-            color: #ff9800;
-            margin-bottom: 10px;
-        }}
-        .reminder-badge {{
-            display: inline-block;
-            padding: 5px 15px;
-            background: linear-gradient(135deg, #ff9800 0%, #f57c00 100%);
-            color: white;
-            border-radius: 20px;
-            font-size: 14px;
-            font-weight: bold;
-            margin-bottom: 15px;
-        }}
-        .content {{
-            margin-bottom: 30px;
-        }}
-        .button {{
-            display: inline-block;
-            padding: 12px 24px;
-            background: linear-gradient(135deg, #ff9800 0%, #f57c00 100%);
-            color: white;
-            text-decoration: none;
-            border-radius: 6px;
-            font-weight: bold;
-            text-align: center;
-            margin: 20px 0;
-        }}
-        .footer {{
-            margin-top: 30px;
-            padding-top: 20px;
-            border-top: 1px solid #e9ecef;
-            font-size: 14px;
-            color: #6c757d;
-            text-align: center;
-        }}
-        .warning {{
-            background: #fff3cd;
-            border: 1px solid #ffeaa7;
-            color: #856404;
-            padding: 15px;
-            border-radius: 5px;
-            margin: 20px 0;
-        }}
-        .urgent {{
-            background: #f8d7da;
-            border: 1px solid #f5c6cb;
-            color: #721c24;
-            padding: 15px;
-            border-radius: 5px;
-            margin: 20px 0;
-        }}
-    </style>
-</head>
-<body>
-    <div class="container">
-        <div class="header">
-            <span class="reminder-badge">📧 Reminder #{}</span>
-            <h1>⏰ Document Signature Reminder</h1>
-            <p>Hello <strong>{}</strong>,</p>
-        </div>
-
-        <div class="content">
-            <p>We noticed that you haven't completed signing the document <strong>"{}"</strong>.</p>
-
-            <div class="{}">
-                <strong>{}:</strong> {}
-            </div>
-
-            <p>Please click the button below to access and complete the document signing:</p>
-
-            <a href="{}" class="button">📝 Sign Document Now</a>
-
-            <p>If the button above doesn't work, you can copy and paste the following link into your browser:</p>
-            <p style="word-break: break-all; background: #f8f9fa; padding: 10px; border-radius: 5px; font-family: monospace;">{}</p>
-        </div>
-
-        <div class="footer">
-            <p>This is an automated reminder from the DocuSeal Pro system.</p>
-            <p>If you have already completed the signing, please ignore this email.</p>
-            <p>&copy; 2025 DocuSeal Pro. All rights reserved.</p>
-        </div>
-    </div>
-</body>
-</html>
-            "#,
-            reminder_number,
-            to_name,
-            submission_name,
-            if reminder_number >= 3 { "urgent" } else { "warning" },
-            if reminder_number >= 3 { "Final Reminder" } else { "Notice" },
-            if reminder_number >= 3 {
-                "This is your final reminder. Please complete the signing as soon as possible to avoid cancellation of the request."
-            } else {
-                "This signature link is only valid for a limited time. Please complete the signing as soon as possible."
-            },
+        let is_final = reminder_number >= 3;
+        let (notice_label, notice) = if is_final {
+            (self.t("email.reminder.final_label"), self.t("email.reminder.final_notice"))
+        } else {
+            (self.t("email.reminder.notice_label"), self.t("email.reminder.notice"))
+        };
+        let quoted_document = format!("\"{}\"", submission_name);
+        
+        let html_body = self.with_layout(format!(
+            r#"<p style="margin: 0 0 8px;"><span style="display: inline-block; padding: 4px 12px; background-color: #ff9800; color: #ffffff; border-radius: 16px; font-size: 13px; font-weight: bold;">{}</span></p>
+<h2 style="margin: 0 0 8px; color: #ff9800;">{}</h2>
+<p>{}</p>
+<p>{}</p>
+<div style="{}">
+    <strong>{}:</strong> {}
+</div>
+<p>{}</p>
+<p style="text-align: center; margin: 24px 0;"><a href="{}" style="display: inline-block; padding: 12px 24px; background-color: #ff9800; color: #ffffff; text-decoration: none; border-radius: 6px; font-weight: bold;">{}</a></p>
+<p>{}</p>
+<p style="word-break: break-all; background-color: #f8f9fa; padding: 10px; border-radius: 5px; font-family: monospace;">{}</p>
+<p style="font-size: 13px; color: #6b7280;">{}</p>"#,
+            self.t_args("email.reminder.badge", &[("number", &number)]),
+            self.t("email.reminder.title"),
+            self.t_args("email.greeting", &[("name", &format!("<strong>{}</strong>", escape_html(to_name)))]),
+            self.t_args("email.reminder.intro", &[("document", &format!("<strong>{}</strong>", escape_html(&quoted_document)))]),
+            if is_final { NOTICE_URGENT_STYLE } else { NOTICE_STYLE },
+            notice_label,
+            notice,
+            self.t("email.reminder.action"),
             signature_link,
-            signature_link
-        );
+            self.t("email.reminder.button"),
+            self.t("email.copy_link"),
+            signature_link,
+            self.t("email.reminder.ignore"),
+        ));
 
         let text_body = format!(
-            "Reminder #{} - Hello {},\n\n\
-            You haven't completed signing the document '{}'.\n\n\
-            {}.\n\n\
-            Please access the following link to sign the document:\n\
-            {}\n\n\
-            Best regards,\n\
-            DocuSeal Pro",
-            reminder_number,
-            to_name,
-            submission_name,
-            if reminder_number >= 3 {
-                "This is your final reminder"
-            } else {
-                "This link is only valid for a limited time"
-            },
-            signature_link
+            "{}\n\n{}\n\n{}\n\n{}\n\n{}\n{}\n\n{}\n{}",
+            self.t_args("email.reminder.badge", &[("number", &number)]),
+            self.t_args("email.greeting", &[("name", to_name)]),
+            self.t_args("email.reminder.intro", &[("document", &quoted_document)]),
+            notice,
+            self.t("email.reminder.action"),
+            signature_link,
+            self.t("email.regards"),
+            self.from_name,
         );
 
-        let email = Message::builder()
-            .from(format!("{} <{}>", self.from_name, self.from_email).parse()?)
+        let email = self.message_builder()?
             .to(format!("{} <{}>", to_name, to_email).parse()?)
             .subject(subject)
-            .multipart(
-                lettre::message::MultiPart::alternative()
-                    .singlepart(
-                        lettre::message::SinglePart::builder()
-                            .header(lettre::message::header::ContentType::parse("text/plain; charset=utf-8").unwrap())
-                            .body(text_body),
-                    )
-                    .singlepart(
-                        lettre::message::SinglePart::builder()
-                            .header(lettre::message::header::ContentType::parse("text/html; charset=utf-8").unwrap())
-                            .body(html_body),
-                    ),
-            )?;
-
-        let creds = Credentials::new(self.smtp_username.clone(), self.smtp_password.clone());
-
-        let mailer = if self.use_tls {
-            AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&self.smtp_host)?
-                .credentials(creds)
-                .build()
-        } else {
-            AsyncSmtpTransport::<Tokio1Executor>::relay(&self.smtp_host)?
-                .credentials(creds)
-                .build()
-        };
+            .multipart(alternative_body(text_body, html_body))?;
 
-        mailer.send(email).await?;
+        self.deliver("reminder", email).await?;
         println!("Reminder email #{} sent successfully to: {}", reminder_number, to_email);
 
         Ok(())
@@ -256,129 +227,45 @@ impl EmailService {
         to_name: &str,
         activation_link: &str,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
-        if self.test_mode {
-            println!("TEST MODE: Would send activation email to {} ({}) with link: {}", to_email, to_name, activation_link);
-            return Ok(());
-        }
-
-        let subject = "Activate Your DocuSeal Pro Account".to_string();
-
-        let html_body = format!(
-            r#"
-        <!DOCTYPE html>
-        <html lang="en">
-        <head>
-            <meta charset="UTF-8">
-            <meta name="viewport" content="width=device-width, initial-scale=1.0">
-            <title>Account Activation</title>
-            <style>
-                body {{
-                    font-family: 'Segoe UI', Tahoma, Geneva, Verdana, sans-serif;
-                    line-height: 1.6;
-                    color: #333;
-                    max-width: 600px;
-                    margin: 0 auto;
-                    background-color: #f8f9fa;
-                    padding: 20px;
-                }}
-                .container {{
-                    background: white;
-                    padding: 30px;
-                    border-radius: 10px;
-                    box-shadow: 0 2px 10px rgba(0,0,0,0.1);
-                }}
-                .header {{
-                    text-align: center;
-                    margin-bottom: 30px;
-                }}
-                .header h1 {{
-                    color: #007bff;
-                    margin-bottom: 10px;
-                }}
-                .content {{
-                    margin-bottom: 30px;
-                }}
-                .button {{
-                    display: inline-block;
-                    padding: 12px 24px;
-                    background-color: #007bff;
-                    color: white;
-                    text-decoration: none;
-                    border-radius: 5px;
-                    font-weight: bold;
-                }}
-                .footer {{
-                    margin-top: 30px;
-                    padding-top: 20px;
-                    border-top: 1px solid #eee;
-                    font-size: 12px;
-                    color: #666;
-                    text-align: center;
-                }}
-            </style>
-        </head>
-        <body>
-            <div class="container">
-                <div class="header">
-                    <h1>Welcome to DocuSeal Pro!</h1>
-                </div>
-                <div class="content">
-                    <p>Hello <strong>{}</strong>,</p>
-                    <p>Your account has been successfully created. To activate your account and start using DocuSeal Pro, please click the button below:</p>
-                    <p style="text-align: center;">
-                        <a href="{}" class="button">Activate Account</a>
-                    </p>
-                    <p>If the button doesn't work, you can copy and paste the following link into your browser:</p>
-                    <p><a href="{}">{}</a></p>
-                    <p>This link will expire after 24 hours.</p>
-                </div>
-                <div class="footer">
-                    <p>This email was sent automatically from the DocuSeal Pro system.</p>
-                    <p>If you do not wish to receive this email, please ignore it.</p>
-                </div>
-            </div>
-        </body>
-        </html>
-            "#,
-            to_name, activation_link, activation_link, activation_link
-        );
+        let account = self.from_name.as_str();
+        let subject = self.t_args("email.activation.subject", &[("account", account)]);
+        let account_html = escape_html(account);
+
+        let html_body = self.with_layout(format!(
+            r#"<h2 style="margin: 0 0 16px; color: #007bff;">{}</h2>
+<p>{}</p>
+<p>{}</p>
+<p style="text-align: center; margin: 24px 0;"><a href="{}" style="display: inline-block; padding: 12px 24px; background-color: #007bff; color: #ffffff; text-decoration: none; border-radius: 6px; font-weight: bold;">{}</a></p>
+<p>{}</p>
+<p style="word-break: break-all;"><a href="{}">{}</a></p>
+<p>{}</p>
+<p style="font-size: 13px; color: #6b7280;">{}</p>"#,
+            self.t_args("email.activation.title", &[("account", &account_html)]),
+            self.t_args("email.greeting", &[("name", &format!("<strong>{}</strong>", escape_html(to_name)))]),
+            self.t_args("email.activation.body", &[("account", &account_html)]),
+            activation_link,
+            self.t("email.activation.button"),
+            self.t("email.copy_link"),
+            activation_link, activation_link,
+            self.t("email.activation.expires"),
+            self.t("email.activation.ignore"),
+        ));
 
         let text_body = format!(
-            "Hello {},\n\nYour account has been created. To activate, visit: {}\n\nThe link expires after 24 hours.\n\nDocuSeal Pro",
-            to_name, activation_link
+            "{}\n\n{}\n{}\n\n{}\n\n{}",
+            self.t_args("email.greeting", &[("name", to_name)]),
+            self.t_args("email.activation.body", &[("account", account)]),
+            activation_link,
+            self.t("email.activation.expires"),
+            account,
         );
 
-        let email = Message::builder()
-            .from(format!("{} <{}>", self.from_name, self.from_email).parse()?)
+        let email = self.message_builder()?
             .to(format!("{} <{}>", to_name, to_email).parse()?)
             .subject(subject)
-            .multipart(
-                lettre::message::MultiPart::alternative()
-                    .singlepart(
-                        lettre::message::SinglePart::builder()
-                            .header(lettre::message::header::ContentType::parse("text/plain; charset=utf-8").unwrap())
-                            .body(text_body),
-                    )
-                    .singlepart(
-                        lettre::message::SinglePart::builder()
-                            .header(lettre::message::header::ContentType::parse("text/html; charset=utf-8").unwrap())
-                            .body(html_body),
-                    ),
-            )?;
-
-        let creds = Credentials::new(self.smtp_username.clone(), self.smtp_password.clone());
-
-        let mailer = if self.use_tls {
-            AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&self.smtp_host)?
-                .credentials(creds)
-                .build()
-        } else {
-            AsyncSmtpTransport::<Tokio1Executor>::relay(&self.smtp_host)?
-                .credentials(creds)
-                .build()
-        };
+            .multipart(alternative_body(text_body, html_body))?;
 
-        mailer.send(email).await?;
+        self.deliver("activation", email).await?;
         println!("Activation email sent successfully to: {}", to_email);
 
         Ok(())
@@ -392,152 +279,65 @@ impl EmailService {
         account_name: &str,
         invitation_link: &str,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
-        if self.test_mode {
-            println!("TEST MODE: Would send team invitation email to {} ({}) with link: {}", to_email, to_name, invitation_link);
-            return Ok(());
-        }
-
-        let subject = format!("{} invited you to join their team on DocuSeal Pro", invited_by);
-
-        let html_body = format!(
-            r#"
-        <!DOCTYPE html>
-        <html lang="en">
-        <head>
-            <meta charset="UTF-8">
-            <meta name="viewport" content="width=device-width, initial-scale=1.0">
-            <title>Team Invitation</title>
-            <style>
-                body {{
-                    font-family: 'Segoe UI', Tahoma, Geneva, Verdana, sans-serif;
-                    line-height: 1.6;
-                    color: #333;
-                    max-width: 600px;
-                    margin: 0 auto;
-                    background-color: #f8f9fa;
-                    padding: 20px;
-                }}
-                .container {{
-                    background: white;
-                    padding: 30px;
-                    border-radius: 10px;
-                    box-shadow: 0 2px 10px rgba(0,0,0,0.1);
-                }}
-                .header {{
-                    text-align: center;
-                    margin-bottom: 30px;
-                }}
-                .header h1 {{
-                    color: #4F46E5;
-                    margin-bottom: 10px;
-                }}
-                .content {{
-                    margin-bottom: 30px;
-                }}
-                .button {{
-                    display: inline-block;
-                    padding: 12px 24px;
-                    background: linear-gradient(135deg, #4F46E5 0%, #7C3AED 100%);
-                    color: white;
-                    text-decoration: none;
-                    border-radius: 5px;
-                    font-weight: bold;
-                }}
-                .info-box {{
-                    background-color: #f0f9ff;
-                    border-left: 4px solid #4F46E5;
-                    padding: 15px;
-                    margin: 20px 0;
-                }}
-                .footer {{
-                    margin-top: 30px;
-                    padding-top: 20px;
-                    border-top: 1px solid #eee;
-                    font-size: 12px;
-                    color: #666;
-                    text-align: center;
-                }}
-            </style>
-        </head>
-        <body>
-            <div class="container">
-                <div class="header">
-                    <h1>You've Been Invited!</h1>
-                </div>
-                <div class="content">
-                    <p>Hello <strong>{}</strong>,</p>
-                    <p><strong>{}</strong> has invited you to join their team on DocuSeal Pro.</p>
-                    
-                    <div class="info-box">
-                        <p><strong>Account:</strong> {}</p>
-                        <p><strong>Invited by:</strong> {}</p>
-                    </div>
-                    
-                    <p>As a team member, you'll be able to:</p>
-                    <ul>
-                        <li>Create and manage templates</li>
-                        <li>Send documents for signature</li>
-                        <li>Track submission status</li>
-                        <li>Collaborate with other team members</li>
-                    </ul>
-                    
-                    <p style="text-align: center; margin: 30px 0;">
-                        <a href="{}" class="button">Accept Invitation</a>
-                    </p>
-                    
-                    <p>If the button doesn't work, you can copy and paste the following link into your browser:</p>
-                    <p style="word-break: break-all;"><a href="{}">{}</a></p>
-                    
-                    <p style="color: #666; font-size: 14px;">This invitation will expire in 7 days.</p>
-                </div>
-                <div class="footer">
-                    <p>This email was sent automatically from DocuSeal Pro.</p>
-                    <p>If you didn't expect this invitation, you can safely ignore this email.</p>
-                </div>
-            </div>
-        </body>
-        </html>
-            "#,
-            to_name, invited_by, account_name, invited_by, 
-            invitation_link, invitation_link, invitation_link
-        );
+        let product = self.from_name.as_str();
+        let subject = self.t_args("email.team_invitation.subject", &[("inviter", invited_by), ("account", product)]);
+        let product_html = escape_html(product);
+        let invited_by_html = escape_html(invited_by);
+
+        let html_body = self.with_layout(format!(
+            r#"<h2 style="margin: 0 0 16px; color: #4f46e5;">{}</h2>
+<p>{}</p>
+<p>{}</p>
+<div style="background-color: #f0f9ff; border-left: 4px solid #4f46e5; padding: 12px 16px; margin: 20px 0;">
+    <p style="margin: 0;"><strong>{}:</strong> {}</p>
+    <p style="margin: 0;"><strong>{}:</strong> {}</p>
+</div>
+<p>{}</p>
+<ul>
+    <li>{}</li>
+    <li>{}</li>
+    <li>{}</li>
+    <li>{}</li>
+</ul>
+<p style="text-align: center; margin: 24px 0;"><a href="{}" style="display: inline-block; padding: 12px 24px; background-color: #4f46e5; color: #ffffff; text-decoration: none; border-radius: 6px; font-weight: bold;">{}</a></p>
+<p>{}</p>
+<p style="word-break: break-all;"><a href="{}">{}</a></p>
+<p>{}</p>
+<p style="font-size: 13px; color: #6b7280;">{}</p>"#,
+            self.t("email.team_invitation.title"),
+            self.t_args("email.greeting", &[("name", &format!("<strong>{}</strong>", escape_html(to_name)))]),
+            self.t_args("email.team_invitation.body", &[("inviter", &format!("<strong>{}</strong>", invited_by_html)), ("account", &product_html)]),
+            self.t("email.team_invitation.account_label"), escape_html(account_name),
+            self.t("email.team_invitation.invited_by_label"), invited_by_html,
+            self.t("email.team_invitation.benefits"),
+            self.t("email.team_invitation.benefit_templates"),
+            self.t("email.team_invitation.benefit_send"),
+            self.t("email.team_invitation.benefit_track"),
+            self.t("email.team_invitation.benefit_collaborate"),
+            invitation_link,
+            self.t("email.team_invitation.button"),
+            self.t("email.copy_link"),
+            invitation_link, invitation_link,
+            self.t("email.team_invitation.expires"),
+            self.t("email.team_invitation.ignore"),
+        ));
 
         let text_body = format!(
-            "Hello {},\n\n{} has invited you to join their team '{}' on DocuSeal Pro.\n\nAccept invitation: {}\n\nThis invitation expires in 7 days.\n\nDocuSeal Pro",
-            to_name, invited_by, account_name, invitation_link
+            "{}\n\n{}\n{}: {}\n\n{}\n\n{}\n\n{}",
+            self.t_args("email.greeting", &[("name", to_name)]),
+            self.t_args("email.team_invitation.body", &[("inviter", invited_by), ("account", product)]),
+            self.t("email.team_invitation.account_label"), account_name,
+            invitation_link,
+            self.t("email.team_invitation.expires"),
+            product,
         );
 
-        let email = Message::builder()
-            .from(format!("{} <{}>", self.from_name, self.from_email).parse()?)
+        let email = self.message_builder()?
             .to(format!("{} <{}>", to_name, to_email).parse()?)
             .subject(subject)
-            .multipart(
-                lettre::message::MultiPart::alternative()
-                    .singlepart(
-                        lettre::message::SinglePart::builder()
-                            .header(lettre::message::header::ContentType::parse("text/plain; charset=utf-8").unwrap())
-                            .body(text_body),
-                    )
-                    .singlepart(
-                        lettre::message::SinglePart::builder()
-                            .header(lettre::message::header::ContentType::parse("text/html; charset=utf-8").unwrap())
-                            .body(html_body),
-                    ),
-            )?;
-
-        let creds = Credentials::new(self.smtp_username.clone(), self.smtp_password.clone());
-
-        let mailer = if self.use_tls {
-            AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&self.smtp_host)?
-                .credentials(creds)
-                .build()
-        } else {
-            AsyncSmtpTransport::<Tokio1Executor>::relay(&self.smtp_host)?
-                .credentials(creds)
-                .build()
-        };
+            .multipart(alternative_body(text_body, html_body))?;
 
-        mailer.send(email).await?;
+        self.deliver("team_invitation", email).await?;
         println!("Team invitation email sent successfully to: {}", to_email);
 
         Ok(())
@@ -551,141 +351,44 @@ impl EmailService {
         submitter_name: &str,
         token: &str,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
-        if self.test_mode {
-            println!("TEST MODE: Would send completion email to {} ({}) for submission: {}", to_email, to_name, submission_name);
-            return Ok(());
-        }
-
-        let subject = format!("Document Signing Completed: {}", submission_name);
+        let subject = self.t_args("email.completed.subject", &[("document", submission_name)]);
         let base_url = std::env::var("BASE_URL").unwrap_or_else(|_| "http://localhost:8081".to_string());
         let link = format!("{}/signed-submission/{}", base_url, token);
-
-        let html_body = format!(
-            r#"
-                <!DOCTYPE html>
-                <html lang="en">
-                <head>
-                    <meta charset="UTF-8">
-                    <meta name="viewport" content="width=device-width, initial-scale=1.0">
-                    <title>Document Signing Completed</title>
-                    <style>
-                        body {{
-                            font-family: 'Segoe UI', Tahoma, Geneva, Verdana, sans-serif;
-                            line-height: 1.6;
-                            color: #333;
-                            max-width: 600px;
-                            margin: 0 auto;
-                            background-color: #f8f9fa;
-                            padding: 20px;
-                        }}
-                        .container {{
-                            background: white;
-                            padding: 30px;
-                            border-radius: 10px;
-                            box-shadow: 0 2px 10px rgba(0,0,0,0.1);
-                        }}
-                        .header {{
-                            text-align: center;
-                            margin-bottom: 30px;
-                        }}
-                        .header h1 {{
-                            color: #28a745;
-                            margin-bottom: 10px;
-                        }}
-                        .success-icon {{
-                            font-size: 48px;
-                            color: #28a745;
-                            margin-bottom: 20px;
-                        }}
-                        .content {{
-                            margin-bottom: 30px;
-                        }}
-                        .footer {{
-                            margin-top: 30px;
-                            padding-top: 20px;
-                            border-top: 1px solid #e9ecef;
-                            font-size: 14px;
-                            color: #6c757d;
-                            text-align: center;
-                        }}
-                        a {{
-                            color: #007bff;
-                            text-decoration: none;
-                        }}
-                        a:hover {{
-                            text-decoration: underline;
-                        }}
-                    </style>
-                </head>
-                <body>
-                    <div class="container">
-                        <div class="header">
-                            <div class="success-icon">✅</div>
-                            <h1>Document Signing Completed</h1>
-                            <p>Hello <strong>{}</strong>,</p>
-                        </div>
-
-                        <div class="content">
-                            <p>We are pleased to inform you that the document <strong><a href="{}">"{}"</a></strong> has been successfully signed by <strong>{}</strong>.</p>
-
-                            <p>The document has been processed and stored securely in the DocuSeal Pro system.</p>
-
-                            <p>Thank you for using our service!</p>
-                        </div>
-
-                        <div class="footer">
-                            <p>This email was sent automatically from the DocuSeal Pro system.</p>
-                            <p>&copy; 2025 DocuSeal Pro. All rights reserved.</p>
-                        </div>
-                    </div>
-                </body>
-                </html>
-            "#,
-            to_name, link, submission_name, submitter_name
-        );
+        let quoted_document = format!("\"{}\"", submission_name);
+
+        let html_body = self.with_layout(format!(
+            r#"<h2 style="margin: 0 0 16px; color: #28a745;">{}</h2>
+<p>{}</p>
+<p>{}</p>
+<p>{}</p>
+<p>{}</p>"#,
+            self.t("email.completed.title"),
+            self.t_args("email.greeting", &[("name", &format!("<strong>{}</strong>", escape_html(to_name)))]),
+            self.t_args("email.completed.body", &[
+                ("document", &format!("<strong><a href=\"{}\">{}</a></strong>", link, escape_html(&quoted_document))),
+                ("signer", &format!("<strong>{}</strong>", escape_html(submitter_name))),
+            ]),
+            self.t("email.completed.stored"),
+            self.t("email.completed.thanks"),
+        ));
 
         let text_body = format!(
-            "Hello {},\n\n\
-            The document '{}' has been successfully signed by {}.\n\n\
-            You can view the signed document here: {}\n\n\
-            The document has been stored securely in the system.\n\n\
-            Thank you for using DocuSeal Pro!\n\n\
-            Best regards,\n\
-            DocuSeal Pro",
-            to_name, submission_name, submitter_name, link
+            "{}\n\n{}\n\n{}\n\n{}\n\n{}\n\n{}\n{}",
+            self.t_args("email.greeting", &[("name", to_name)]),
+            self.t_args("email.completed.body", &[("document", &quoted_document), ("signer", submitter_name)]),
+            self.t_args("email.completed.view", &[("link", &link)]),
+            self.t("email.completed.stored"),
+            self.t("email.completed.thanks"),
+            self.t("email.regards"),
+            self.from_name,
         );
 
-        let email = Message::builder()
-            .from(format!("{} <{}>", self.from_name, self.from_email).parse()?)
+        let email = self.message_builder()?
             .to(format!("{} <{}>", to_name, to_email).parse()?)
             .subject(subject)
-            .multipart(
-                lettre::message::MultiPart::alternative()
-                    .singlepart(
-                        lettre::message::SinglePart::builder()
-                            .header(lettre::message::header::ContentType::parse("text/plain; charset=utf-8").unwrap())
-                            .body(text_body),
-                    )
-                    .singlepart(
-                        lettre::message::SinglePart::builder()
-                            .header(lettre::message::header::ContentType::parse("text/html; charset=utf-8").unwrap())
-                            .body(html_body),
-                    ),
-            )?;
-
-        let creds = Credentials::new(self.smtp_username.clone(), self.smtp_password.clone());
-
-        let mailer = if self.use_tls {
-            AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&self.smtp_host)?
-                .credentials(creds)
-                .build()
-        } else {
-            AsyncSmtpTransport::<Tokio1Executor>::relay(&self.smtp_host)?
-                .credentials(creds)
-                .build()
-        };
+            .multipart(alternative_body(text_body, html_body))?;
 
-        mailer.send(email).await?;
+        self.deliver("completion", email).await?;
         println!("Completion email sent successfully to: {}", to_email);
 
         Ok(())
@@ -697,67 +400,43 @@ impl EmailService {
         to_name: &str,
         reset_code: &str,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
-        if self.test_mode {
-            println!("TEST MODE: Would send password reset code '{}' to {} ({})", reset_code, to_email, to_name);
-            return Ok(());
-        }
-
-        let subject = "Password Reset Code - DocuSeal Pro";
-        let html_body = format!(
-            r#"
-            <html>
-            <body>
-                <h2>Password Reset Request</h2>
-                <p>Hello {},</p>
-                <p>You have requested to reset your password for your DocuSeal Pro account.</p>
-                <p>Your password reset code is:</p>
-                <h1 style="color: #007bff; font-size: 32px; letter-spacing: 5px;">{}</h1>
-                <p>This code will expire in 3 minutes.</p>
-                <p>If you didn't request this password reset, please ignore this email.</p>
-                <p>Best regards,<br>DocuSeal Pro Team</p>
-            </body>
-            </html>
-            "#,
-            to_name, reset_code
-        );
+        let account = self.from_name.as_str();
+        let subject = self.t_args("email.password_reset.subject", &[("account", account)]);
+        let html_body = self.with_layout(format!(
+            r#"<h2 style="margin: 0 0 16px;">{}</h2>
+<p>{}</p>
+<p>{}</p>
+<p>{}</p>
+<p style="color: #007bff; font-size: 32px; font-weight: bold; letter-spacing: 5px;">{}</p>
+<p>{}</p>
+<p>{}</p>
+<p>{}<br>{}</p>"#,
+            self.t("email.password_reset.title"),
+            self.t_args("email.greeting", &[("name", &escape_html(to_name))]),
+            self.t_args("email.password_reset.body", &[("account", &escape_html(account))]),
+            self.t("email.password_reset.code"),
+            reset_code,
+            self.t("email.password_reset.expires"),
+            self.t("email.password_reset.ignore"),
+            self.t("email.regards"), escape_html(account),
+        ));
 
         let text_body = format!(
-            "Hello {},\n\nYou have requested to reset your password for your DocuSeal Pro account.\n\nYour password reset code is: {}\n\nThis code will expire in 3 minutes.\n\nIf you didn't request this password reset, please ignore this email.\n\nBest regards,\nDocuSeal Pro Team",
-            to_name, reset_code
+            "{}\n\n{}\n\n{} {}\n\n{}\n\n{}\n\n{}\n{}",
+            self.t_args("email.greeting", &[("name", to_name)]),
+            self.t_args("email.password_reset.body", &[("account", account)]),
+            self.t("email.password_reset.code"), reset_code,
+            self.t("email.password_reset.expires"),
+            self.t("email.password_reset.ignore"),
+            self.t("email.regards"), account,
         );
 
-        let email = Message::builder()
-            .from(format!("{} <{}>", self.from_name, self.from_email).parse()?)
+        let email = self.message_builder()?
             .to(format!("{} <{}>", to_name, to_email).parse()?)
             .subject(subject)
-            .multipart(
-                lettre::message::MultiPart::alternative()
-                    .singlepart(
-                        lettre::message::SinglePart::builder()
-                            .header(lettre::message::header::ContentType::parse("text/plain; charset=utf-8").unwrap())
-                            .body(text_body),
-                    )
-                    .singlepart(
-                        lettre::message::SinglePart::builder()
-                            .header(lettre::message::header::ContentType::parse("text/html; charset=utf-8").unwrap())
-                            .body(html_body),
-                    ),
-            )?;
-
-        let creds = Credentials::new(self.smtp_username.clone(), self.smtp_password.clone());
-
-        let mailer = if self.use_tls {
-            AsyncSmtpTransport::<Tokio1Executor>::relay(&self.smtp_host)?
-                .credentials(creds)
-                .build()
-        } else {
-            AsyncSmtpTransport::<Tokio1Executor>::relay(&self.smtp_host)?
-                .credentials(creds)
-                .port(self.smtp_port)
-                .build()
-        };
+            .multipart(alternative_body(text_body, html_body))?;
 
-        mailer.send(email).await?;
+        self.deliver("password_reset", email).await?;
         println!("Password reset code sent successfully to: {}", to_email);
 
         Ok(())
@@ -770,136 +449,58 @@ impl EmailService {
         progress: &str,
         signers: &str,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
-        if self.test_mode {
-            println!("TEST MODE: Would send completion notification to {} for submission: {}", to_email, submission_name);
-            return Ok(());
-        }
-
         println!("Attempting to send completion notification email to: {}", to_email);
-        println!("SMTP Host: {}, Port: {}, Username: {}", self.smtp_host, self.smtp_port, self.smtp_username);
-
-        let subject = format!("Document '{}' - Signature Progress Update", submission_name);
-
-        let html_body = format!(
-            r#"
-<!DOCTYPE html>
-<html lang="en">
-<head>
-    <meta charset="UTF-8">
-    <meta name="viewport" content="width=device-width, initial-scale=1.0">
-    <title>Document Signature Progress</title>
-    <style>
-        body {{
-            font-family: 'Segoe UI', Tahoma, Geneva, Verdana, sans-serif;
-            line-height: 1.6;
-            color: #333;
-            max-width: 600px;
-            margin: 0 auto;
-            padding: 20px;
-        }}
-        .header {{
-            background-color: #f8f9fa;
-            padding: 20px;
-            border-radius: 8px;
-            margin-bottom: 20px;
-        }}
-        .content {{
-            background-color: #ffffff;
-            padding: 20px;
-            border: 1px solid #e9ecef;
-            border-radius: 8px;
-        }}
-        .progress {{
-            background-color: #e9ecef;
-            border-radius: 20px;
-            height: 20px;
-            margin: 10px 0;
-        }}
-        .progress-bar {{
-            background-color: #007bff;
-            height: 100%;
-            border-radius: 20px;
-            transition: width 0.3s ease;
-        }}
-        .footer {{
-            margin-top: 20px;
-            padding-top: 20px;
-            border-top: 1px solid #e9ecef;
-            font-size: 12px;
-            color: #6c757d;
-        }}
-    </style>
-</head>
-<body>
-    <div class="header">
-        <h2>Document Signature Progress Update</h2>
-    </div>
-    <div class="content">
-        <p>Hi there,</p>
-        <p>There's an update on document "<strong>{}</strong>".</p>
-        <p><strong>Progress: {}</strong></p>
-        <p><strong>Completed Signers: {}</strong></p>
-        <p>You will receive another notification when the document is fully completed by all signers.</p>
-        <div class="footer">
-            <p>This is an automated notification from DocuSeal.</p>
-        </div>
-    </div>
-</body>
-</html>
-            "#,
-            submission_name, progress, signers
-        );
 
-        let email = Message::builder()
-            .from(self.from_email.parse()?)
+        let subject = self.t_args("email.progress.subject", &[("document", submission_name)]);
+
+        let html_body = self.with_layout(format!(
+            r#"<h2 style="margin: 0 0 16px;">{}</h2>
+<p>{}</p>
+<p>{}</p>
+<p><strong>{}</strong></p>
+<p><strong>{}</strong></p>
+<p>{}</p>"#,
+            self.t("email.progress.title"),
+            self.t("email.progress.hi"),
+            self.t_args("email.progress.body", &[("document", &format!("\"<strong>{}</strong>\"", escape_html(submission_name)))]),
+            self.t_args("email.progress.progress", &[("progress", &escape_html(progress))]),
+            self.t_args("email.progress.signers", &[("signers", &escape_html(signers))]),
+            self.t("email.progress.next"),
+        ));
+
+        let email = self.message_builder()?
             .to(to_email.parse()?)
             .subject(subject)
             .header(lettre::message::header::ContentType::parse("text/html; charset=utf-8").unwrap())
             .body(html_body)?;
 
-        let mailer = if self.smtp_host == "localhost" {
-            AsyncSmtpTransport::<Tokio1Executor>::unencrypted_localhost()
-        } else {
-            let creds = Credentials::new(self.smtp_username.clone(), self.smtp_password.clone());
-
-            // Try STARTTLS first (port 587), fallback to SSL/TLS (port 465) if it fails
-            let transport_result = if self.smtp_port == 587 {
-                println!("Trying STARTTLS on port 587...");
-                AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&self.smtp_host)
-                    .map(|t| t.credentials(creds.clone()).port(self.smtp_port).build())
-                    .or_else(|_| {
-                        println!("STARTTLS failed, trying SSL/TLS on port 465...");
-                        AsyncSmtpTransport::<Tokio1Executor>::relay(&self.smtp_host)
-                            .map(|t| t.credentials(creds).port(465).build())
-                    })
-            } else {
-                println!("Using direct relay on port {}...", self.smtp_port);
-                AsyncSmtpTransport::<Tokio1Executor>::relay(&self.smtp_host)
-                    .map(|t| t.credentials(creds).port(self.smtp_port).build())
-            };
-
-            match transport_result {
-                Ok(mailer) => {
-                    println!("SMTP transport created successfully");
-                    mailer
-                }
-                Err(e) => {
-                    eprintln!("Failed to create SMTP transport: {}", e);
-                    return Err(Box::new(e));
-                }
-            }
-        };
+        self.deliver("progress_notification", email).await?;
+        println!("Completion notification sent successfully to: {}", to_email);
 
-        match mailer.send(email).await {
-            Ok(_) => {
-                println!("Completion notification sent successfully to: {}", to_email);
-                Ok(())
-            }
-            Err(e) => {
-                eprintln!("Failed to send email via SMTP: {}", e);
-                Err(Box::new(e))
-            }
-        }
+        Ok(())
+    }
+
+    pub async fn send_submission_expired(
+        &self,
+        to_email: &str,
+        to_name: &str,
+        submission_name: &str,
+        unsigned_signers: &str,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+        let subject = self.t_args("email.expired.subject", &[("document", submission_name)]);
+        let body = self.with_layout(format!(
+            r#"<p>{}</p>
+<p>{}</p>
+<p><strong>{}</strong> {}</p>
+<p>{}</p>"#,
+            self.t_args("email.greeting", &[("name", &escape_html(to_name))]),
+            self.t_args("email.expired.body", &[("document", &format!("\"<strong>{}</strong>\"", escape_html(submission_name)))]),
+            self.t("email.expired.not_signed"),
+            escape_html(unsigned_signers),
+            self.t("email.expired.extend"),
+        ));
+
+        self.send_template_email(to_email, to_name, &subject, &body, "html", false, false, None, None).await
     }
 
     pub async fn send_template_email(
@@ -914,13 +515,7 @@ impl EmailService {
         document_path: Option<&str>,
         audit_log_path: Option<&str>,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
-        if self.test_mode {
-            println!("TEST MODE: Would send template email to {} ({}) with subject: {}", to_email, to_name, subject);
-            return Ok(());
-        }
-
-        let mut email_builder = Message::builder()
-            .from(format!("{} <{}>", self.from_name, self.from_email).parse()?)
+        let mut email_builder = self.message_builder()?
             .to(format!("{} <{}>", to_name, to_email).parse()?)
             .subject(subject.to_string());
 
@@ -969,21 +564,74 @@ impl EmailService {
         let multipart = multipart_builder;
         let email = email_builder.multipart(multipart)?;
 
-        let creds = Credentials::new(self.smtp_username.clone(), self.smtp_password.clone());
-
-        let mailer = if self.use_tls {
-            AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&self.smtp_host)?
-                .credentials(creds)
-                .build()
-        } else {
-            AsyncSmtpTransport::<Tokio1Executor>::relay(&self.smtp_host)?
-                .credentials(creds)
-                .build()
-        };
-
-        mailer.send(email).await?;
+        self.deliver("notification", email).await?;
         println!("Template email sent successfully to: {}", to_email);
 
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+// Plain text and html versions of the same message
+fn alternative_body(text_body: String, html_body: String) -> MultiPart {
+    MultiPart::alternative()
+        .singlepart(
+            SinglePart::builder()
+                .header(lettre::message::header::ContentType::parse("text/plain; charset=utf-8").unwrap())
+                .body(text_body),
+        )
+        .singlepart(
+            SinglePart::builder()
+                .header(lettre::message::header::ContentType::parse("text/html; charset=utf-8").unwrap())
+                .body(html_body),
+        )
+}
+
+/// Send a message from the outbox again, exactly as it was first rendered,
+/// through the current transport. Returns the row with the new attempt recorded.
+pub async fn resend_outbox_email(pool: &DbPool, email: &DbOutboxEmail) -> Result<DbOutboxEmail, String> {
+    let raw_message = email.raw_message.as_deref()
+        .ok_or_else(|| "The message is no longer kept and cannot be resent".to_string())?;
+    let transport = email_transport::default_transport()?;
+    let envelope = Envelope::new(
+        Some(email.from_email.parse().map_err(|e| format!("Invalid sender address '{}': {}", email.from_email, e))?),
+        vec![email.to_email.parse().map_err(|e| format!("Invalid recipient address '{}': {}", email.to_email, e))?],
+    ).map_err(|e| format!("Invalid envelope: {}", e))?;
+
+    let result = transport.send(&envelope, raw_message).await;
+    let updated = EmailOutboxQueries::record_attempt(pool, email.id, transport.name(), result.as_ref().err().map(String::as_str)).await
+        .map_err(|e| format!("Failed to record delivery: {}", e))?
+        .ok_or_else(|| format!("Outbox email {} no longer exists", email.id))?;
+    result.map(|_| updated)
+}
+
+/// Kinds of email that carry a password reset code or an account link, which are
+/// never stored and cannot be resent
+const CREDENTIAL_KINDS: &[&str] = &["password_reset", "activation", "team_invitation"];
+
+pub fn is_credential_kind(kind: &str) -> bool {
+    CREDENTIAL_KINDS.contains(&kind)
+}
+
+const DEFAULT_EMAIL_BODY_RETENTION_DAYS: i64 = 30;
+const PURGE_INTERVAL_HOURS: i64 = 24;
+
+/// Days a sent message is kept for resending, from EMAIL_BODY_RETENTION_DAYS
+fn email_body_retention_days() -> i64 {
+    env::var("EMAIL_BODY_RETENTION_DAYS").ok()
+        .and_then(|days| days.parse::<i64>().ok())
+        .filter(|days| *days > 0)
+        .unwrap_or(DEFAULT_EMAIL_BODY_RETENTION_DAYS)
+}
+
+/// Job handler for `purge_email_bodies`: drops outbox messages past retention, then runs again a day later
+pub fn purge_email_bodies_job(pool: DbPool, _payload: serde_json::Value) -> BoxFuture<'static, Result<JobOutcome, String>> {
+    Box::pin(async move {
+        let before = Utc::now() - chrono::Duration::days(email_body_retention_days());
+        let purged = EmailOutboxQueries::purge_raw_messages(&pool, before).await
+            .map_err(|e| format!("Failed to purge outbox messages: {}", e))?;
+        if purged > 0 {
+            println!("🧹 Dropped the body of {} outbox email(s) sent before {}", purged, before);
+        }
+        Ok(JobOutcome::RunAt(Utc::now() + chrono::Duration::hours(PURGE_INTERVAL_HOURS)))
+    })
+}
@@ -0,0 +1,277 @@
+use chrono::FixedOffset;
+use lettre::message::Mailbox;
+
+use crate::database::connection::DbPool;
+use crate::database::models::{DbAccount, DbEmailTemplate, DbGlobalSettings, DbSubmitter};
+use crate::database::queries::{AccountQueries, GlobalSettingsQueries, UserQueries};
+use crate::services::email_template_engine::{self, Template, TemplateContext};
+use crate::services::{i18n, timezone};
+
+/// Name used for users without an account that have not set a company name
+pub const DEFAULT_ACCOUNT_NAME: &str = "DocuSeal Pro";
+
+/// Variables an email layout can use
+const LAYOUT_VARIABLES: &[&str] = &["content", "account.name", "account.logo_url", "layout.footer", "now"];
+
+/// Wraps html email templates of accounts without a layout of their own
+pub const DEFAULT_LAYOUT: &str = r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+</head>
+<body style="margin: 0; padding: 0; background-color: #f4f5f7; font-family: Arial, Helvetica, sans-serif; color: #333333;">
+<table role="presentation" width="100%" cellpadding="0" cellspacing="0" style="background-color: #f4f5f7; padding: 24px 0;">
+<tr><td align="center">
+<table role="presentation" width="600" cellpadding="0" cellspacing="0" style="max-width: 600px; width: 100%; background-color: #ffffff; border-radius: 8px;">
+<tr><td style="padding: 24px 32px; border-bottom: 1px solid #e5e7eb;">
+{{#if account.logo_url}}<img src="{{account.logo_url}}" alt="{{account.name}}" style="max-height: 48px; max-width: 200px;">{{else}}<strong style="font-size: 20px;">{{account.name}}</strong>{{/if}}
+</td></tr>
+<tr><td style="padding: 32px; font-size: 15px; line-height: 1.6;">
+{{content}}
+</td></tr>
+<tr><td style="padding: 16px 32px; border-top: 1px solid #e5e7eb; font-size: 12px; color: #6b7280;">
+{{layout.footer}}
+</td></tr>
+</table>
+</td></tr>
+</table>
+</body>
+</html>"#;
+
+/// How an account's emails look and who they come from, taken from its settings
+#[derive(Debug, Clone)]
+pub struct EmailBranding {
+    pub account_name: String,
+    /// Sender display name; the address itself is always FROM_EMAIL
+    pub from_name: Option<String>,
+    pub reply_to: Option<Mailbox>,
+    /// Absolute URL, so it loads inside a mail client
+    pub logo_url: Option<String>,
+    pub layout: Option<String>,
+    pub utc_offset: FixedOffset,
+    /// Language of the built-in email texts and dates
+    pub locale: &'static str,
+}
+
+impl Default for EmailBranding {
+    fn default() -> Self {
+        Self::from_settings(None, None)
+    }
+}
+
+fn non_empty(value: &Option<String>) -> Option<String> {
+    value.as_deref().map(str::trim).filter(|v| !v.is_empty()).map(str::to_string)
+}
+
+impl EmailBranding {
+    /// The company name in the settings wins over the account's own name
+    pub fn from_settings(settings: Option<&DbGlobalSettings>, account: Option<&DbAccount>) -> Self {
+        let company_name = settings.and_then(|s| non_empty(&s.company_name))
+            .or_else(|| account.map(|a| a.name.trim()).filter(|name| !name.is_empty()).map(str::to_string));
+        let logo_url = settings.and_then(|s| non_empty(&s.logo_url)).map(|url| {
+            if url.starts_with("http://") || url.starts_with("https://") {
+                url
+            } else {
+                let base_url = std::env::var("BASE_URL").unwrap_or_else(|_| "http://localhost:8081".to_string());
+                format!("{}/{}", base_url.trim_end_matches('/'), url.trim_start_matches('/'))
+            }
+        });
+        // Settings are checked when saved; anything unusable here is ignored rather than failing emails
+        let reply_to = settings.and_then(|s| non_empty(&s.email_reply_to)).and_then(|r| r.parse().ok());
+
+        Self {
+            from_name: settings.and_then(|s| non_empty(&s.email_from_name)).or_else(|| company_name.clone()),
+            account_name: company_name.unwrap_or_else(|| DEFAULT_ACCOUNT_NAME.to_string()),
+            reply_to,
+            logo_url,
+            layout: settings.and_then(|s| non_empty(&s.email_layout)),
+            utc_offset: timezone::utc_offset(settings.and_then(|s| s.timezone.as_deref())),
+            locale: i18n::resolve_locale([settings.and_then(|s| s.locale.as_deref())]),
+        }
+    }
+
+    /// Write to a submitter in the language they were sent the submission in, if one was chosen
+    pub fn for_recipient(mut self, submitter: &DbSubmitter) -> Self {
+        self.locale = i18n::supported_locale(submitter.locale.as_deref()).unwrap_or(self.locale);
+        self
+    }
+
+    /// Branding of the account a user sends from. A failed lookup falls back to the
+    /// defaults so the email still goes out.
+    pub async fn for_user(pool: &DbPool, user_id: i64) -> Self {
+        let account = match UserQueries::get_user_by_id(pool, user_id).await {
+            Ok(user) => match user.and_then(|u| u.account_id) {
+                Some(account_id) => AccountQueries::get_account_by_id(pool, account_id).await.unwrap_or_else(|e| {
+                    eprintln!("Failed to get account {}: {}", account_id, e);
+                    None
+                }),
+                None => None,
+            },
+            Err(e) => {
+                eprintln!("Failed to get user {}: {}", user_id, e);
+                None
+            }
+        };
+        match GlobalSettingsQueries::get_user_settings(pool, user_id as i32).await {
+            Ok(settings) => Self::from_settings(settings.as_ref(), account.as_ref()),
+            Err(e) => {
+                eprintln!("Failed to get settings of user {}: {}", user_id, e);
+                Self::default()
+            }
+        }
+    }
+
+    /// Context with the account variables set, dates in the account timezone
+    pub fn context(&self) -> TemplateContext {
+        let mut context = TemplateContext::new(self.utc_offset);
+        context
+            .set_locale(self.locale)
+            .insert("account.name", self.account_name.as_str())
+            .insert("layout.footer", i18n::t_args(self.locale, "email.sent_on_behalf", &[("account", &self.account_name)]));
+        if let Some(logo_url) = &self.logo_url {
+            context.insert("account.logo_url", logo_url.as_str());
+        }
+        context
+    }
+
+    /// Render an email template and wrap html bodies in the account layout
+    pub fn render(&self, email_template: &DbEmailTemplate, context: &TemplateContext) -> Result<(String, String), String> {
+        let (subject, body) = email_template_engine::render_email(
+            &email_template.subject,
+            &email_template.body,
+            &email_template.body_format,
+            context,
+        )?;
+        Ok((subject, self.apply_layout(body, &email_template.body_format, context)?))
+    }
+
+    /// Wrap a rendered html body in the account layout. Text bodies, and html bodies
+    /// that are complete documents with a layout of their own, are returned as they are.
+    pub fn apply_layout(&self, body: String, body_format: &str, context: &TemplateContext) -> Result<String, String> {
+        if body_format != "html" || is_html_document(&body) {
+            return Ok(body);
+        }
+        let layout = Template::parse(self.layout.as_deref().unwrap_or(DEFAULT_LAYOUT))
+            .map_err(|e| format!("Invalid email layout: {}", e))?;
+        let mut context = context.clone();
+        context.insert_html("content", &body, body.clone());
+        Ok(layout.render(&context, true))
+    }
+}
+
+fn is_html_document(body: &str) -> bool {
+    let start: String = body.trim_start().chars().take(9).collect::<String>().to_ascii_lowercase();
+    start.starts_with("<!doctype") || start.starts_with("<html")
+}
+
+/// Check email settings before they are saved. Empty strings clear a setting and are fine.
+pub fn validate_settings(from_name: Option<&str>, reply_to: Option<&str>, layout: Option<&str>) -> Result<(), String> {
+    if let Some(name) = from_name {
+        if name.chars().any(char::is_control) {
+            return Err("email_from_name must be a single line".to_string());
+        }
+        if name.chars().count() > 100 {
+            return Err("email_from_name must be at most 100 characters".to_string());
+        }
+    }
+    if let Some(reply_to) = reply_to.map(str::trim).filter(|r| !r.is_empty()) {
+        reply_to.parse::<Mailbox>()
+            .map_err(|e| format!("email_reply_to '{}' is not a valid email address: {}", reply_to, e))?;
+    }
+    if let Some(layout) = layout.filter(|l| !l.trim().is_empty()) {
+        let template = Template::parse(layout).map_err(|e| format!("email_layout: {}", e))?;
+        let unknown = template.unknown_variables(LAYOUT_VARIABLES);
+        if !unknown.is_empty() {
+            return Err(format!("email_layout uses unknown variables: {}", unknown.join(", ")));
+        }
+        if !template.unknown_variables(&LAYOUT_VARIABLES[1..]).iter().any(|v| v == "content") {
+            return Err("email_layout must include {{content}} where the email goes".to_string());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn email_template(body_format: &str) -> DbEmailTemplate {
+        DbEmailTemplate {
+            id: 1,
+            user_id: 1,
+            template_type: "invitation".to_string(),
+            subject: "Sign {{template.name}}".to_string(),
+            body: "<p>Hi {{submitter.name}}</p>".to_string(),
+            body_format: body_format.to_string(),
+            is_default: true,
+            attach_documents: false,
+            attach_audit_log: false,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_render_wraps_html_in_layout() {
+        let branding = EmailBranding {
+            account_name: "Acme & Co".to_string(),
+            from_name: None,
+            reply_to: None,
+            logo_url: Some("https://example.com/logo.png".to_string()),
+            layout: Some("<div>{{account.name}}|{{content}}</div>".to_string()),
+            utc_offset: FixedOffset::east_opt(0).unwrap(),
+            locale: "en",
+        };
+        let mut context = branding.context();
+        context.insert("template.name", "NDA").insert("submitter.name", "<Jane>");
+
+        let (subject, body) = branding.render(&email_template("html"), &context).unwrap();
+        assert_eq!(subject, "Sign NDA");
+        assert_eq!(body, "<div>Acme &amp; Co|<p>Hi &lt;Jane&gt;</p></div>");
+
+        let (_, body) = branding.render(&email_template("text"), &context).unwrap();
+        assert_eq!(body, "<p>Hi <Jane></p>");
+
+        let default_layout = EmailBranding { layout: None, ..branding };
+        let (_, body) = default_layout.render(&email_template("html"), &context).unwrap();
+        assert!(body.contains(r#"<img src="https://example.com/logo.png" alt="Acme &amp; Co""#));
+        assert!(body.contains("<p>Hi &lt;Jane&gt;</p>"));
+
+        let mut document = email_template("html");
+        document.body = "<!DOCTYPE html><html><body>{{submitter.name}}</body></html>".to_string();
+        let (_, body) = default_layout.render(&document, &context).unwrap();
+        assert_eq!(body, "<!DOCTYPE html><html><body>&lt;Jane&gt;</body></html>");
+
+        let french = EmailBranding { locale: "fr", ..default_layout };
+        let (_, body) = french.render(&email_template("html"), &french.context()).unwrap();
+        assert!(body.contains("Envoyé au nom de Acme &amp; Co"));
+    }
+
+    #[test]
+    fn test_account_name_falls_back_to_the_account() {
+        let account = DbAccount {
+            id: 1,
+            name: "Acme Legal".to_string(),
+            slug: "acme-legal".to_string(),
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        };
+        let branding = EmailBranding::from_settings(None, Some(&account));
+        assert_eq!(branding.account_name, "Acme Legal");
+        assert_eq!(branding.from_name.as_deref(), Some("Acme Legal"));
+
+        assert_eq!(EmailBranding::from_settings(None, None).account_name, DEFAULT_ACCOUNT_NAME);
+    }
+
+    #[test]
+    fn test_validate_settings() {
+        assert!(validate_settings(Some("Acme Legal"), Some("legal@acme.com"), Some("<b>{{{content}}}</b>")).is_ok());
+        assert!(validate_settings(Some(""), Some(""), Some("")).is_ok());
+        assert!(validate_settings(Some("Acme\r\nBcc: x@y.z"), None, None).is_err());
+        assert!(validate_settings(None, Some("not an address"), None).is_err());
+        assert!(validate_settings(None, None, Some("<b>{{account.name}}</b>")).unwrap_err().contains("{{content}}"));
+        assert!(validate_settings(None, None, Some("{{content}} {{submitter.name}}")).unwrap_err().contains("submitter.name"));
+        assert!(validate_settings(None, None, Some(DEFAULT_LAYOUT)).is_ok());
+    }
+}
@@ -0,0 +1,728 @@
+//! Renders email template subjects and bodies.
+//!
+//! The syntax is a small, sandboxed subset of Handlebars: templates can only read
+//! the values they are given and call the helpers listed here.
+//!
+//! - `{{submitter.name}}` prints a value, HTML-escaped in `html` bodies; `{{{value}}}` prints it as is
+//! - `{{#if value}}...{{else}}...{{/if}}` and `{{#unless value}}...{{/unless}}`
+//! - `{{#each submitters}}{{name}} {{@index}} {{#if @last}}...{{/if}}{{/each}}`
+//! - `{{format_date submission.expires_at "%d %B %Y"}}` formats a date in the account timezone,
+//!   month names in the recipient's language
+//! - `{{! comment }}`
+//!
+//! Placeholders of the old `{submitter.name}` form keep working; ones without a
+//! value are left as they are, like before.
+
+use std::collections::{BTreeSet, HashMap};
+use chrono::{DateTime, FixedOffset, Utc};
+use chrono::format::{Item, StrftimeItems};
+use serde_json::{json, Map, Value};
+
+use crate::database::connection::DbPool;
+use crate::database::models::DbSubmitter;
+use crate::database::queries::{SubmissionQueries, SubmitterQueries};
+use crate::services::i18n::{self, Localizer};
+
+pub const HELPER_FORMAT_DATE: &str = "format_date";
+const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d %H:%M";
+const MAX_DEPTH: usize = 16;
+
+/// Variables every email template can use
+const COMMON_VARIABLES: &[&str] = &[
+    "account.name",
+    "account.logo_url",
+    "now",
+    "template.name",
+    "submitter.name",
+    "submitter.email",
+    "submitter.status",
+    "submission.expires_at",
+    "submitters",
+    "submitters.name",
+    "submitters.email",
+    "submitters.status",
+    "submitters.signed_at",
+];
+
+/// Variables an email template of the given type can use; None for unknown types
+pub fn known_variables(template_type: &str) -> Option<Vec<&'static str>> {
+    let specific: &[&str] = match template_type {
+        "invitation" => &["submitter.link"],
+        "reminder" => &["submitter.link", "reminder.number"],
+        "completion" => &["submitter.link", "completed.signers", "progress"],
+        "copy" => &[],
+        "cancellation" => &["void.reason"],
+        _ => return None,
+    };
+    Some(COMMON_VARIABLES.iter().chain(specific).copied().collect())
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Arg {
+    Path(String),
+    Literal(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    Text(String),
+    Output { helper: Option<String>, args: Vec<Arg>, raw: bool, line: usize },
+    Legacy { path: String, source: String },
+    If { path: String, negate: bool, then: Vec<Node>, otherwise: Vec<Node> },
+    Each { path: String, body: Vec<Node>, otherwise: Vec<Node> },
+}
+
+enum Token {
+    Text(String),
+    Tag { content: String, raw: bool, line: usize },
+    Legacy { path: String, source: String },
+}
+
+fn is_path(path: &str) -> bool {
+    if path == "this" || matches!(path, "@index" | "@first" | "@last") {
+        return true;
+    }
+    !path.is_empty() && path.split('.').all(|part| {
+        let mut chars = part.chars();
+        matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+    })
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut text = String::new();
+    let mut rest = source;
+    let mut line = 1;
+
+    while let Some(start) = rest.find('{') {
+        text.push_str(&rest[..start]);
+        line += rest[..start].matches('\n').count();
+        let after = &rest[start..];
+
+        if after.starts_with("{{") {
+            let raw = after.starts_with("{{{");
+            let (open, close) = if raw { ("{{{", "}}}") } else { ("{{", "}}") };
+            let inner = &after[open.len()..];
+            let end = inner.find(close)
+                .ok_or_else(|| format!("Line {}: '{}' is never closed with '{}'", line, open, close))?;
+            if !text.is_empty() {
+                tokens.push(Token::Text(std::mem::take(&mut text)));
+            }
+            let content = &inner[..end];
+            if !content.starts_with('!') {
+                tokens.push(Token::Tag { content: content.trim().to_string(), raw, line });
+            }
+            line += content.matches('\n').count();
+            rest = &inner[end + close.len()..];
+            continue;
+        }
+
+        // Old-style {path} placeholder
+        let legacy = after[1..].find('}')
+            .map(|end| &after[1..1 + end])
+            .filter(|path| is_path(path) && !path.starts_with('@') && *path != "this");
+        match legacy {
+            Some(path) => {
+                if !text.is_empty() {
+                    tokens.push(Token::Text(std::mem::take(&mut text)));
+                }
+                let source = format!("{{{}}}", path);
+                rest = &after[source.len()..];
+                tokens.push(Token::Legacy { path: path.to_string(), source });
+            }
+            None => {
+                text.push('{');
+                rest = &after[1..];
+            }
+        }
+    }
+    text.push_str(rest);
+    if !text.is_empty() {
+        tokens.push(Token::Text(text));
+    }
+    Ok(tokens)
+}
+
+// Split helper arguments on whitespace, keeping double-quoted strings together
+fn split_args(content: &str, line: usize) -> Result<Vec<Arg>, String> {
+    let mut args = Vec::new();
+    let mut chars = content.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c == '"' {
+            chars.next();
+            let mut literal = String::new();
+            loop {
+                match chars.next() {
+                    Some('"') => break,
+                    Some(c) => literal.push(c),
+                    None => return Err(format!("Line {}: unterminated string in '{{{{{}}}}}'", line, content)),
+                }
+            }
+            args.push(Arg::Literal(literal));
+        } else {
+            let mut word = String::new();
+            while let Some(&c) = chars.peek() {
+                if c.is_whitespace() {
+                    break;
+                }
+                word.push(c);
+                chars.next();
+            }
+            if !is_path(&word) {
+                return Err(format!("Line {}: '{}' is not a valid variable name", line, word));
+            }
+            args.push(Arg::Path(word));
+        }
+    }
+    Ok(args)
+}
+
+/// Parsed nodes and the closing tag that ended them, with its line
+type ParsedNodes = (Vec<Node>, Option<(String, usize)>);
+
+struct Parser {
+    tokens: std::vec::IntoIter<Token>,
+}
+
+impl Parser {
+    // Parse nodes until a closing tag or `{{else}}`; returns the nodes and the tag that ended them
+    fn parse_nodes(&mut self, depth: usize) -> Result<ParsedNodes, String> {
+        let mut nodes = Vec::new();
+        while let Some(token) = self.tokens.next() {
+            let (content, raw, line) = match token {
+                Token::Text(text) => {
+                    nodes.push(Node::Text(text));
+                    continue;
+                }
+                Token::Legacy { path, source } => {
+                    nodes.push(Node::Legacy { path, source });
+                    continue;
+                }
+                Token::Tag { content, raw, line } => (content, raw, line),
+            };
+
+            if content.starts_with('/') || content == "else" {
+                if raw {
+                    return Err(format!("Line {}: '{{{{{{{}}}}}}}' is not allowed", line, content));
+                }
+                return Ok((nodes, Some((content, line))));
+            }
+
+            if let Some(block) = content.strip_prefix('#') {
+                if raw {
+                    return Err(format!("Line {}: blocks cannot use triple braces", line));
+                }
+                if depth >= MAX_DEPTH {
+                    return Err(format!("Line {}: blocks are nested too deeply", line));
+                }
+                let mut parts = block.split_whitespace();
+                let name = parts.next().unwrap_or("");
+                let path = parts.next()
+                    .ok_or_else(|| format!("Line {}: '{{{{#{}}}}}' needs a variable", line, name))?
+                    .to_string();
+                if parts.next().is_some() || !is_path(&path) {
+                    return Err(format!("Line {}: '{{{{#{}}}}}' takes exactly one variable", line, block));
+                }
+                if !matches!(name, "if" | "unless" | "each") {
+                    return Err(format!("Line {}: unknown block '{{{{#{}}}}}'. Use #if, #unless or #each", line, name));
+                }
+
+                let (body, end) = self.parse_nodes(depth + 1)?;
+                let (otherwise, end) = match end {
+                    Some((tag, _)) if tag == "else" => self.parse_nodes(depth + 1)?,
+                    end => (Vec::new(), end),
+                };
+                match end {
+                    Some((tag, _)) if tag.trim_start_matches('/').trim() == name => {}
+                    Some((tag, end_line)) => return Err(format!("Line {}: '{{{{{}}}}}' does not close '{{{{#{}}}}}' from line {}", end_line, tag, name, line)),
+                    None => return Err(format!("Line {}: '{{{{#{}}}}}' is never closed", line, name)),
+                }
+
+                nodes.push(match name {
+                    "each" => Node::Each { path, body, otherwise },
+                    _ => Node::If { path, negate: name == "unless", then: body, otherwise },
+                });
+                continue;
+            }
+
+            let mut args = split_args(&content, line)?;
+            if args.is_empty() {
+                return Err(format!("Line {}: empty '{{{{}}}}'", line));
+            }
+            let helper = match (&args[0], args.len()) {
+                (Arg::Path(path), 1) => {
+                    let path = path.clone();
+                    args = vec![Arg::Path(path)];
+                    None
+                }
+                (Arg::Path(name), _) if name == HELPER_FORMAT_DATE => {
+                    if !matches!(args.get(1), Some(Arg::Path(_))) || args.len() > 3 || matches!(args.get(2), Some(Arg::Path(_))) {
+                        return Err(format!("Line {}: use {{{{format_date variable \"%d/%m/%Y\"}}}}", line));
+                    }
+                    if let Some(Arg::Literal(format)) = args.get(2) {
+                        check_date_format(format).map_err(|e| format!("Line {}: {}", line, e))?;
+                    }
+                    args.remove(0);
+                    Some(HELPER_FORMAT_DATE.to_string())
+                }
+                (Arg::Path(name), _) => return Err(format!("Line {}: unknown helper '{}'", line, name)),
+                (Arg::Literal(_), _) => return Err(format!("Line {}: '{{{{{}}}}}' must start with a variable or helper", line, content)),
+            };
+            nodes.push(Node::Output { helper, args, raw, line });
+        }
+        Ok((nodes, None))
+    }
+}
+
+fn check_date_format(format: &str) -> Result<(), String> {
+    if StrftimeItems::new(format).any(|item| matches!(item, Item::Error)) {
+        return Err(format!("invalid date format '{}'", format));
+    }
+    Ok(())
+}
+
+/// A parsed template, ready to render many times
+#[derive(Debug, Clone)]
+pub struct Template {
+    nodes: Vec<Node>,
+}
+
+impl Template {
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let mut parser = Parser { tokens: tokenize(source)?.into_iter() };
+        match parser.parse_nodes(0)? {
+            (nodes, None) => Ok(Self { nodes }),
+            (_, Some((tag, line))) => Err(format!("Line {}: '{{{{{}}}}}' has no matching opening block", line, tag)),
+        }
+    }
+
+    /// Variables used by the template that are not in `known`; inside `{{#each list}}`
+    /// a variable `x` may also refer to the `list.x` field of each item.
+    pub fn unknown_variables(&self, known: &[&str]) -> Vec<String> {
+        let mut unknown = BTreeSet::new();
+        collect_unknown(&self.nodes, known, &mut Vec::new(), &mut unknown);
+        unknown.into_iter().collect()
+    }
+
+    pub fn render(&self, context: &TemplateContext, escape_html: bool) -> String {
+        let mut out = String::new();
+        let mut scopes = vec![Scope { value: &context.data, path: String::new(), index: None }];
+        render_nodes(&self.nodes, context, escape_html, &mut scopes, &mut out);
+        out
+    }
+}
+
+fn check_variable(path: &str, known: &[&str], loops: &[String], unknown: &mut BTreeSet<String>) {
+    if path.starts_with('@') {
+        if loops.is_empty() {
+            unknown.insert(path.to_string());
+        }
+        return;
+    }
+    let field = path.strip_prefix("this.").unwrap_or(path);
+    let in_loop = |list: &String| path == "this" || known.contains(&format!("{}.{}", list, field).as_str());
+    if !known.contains(&path) && !loops.iter().any(in_loop) {
+        unknown.insert(path.to_string());
+    }
+}
+
+fn collect_unknown(nodes: &[Node], known: &[&str], loops: &mut Vec<String>, unknown: &mut BTreeSet<String>) {
+    for node in nodes {
+        match node {
+            Node::Text(_) => {}
+            Node::Output { args, .. } => {
+                for arg in args {
+                    if let Arg::Path(path) = arg {
+                        check_variable(path, known, loops, unknown);
+                    }
+                }
+            }
+            Node::Legacy { path, .. } => check_variable(path, known, loops, unknown),
+            Node::If { path, then, otherwise, .. } => {
+                check_variable(path, known, loops, unknown);
+                collect_unknown(then, known, loops, unknown);
+                collect_unknown(otherwise, known, loops, unknown);
+            }
+            Node::Each { path, body, otherwise } => {
+                check_variable(path, known, loops, unknown);
+                loops.push(path.clone());
+                collect_unknown(body, known, loops, unknown);
+                loops.pop();
+                collect_unknown(otherwise, known, loops, unknown);
+            }
+        }
+    }
+}
+
+/// Values a template is rendered with. Paths are dotted (`submitter.name`).
+#[derive(Debug, Clone)]
+pub struct TemplateContext {
+    data: Value,
+    // Values that are already HTML and are printed unescaped, e.g. a linked document name
+    html: HashMap<String, String>,
+    utc_offset: FixedOffset,
+    locale: &'static str,
+}
+
+impl Default for TemplateContext {
+    fn default() -> Self {
+        Self::new(FixedOffset::east_opt(0).unwrap())
+    }
+}
+
+impl TemplateContext {
+    /// `utc_offset` is the account timezone used by `format_date`
+    pub fn new(utc_offset: FixedOffset) -> Self {
+        let mut context = Self { data: Value::Object(Map::new()), html: HashMap::new(), utc_offset, locale: i18n::DEFAULT_LOCALE };
+        context.insert("now", Utc::now().to_rfc3339());
+        context
+    }
+
+    /// Language `format_date` writes month names in
+    pub fn set_locale(&mut self, locale: &'static str) -> &mut Self {
+        self.locale = locale;
+        self
+    }
+
+    pub fn insert(&mut self, path: &str, value: impl Into<Value>) -> &mut Self {
+        let mut target = &mut self.data;
+        for part in path.split('.') {
+            if !target.is_object() {
+                *target = Value::Object(Map::new());
+            }
+            target = target.as_object_mut().unwrap().entry(part.to_string()).or_insert(Value::Null);
+        }
+        *target = value.into();
+        self
+    }
+
+    /// Value printed as is in HTML bodies; text bodies and subjects get `text` instead
+    pub fn insert_html(&mut self, path: &str, text: &str, html: String) -> &mut Self {
+        self.html.insert(path.to_string(), html);
+        self.insert(path, text)
+    }
+}
+
+struct Scope<'a> {
+    value: &'a Value,
+    path: String,
+    index: Option<(usize, usize)>,
+}
+
+fn lookup<'a>(scopes: &[Scope<'a>], path: &str) -> Option<Value> {
+    let current = scopes.last()?;
+    match path {
+        "this" => return Some(current.value.clone()),
+        "@index" => return current.index.map(|(i, _)| Value::from(i)),
+        "@first" => return current.index.map(|(i, _)| Value::Bool(i == 0)),
+        "@last" => return current.index.map(|(i, len)| Value::Bool(i + 1 == len)),
+        _ => {}
+    }
+    let (path, scopes) = match path.strip_prefix("this.") {
+        Some(rest) => (rest, &scopes[scopes.len() - 1..]),
+        None => (path, scopes),
+    };
+    scopes.iter().rev().find_map(|scope| {
+        path.split('.').try_fold(scope.value, |value, part| value.get(part)).filter(|v| !v.is_null()).cloned()
+    })
+}
+
+// Full dotted path of a variable in the root context, used to find HTML overrides
+fn absolute_path(scopes: &[Scope], path: &str) -> String {
+    let path = path.strip_prefix("this.").unwrap_or(path);
+    match scopes.last() {
+        Some(scope) if !scope.path.is_empty() => format!("{}.{}", scope.path, path),
+        _ => path.to_string(),
+    }
+}
+
+fn is_truthy(value: &Option<Value>) -> bool {
+    match value {
+        None | Some(Value::Null) => false,
+        Some(Value::Bool(b)) => *b,
+        Some(Value::String(s)) => !s.is_empty(),
+        Some(Value::Number(n)) => n.as_f64().map(|n| n != 0.0).unwrap_or(true),
+        Some(Value::Array(items)) => !items.is_empty(),
+        Some(Value::Object(_)) => true,
+    }
+}
+
+fn display(value: &Option<Value>) -> String {
+    match value {
+        Some(Value::String(s)) => s.clone(),
+        Some(Value::Number(n)) => n.to_string(),
+        Some(Value::Bool(b)) => b.to_string(),
+        _ => String::new(),
+    }
+}
+
+pub fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn format_date(value: &Option<Value>, format: Option<&str>, context: &TemplateContext) -> String {
+    let text = display(value);
+    match DateTime::parse_from_rfc3339(&text) {
+        Ok(date) => {
+            let localizer = Localizer { locale: context.locale, utc_offset: context.utc_offset };
+            // Formats are checked when the template is parsed
+            localizer.format_datetime(date.with_timezone(&Utc), format.unwrap_or(DEFAULT_DATE_FORMAT))
+        }
+        Err(_) => text,
+    }
+}
+
+fn render_nodes<'a>(nodes: &'a [Node], context: &'a TemplateContext, escape: bool, scopes: &mut Vec<Scope<'a>>, out: &mut String) {
+    for node in nodes {
+        match node {
+            Node::Text(text) => out.push_str(text),
+            Node::Output { helper, args, raw, .. } => {
+                let Some(Arg::Path(path)) = args.first() else { continue };
+                let value = lookup(scopes, path);
+                let text = match helper.as_deref() {
+                    Some(HELPER_FORMAT_DATE) => {
+                        let format = match args.get(1) {
+                            Some(Arg::Literal(format)) => Some(format.as_str()),
+                            _ => None,
+                        };
+                        format_date(&value, format, context)
+                    }
+                    _ => {
+                        if escape && !raw {
+                            if let Some(html) = context.html.get(&absolute_path(scopes, path)) {
+                                out.push_str(html);
+                                continue;
+                            }
+                        }
+                        display(&value)
+                    }
+                };
+                out.push_str(&if escape && !raw { escape_html(&text) } else { text });
+            }
+            Node::Legacy { path, source } => match lookup(scopes, path) {
+                None => out.push_str(source),
+                value => {
+                    match context.html.get(path) {
+                        Some(html) if escape => out.push_str(html),
+                        _ => {
+                            let text = display(&value);
+                            out.push_str(&if escape { escape_html(&text) } else { text });
+                        }
+                    }
+                }
+            },
+            Node::If { path, negate, then, otherwise } => {
+                let branch = if is_truthy(&lookup(scopes, path)) != *negate { then } else { otherwise };
+                render_nodes(branch, context, escape, scopes, out);
+            }
+            Node::Each { path, body, otherwise } => {
+                let items = match lookup_ref(scopes, path) {
+                    Some(Value::Array(items)) if !items.is_empty() => items,
+                    _ => {
+                        render_nodes(otherwise, context, escape, scopes, out);
+                        continue;
+                    }
+                };
+                let list_path = absolute_path(scopes, path);
+                for (index, item) in items.iter().enumerate() {
+                    scopes.push(Scope { value: item, path: list_path.clone(), index: Some((index, items.len())) });
+                    render_nodes(body, context, escape, scopes, out);
+                    scopes.pop();
+                }
+            }
+        }
+    }
+}
+
+// Borrowing lookup for lists, so loop items can be scoped without cloning
+fn lookup_ref<'a>(scopes: &[Scope<'a>], path: &str) -> Option<&'a Value> {
+    let (path, scopes) = match path.strip_prefix("this.") {
+        Some(rest) => (rest, &scopes[scopes.len().saturating_sub(1)..]),
+        None => (path, scopes),
+    };
+    scopes.iter().rev().find_map(|scope| {
+        path.split('.').try_fold(scope.value, |value, part| value.get(part)).filter(|v| !v.is_null())
+    })
+}
+
+/// Add the variables shared by every email about a submitter: the submitter, the
+/// document and the other submitters of the envelope. Lookups that fail leave their
+/// variables unset rather than failing the email.
+pub async fn insert_submitter_variables(pool: &DbPool, context: &mut TemplateContext, submitter: &DbSubmitter, template_name: &str) {
+    context
+        .insert("template.name", template_name)
+        .insert("submitter.name", submitter.name.as_str())
+        .insert("submitter.email", submitter.email.as_str())
+        .insert("submitter.status", submitter.status.as_str());
+
+    let Some(submission_id) = submitter.submission_id else {
+        context.insert("submitters", json!([submitter_json(submitter)]));
+        return;
+    };
+    if let Ok(Some(submission)) = SubmissionQueries::get_submission_by_id(pool, submission_id).await {
+        if let Some(expires_at) = submission.expires_at {
+            context.insert("submission.expires_at", expires_at.to_rfc3339());
+        }
+    }
+    let submitters = match SubmitterQueries::get_submitters_by_submission_id(pool, submission_id).await {
+        Ok(submitters) => submitters.iter().map(submitter_json).collect(),
+        Err(_) => vec![submitter_json(submitter)],
+    };
+    context.insert("submitters", Value::Array(submitters));
+}
+
+fn submitter_json(submitter: &DbSubmitter) -> Value {
+    json!({
+        "name": submitter.name,
+        "email": submitter.email,
+        "status": submitter.status,
+        "signed_at": submitter.signed_at.map(|signed_at| signed_at.to_rfc3339()),
+    })
+}
+
+/// Problems found in an email template's subject and body
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TemplateCheck {
+    pub errors: Vec<String>,
+    pub unknown_variables: Vec<String>,
+}
+
+impl TemplateCheck {
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty() && self.unknown_variables.is_empty()
+    }
+}
+
+/// Parse an email template's subject and body and report syntax errors and
+/// variables that emails of its type do not provide
+pub fn check_email_template(template_type: &str, subject: &str, body: &str) -> TemplateCheck {
+    let mut check = TemplateCheck::default();
+    let known = match known_variables(template_type) {
+        Some(known) => known,
+        None => {
+            check.errors.push(format!("Unknown template type '{}'", template_type));
+            return check;
+        }
+    };
+    if subject.trim().is_empty() {
+        check.errors.push("Subject: must not be empty".to_string());
+    }
+
+    let mut unknown = BTreeSet::new();
+    for (part, source) in [("Subject", subject), ("Body", body)] {
+        match Template::parse(source) {
+            Ok(template) => unknown.extend(template.unknown_variables(&known)),
+            Err(e) => check.errors.push(format!("{}: {}", part, e)),
+        }
+    }
+    check.unknown_variables = unknown.into_iter().collect();
+    check
+}
+
+/// Render an email template's subject and body. Subjects are plain text; bodies are
+/// HTML-escaped when `body_format` is html.
+pub fn render_email(subject: &str, body: &str, body_format: &str, context: &TemplateContext) -> Result<(String, String), String> {
+    let subject = Template::parse(subject).map_err(|e| format!("Invalid email subject: {}", e))?
+        .render(context, false);
+    let body = Template::parse(body).map_err(|e| format!("Invalid email body: {}", e))?
+        .render(context, body_format == "html");
+    // Subjects are a single header line
+    Ok((subject.split_whitespace().collect::<Vec<_>>().join(" "), body))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context() -> TemplateContext {
+        let mut context = TemplateContext::new(FixedOffset::east_opt(7 * 3600).unwrap());
+        context
+            .insert("submitter.name", "Jane <b>Doe</b>")
+            .insert("template.name", "NDA")
+            .insert("submission.expires_at", "2025-01-31T20:00:00+00:00")
+            .insert("submitters", json!([
+                { "name": "Jane", "email": "jane@example.com", "status": "signed" },
+                { "name": "Bob & Co", "email": "bob@example.com", "status": "sent" }
+            ]));
+        context
+    }
+
+    fn render(source: &str, escape: bool) -> String {
+        Template::parse(source).unwrap().render(&context(), escape)
+    }
+
+    #[test]
+    fn test_variables_are_escaped_in_html() {
+        assert_eq!(render("Hi {{submitter.name}}", true), "Hi Jane &lt;b&gt;Doe&lt;/b&gt;");
+        assert_eq!(render("Hi {{{submitter.name}}}", true), "Hi Jane <b>Doe</b>");
+        assert_eq!(render("Hi {{submitter.name}}", false), "Hi Jane <b>Doe</b>");
+        assert_eq!(render("Hi {submitter.name}, {unknown.var} {not a var}", true), "Hi Jane &lt;b&gt;Doe&lt;/b&gt;, {unknown.var} {not a var}");
+        assert_eq!(render("body { color: red; } {{missing}}!", true), "body { color: red; } !");
+
+        let mut linked = context();
+        linked.insert_html("template.name", "NDA", "<a href=\"https://example.com\">NDA</a>".to_string());
+        let template = Template::parse("{{template.name}}").unwrap();
+        assert_eq!(template.render(&linked, true), "<a href=\"https://example.com\">NDA</a>");
+        assert_eq!(template.render(&linked, false), "NDA");
+    }
+
+    #[test]
+    fn test_blocks_loops_and_dates() {
+        assert_eq!(render("{{#if submission.expires_at}}expires{{else}}open{{/if}}", true), "expires");
+        assert_eq!(render("{{#unless reminder.number}}first{{/unless}}", true), "first");
+        assert_eq!(
+            render("{{#each submitters}}{{@index}}:{{name}}/{{this.status}}{{#unless @last}}, {{/unless}}{{/each}}", true),
+            "0:Jane/signed, 1:Bob &amp; Co/sent"
+        );
+        assert_eq!(render("{{#each nobody}}x{{else}}none{{/each}}", true), "none");
+        assert_eq!(render("{{#each submitters}}{{template.name}}{{/each}}", true), "NDANDA");
+        // 20:00 UTC is the next morning at GMT+7
+        assert_eq!(render("{{format_date submission.expires_at \"%d/%m/%Y %H:%M\"}}", true), "01/02/2025 03:00");
+        assert_eq!(render("{{! a comment }}{{format_date submission.expires_at}}", true), "2025-02-01 03:00");
+        let mut french = context();
+        french.set_locale("fr");
+        let template = Template::parse("{{format_date submission.expires_at \"%d %B %Y\"}}").unwrap();
+        assert_eq!(template.render(&french, true), "01 février 2025");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(Template::parse("{{#if a}}x").unwrap_err().contains("never closed"));
+        assert!(Template::parse("{{#if a}}x{{/each}}").unwrap_err().contains("does not close"));
+        assert!(Template::parse("x{{/if}}").unwrap_err().contains("no matching"));
+        assert!(Template::parse("{{#with a}}{{/with}}").unwrap_err().contains("unknown block"));
+        assert!(Template::parse("{{lookup a b}}").unwrap_err().contains("unknown helper"));
+        assert!(Template::parse("{{format_date a \"%Q\"}}").unwrap_err().contains("invalid date format"));
+        assert!(Template::parse("{{a").unwrap_err().contains("never closed"));
+    }
+
+    #[test]
+    fn test_check_email_template() {
+        let check = check_email_template(
+            "reminder",
+            "Reminder #{{reminder.number}}: {template.name}",
+            "{{#each submitters}}{{name}} {{phone}}{{/each}} {{submitter.link}} {{void.reason}} {{@index}}",
+        );
+        assert!(check.errors.is_empty());
+        assert_eq!(check.unknown_variables, vec!["@index", "phone", "void.reason"]);
+
+        assert!(check_email_template("invitation", "Sign {{template.name}}", "{{submitter.link}}").is_valid());
+        assert!(!check_email_template("invitation", " ", "ok").errors.is_empty());
+        assert!(!check_email_template("newsletter", "Hi", "ok").errors.is_empty());
+        assert_eq!(check_email_template("copy", "{{#if x}}", "").errors.len(), 1);
+    }
+}
@@ -0,0 +1,225 @@
+use std::env;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, OnceLock};
+use futures::future::BoxFuture;
+use lettre::address::Envelope;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Tokio1Executor};
+
+pub const TRANSPORT_SMTP: &str = "smtp";
+pub const TRANSPORT_FILE: &str = "file";
+pub const TRANSPORT_MEMORY: &str = "memory";
+
+/// Hands a fully rendered message to whatever delivers it. Messages are passed
+/// as raw RFC 5322 bytes so the outbox can store them and resend them unchanged.
+pub trait EmailTransport: Send + Sync {
+    /// Short name stored with each outbox row: smtp, file or memory
+    fn name(&self) -> &'static str;
+
+    fn send<'a>(&'a self, envelope: &'a Envelope, raw: &'a [u8]) -> BoxFuture<'a, Result<(), String>>;
+}
+
+/// Delivers through an SMTP relay; the connection settings are resolved once
+pub struct SmtpEmailTransport {
+    mailer: AsyncSmtpTransport<Tokio1Executor>,
+}
+
+impl SmtpEmailTransport {
+    /// `localhost` gets an unencrypted connection (a local relay or mail catcher),
+    /// other hosts STARTTLS when `use_tls` is set and implicit TLS otherwise.
+    pub fn new(host: &str, port: u16, username: &str, password: &str, use_tls: bool) -> Result<Self, String> {
+        let creds = Credentials::new(username.to_string(), password.to_string());
+        let mailer = if host == "localhost" {
+            AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(host).port(port).build()
+        } else if use_tls {
+            AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(host)
+                .map_err(|e| format!("Invalid SMTP host '{}': {}", host, e))?
+                .credentials(creds)
+                .port(port)
+                .build()
+        } else {
+            AsyncSmtpTransport::<Tokio1Executor>::relay(host)
+                .map_err(|e| format!("Invalid SMTP host '{}': {}", host, e))?
+                .credentials(creds)
+                .port(port)
+                .build()
+        };
+        Ok(Self { mailer })
+    }
+}
+
+impl EmailTransport for SmtpEmailTransport {
+    fn name(&self) -> &'static str {
+        TRANSPORT_SMTP
+    }
+
+    fn send<'a>(&'a self, envelope: &'a Envelope, raw: &'a [u8]) -> BoxFuture<'a, Result<(), String>> {
+        Box::pin(async move {
+            self.mailer.send_raw(envelope, raw).await
+                .map(|_| ())
+                .map_err(|e| format!("SMTP delivery failed: {}", e))
+        })
+    }
+}
+
+/// Writes each message as an `.eml` file into a maildir (`tmp/`, then renamed into
+/// `new/`), so local development and tests can read what would have been sent.
+pub struct FileEmailTransport {
+    dir: PathBuf,
+}
+
+impl FileEmailTransport {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+}
+
+impl EmailTransport for FileEmailTransport {
+    fn name(&self) -> &'static str {
+        TRANSPORT_FILE
+    }
+
+    fn send<'a>(&'a self, _envelope: &'a Envelope, raw: &'a [u8]) -> BoxFuture<'a, Result<(), String>> {
+        Box::pin(async move {
+            let tmp_dir = self.dir.join("tmp");
+            let new_dir = self.dir.join("new");
+            for dir in [&tmp_dir, &new_dir] {
+                tokio::fs::create_dir_all(dir).await
+                    .map_err(|e| format!("Failed to create mail directory {}: {}", dir.display(), e))?;
+            }
+
+            let file_name = format!("{}.{}.eml", chrono::Utc::now().timestamp_millis(), uuid::Uuid::new_v4().simple());
+            let tmp_path = tmp_dir.join(&file_name);
+            tokio::fs::write(&tmp_path, raw).await
+                .map_err(|e| format!("Failed to write {}: {}", tmp_path.display(), e))?;
+            tokio::fs::rename(&tmp_path, new_dir.join(&file_name)).await
+                .map_err(|e| format!("Failed to move {} into the maildir: {}", file_name, e))
+        })
+    }
+}
+
+/// A message captured by [`MemoryEmailTransport`]
+#[allow(dead_code)]
+#[derive(Debug, Clone)]
+pub struct CapturedEmail {
+    pub from: Option<String>,
+    pub to: Vec<String>,
+    pub raw: Vec<u8>,
+}
+
+/// Keeps messages in memory instead of sending them
+#[derive(Clone, Default)]
+pub struct MemoryEmailTransport {
+    messages: Arc<Mutex<Vec<CapturedEmail>>>,
+}
+
+impl MemoryEmailTransport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Messages captured so far, oldest first
+    #[allow(dead_code)]
+    pub fn messages(&self) -> Vec<CapturedEmail> {
+        self.messages.lock().map(|messages| messages.clone()).unwrap_or_default()
+    }
+}
+
+impl EmailTransport for MemoryEmailTransport {
+    fn name(&self) -> &'static str {
+        TRANSPORT_MEMORY
+    }
+
+    fn send<'a>(&'a self, envelope: &'a Envelope, raw: &'a [u8]) -> BoxFuture<'a, Result<(), String>> {
+        Box::pin(async move {
+            let captured = CapturedEmail {
+                from: envelope.from().map(|from| from.to_string()),
+                to: envelope.to().iter().map(|to| to.to_string()).collect(),
+                raw: raw.to_vec(),
+            };
+            println!("📭 Captured email to {} ({} bytes), not sent", captured.to.join(", "), raw.len());
+            self.messages.lock()
+                .map_err(|_| "Captured email store is poisoned".to_string())?
+                .push(captured);
+            Ok(())
+        })
+    }
+}
+
+static DEFAULT_TRANSPORT: OnceLock<Arc<dyn EmailTransport>> = OnceLock::new();
+
+/// Transport picked by `EMAIL_TRANSPORT` (smtp, file or memory), built on first use
+/// and shared by every `EmailService`. `EMAIL_TEST_MODE=true` keeps its old meaning
+/// of not sending anything and selects the memory transport.
+pub fn default_transport() -> Result<Arc<dyn EmailTransport>, String> {
+    if let Some(transport) = DEFAULT_TRANSPORT.get() {
+        return Ok(transport.clone());
+    }
+
+    let test_mode = env::var("EMAIL_TEST_MODE").ok().and_then(|v| v.parse::<bool>().ok()).unwrap_or(false);
+    let kind = env::var("EMAIL_TRANSPORT").unwrap_or_else(|_| {
+        if test_mode { TRANSPORT_MEMORY } else { TRANSPORT_SMTP }.to_string()
+    });
+
+    let transport: Arc<dyn EmailTransport> = match kind.as_str() {
+        TRANSPORT_SMTP => {
+            let host = env::var("SMTP_HOST").unwrap_or_else(|_| "smtp.gmail.com".to_string());
+            let port = env::var("SMTP_PORT").ok().and_then(|v| v.parse().ok()).unwrap_or(587);
+            let username = env::var("SMTP_USERNAME").map_err(|_| "SMTP_USERNAME is not set".to_string())?;
+            let password = env::var("SMTP_PASSWORD").map_err(|_| "SMTP_PASSWORD is not set".to_string())?;
+            let use_tls = env::var("SMTP_USE_TLS").ok().and_then(|v| v.parse().ok()).unwrap_or(true);
+            Arc::new(SmtpEmailTransport::new(&host, port, &username, &password, use_tls)?)
+        }
+        TRANSPORT_FILE => {
+            let dir = env::var("EMAIL_FILE_DIR").unwrap_or_else(|_| "tmp/mail".to_string());
+            Arc::new(FileEmailTransport::new(dir))
+        }
+        TRANSPORT_MEMORY => Arc::new(MemoryEmailTransport::new()),
+        other => return Err(format!("Unknown EMAIL_TRANSPORT '{}'. Must be 'smtp', 'file' or 'memory'", other)),
+    };
+
+    println!("Email transport: {}", transport.name());
+    Ok(DEFAULT_TRANSPORT.get_or_init(|| transport).clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lettre::Message;
+
+    fn message() -> Message {
+        Message::builder()
+            .from("DocuSeal Pro <noreply@example.com>".parse().unwrap())
+            .to("Jane <jane@example.com>".parse().unwrap())
+            .subject("Please sign")
+            .body("Hello Jane".to_string())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_memory_transport_captures_messages() {
+        let transport = MemoryEmailTransport::new();
+        let message = message();
+        transport.send(message.envelope(), &message.formatted()).await.unwrap();
+
+        let captured = transport.messages();
+        assert_eq!(captured.len(), 1);
+        assert_eq!(captured[0].to, vec!["jane@example.com"]);
+        assert_eq!(captured[0].from.as_deref(), Some("noreply@example.com"));
+        assert!(String::from_utf8_lossy(&captured[0].raw).contains("Subject: Please sign"));
+    }
+
+    #[tokio::test]
+    async fn test_file_transport_writes_maildir() {
+        let dir = env::temp_dir().join(format!("email_transport_test_{}", uuid::Uuid::new_v4().simple()));
+        let transport = FileEmailTransport::new(&dir);
+        let message = message();
+        transport.send(message.envelope(), &message.formatted()).await.unwrap();
+
+        let files: Vec<_> = std::fs::read_dir(dir.join("new")).unwrap().map(|entry| entry.unwrap().path()).collect();
+        assert_eq!(files.len(), 1);
+        assert!(std::fs::read_to_string(&files[0]).unwrap().contains("Hello Jane"));
+        assert_eq!(std::fs::read_dir(dir.join("tmp")).unwrap().count(), 0);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,204 @@
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tokio::time::{sleep, Duration};
+use chrono::{DateTime, Utc};
+
+use crate::database::connection::DbPool;
+use crate::database::models::{DbSubmission, DbSubmitter};
+use crate::database::queries::{SubmissionQueries, SubmitterQueries, UserQueries};
+use crate::services::audit::{self, AuditContext};
+use crate::services::email::{EmailContext, EmailService};
+use crate::services::i18n::{self, Localizer};
+use crate::services::webhooks;
+
+const SWEEP_INTERVAL_SECS: u64 = 60;
+
+/// Whether a submission can no longer be signed. A pending submission whose
+/// `expires_at` has passed counts even before the sweeper has marked it.
+pub fn is_expired(status: &str, expires_at: Option<DateTime<Utc>>, now: DateTime<Utc>) -> bool {
+    match status {
+        "expired" => true,
+        "pending" => expires_at.is_some_and(|expires_at| expires_at <= now),
+        _ => false,
+    }
+}
+
+/// Check a new expiry date for a submission in the given status
+pub fn validate_extension(status: &str, expires_at: DateTime<Utc>, now: DateTime<Utc>) -> Result<(), String> {
+    if status != "pending" && status != "expired" {
+        return Err(format!("Only pending or expired submissions can be extended; this one is {}", status));
+    }
+    if expires_at <= now {
+        return Err("expires_at must be in the future".to_string());
+    }
+    Ok(())
+}
+
+/// Why a signing link stopped working
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClosedLink {
+    Expired,
+    Voided,
+}
+
+impl ClosedLink {
+    /// What the signer is told, in the given locale
+    pub fn message(self, locale: &str) -> String {
+        match self {
+            ClosedLink::Expired => i18n::t(locale, "error.link_expired"),
+            ClosedLink::Voided => i18n::t(locale, "error.link_voided"),
+        }
+    }
+}
+
+/// Why a submitter's signing link no longer works, if it doesn't. Voiding closes
+/// every link; expiry only those of submitters who have not signed yet, so signers
+/// keep access to their copy.
+pub async fn closed_link_reason(pool: &DbPool, submitter: &DbSubmitter) -> Result<Option<ClosedLink>, sqlx::Error> {
+    let signed = submitter.status == "signed" || submitter.status == "completed";
+    closed_reason(pool, submitter, !signed).await
+}
+
+/// Why a signer may not reopen their form for resubmission: unlike viewing their
+/// copy, that needs the envelope to be neither voided nor expired
+pub async fn closed_for_resubmission(pool: &DbPool, submitter: &DbSubmitter) -> Result<Option<ClosedLink>, sqlx::Error> {
+    closed_reason(pool, submitter, true).await
+}
+
+async fn closed_reason(pool: &DbPool, submitter: &DbSubmitter, check_expiry: bool) -> Result<Option<ClosedLink>, sqlx::Error> {
+    let Some(submission_id) = submitter.submission_id else {
+        return Ok(None);
+    };
+    let Some(submission) = SubmissionQueries::get_submission_by_id(pool, submission_id).await? else {
+        return Ok(None);
+    };
+    if submission.status == "voided" {
+        return Ok(Some(ClosedLink::Voided));
+    }
+    if check_expiry && is_expired(&submission.status, submission.expires_at, Utc::now()) {
+        return Ok(Some(ClosedLink::Expired));
+    }
+    Ok(None)
+}
+
+/// Background task that expires submissions past their `expires_at`
+#[derive(Clone)]
+pub struct ExpirySweeper {
+    db_pool: Arc<Mutex<DbPool>>,
+    email_service: Arc<EmailService>,
+}
+
+impl ExpirySweeper {
+    pub fn new(db_pool: Arc<Mutex<DbPool>>, email_service: EmailService) -> Self {
+        Self {
+            db_pool,
+            email_service: Arc::new(email_service),
+        }
+    }
+
+    pub async fn start_processing(&self) {
+        println!("⌛ Starting submission expiry sweeper...");
+
+        loop {
+            if let Err(e) = self.expire_due_submissions().await {
+                eprintln!("❌ Error expiring submissions: {}", e);
+            }
+
+            sleep(Duration::from_secs(SWEEP_INTERVAL_SECS)).await;
+        }
+    }
+
+    /// Expire every due submission, then record and announce each one
+    pub async fn expire_due_submissions(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let pool = self.db_pool.lock().await.clone();
+
+        for submission in SubmissionQueries::expire_due_submissions(&pool).await? {
+            let submitters = SubmitterQueries::get_submitters_by_submission_id(&pool, submission.id).await?;
+            println!("⌛ Submission {} expired", submission.id);
+            self.announce_expiry(&pool, &submission, &submitters).await;
+        }
+
+        Ok(())
+    }
+
+    async fn announce_expiry(&self, pool: &DbPool, submission: &DbSubmission, submitters: &[DbSubmitter]) {
+        let unsigned: Vec<&DbSubmitter> = submitters.iter()
+            .filter(|s| s.status != "signed" && s.status != "completed")
+            .collect();
+
+        for submitter in &unsigned {
+            audit::record_event(
+                pool,
+                submitter,
+                audit::EVENT_EXPIRED,
+                "System",
+                format!("Submission expired before {} signed", submitter.email),
+                AuditContext::system(),
+                Some(serde_json::json!({ "expires_at": submission.expires_at })),
+            ).await;
+        }
+
+        webhooks::dispatch_event(
+            pool,
+            submission.user_id,
+            webhooks::EVENT_SUBMISSION_EXPIRED,
+            webhooks::submission_event_data(submission, submitters),
+        ).await;
+
+        let owner = match UserQueries::get_user_by_id(pool, submission.user_id).await {
+            Ok(Some(owner)) => owner,
+            Ok(None) => return,
+            Err(e) => {
+                eprintln!("❌ Failed to load owner of expired submission {}: {}", submission.id, e);
+                return;
+            }
+        };
+        let submission_name = submission.name.clone().unwrap_or_else(|| format!("Submission #{}", submission.id));
+        let unsigned_signers = unsigned.iter()
+            .map(|s| format!("{} ({})", s.name, s.email))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let email_service = self.email_service.as_ref().clone().with_context(EmailContext {
+            user_id: Some(owner.id),
+            kind: Some("expired".to_string()),
+            ..Default::default()
+        }).with_locale(Localizer::for_user(pool, owner.id).await.locale);
+        if let Err(e) = email_service.send_submission_expired(&owner.email, &owner.name, &submission_name, &unsigned_signers).await {
+            eprintln!("❌ Failed to notify {} about expired submission {}: {}", owner.email, submission.id, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration as ChronoDuration;
+
+    #[test]
+    fn test_is_expired() {
+        let now = Utc::now();
+        let past = Some(now - ChronoDuration::minutes(1));
+        let future = Some(now + ChronoDuration::minutes(1));
+
+        assert!(is_expired("expired", None, now));
+        assert!(is_expired("pending", past, now));
+        assert!(is_expired("pending", Some(now), now));
+        assert!(!is_expired("pending", future, now));
+        assert!(!is_expired("pending", None, now));
+        assert!(!is_expired("completed", past, now));
+        assert!(!is_expired("declined", past, now));
+    }
+
+    #[test]
+    fn test_validate_extension() {
+        let now = Utc::now();
+        let future = now + ChronoDuration::days(7);
+
+        assert!(validate_extension("pending", future, now).is_ok());
+        assert!(validate_extension("expired", future, now).is_ok());
+        assert!(validate_extension("completed", future, now).is_err());
+        assert!(validate_extension("declined", future, now).is_err());
+        assert!(validate_extension("expired", now - ChronoDuration::minutes(1), now).is_err());
+    }
+}
@@ -0,0 +1,276 @@
+use std::collections::HashMap;
+
+use crate::models::template::{ConditionAction, ConditionOperator, FieldCondition};
+use crate::services::template_roles;
+
+/// A field as seen by the condition evaluator
+pub struct ConditionalField<'a> {
+    pub name: &'a str,
+    pub required: bool,
+    pub conditions: Vec<FieldCondition>,
+}
+
+/// A template field as seen when resolving values across the roles of an envelope
+pub struct RoleField<'a> {
+    pub template_field_id: i64,
+    pub name: &'a str,
+    pub role_id: Option<i64>,
+}
+
+/// Values by field name as seen by a submitter of `role_id`, from values keyed
+/// by template field id.
+///
+/// Fields the role fills win over same-named fields of other roles, and one of
+/// its own fields left empty stays empty rather than borrowing another role's value.
+pub fn role_scoped_values(fields: &[RoleField], values: &HashMap<i64, String>, role_id: Option<i64>) -> HashMap<String, String> {
+    let mut scoped = HashMap::new();
+    for field in fields.iter().filter(|f| !template_roles::can_fill(f.role_id, role_id)) {
+        if let Some(value) = values.get(&field.template_field_id) {
+            scoped.insert(field.name.to_string(), value.clone());
+        }
+    }
+    for field in fields.iter().filter(|f| template_roles::can_fill(f.role_id, role_id)) {
+        match values.get(&field.template_field_id) {
+            Some(value) => scoped.insert(field.name.to_string(), value.clone()),
+            None => scoped.remove(field.name),
+        };
+    }
+    scoped
+}
+
+/// Effective state of a field once its conditions are applied
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FieldState {
+    pub visible: bool,
+    pub required: bool,
+}
+
+/// Read the stored `conditions` column; malformed entries are ignored
+pub fn parse_conditions(value: Option<&serde_json::Value>) -> Vec<FieldCondition> {
+    value
+        .and_then(|v| v.as_array())
+        .map(|items| items.iter().filter_map(|item| serde_json::from_value(item.clone()).ok()).collect())
+        .unwrap_or_default()
+}
+
+/// Reject conditions that can never be evaluated meaningfully
+pub fn validate_conditions(field_name: &str, conditions: &[FieldCondition]) -> Result<(), String> {
+    for condition in conditions {
+        if condition.field.trim().is_empty() {
+            return Err(format!("Condition on field '{}' does not name a controlling field", field_name));
+        }
+        if condition.field == field_name {
+            return Err(format!("Field '{}' cannot depend on itself", field_name));
+        }
+        let needs_value = matches!(
+            condition.operator,
+            ConditionOperator::Equals | ConditionOperator::NotEquals | ConditionOperator::Contains
+        );
+        if needs_value && condition.value.is_none() {
+            return Err(format!("Condition on field '{}' needs a value to compare against", field_name));
+        }
+    }
+    Ok(())
+}
+
+fn is_checked(value: &str) -> bool {
+    matches!(value.trim().to_lowercase().as_str(), "true" | "on" | "yes" | "1")
+}
+
+fn condition_holds(condition: &FieldCondition, value: &str) -> bool {
+    let value = value.trim();
+    let expected = condition.value.as_deref().unwrap_or("").trim();
+    match condition.operator {
+        ConditionOperator::Checked => is_checked(value),
+        ConditionOperator::Unchecked => !is_checked(value),
+        ConditionOperator::Equals => value == expected,
+        ConditionOperator::NotEquals => value != expected,
+        ConditionOperator::Contains => value.split(',').any(|item| item.trim() == expected),
+        ConditionOperator::Empty => value.is_empty(),
+        ConditionOperator::NotEmpty => !value.is_empty(),
+    }
+}
+
+/// Evaluate every field against the values filled in so far (by field name).
+///
+/// All `show` conditions must hold for a field to be visible and all `require`
+/// conditions must hold for it to become required; a hidden field is never
+/// required. A hidden controlling field counts as empty, so conditions chain.
+/// Fields caught in a dependency cycle stay visible.
+pub fn evaluate(fields: &[ConditionalField], values: &HashMap<String, String>) -> HashMap<String, FieldState> {
+    fn visible(
+        name: &str,
+        fields: &HashMap<&str, &ConditionalField>,
+        values: &HashMap<String, String>,
+        cache: &mut HashMap<String, bool>,
+        path: &mut Vec<String>,
+    ) -> bool {
+        if let Some(known) = cache.get(name) {
+            return *known;
+        }
+        let Some(field) = fields.get(name) else { return true };
+        if path.iter().any(|n| n == name) {
+            return true;
+        }
+
+        path.push(name.to_string());
+        let result = field.conditions.iter()
+            .filter(|c| c.action == ConditionAction::Show)
+            .all(|c| {
+                let value = effective_value(&c.field, fields, values, cache, path);
+                condition_holds(c, &value)
+            });
+        path.pop();
+
+        cache.insert(name.to_string(), result);
+        result
+    }
+
+    fn effective_value(
+        name: &str,
+        fields: &HashMap<&str, &ConditionalField>,
+        values: &HashMap<String, String>,
+        cache: &mut HashMap<String, bool>,
+        path: &mut Vec<String>,
+    ) -> String {
+        if visible(name, fields, values, cache, path) {
+            values.get(name).cloned().unwrap_or_default()
+        } else {
+            String::new()
+        }
+    }
+
+    let by_name: HashMap<&str, &ConditionalField> = fields.iter().map(|f| (f.name, f)).collect();
+    let mut cache = HashMap::new();
+    let mut states = HashMap::new();
+    for field in fields {
+        let is_visible = visible(field.name, &by_name, values, &mut cache, &mut Vec::new());
+        let require_conditions: Vec<&FieldCondition> = field.conditions.iter()
+            .filter(|c| c.action == ConditionAction::Require)
+            .collect();
+        let conditionally_required = !require_conditions.is_empty() && require_conditions.iter().all(|c| {
+            let value = effective_value(&c.field, &by_name, values, &mut cache, &mut Vec::new());
+            condition_holds(c, &value)
+        });
+        states.insert(field.name.to_string(), FieldState {
+            visible: is_visible,
+            required: is_visible && (field.required || conditionally_required),
+        });
+    }
+    states
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn condition(field: &str, action: ConditionAction, operator: ConditionOperator, value: Option<&str>) -> FieldCondition {
+        FieldCondition { field: field.to_string(), action, operator, value: value.map(str::to_string) }
+    }
+
+    fn values(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn test_checkbox_shows_and_requires_dependent_field() {
+        let fields = vec![
+            ConditionalField { name: "Has company", required: false, conditions: vec![] },
+            ConditionalField {
+                name: "Company",
+                required: true,
+                conditions: vec![condition("Has company", ConditionAction::Show, ConditionOperator::Checked, None)],
+            },
+        ];
+
+        let hidden = evaluate(&fields, &values(&[("Has company", "false")]));
+        assert_eq!(hidden["Company"], FieldState { visible: false, required: false });
+
+        let shown = evaluate(&fields, &values(&[("Has company", "true")]));
+        assert_eq!(shown["Company"], FieldState { visible: true, required: true });
+    }
+
+    #[test]
+    fn test_select_value_makes_field_required() {
+        let fields = vec![
+            ConditionalField { name: "Country", required: true, conditions: vec![] },
+            ConditionalField {
+                name: "Tax ID",
+                required: false,
+                conditions: vec![condition("Country", ConditionAction::Require, ConditionOperator::Equals, Some("VN"))],
+            },
+        ];
+
+        assert!(evaluate(&fields, &values(&[("Country", "VN")]))["Tax ID"].required);
+        let other = evaluate(&fields, &values(&[("Country", "US")]));
+        assert_eq!(other["Tax ID"], FieldState { visible: true, required: false });
+    }
+
+    #[test]
+    fn test_hidden_controller_hides_its_dependents() {
+        let fields = vec![
+            ConditionalField { name: "A", required: false, conditions: vec![] },
+            ConditionalField {
+                name: "B",
+                required: false,
+                conditions: vec![condition("A", ConditionAction::Show, ConditionOperator::Checked, None)],
+            },
+            ConditionalField {
+                name: "C",
+                required: true,
+                conditions: vec![condition("B", ConditionAction::Show, ConditionOperator::Contains, Some("x"))],
+            },
+        ];
+
+        // B still holds "x" from before A was unchecked, but B is hidden so C is too
+        let states = evaluate(&fields, &values(&[("A", "false"), ("B", "x,y")]));
+        assert!(!states["B"].visible);
+        assert_eq!(states["C"], FieldState { visible: false, required: false });
+    }
+
+    #[test]
+    fn test_cycles_stay_visible() {
+        let fields = vec![
+            ConditionalField {
+                name: "A",
+                required: false,
+                conditions: vec![condition("B", ConditionAction::Show, ConditionOperator::NotEmpty, None)],
+            },
+            ConditionalField {
+                name: "B",
+                required: false,
+                conditions: vec![condition("A", ConditionAction::Show, ConditionOperator::NotEmpty, None)],
+            },
+        ];
+        let states = evaluate(&fields, &values(&[("A", "1"), ("B", "1")]));
+        assert!(states["A"].visible && states["B"].visible);
+    }
+
+    #[test]
+    fn test_validate_conditions() {
+        let self_reference = [condition("A", ConditionAction::Show, ConditionOperator::Checked, None)];
+        assert!(validate_conditions("A", &self_reference).is_err());
+        let missing_value = [condition("B", ConditionAction::Show, ConditionOperator::Equals, None)];
+        assert!(validate_conditions("A", &missing_value).is_err());
+        let ok = [condition("B", ConditionAction::Require, ConditionOperator::Unchecked, None)];
+        assert!(validate_conditions("A", &ok).is_ok());
+    }
+
+    #[test]
+    fn test_values_are_scoped_to_the_role() {
+        let fields = vec![
+            RoleField { template_field_id: 1, name: "Name", role_id: Some(10) },
+            RoleField { template_field_id: 2, name: "Name", role_id: Some(20) },
+            RoleField { template_field_id: 3, name: "Country", role_id: Some(20) },
+        ];
+        let filled: HashMap<i64, String> = [(2, "Bob".to_string()), (3, "VN".to_string())].into_iter().collect();
+
+        // The first signer's own empty "Name" is not filled in by the second signer's
+        let first = role_scoped_values(&fields, &filled, Some(10));
+        assert_eq!(first.get("Name"), None);
+        assert_eq!(first["Country"], "VN");
+
+        let second = role_scoped_values(&fields, &filled, Some(20));
+        assert_eq!(second["Name"], "Bob");
+    }
+}
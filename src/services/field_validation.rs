@@ -0,0 +1,203 @@
+use chrono::format::{Item, StrftimeItems};
+use chrono::{NaiveDate, NaiveDateTime};
+use regex::Regex;
+
+use crate::models::template::{FieldValidation, ValueFormat};
+
+/// Why a submitted value was rejected; `code` is stable for clients to map
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldViolation {
+    pub code: &'static str,
+    pub message: String,
+}
+
+impl FieldViolation {
+    fn new(code: &'static str, message: String) -> Self {
+        Self { code, message }
+    }
+}
+
+/// Read the stored `validation` column; a malformed object is ignored
+pub fn parse_validation(value: Option<&serde_json::Value>) -> Option<FieldValidation> {
+    value
+        .filter(|v| !v.is_null())
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+}
+
+/// Reject rules that could never be satisfied or cannot be evaluated
+pub fn validate_rules(field_name: &str, rules: &FieldValidation) -> Result<(), String> {
+    if let Some(pattern) = &rules.pattern {
+        Regex::new(pattern).map_err(|e| format!("Invalid pattern for field '{}': {}", field_name, e))?;
+    }
+    if let (Some(min), Some(max)) = (rules.min_length, rules.max_length) {
+        if min > max {
+            return Err(format!("min_length is greater than max_length for field '{}'", field_name));
+        }
+    }
+    if let (Some(min), Some(max)) = (rules.min, rules.max) {
+        if min > max {
+            return Err(format!("min is greater than max for field '{}'", field_name));
+        }
+    }
+    if let Some(date_format) = &rules.date_format {
+        if date_format.trim().is_empty() || StrftimeItems::new(date_format).any(|item| matches!(item, Item::Error)) {
+            return Err(format!("Invalid date_format for field '{}'", field_name));
+        }
+    }
+    Ok(())
+}
+
+fn parse_date(value: &str, format: &str) -> bool {
+    NaiveDate::parse_from_str(value, format).is_ok() || NaiveDateTime::parse_from_str(value, format).is_ok()
+}
+
+pub fn is_email(value: &str) -> bool {
+    let Some((local, domain)) = value.split_once('@') else { return false };
+    !local.is_empty()
+        && !domain.contains('@')
+        && !value.chars().any(char::is_whitespace)
+        && domain.split('.').count() > 1
+        && domain.split('.').all(|label| !label.is_empty())
+}
+
+fn is_phone(value: &str) -> bool {
+    let digits = value.chars().filter(|c| c.is_ascii_digit()).count();
+    let body = value.strip_prefix('+').unwrap_or(value);
+    (7..=15).contains(&digits)
+        && body.chars().all(|c| c.is_ascii_digit() || matches!(c, ' ' | '-' | '.' | '(' | ')'))
+}
+
+/// Check a submitted value against the field's rules.
+///
+/// Empty values pass, `required` is enforced separately. Number fields must
+/// parse as numbers even without rules.
+pub fn check_value(field_type: &str, rules: Option<&FieldValidation>, value: &str) -> Result<(), FieldViolation> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(());
+    }
+
+    let default_rules = FieldValidation::default();
+    let rules = rules.unwrap_or(&default_rules);
+    let fail = |code: &'static str, message: String| {
+        Err(FieldViolation::new(code, rules.message.clone().unwrap_or(message)))
+    };
+
+    let length = value.chars().count();
+    if let Some(min_length) = rules.min_length {
+        if length < min_length {
+            return fail("min_length", format!("Must be at least {} characters", min_length));
+        }
+    }
+    if let Some(max_length) = rules.max_length {
+        if length > max_length {
+            return fail("max_length", format!("Must be at most {} characters", max_length));
+        }
+    }
+
+    if field_type == "number" || rules.min.is_some() || rules.max.is_some() {
+        let Ok(number) = value.parse::<f64>() else {
+            return fail("number", "Must be a number".to_string());
+        };
+        if let Some(min) = rules.min {
+            if number < min {
+                return fail("min", format!("Must be at least {}", min));
+            }
+        }
+        if let Some(max) = rules.max {
+            if number > max {
+                return fail("max", format!("Must be at most {}", max));
+            }
+        }
+    }
+
+    if let Some(date_format) = &rules.date_format {
+        if !parse_date(value, date_format) {
+            return fail("date_format", format!("Must be a date in the format {}", date_format));
+        }
+    }
+
+    match rules.format {
+        Some(ValueFormat::Email) if !is_email(value) => {
+            return fail("email", "Must be a valid email address".to_string());
+        }
+        Some(ValueFormat::Phone) if !is_phone(value) => {
+            return fail("phone", "Must be a valid phone number".to_string());
+        }
+        _ => {}
+    }
+
+    if let Some(pattern) = &rules.pattern {
+        // Anchored so the whole value has to match, not just a part of it
+        let matches = Regex::new(&format!("^(?:{})$", pattern))
+            .map(|re| re.is_match(value))
+            .unwrap_or(false);
+        if !matches {
+            return fail("pattern", "Does not match the expected format".to_string());
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_length_and_pattern() {
+        let rules = FieldValidation {
+            pattern: Some("[A-Z]{2}[0-9]+".to_string()),
+            min_length: Some(3),
+            max_length: Some(6),
+            ..Default::default()
+        };
+        assert!(check_value("text", Some(&rules), "VN123").is_ok());
+        assert_eq!(check_value("text", Some(&rules), "V1").unwrap_err().code, "min_length");
+        assert_eq!(check_value("text", Some(&rules), "VN12345").unwrap_err().code, "max_length");
+        // Pattern is anchored to the whole value
+        assert_eq!(check_value("text", Some(&rules), "xVN12").unwrap_err().code, "pattern");
+        assert!(check_value("text", Some(&rules), "").is_ok());
+    }
+
+    #[test]
+    fn test_numeric_bounds() {
+        let rules = FieldValidation { min: Some(1.0), max: Some(10.0), ..Default::default() };
+        assert!(check_value("number", Some(&rules), "2.5").is_ok());
+        assert_eq!(check_value("number", Some(&rules), "11").unwrap_err().code, "max");
+        assert_eq!(check_value("number", None, "abc").unwrap_err().code, "number");
+        assert!(check_value("text", None, "abc").is_ok());
+    }
+
+    #[test]
+    fn test_date_email_phone() {
+        let date = FieldValidation { date_format: Some("%d/%m/%Y".to_string()), ..Default::default() };
+        assert!(check_value("date", Some(&date), "31/12/2025").is_ok());
+        assert_eq!(check_value("date", Some(&date), "2025-12-31").unwrap_err().code, "date_format");
+
+        let email = FieldValidation { format: Some(ValueFormat::Email), ..Default::default() };
+        assert!(check_value("text", Some(&email), "a.b@example.com").is_ok());
+        assert!(check_value("text", Some(&email), "a@b").is_err());
+
+        let phone = FieldValidation {
+            format: Some(ValueFormat::Phone),
+            message: Some("Enter a phone number".to_string()),
+            ..Default::default()
+        };
+        assert!(check_value("text", Some(&phone), "+84 (28) 1234-5678").is_ok());
+        let violation = check_value("text", Some(&phone), "12ab").unwrap_err();
+        assert_eq!(violation, FieldViolation::new("phone", "Enter a phone number".to_string()));
+    }
+
+    #[test]
+    fn test_validate_rules() {
+        let bad_pattern = FieldValidation { pattern: Some("(".to_string()), ..Default::default() };
+        assert!(validate_rules("A", &bad_pattern).is_err());
+        let bad_range = FieldValidation { min: Some(5.0), max: Some(1.0), ..Default::default() };
+        assert!(validate_rules("A", &bad_range).is_err());
+        let bad_date = FieldValidation { date_format: Some("%Q".to_string()), ..Default::default() };
+        assert!(validate_rules("A", &bad_date).is_err());
+        let ok = FieldValidation { date_format: Some("%Y-%m-%d".to_string()), ..Default::default() };
+        assert!(validate_rules("A", &ok).is_ok());
+    }
+}
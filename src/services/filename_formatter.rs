@@ -1,6 +1,8 @@
 use chrono::NaiveDateTime;
 use serde_json::Value;
 
+use crate::services::i18n::Localizer;
+
 /// Apply filename format template with placeholders
 /// 
 /// Placeholders:
@@ -8,13 +10,18 @@ use serde_json::Value;
 /// - {submission.status} -> "Signed" or "Completed"
 /// - {submission.submitters} -> submitter email(s)
 /// - {submission.completed_at} -> completion date
+///
+/// The status and date are written in `locale`; `completed_at` is already in the account's timezone.
 pub fn apply_filename_format(
     format: &str,
     document_name: &str,
     submission_status: &str,
     submitter_emails: Vec<String>,
     completed_at: Option<NaiveDateTime>,
+    locale: &str,
 ) -> String {
+    let loc = Localizer::new(Some(locale), None);
+
     let mut result = format.to_string();
     
     // Remove .pdf extension from document name if exists
@@ -25,10 +32,10 @@ pub fn apply_filename_format(
     
     // Replace {submission.status}
     let status_display = match submission_status {
-        "completed" | "signed" => "Signed",
-        _ => "Completed"
+        "completed" | "signed" => loc.t("filename.signed"),
+        _ => loc.t("filename.completed")
     };
-    result = result.replace("{submission.status}", status_display);
+    result = result.replace("{submission.status}", &status_display);
     
     // Replace {submission.submitters}
     let submitters_str = if submitter_emails.is_empty() {
@@ -40,7 +47,7 @@ pub fn apply_filename_format(
     
     // Replace {submission.completed_at}
     if let Some(date) = completed_at {
-        let formatted_date = date.format("%b %d, %Y").to_string();
+        let formatted_date = loc.medium_date(date.date());
         result = result.replace("{submission.completed_at}", &formatted_date);
     } else {
         result = result.replace("{submission.completed_at}", "");
@@ -77,6 +84,7 @@ mod tests {
             "signed",
             vec![],
             None,
+            "en",
         );
         assert_eq!(result, "Contract.pdf");
     }
@@ -89,6 +97,7 @@ mod tests {
             "signed",
             vec![],
             None,
+            "en",
         );
         assert_eq!(result, "Contract - Signed.pdf");
     }
@@ -101,6 +110,7 @@ mod tests {
             "signed",
             vec!["user@example.com".to_string()],
             None,
+            "en",
         );
         assert_eq!(result, "Contract - user@example.com.pdf");
     }
@@ -118,7 +128,26 @@ mod tests {
             "signed",
             vec!["user@example.com".to_string()],
             Some(date),
+            "en",
         );
         assert_eq!(result, "Contract - user@example.com - Dec 05, 2025.pdf");
     }
+
+    #[test]
+    fn test_localized_status_and_date() {
+        let date = NaiveDate::from_ymd_opt(2025, 12, 5)
+            .unwrap()
+            .and_hms_opt(10, 0, 0)
+            .unwrap();
+
+        let result = apply_filename_format(
+            "{document.name} - {submission.status} - {submission.completed_at}",
+            "Contrat",
+            "completed",
+            vec![],
+            Some(date),
+            "fr-FR",
+        );
+        assert_eq!(result, "Contrat - Signé - 05 déc. 2025.pdf");
+    }
 }
@@ -0,0 +1,387 @@
+use std::collections::{HashMap, HashSet};
+
+/// Field type whose value is computed on the server from other fields
+pub const FIELD_TYPE_FORMULA: &str = "formula";
+
+/// Parsed formula expression.
+///
+/// Syntax: numbers, `{Field name}` references, `+ - * /`, parentheses and the
+/// functions `sum`, `round(x[, digits])`, `min`, `max`, `abs`, `ceil`, `floor`,
+/// e.g. `round({Quantity} * {Unit price} * 1.1, 2)`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Number(f64),
+    Field(String),
+    Neg(Box<Expr>),
+    Binary(Box<Expr>, char, Box<Expr>),
+    Call(String, Vec<Expr>),
+}
+
+const FUNCTIONS: &[&str] = &["sum", "round", "min", "max", "abs", "ceil", "floor"];
+/// Longest formula accepted, in characters
+const MAX_LENGTH: usize = 2000;
+/// Deepest nesting of parentheses, signs and function calls; keeps the parser off the end of the stack
+const MAX_DEPTH: usize = 32;
+
+struct Parser<'a> {
+    chars: std::iter::Peekable<std::str::CharIndices<'a>>,
+    source: &'a str,
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    // Parse one nested level with `parse`
+    fn nested<T>(&mut self, parse: impl FnOnce(&mut Self) -> Result<T, String>) -> Result<T, String> {
+        if self.depth >= MAX_DEPTH {
+            return Err(format!("Formula is nested more than {} levels deep", MAX_DEPTH));
+        }
+        self.depth += 1;
+        let result = parse(self);
+        self.depth -= 1;
+        result
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.skip_whitespace();
+        self.chars.peek().map(|(_, c)| *c)
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        match self.peek() {
+            Some(c) if c == expected => {
+                self.chars.next();
+                Ok(())
+            }
+            Some(c) => Err(format!("Expected '{}' but found '{}'", expected, c)),
+            None => Err(format!("Expected '{}' at end of formula", expected)),
+        }
+    }
+
+    fn expression(&mut self) -> Result<Expr, String> {
+        let mut left = self.term()?;
+        while let Some(op @ ('+' | '-')) = self.peek() {
+            self.chars.next();
+            left = Expr::Binary(Box::new(left), op, Box::new(self.term()?));
+        }
+        Ok(left)
+    }
+
+    fn term(&mut self) -> Result<Expr, String> {
+        let mut left = self.factor()?;
+        while let Some(op @ ('*' | '/')) = self.peek() {
+            self.chars.next();
+            left = Expr::Binary(Box::new(left), op, Box::new(self.factor()?));
+        }
+        Ok(left)
+    }
+
+    fn factor(&mut self) -> Result<Expr, String> {
+        match self.peek() {
+            Some('-') => {
+                self.chars.next();
+                Ok(Expr::Neg(Box::new(self.nested(Self::factor)?)))
+            }
+            Some('+') => {
+                self.chars.next();
+                self.nested(Self::factor)
+            }
+            _ => self.primary(),
+        }
+    }
+
+    fn primary(&mut self) -> Result<Expr, String> {
+        match self.peek() {
+            Some('(') => {
+                self.chars.next();
+                let inner = self.nested(Self::expression)?;
+                self.expect(')')?;
+                Ok(inner)
+            }
+            Some('{') => {
+                self.chars.next();
+                let mut name = String::new();
+                loop {
+                    match self.chars.next() {
+                        Some((_, '}')) => break,
+                        Some((_, c)) => name.push(c),
+                        None => return Err("Unclosed field reference".to_string()),
+                    }
+                }
+                let name = name.trim();
+                if name.is_empty() {
+                    return Err("Empty field reference".to_string());
+                }
+                Ok(Expr::Field(name.to_string()))
+            }
+            Some(c) if c.is_ascii_digit() || c == '.' => {
+                let (start, _) = *self.chars.peek().unwrap();
+                let mut end = start;
+                while let Some((i, c)) = self.chars.next_if(|(_, c)| c.is_ascii_digit() || *c == '.') {
+                    end = i + c.len_utf8();
+                }
+                let literal = &self.source[start..end];
+                literal.parse().map(Expr::Number).map_err(|_| format!("Invalid number '{}'", literal))
+            }
+            Some(c) if c.is_ascii_alphabetic() => {
+                let mut name = String::new();
+                while let Some((_, c)) = self.chars.next_if(|(_, c)| c.is_ascii_alphanumeric() || *c == '_') {
+                    name.push(c.to_ascii_lowercase());
+                }
+                if !FUNCTIONS.contains(&name.as_str()) {
+                    return Err(format!("Unknown function '{}'", name));
+                }
+                self.expect('(')?;
+                let args = self.nested(|parser| {
+                    let mut args = Vec::new();
+                    if parser.peek() != Some(')') {
+                        args.push(parser.expression()?);
+                        while parser.peek() == Some(',') {
+                            parser.chars.next();
+                            args.push(parser.expression()?);
+                        }
+                    }
+                    Ok(args)
+                })?;
+                self.expect(')')?;
+                let arity_ok = match name.as_str() {
+                    "round" => (1..=2).contains(&args.len()),
+                    "abs" | "ceil" | "floor" => args.len() == 1,
+                    _ => !args.is_empty(),
+                };
+                if !arity_ok {
+                    return Err(format!("Wrong number of arguments for '{}'", name));
+                }
+                Ok(Expr::Call(name, args))
+            }
+            Some(c) => Err(format!("Unexpected '{}' in formula", c)),
+            None => Err("Unexpected end of formula".to_string()),
+        }
+    }
+}
+
+/// Parse a formula expression
+pub fn parse(source: &str) -> Result<Expr, String> {
+    if source.chars().count() > MAX_LENGTH {
+        return Err(format!("Formula is longer than {} characters", MAX_LENGTH));
+    }
+    let mut parser = Parser { chars: source.char_indices().peekable(), source, depth: 0 };
+    let expr = parser.expression()?;
+    match parser.peek() {
+        None => Ok(expr),
+        Some(c) => Err(format!("Unexpected '{}' in formula", c)),
+    }
+}
+
+/// Names of the fields an expression reads
+pub fn references(expr: &Expr) -> Vec<String> {
+    fn collect(expr: &Expr, out: &mut Vec<String>) {
+        match expr {
+            Expr::Number(_) => {}
+            Expr::Field(name) => {
+                if !out.contains(name) {
+                    out.push(name.clone());
+                }
+            }
+            Expr::Neg(inner) => collect(inner, out),
+            Expr::Binary(left, _, right) => {
+                collect(left, out);
+                collect(right, out);
+            }
+            Expr::Call(_, args) => args.iter().for_each(|arg| collect(arg, out)),
+        }
+    }
+    let mut out = Vec::new();
+    collect(expr, &mut out);
+    out
+}
+
+/// Evaluate an expression, resolving field references through `lookup`
+pub fn evaluate(expr: &Expr, lookup: &mut dyn FnMut(&str) -> Result<f64, String>) -> Result<f64, String> {
+    let result = match expr {
+        Expr::Number(n) => *n,
+        Expr::Field(name) => lookup(name)?,
+        Expr::Neg(inner) => -evaluate(inner, lookup)?,
+        Expr::Binary(left, op, right) => {
+            let (left, right) = (evaluate(left, lookup)?, evaluate(right, lookup)?);
+            match op {
+                '+' => left + right,
+                '-' => left - right,
+                '*' => left * right,
+                _ if right == 0.0 => return Err("Division by zero".to_string()),
+                _ => left / right,
+            }
+        }
+        Expr::Call(name, args) => {
+            let args = args.iter().map(|arg| evaluate(arg, lookup)).collect::<Result<Vec<f64>, String>>()?;
+            match name.as_str() {
+                "sum" => args.iter().sum(),
+                "min" => args.iter().copied().fold(f64::INFINITY, f64::min),
+                "max" => args.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+                "abs" => args[0].abs(),
+                "ceil" => args[0].ceil(),
+                "floor" => args[0].floor(),
+                _ => {
+                    let factor = 10f64.powi(args.get(1).copied().unwrap_or(0.0) as i32);
+                    (args[0] * factor).round() / factor
+                }
+            }
+        }
+    };
+    if result.is_finite() {
+        Ok(result)
+    } else {
+        Err("Formula result is not a finite number".to_string())
+    }
+}
+
+/// The expression of a formula field, kept in its `options` as `{"formula": "..."}`
+pub fn formula_of(options: Option<&serde_json::Value>) -> Option<&str> {
+    options.and_then(|o| o.get("formula")).and_then(|f| f.as_str())
+}
+
+/// Check a formula field definition when a template field is saved
+pub fn validate_formula_field(field_name: &str, field_type: &str, options: Option<&serde_json::Value>) -> Result<(), String> {
+    if field_type != FIELD_TYPE_FORMULA {
+        return Ok(());
+    }
+    let source = formula_of(options)
+        .ok_or_else(|| format!("Formula field '{}' needs options.formula", field_name))?;
+    let expr = parse(source).map_err(|e| format!("Invalid formula for field '{}': {}", field_name, e))?;
+    if references(&expr).iter().any(|r| r == field_name) {
+        return Err(format!("Formula field '{}' cannot reference itself", field_name));
+    }
+    Ok(())
+}
+
+/// Render a computed number the way it is stored and drawn
+pub fn format_number(value: f64) -> String {
+    if value.fract() == 0.0 && value.abs() < 1e15 {
+        format!("{}", value as i64)
+    } else {
+        let formatted = format!("{:.10}", value);
+        formatted.trim_end_matches('0').trim_end_matches('.').to_string()
+    }
+}
+
+/// Compute every formula (by field name) against the filled-in values.
+///
+/// Formulas may reference other formulas; empty references count as 0.
+/// Returns per-field results so one broken formula does not hide the others.
+pub fn compute_all(
+    formulas: &HashMap<String, String>,
+    values: &HashMap<String, String>,
+) -> HashMap<String, Result<String, String>> {
+    fn resolve(
+        name: &str,
+        formulas: &HashMap<String, String>,
+        values: &HashMap<String, String>,
+        done: &mut HashMap<String, Result<f64, String>>,
+        visiting: &mut HashSet<String>,
+    ) -> Result<f64, String> {
+        if let Some(result) = done.get(name) {
+            return result.clone();
+        }
+        let Some(source) = formulas.get(name) else {
+            let raw = values.get(name).map(|v| v.trim()).unwrap_or("");
+            if raw.is_empty() {
+                return Ok(0.0);
+            }
+            return raw.parse::<f64>().map_err(|_| format!("Field '{}' is not a number", name));
+        };
+        if !visiting.insert(name.to_string()) {
+            return Err(format!("Formula '{}' references itself", name));
+        }
+        let result = parse(source).and_then(|expr| {
+            evaluate(&expr, &mut |reference| resolve(reference, formulas, values, done, visiting))
+        });
+        visiting.remove(name);
+        done.insert(name.to_string(), result.clone());
+        result
+    }
+
+    let mut done = HashMap::new();
+    formulas.keys()
+        .map(|name| {
+            let result = resolve(name, formulas, values, &mut done, &mut HashSet::new());
+            (name.clone(), result.map(format_number))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn compute(formula: &str, pairs: &[(&str, &str)]) -> Result<String, String> {
+        let formulas = HashMap::from([("Total".to_string(), formula.to_string())]);
+        let values = pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        compute_all(&formulas, &values).remove("Total").unwrap()
+    }
+
+    #[test]
+    fn test_arithmetic_and_precedence() {
+        assert_eq!(compute("1 + 2 * 3 - -4 / 2", &[]), Ok("9".to_string()));
+        assert_eq!(compute("(1 + 2) * 3", &[]), Ok("9".to_string()));
+        assert_eq!(compute("{Quantity} * {Unit price}", &[("Quantity", "3"), ("Unit price", "19.99")]), Ok("59.97".to_string()));
+    }
+
+    #[test]
+    fn test_functions() {
+        assert_eq!(compute("round({A} / 3, 2)", &[("A", "10")]), Ok("3.33".to_string()));
+        assert_eq!(compute("sum({A}, {B}, {C})", &[("A", "1"), ("B", "2.5")]), Ok("3.5".to_string()));
+        assert_eq!(compute("max(1, {A}) + min(4, 2) + abs(-1) + ceil(0.2) + floor(1.8)", &[("A", "5")]), Ok("10".to_string()));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(compute("{A} / 0", &[("A", "1")]).is_err());
+        assert!(compute("{A} + 1", &[("A", "abc")]).is_err());
+        assert!(parse("1 +").is_err());
+        assert!(parse("exec(1)").is_err());
+        assert!(parse("round(1, 2, 3)").is_err());
+        assert!(parse("{A").is_err());
+    }
+
+    #[test]
+    fn test_length_and_depth_are_bounded() {
+        let nested = |depth: usize| format!("{}1{}", "(".repeat(depth), ")".repeat(depth));
+        assert!(parse(&nested(MAX_DEPTH)).is_ok());
+        assert!(parse(&nested(MAX_DEPTH + 1)).unwrap_err().contains("nested"));
+        assert!(parse(&format!("{}1", "-".repeat(MAX_DEPTH + 1))).is_err());
+        assert!(parse(&format!("{}1{}", "abs(".repeat(MAX_DEPTH + 1), ")".repeat(MAX_DEPTH + 1))).is_err());
+        assert!(parse(&"(".repeat(500_000)).unwrap_err().contains("longer than"));
+        assert!(parse(&vec!["1"; MAX_LENGTH].join("+")).is_err());
+    }
+
+    #[test]
+    fn test_chained_and_cyclic_formulas() {
+        let formulas = HashMap::from([
+            ("Subtotal".to_string(), "{Qty} * {Price}".to_string()),
+            ("Total".to_string(), "round({Subtotal} * 1.1, 2)".to_string()),
+            ("Loop A".to_string(), "{Loop B} + 1".to_string()),
+            ("Loop B".to_string(), "{Loop A} + 1".to_string()),
+        ]);
+        let values = HashMap::from([
+            ("Qty".to_string(), "2".to_string()),
+            ("Price".to_string(), "5".to_string()),
+            // Client-sent values for formula fields are ignored
+            ("Subtotal".to_string(), "1000".to_string()),
+        ]);
+        let results = compute_all(&formulas, &values);
+        assert_eq!(results["Total"], Ok("11".to_string()));
+        assert!(results["Loop A"].is_err() && results["Loop B"].is_err());
+    }
+
+    #[test]
+    fn test_validate_formula_field() {
+        let options = serde_json::json!({ "formula": "{Total} + 1" });
+        assert!(validate_formula_field("Total", FIELD_TYPE_FORMULA, Some(&options)).is_err());
+        assert!(validate_formula_field("Grand total", FIELD_TYPE_FORMULA, Some(&options)).is_ok());
+        assert!(validate_formula_field("Grand total", FIELD_TYPE_FORMULA, None).is_err());
+        assert!(validate_formula_field("Name", "text", None).is_ok());
+    }
+}
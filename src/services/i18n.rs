@@ -0,0 +1,292 @@
+//! Message catalogs for what the server writes itself: built-in emails, audit log
+//! PDFs, public API errors and dates. Keys missing from a catalog fall back to English.
+//!
+//! Dates use the catalog's strftime patterns (`date.short`, `date.medium`,
+//! `date.datetime`); `%b` and `%B` print the catalog's month names.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use axum::http::{header, HeaderMap};
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, Utc};
+
+use crate::database::connection::DbPool;
+use crate::database::models::{DbGlobalSettings, DbSubmitter};
+use crate::database::queries::GlobalSettingsQueries;
+use crate::services::timezone;
+
+pub const DEFAULT_LOCALE: &str = "en";
+
+/// Locales with a catalog, the same ones the web app ships
+pub const SUPPORTED_LOCALES: &[&str] = &["en", "fr", "es", "pt", "de", "it", "nl", "vi"];
+
+static CATALOGS: OnceLock<HashMap<&'static str, HashMap<String, String>>> = OnceLock::new();
+
+fn catalog_source(locale: &str) -> &'static str {
+    match locale {
+        "fr" => include_str!("locales/fr.json"),
+        "es" => include_str!("locales/es.json"),
+        "pt" => include_str!("locales/pt.json"),
+        "de" => include_str!("locales/de.json"),
+        "it" => include_str!("locales/it.json"),
+        "nl" => include_str!("locales/nl.json"),
+        "vi" => include_str!("locales/vi.json"),
+        _ => include_str!("locales/en.json"),
+    }
+}
+
+fn catalogs() -> &'static HashMap<&'static str, HashMap<String, String>> {
+    CATALOGS.get_or_init(|| {
+        SUPPORTED_LOCALES.iter().map(|&locale| {
+            // Catalogs are compiled in and covered by tests, so a broken one is a build mistake
+            let messages = serde_json::from_str(catalog_source(locale))
+                .unwrap_or_else(|e| panic!("Invalid message catalog for '{}': {}", locale, e));
+            (locale, messages)
+        }).collect()
+    })
+}
+
+/// Supported locale for a tag like `fr`, `fr-CA` or `pt_BR`; None when there is no catalog for it
+pub fn supported_locale(tag: Option<&str>) -> Option<&'static str> {
+    let language = tag?.trim().split(['-', '_']).next()?.to_ascii_lowercase();
+    SUPPORTED_LOCALES.iter().copied().find(|&locale| locale == language)
+}
+
+/// First candidate with a catalog, English if none has one
+pub fn resolve_locale<'a>(candidates: impl IntoIterator<Item = Option<&'a str>>) -> &'static str {
+    candidates.into_iter().find_map(supported_locale).unwrap_or(DEFAULT_LOCALE)
+}
+
+/// Preferred supported language of an `Accept-Language` header, by q-value
+pub fn accept_language(headers: &HeaderMap) -> Option<&'static str> {
+    let value = headers.get(header::ACCEPT_LANGUAGE)?.to_str().ok()?;
+    let mut languages: Vec<(f32, &'static str)> = value.split(',').filter_map(|part| {
+        let mut pieces = part.split(';');
+        let locale = supported_locale(pieces.next())?;
+        let quality = pieces
+            .find_map(|p| p.trim().strip_prefix("q="))
+            .and_then(|q| q.trim().parse::<f32>().ok())
+            .unwrap_or(1.0);
+        (quality > 0.0).then_some((quality, locale))
+    }).collect();
+    // Stable sort keeps the header order between equal q-values
+    languages.sort_by(|a, b| b.0.total_cmp(&a.0));
+    languages.first().map(|&(_, locale)| locale)
+}
+
+/// Check a locale given through the API, returning the catalog it maps to
+pub fn validate_locale(tag: &str) -> Result<&'static str, String> {
+    supported_locale(Some(tag)).ok_or_else(|| format!(
+        "Unsupported locale '{}'. Must be one of: {}", tag, SUPPORTED_LOCALES.join(", ")
+    ))
+}
+
+/// Message for `key`, the key itself if no catalog has it
+pub fn t(locale: &str, key: &str) -> String {
+    let catalogs = catalogs();
+    catalogs.get(locale).and_then(|messages| messages.get(key))
+        .or_else(|| catalogs.get(DEFAULT_LOCALE).and_then(|messages| messages.get(key)))
+        .cloned()
+        .unwrap_or_else(|| key.to_string())
+}
+
+/// Message for `key` with its `{name}` placeholders filled in
+pub fn t_args(locale: &str, key: &str, args: &[(&str, &str)]) -> String {
+    args.iter().fold(t(locale, key), |message, (name, value)| {
+        message.replace(&format!("{{{}}}", name), value)
+    })
+}
+
+// Swap %b/%B for the catalog's month names; they may contain characters strftime
+// would treat specially, so a literal '%' in them is escaped
+fn localize_pattern(locale: &str, pattern: &str, month0: u32) -> String {
+    let month_name = |key: &str| {
+        t(locale, key).split('|').nth(month0 as usize).unwrap_or_default().replace('%', "%%")
+    };
+    let mut localized = String::with_capacity(pattern.len());
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            localized.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('b') | Some('h') => localized.push_str(&month_name("date.months_short")),
+            Some('B') => localized.push_str(&month_name("date.months_long")),
+            Some(other) => {
+                localized.push('%');
+                localized.push(other);
+            }
+            None => localized.push('%'),
+        }
+    }
+    localized
+}
+
+/// Format a date or time with a strftime pattern, month names in the given locale
+pub fn format_with_pattern<T: Datelike>(locale: &str, value: &T, pattern: &str, format: impl FnOnce(&str) -> String) -> String {
+    format(&localize_pattern(locale, pattern, value.month0()))
+}
+
+/// Writes messages and dates for one reader: a locale and the timezone dates are shown in
+#[derive(Debug, Clone, Copy)]
+pub struct Localizer {
+    pub locale: &'static str,
+    pub utc_offset: FixedOffset,
+}
+
+impl Default for Localizer {
+    fn default() -> Self {
+        Self::new(None, None)
+    }
+}
+
+impl Localizer {
+    pub fn new(locale: Option<&str>, timezone: Option<&str>) -> Self {
+        Self {
+            locale: resolve_locale([locale]),
+            utc_offset: timezone::utc_offset(timezone),
+        }
+    }
+
+    /// The account's locale and timezone; `preferred` (e.g. a submitter's language) wins when supported
+    pub fn from_settings(settings: Option<&DbGlobalSettings>, preferred: Option<&str>) -> Self {
+        Self {
+            locale: resolve_locale([preferred, settings.and_then(|s| s.locale.as_deref())]),
+            utc_offset: timezone::utc_offset(settings.and_then(|s| s.timezone.as_deref())),
+        }
+    }
+
+    /// Localizer of the account a user belongs to. A failed lookup falls back to the defaults.
+    pub async fn for_user(pool: &DbPool, user_id: i64) -> Self {
+        match GlobalSettingsQueries::get_user_settings(pool, user_id as i32).await {
+            Ok(settings) => Self::from_settings(settings.as_ref(), None),
+            Err(e) => {
+                eprintln!("Failed to get settings of user {}: {}", user_id, e);
+                Self::default()
+            }
+        }
+    }
+
+    /// Localizer for a signer on a public page: the submitter's language, then the
+    /// browser's, then the account's
+    pub async fn for_signer(pool: &DbPool, submitter: &DbSubmitter, headers: &HeaderMap) -> Self {
+        Self::for_user(pool, submitter.user_id).await
+            .preferring(accept_language(headers))
+            .preferring(submitter.locale.as_deref())
+    }
+
+    /// Same timezone, `locale` instead when it has a catalog
+    pub fn preferring(self, locale: Option<&str>) -> Self {
+        Self { locale: supported_locale(locale).unwrap_or(self.locale), ..self }
+    }
+
+    pub fn t(&self, key: &str) -> String {
+        t(self.locale, key)
+    }
+
+    pub fn t_args(&self, key: &str, args: &[(&str, &str)]) -> String {
+        t_args(self.locale, key, args)
+    }
+
+    /// Numeric date, e.g. 12/05/2025 in English and 05.12.2025 in German
+    pub fn date(&self, date: NaiveDate) -> String {
+        self.format_date(date, &self.t("date.short"))
+    }
+
+    /// Date with the month name, e.g. Dec 05, 2025 in English and 05 déc. 2025 in French
+    pub fn medium_date(&self, date: NaiveDate) -> String {
+        self.format_date(date, &self.t("date.medium"))
+    }
+
+    /// Date and time in the reader's timezone
+    pub fn datetime(&self, at: DateTime<Utc>) -> String {
+        self.format_datetime(at, &self.t("date.datetime"))
+    }
+
+    /// Value of a date field as the reader writes dates. ISO dates and RFC 3339 times
+    /// are localized; anything else was entered in a format of its own and is kept.
+    pub fn date_value(&self, value: &str) -> String {
+        let value = value.trim();
+        if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+            self.date(date)
+        } else if let Ok(at) = DateTime::parse_from_rfc3339(value) {
+            self.datetime(at.with_timezone(&Utc))
+        } else {
+            value.to_string()
+        }
+    }
+
+    pub fn format_date(&self, date: NaiveDate, pattern: &str) -> String {
+        format_with_pattern(self.locale, &date, pattern, |pattern| date.format(pattern).to_string())
+    }
+
+    /// `at` in the reader's timezone with a strftime pattern
+    pub fn format_datetime(&self, at: DateTime<Utc>, pattern: &str) -> String {
+        let local = at.with_timezone(&self.utc_offset);
+        format_with_pattern(self.locale, &local, pattern, |pattern| local.format(pattern).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use chrono::format::{Item, StrftimeItems};
+
+    fn is_valid_date_pattern(pattern: &str) -> bool {
+        !StrftimeItems::new(pattern).any(|item| matches!(item, Item::Error))
+    }
+
+    #[test]
+    fn test_catalogs_have_the_same_keys() {
+        let english = &catalogs()[DEFAULT_LOCALE];
+        for &locale in SUPPORTED_LOCALES {
+            let messages = &catalogs()[locale];
+            let missing: Vec<_> = english.keys().filter(|key| !messages.contains_key(*key)).collect();
+            let extra: Vec<_> = messages.keys().filter(|key| !english.contains_key(*key)).collect();
+            assert!(missing.is_empty() && extra.is_empty(), "{}: missing {:?}, extra {:?}", locale, missing, extra);
+            for pattern in ["date.short", "date.medium", "date.datetime"] {
+                assert!(is_valid_date_pattern(&messages[pattern]), "{}: {}", locale, pattern);
+            }
+            for months in ["date.months_short", "date.months_long"] {
+                assert_eq!(messages[months].split('|').count(), 12, "{}: {}", locale, months);
+            }
+        }
+    }
+
+    #[test]
+    fn test_locale_resolution() {
+        assert_eq!(supported_locale(Some("fr-CA")), Some("fr"));
+        assert_eq!(supported_locale(Some("pt_BR")), Some("pt"));
+        assert_eq!(supported_locale(Some("ja")), None);
+        assert_eq!(resolve_locale([None, Some("xx"), Some("de-DE")]), "de");
+        assert_eq!(resolve_locale([None]), "en");
+        assert!(validate_locale("klingon").unwrap_err().contains("en, fr"));
+
+        let mut headers = HeaderMap::new();
+        headers.insert(header::ACCEPT_LANGUAGE, "ja;q=1.0, nl;q=0.5, es-ES;q=0.8, *;q=0.1".parse().unwrap());
+        assert_eq!(accept_language(&headers), Some("es"));
+    }
+
+    #[test]
+    fn test_messages_and_dates() {
+        assert_eq!(t_args("fr", "email.greeting", &[("name", "Jeanne")]), "Bonjour Jeanne,");
+        assert_eq!(t("xx", "error.link_voided"), "This signing request has been cancelled by the sender.");
+        assert_eq!(t("de", "no.such.key"), "no.such.key");
+
+        let date = NaiveDate::from_ymd_opt(2025, 12, 5).unwrap();
+        assert_eq!(Localizer::new(Some("en-US"), None).medium_date(date), "Dec 05, 2025");
+        assert_eq!(Localizer::new(Some("fr"), None).medium_date(date), "05 déc. 2025");
+        assert_eq!(Localizer::new(Some("de"), None).date(date), "05.12.2025");
+
+        let at = Utc.with_ymd_and_hms(2025, 12, 31, 23, 30, 0).unwrap();
+        let berlin = Localizer::new(Some("de"), Some("Europe/Berlin"));
+        assert_eq!(berlin.datetime(at), "01.01.2026, 00:30:00");
+        assert_eq!(berlin.format_datetime(at, "%d %B %Y"), "01 Januar 2026");
+        assert_eq!(Localizer::new(Some("en"), Some("UTC")).datetime(at), "12/31/2025, 23:30:00");
+
+        assert_eq!(berlin.date_value("2025-12-05"), "05.12.2025");
+        assert_eq!(berlin.date_value("2025-12-31T23:30:00Z"), "01.01.2026, 00:30:00");
+        assert_eq!(berlin.date_value("31/12/2025"), "31/12/2025");
+    }
+}
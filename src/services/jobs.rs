@@ -0,0 +1,152 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tokio::time::{sleep, Duration};
+use chrono::{DateTime, Utc};
+use futures::future::BoxFuture;
+use futures::stream::{self, StreamExt};
+
+use crate::database::connection::DbPool;
+use crate::database::models::DbJob;
+use crate::database::queries::JobQueries;
+
+pub const JOB_RECORD_PAYMENT: &str = "record_payment";
+pub const JOB_SEND_REMINDER: &str = "send_reminder";
+pub const JOB_AUTO_SIGN: &str = "auto_sign";
+pub const JOB_COMPLETION_EMAILS: &str = "completion_emails";
+pub const JOB_BULK_SEND: &str = "bulk_send";
+pub const JOB_PURGE_EMAIL_BODIES: &str = "purge_email_bodies";
+
+/// Runs before a failing job is dead-lettered
+pub const DEFAULT_MAX_ATTEMPTS: i32 = 5;
+
+const BASE_RETRY_SECS: i64 = 30;
+const MAX_RETRY_SECS: i64 = 60 * 60;
+/// Long enough for PDF generation; a job whose worker dies is retried once its lease runs out
+const LEASE_SECS: i64 = 10 * 60;
+const IDLE_POLL_MILLIS: u64 = 1000;
+
+/// What a handler wants done with its job after a successful run
+#[derive(Debug, Clone, PartialEq)]
+pub enum JobOutcome {
+    Done,
+    /// Not due yet; run again at the given time without counting an attempt
+    RunAt(DateTime<Utc>),
+}
+
+pub type JobHandler = Arc<dyn Fn(DbPool, serde_json::Value) -> BoxFuture<'static, Result<JobOutcome, String>> + Send + Sync>;
+
+/// Delay before retrying after the given (1-based) failed attempt: 30s, 1m, 2m, ... capped at 1h
+pub fn retry_delay_secs(attempt: i32) -> i64 {
+    let exponent = (attempt.max(1) - 1).min(20) as u32;
+    (BASE_RETRY_SECS * 2_i64.pow(exponent)).min(MAX_RETRY_SECS)
+}
+
+/// Queue a job. With an idempotency key, enqueueing the same work twice is a no-op
+/// and returns Ok(None).
+pub async fn enqueue(
+    pool: &DbPool,
+    kind: &str,
+    payload: serde_json::Value,
+    run_at: Option<DateTime<Utc>>,
+    idempotency_key: Option<&str>,
+) -> Result<Option<DbJob>, sqlx::Error> {
+    JobQueries::enqueue(pool, kind, &payload, run_at.unwrap_or_else(Utc::now), idempotency_key, DEFAULT_MAX_ATTEMPTS).await
+}
+
+/// Background task that runs due jobs with the handlers registered for their kind
+#[derive(Clone)]
+pub struct JobWorker {
+    db_pool: Arc<Mutex<DbPool>>,
+    handlers: HashMap<&'static str, JobHandler>,
+    concurrency: usize,
+}
+
+impl JobWorker {
+    pub fn new(db_pool: Arc<Mutex<DbPool>>, concurrency: usize) -> Self {
+        Self {
+            db_pool,
+            handlers: HashMap::new(),
+            concurrency: concurrency.max(1),
+        }
+    }
+
+    pub fn with_handler(mut self, kind: &'static str, handler: JobHandler) -> Self {
+        self.handlers.insert(kind, handler);
+        self
+    }
+
+    pub async fn start_processing(&self) {
+        println!("🧰 Starting job worker ({} kinds, {} at a time)...", self.handlers.len(), self.concurrency);
+
+        loop {
+            match self.process_due_jobs().await {
+                // A full batch means more work is probably waiting
+                Ok(claimed) if claimed >= self.concurrency => continue,
+                Ok(_) => {}
+                Err(e) => eprintln!("❌ Error processing jobs: {}", e),
+            }
+
+            sleep(Duration::from_millis(IDLE_POLL_MILLIS)).await;
+        }
+    }
+
+    /// Claim and run one batch of due jobs; returns how many were claimed
+    pub async fn process_due_jobs(&self) -> Result<usize, sqlx::Error> {
+        // Clone the pool so the shared lock is not held while jobs run
+        let pool = self.db_pool.lock().await.clone();
+
+        let jobs = JobQueries::claim_due_jobs(&pool, self.concurrency as i64, LEASE_SECS).await?;
+        let claimed = jobs.len();
+
+        stream::iter(jobs)
+            .map(|job| self.run(&pool, job))
+            .buffer_unordered(self.concurrency)
+            .collect::<Vec<_>>()
+            .await;
+
+        Ok(claimed)
+    }
+
+    async fn run(&self, pool: &DbPool, job: DbJob) {
+        let result = match self.handlers.get(job.kind.as_str()) {
+            Some(handler) => handler(pool.clone(), job.payload.clone()).await,
+            None => Err(format!("No handler registered for job kind '{}'", job.kind)),
+        };
+
+        let lease = job.locked_until;
+        let recorded = match result {
+            Ok(JobOutcome::Done) => JobQueries::complete_job(pool, job.id, lease).await,
+            Ok(JobOutcome::RunAt(run_at)) => JobQueries::reschedule_job(pool, job.id, lease, run_at).await,
+            Err(e) if job.attempts >= job.max_attempts || !self.handlers.contains_key(job.kind.as_str()) => {
+                eprintln!("❌ Job {} ({}) failed permanently after {} attempts: {}", job.id, job.kind, job.attempts, e);
+                JobQueries::fail_job(pool, job.id, lease, &e, None).await
+            }
+            Err(e) => {
+                let delay = retry_delay_secs(job.attempts);
+                eprintln!("⚠️  Job {} ({}) attempt {} failed, retrying in {}s: {}", job.id, job.kind, job.attempts, delay, e);
+                JobQueries::fail_job(pool, job.id, lease, &e, Some(Utc::now() + chrono::Duration::seconds(delay))).await
+            }
+        };
+
+        match recorded {
+            Ok(true) => {}
+            Ok(false) => eprintln!("⚠️  Job {} ({}) outlived its lease; the result of this run was dropped", job.id, job.kind),
+            Err(e) => eprintln!("❌ Failed to record result of job {}: {}", job.id, e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_delay_backs_off_exponentially() {
+        assert_eq!(retry_delay_secs(1), 30);
+        assert_eq!(retry_delay_secs(2), 60);
+        assert_eq!(retry_delay_secs(4), 240);
+        assert_eq!(retry_delay_secs(0), 30);
+        assert_eq!(retry_delay_secs(50), MAX_RETRY_SECS);
+    }
+}
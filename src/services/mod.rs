@@ -5,4 +5,25 @@ pub mod cache;
 pub mod reminder_queue;
 pub mod digital_signature;
 pub mod filename_formatter;
-pub mod pdf_preferences;
\ No newline at end of file
+pub mod pdf_preferences;
+pub mod pdf_merge;
+pub mod audit;
+pub mod webhooks;
+pub mod timestamp;
+pub mod cms_builder;
+pub mod pades;
+pub mod pdf_fonts;
+pub mod field_conditions;
+pub mod field_validation;
+pub mod formula;
+pub mod spreadsheet;
+pub mod bulk_send;
+pub mod template_roles;
+pub mod expiry;
+pub mod reassignment;
+pub mod jobs;
+pub mod email_transport;
+pub mod timezone;
+pub mod email_template_engine;
+pub mod email_branding;
+pub mod i18n;
@@ -0,0 +1,877 @@
+use anyhow::{anyhow, Context, Result};
+use chrono::Utc;
+use cms::cert::CertificateChoices;
+use cms::content_info::ContentInfo;
+use cms::signed_data::SignedData;
+use der::{Decode, Encode, SliceReader};
+use lopdf::content::{Content, Operation};
+use lopdf::{dictionary, Dictionary, Document, IncrementalDocument, Object, ObjectId, Stream, StringFormat};
+use openssl::hash::MessageDigest;
+use openssl::ocsp::{OcspCertId, OcspRequest, OcspResponse, OcspResponseStatus};
+use openssl::pkey::{PKey, Private};
+use openssl::x509::{X509Crl, X509VerifyResult, X509};
+use sqlx::{PgPool, Row};
+
+use crate::services::cms_builder::{build_signed_data, ID_AA_TIME_STAMP_TOKEN, ID_DATA};
+use crate::services::digital_signature::create_pkcs7_signature;
+use crate::services::pdf_fonts::DocumentFonts;
+use crate::services::timestamp::{timestamp_pkcs7_signature, TimestampAuthority};
+
+pub const PROFILE_PKCS7_DETACHED: &str = "adbe.pkcs7.detached";
+pub const PROFILE_PADES_B_B: &str = "pades-b-b";
+pub const PROFILE_PADES_B_T: &str = "pades-b-t";
+pub const PROFILE_PADES_B_LT: &str = "pades-b-lt";
+
+pub const SUPPORTED_PROFILES: [&str; 4] = [
+    PROFILE_PKCS7_DETACHED,
+    PROFILE_PADES_B_B,
+    PROFILE_PADES_B_T,
+    PROFILE_PADES_B_LT,
+];
+
+/// Bytes reserved for the CMS blob in `/Contents`; fits a certificate chain plus a timestamp token with its own chain
+const SIGNATURE_PLACEHOLDER_SIZE: usize = 32 * 1024;
+
+/// Ten digits wide so the real offsets always fit when patched in place
+const BYTE_RANGE_PLACEHOLDER: i64 = 9_999_999_999;
+
+const REVOCATION_FETCH_TIMEOUT_SECS: u64 = 10;
+
+// Appearance text layout, matching the signature ID block drawn on flattened pages
+const APPEARANCE_FONT_SIZE: f64 = 8.0;
+const APPEARANCE_LINE_HEIGHT: f64 = 8.5;
+const APPEARANCE_PADDING: f64 = 5.0;
+
+/// Signature format written into the PDF, configured per account in `pdf_signature_settings`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignatureProfile {
+    /// Legacy PKCS#7 signature (`adbe.pkcs7.detached`)
+    Pkcs7Detached,
+    /// PAdES baseline B-B: CAdES signature with signing-certificate-v2
+    PadesBB,
+    /// B-B plus an RFC 3161 signature timestamp
+    PadesBT,
+    /// B-T plus a DSS dictionary with certificates and revocation data
+    PadesBLT,
+}
+
+impl SignatureProfile {
+    pub fn from_setting(value: &str) -> Option<Self> {
+        match value {
+            PROFILE_PKCS7_DETACHED => Some(Self::Pkcs7Detached),
+            PROFILE_PADES_B_B => Some(Self::PadesBB),
+            PROFILE_PADES_B_T => Some(Self::PadesBT),
+            PROFILE_PADES_B_LT => Some(Self::PadesBLT),
+            _ => None,
+        }
+    }
+
+    pub fn as_setting(self) -> &'static str {
+        match self {
+            Self::Pkcs7Detached => PROFILE_PKCS7_DETACHED,
+            Self::PadesBB => PROFILE_PADES_B_B,
+            Self::PadesBT => PROFILE_PADES_B_T,
+            Self::PadesBLT => PROFILE_PADES_B_LT,
+        }
+    }
+
+    fn sub_filter(self) -> &'static str {
+        match self {
+            Self::Pkcs7Detached => "adbe.pkcs7.detached",
+            _ => "ETSI.CAdES.detached",
+        }
+    }
+
+    pub fn requires_timestamp(self) -> bool {
+        matches!(self, Self::PadesBT | Self::PadesBLT)
+    }
+}
+
+/// Certificate and key that produce the signature, plus the chain to embed
+pub struct SigningIdentity<'a> {
+    pub certificate: &'a X509,
+    pub key: &'a PKey<Private>,
+    pub chain: &'a [X509],
+}
+
+/// Entries of the signature dictionary
+pub struct SignatureMetadata<'a> {
+    pub field_name: &'a str,
+    pub name: &'a str,
+    pub reason: &'a str,
+    pub location: &'a str,
+    pub contact_info: Option<&'a str>,
+    /// Visible widget; `None` keeps the signature field invisible
+    pub appearance: Option<&'a SignatureAppearance>,
+}
+
+/// Where and how the signature widget is drawn
+pub struct SignatureAppearance {
+    /// 0-based page index
+    pub page_index: usize,
+    /// `[llx, lly, urx, ury]` in page space
+    pub rect: [f64; 4],
+    /// Drawn signature, scaled into the area above the text lines
+    pub image: Option<image::RgbaImage>,
+    /// Text lines at the bottom of the widget, first line on top
+    pub lines: Vec<String>,
+    /// Primary font for the text lines, see [`crate::services::pdf_fonts`]
+    pub font: String,
+}
+
+/// Sign a PDF as an incremental update.
+///
+/// The original bytes are kept verbatim and the signature revision is appended
+/// after them, so signatures already in the document stay valid. `/ByteRange`
+/// covers the whole file except the `/Contents` placeholder.
+pub async fn sign_pdf(
+    pdf_bytes: &[u8],
+    identity: &SigningIdentity<'_>,
+    metadata: &SignatureMetadata<'_>,
+    profile: SignatureProfile,
+    tsa: Option<&TimestampAuthority>,
+) -> Result<Vec<u8>> {
+    if profile.requires_timestamp() && tsa.is_none() {
+        return Err(anyhow!("The {} profile requires a timestamp authority", profile.as_setting()));
+    }
+
+    let (mut output, byte_range) = prepare_signature_revision(pdf_bytes, metadata, profile)?;
+    let contents_start = byte_range[1] as usize;
+    let contents_end = byte_range[2] as usize;
+
+    let signature = match profile {
+        SignatureProfile::Pkcs7Detached => {
+            create_pkcs7_signature(&output, &byte_range, identity.certificate, identity.key, identity.chain)?
+        }
+        _ => {
+            let mut signed_content = output[..contents_start].to_vec();
+            signed_content.extend_from_slice(&output[contents_end..]);
+            build_signed_data(ID_DATA, &signed_content, false, identity.certificate, identity.key, identity.chain)?.to_der()?
+        }
+    };
+
+    let signature = match tsa {
+        Some(tsa) => timestamp_pkcs7_signature(&signature, tsa).await?,
+        None => signature,
+    };
+
+    embed_signature(&mut output, contents_start, contents_end, &signature)?;
+
+    if profile == SignatureProfile::PadesBLT {
+        output = append_validation_data(output, &signature).await?;
+    }
+
+    Ok(output)
+}
+
+/// Load the account's signature profile; accounts without settings keep the legacy format
+pub async fn load_signature_profile(
+    pool: &PgPool,
+    user_id: i64,
+    account_id: Option<i64>,
+) -> Result<SignatureProfile, sqlx::Error> {
+    let row = sqlx::query(
+        "SELECT signature_profile FROM pdf_signature_settings WHERE user_id = $1 OR account_id = $2 LIMIT 1"
+    )
+    .bind(user_id)
+    .bind(account_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(row
+        .and_then(|row| SignatureProfile::from_setting(&row.get::<String, _>("signature_profile")))
+        .unwrap_or(SignatureProfile::Pkcs7Detached))
+}
+
+/// Append the signature field, widget and value as a new revision with zeroed
+/// `/Contents` and a placeholder `/ByteRange`, then patch in the real range.
+fn prepare_signature_revision(
+    pdf_bytes: &[u8],
+    metadata: &SignatureMetadata<'_>,
+    profile: SignatureProfile,
+) -> Result<(Vec<u8>, [u32; 4])> {
+    let previous = Document::load_mem(pdf_bytes).context("Failed to load PDF")?;
+    let page_index = metadata.appearance.map(|appearance| appearance.page_index).unwrap_or(0);
+    let page_id = previous.get_pages().values().nth(page_index).copied()
+        .ok_or_else(|| anyhow!("PDF has no page {}", page_index + 1))?;
+    let catalog_id = previous.trailer.get(b"Root").and_then(Object::as_reference)
+        .context("PDF has no catalog")?;
+    let field_name = unique_field_name(&previous, metadata.field_name);
+
+    let mut document = IncrementalDocument::create_from(pdf_bytes.to_vec(), previous);
+
+    let mut signature = Dictionary::new();
+    signature.set("Type", Object::Name(b"Sig".to_vec()));
+    signature.set("Filter", Object::Name(b"Adobe.PPKLite".to_vec()));
+    signature.set("SubFilter", Object::Name(profile.sub_filter().as_bytes().to_vec()));
+    signature.set("ByteRange", Object::Array(vec![
+        Object::Integer(0),
+        Object::Integer(BYTE_RANGE_PLACEHOLDER),
+        Object::Integer(BYTE_RANGE_PLACEHOLDER),
+        Object::Integer(BYTE_RANGE_PLACEHOLDER),
+    ]));
+    signature.set("Contents", Object::String(vec![0; SIGNATURE_PLACEHOLDER_SIZE], StringFormat::Hexadecimal));
+    let date = format!("D:{}", Utc::now().format("%Y%m%d%H%M%S+00'00'"));
+    signature.set("M", Object::String(date.into_bytes(), StringFormat::Literal));
+    signature.set("Name", Object::String(metadata.name.as_bytes().to_vec(), StringFormat::Literal));
+    signature.set("Reason", Object::String(metadata.reason.as_bytes().to_vec(), StringFormat::Literal));
+    signature.set("Location", Object::String(metadata.location.as_bytes().to_vec(), StringFormat::Literal));
+    if let Some(contact_info) = metadata.contact_info {
+        signature.set("ContactInfo", Object::String(contact_info.as_bytes().to_vec(), StringFormat::Literal));
+    }
+    let signature_id = document.new_document.add_object(signature);
+
+    // Print + locked flags; without an appearance the widget has a zero-size rect and stays invisible
+    let mut field = dictionary! {
+        "Type" => "Annot",
+        "Subtype" => "Widget",
+        "FT" => "Sig",
+        "T" => Object::string_literal(field_name),
+        "V" => signature_id,
+        "Rect" => vec![0.into(), 0.into(), 0.into(), 0.into()],
+        "F" => 132,
+        "P" => page_id,
+    };
+    if let Some(appearance) = metadata.appearance {
+        let stream_id = add_appearance_stream(&mut document, appearance)?;
+        field.set("Rect", appearance.rect.iter().map(|&v| Object::Real(v as f32)).collect::<Vec<_>>());
+        field.set("AP", dictionary! { "N" => stream_id });
+    }
+    let field_id = document.new_document.add_object(field);
+
+    push_to_array(&mut document, page_id, b"Annots", Object::Reference(field_id))?;
+
+    document.opt_clone_object_to_new_document(catalog_id)?;
+    let acro_form = document.new_document.get_dictionary(catalog_id)?.get(b"AcroForm").ok().cloned();
+    let acro_form_id = match acro_form {
+        Some(Object::Reference(id)) => id,
+        other => {
+            // Inline or missing AcroForm: move it into its own object so it can be extended
+            let acro_form = match other {
+                Some(Object::Dictionary(dict)) => dict,
+                _ => Dictionary::new(),
+            };
+            let id = document.new_document.add_object(acro_form);
+            document.new_document.get_dictionary_mut(catalog_id)?.set("AcroForm", Object::Reference(id));
+            id
+        }
+    };
+    push_to_array(&mut document, acro_form_id, b"Fields", Object::Reference(field_id))?;
+    // SignaturesExist | AppendOnly
+    document.new_document.get_dictionary_mut(acro_form_id)?.set("SigFlags", 3);
+
+    let mut output = Vec::new();
+    document.save_to(&mut output)?;
+
+    let revision_start = pdf_bytes.len();
+    let mut contents_placeholder = vec![b'<'];
+    contents_placeholder.extend(std::iter::repeat_n(b'0', SIGNATURE_PLACEHOLDER_SIZE * 2));
+    contents_placeholder.push(b'>');
+    let contents_start = revision_start + find_subslice(&output[revision_start..], &contents_placeholder)
+        .ok_or_else(|| anyhow!("Signature placeholder not found in output"))?;
+    let contents_end = contents_start + contents_placeholder.len();
+
+    let range_placeholder = format!("[0 {0} {0} {0}]", BYTE_RANGE_PLACEHOLDER);
+    let range_start = revision_start + find_subslice(&output[revision_start..], range_placeholder.as_bytes())
+        .ok_or_else(|| anyhow!("ByteRange placeholder not found in output"))?;
+
+    let byte_range = [
+        0,
+        u32::try_from(contents_start)?,
+        u32::try_from(contents_end)?,
+        u32::try_from(output.len() - contents_end)?,
+    ];
+    // Pad with spaces before the closing bracket so no offsets move
+    let range = format!("[0 {} {} {}", byte_range[1], byte_range[2], byte_range[3]);
+    let range = format!("{:<width$}]", range, width = range_placeholder.len() - 1);
+    output[range_start..range_start + range.len()].copy_from_slice(range.as_bytes());
+
+    Ok((output, byte_range))
+}
+
+/// Form XObject for the widget: an opaque background (so it covers any flattened
+/// copy of the signature underneath), the image on top and the text lines below it
+fn add_appearance_stream(document: &mut IncrementalDocument, appearance: &SignatureAppearance) -> Result<ObjectId> {
+    let [llx, lly, urx, ury] = appearance.rect;
+    let (width, height) = (urx - llx, ury - lly);
+    if width <= 0.0 || height <= 0.0 {
+        return Err(anyhow!("Signature widget rect is empty"));
+    }
+
+    let mut operations = vec![
+        Operation::new("q", vec![]),
+        Operation::new("g", vec![1.into()]),
+        Operation::new("re", vec![0.into(), 0.into(), Object::Real(width as f32), Object::Real(height as f32)]),
+        Operation::new("f", vec![]),
+        Operation::new("Q", vec![]),
+    ];
+    let mut resources = Dictionary::new();
+
+    let text_height = if appearance.lines.is_empty() {
+        0.0
+    } else {
+        (appearance.lines.len() as f64 - 1.0) * APPEARANCE_LINE_HEIGHT + APPEARANCE_FONT_SIZE + 2.0 + 10.0
+    };
+
+    if let Some(image) = &appearance.image {
+        let (image_width, image_height) = image.dimensions();
+        let area_height = (height - text_height).max(0.0);
+        let scale = (width / image_width as f64).min(area_height / image_height as f64);
+        if scale > 0.0 {
+            let image_id = add_image_xobject(document, image)?;
+            resources.set("XObject", dictionary! { "Im1" => image_id });
+
+            let (scaled_width, scaled_height) = (image_width as f64 * scale, image_height as f64 * scale);
+            operations.extend([
+                Operation::new("q", vec![]),
+                Operation::new("cm", vec![
+                    Object::Real(scaled_width as f32),
+                    0.into(),
+                    0.into(),
+                    Object::Real(scaled_height as f32),
+                    Object::Real(((width - scaled_width) / 2.0) as f32),
+                    Object::Real((text_height + (area_height - scaled_height) / 2.0) as f32),
+                ]),
+                Operation::new("Do", vec![Object::Name(b"Im1".to_vec())]),
+                Operation::new("Q", vec![]),
+            ]);
+        }
+    }
+
+    if !appearance.lines.is_empty() {
+        let mut fonts = DocumentFonts::new(&appearance.font);
+        operations.push(Operation::new("BT", vec![]));
+        operations.push(Operation::new("rg", vec![0.into(), 0.into(), 0.into()]));
+        let count = appearance.lines.len();
+        for (index, line) in appearance.lines.iter().enumerate() {
+            let y = 2.0 + (count - 1 - index) as f64 * APPEARANCE_LINE_HEIGHT;
+            operations.push(Operation::new("Tm", vec![
+                1.into(), 0.into(), 0.into(), 1.into(),
+                Object::Real(APPEARANCE_PADDING as f32),
+                Object::Real(y as f32),
+            ]));
+            operations.extend(fonts.text_operations(line, APPEARANCE_FONT_SIZE));
+        }
+        operations.push(Operation::new("ET", vec![]));
+        resources.set("Font", fonts.resources(&mut document.new_document));
+        fonts.finish(&mut document.new_document)?;
+    }
+
+    let stream = Stream::new(
+        dictionary! {
+            "Type" => "XObject",
+            "Subtype" => "Form",
+            "BBox" => vec![0.into(), 0.into(), Object::Real(width as f32), Object::Real(height as f32)],
+            "Resources" => resources,
+        },
+        Content { operations }.encode()?,
+    );
+    Ok(document.new_document.add_object(stream))
+}
+
+/// RGB image with its alpha channel as a soft mask, so transparent strokes stay transparent
+fn add_image_xobject(document: &mut IncrementalDocument, image: &image::RgbaImage) -> Result<ObjectId> {
+    let (width, height) = image.dimensions();
+    let mut rgb = Vec::with_capacity((width * height * 3) as usize);
+    let mut alpha = Vec::with_capacity((width * height) as usize);
+    for pixel in image.pixels() {
+        rgb.extend_from_slice(&pixel.0[..3]);
+        alpha.push(pixel.0[3]);
+    }
+
+    let mut mask = Stream::new(
+        dictionary! {
+            "Type" => "XObject",
+            "Subtype" => "Image",
+            "Width" => width as i64,
+            "Height" => height as i64,
+            "ColorSpace" => "DeviceGray",
+            "BitsPerComponent" => 8,
+        },
+        alpha,
+    );
+    mask.compress()?;
+    let mask_id = document.new_document.add_object(mask);
+
+    let mut image = Stream::new(
+        dictionary! {
+            "Type" => "XObject",
+            "Subtype" => "Image",
+            "Width" => width as i64,
+            "Height" => height as i64,
+            "ColorSpace" => "DeviceRGB",
+            "BitsPerComponent" => 8,
+            "SMask" => mask_id,
+        },
+        rgb,
+    );
+    image.compress()?;
+    Ok(document.new_document.add_object(image))
+}
+
+/// Write the DER signature as hex into the zeroed `/Contents` placeholder
+fn embed_signature(output: &mut [u8], contents_start: usize, contents_end: usize, signature: &[u8]) -> Result<()> {
+    let hex = hex::encode_upper(signature);
+    let capacity = contents_end - contents_start - 2;
+    if hex.len() > capacity {
+        return Err(anyhow!(
+            "Signature is {} bytes but only {} bytes are reserved",
+            signature.len(),
+            capacity / 2
+        ));
+    }
+
+    output[contents_start + 1..contents_start + 1 + hex.len()].copy_from_slice(hex.as_bytes());
+    Ok(())
+}
+
+/// Append a reference to an array entry that may be inline, indirect or missing
+fn push_to_array(document: &mut IncrementalDocument, owner_id: ObjectId, key: &[u8], value: Object) -> Result<()> {
+    document.opt_clone_object_to_new_document(owner_id)?;
+    let existing = document.new_document.get_dictionary(owner_id)?.get(key).ok().cloned();
+
+    match existing {
+        Some(Object::Reference(array_id)) => {
+            document.opt_clone_object_to_new_document(array_id)?;
+            document.new_document.get_object_mut(array_id)?.as_array_mut()?.push(value);
+        }
+        Some(Object::Array(mut array)) => {
+            array.push(value);
+            document.new_document.get_dictionary_mut(owner_id)?.set(key.to_vec(), Object::Array(array));
+        }
+        _ => {
+            document.new_document.get_dictionary_mut(owner_id)?.set(key.to_vec(), Object::Array(vec![value]));
+        }
+    }
+
+    Ok(())
+}
+
+/// Field names must be unique or viewers merge the fields
+fn unique_field_name(document: &Document, base: &str) -> String {
+    let existing: Vec<Vec<u8>> = document.objects.values()
+        .filter_map(|object| object.as_dict().ok())
+        .filter_map(|dict| dict.get(b"T").and_then(Object::as_str).ok())
+        .map(|name| name.to_vec())
+        .collect();
+
+    let mut name = base.to_string();
+    let mut suffix = 1;
+    while existing.iter().any(|taken| taken.as_slice() == name.as_bytes()) {
+        suffix += 1;
+        name = format!("{}{}", base, suffix);
+    }
+    name
+}
+
+/// `(offset after %%EOF, offset after its line ending)` for every revision in the file
+fn eof_markers(pdf_bytes: &[u8]) -> Vec<(usize, usize)> {
+    let mut markers = Vec::new();
+    let mut offset = 0;
+    while let Some(position) = find_subslice(&pdf_bytes[offset..], b"%%EOF") {
+        let eof = offset + position + 5;
+        let mut end = eof;
+        while end < pdf_bytes.len() && matches!(pdf_bytes[end], b'\r' | b'\n') {
+            end += 1;
+        }
+        markers.push((eof, end));
+        offset = eof;
+    }
+    markers
+}
+
+/// Number of revisions (original file plus incremental updates)
+pub fn revision_count(pdf_bytes: &[u8]) -> usize {
+    eof_markers(pdf_bytes).len()
+}
+
+/// The 1-based revision a signature covers, given where its ByteRange ends.
+/// `None` means the signed bytes stop somewhere other than a revision boundary.
+pub fn signed_revision(pdf_bytes: &[u8], byte_range_end: usize) -> Option<usize> {
+    eof_markers(pdf_bytes)
+        .iter()
+        .position(|&(eof, end)| (eof..=end).contains(&byte_range_end))
+        .map(|index| index + 1)
+}
+
+fn find_subslice(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|window| window == needle)
+}
+
+/// Append a revision with a DSS dictionary holding every certificate in the
+/// signature (including the timestamp authority's) and whatever OCSP
+/// responses and CRLs their issuers publish.
+async fn append_validation_data(pdf_bytes: Vec<u8>, signature: &[u8]) -> Result<Vec<u8>> {
+    let certificates = collect_certificates(signature)?;
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(REVOCATION_FETCH_TIMEOUT_SECS))
+        .build()?;
+
+    let mut ocsps: Vec<Vec<u8>> = Vec::new();
+    let mut crls: Vec<Vec<u8>> = Vec::new();
+
+    for certificate in &certificates {
+        // Trust anchors carry no revocation information
+        if certificate.issued(certificate) == X509VerifyResult::OK {
+            continue;
+        }
+        let issuer = certificates.iter().find(|candidate| candidate.issued(certificate) == X509VerifyResult::OK);
+
+        if let Some(issuer) = issuer {
+            match fetch_ocsp_response(&client, certificate, issuer).await {
+                Ok(Some(response)) => {
+                    ocsps.push(response);
+                    continue;
+                }
+                Ok(None) => {}
+                Err(e) => eprintln!("⚠️  OCSP lookup failed, falling back to CRL: {}", e),
+            }
+        }
+
+        for url in crl_urls(certificate) {
+            match fetch_crl(&client, &url).await {
+                Ok(crl) => {
+                    crls.push(crl);
+                    break;
+                }
+                Err(e) => eprintln!("⚠️  Failed to fetch CRL from {}: {}", url, e),
+            }
+        }
+    }
+
+    let certificates = certificates.iter()
+        .map(|certificate| certificate.to_der())
+        .collect::<Result<Vec<_>, _>>()?;
+    add_dss(pdf_bytes, &certificates, &ocsps, &crls)
+}
+
+/// Certificates embedded in the signature and in any timestamp tokens it carries
+fn collect_certificates(signature: &[u8]) -> Result<Vec<X509>> {
+    let mut reader = SliceReader::new(signature)?;
+    let content_info = ContentInfo::decode(&mut reader)?;
+    let signed_data: SignedData = content_info.content.decode_as()?;
+
+    let mut sets = vec![signed_data.certificates.clone()];
+    for signer_info in signed_data.signer_infos.0.iter() {
+        let tokens = signer_info.unsigned_attrs.iter()
+            .flat_map(|attrs| attrs.iter())
+            .filter(|attr| attr.oid == ID_AA_TIME_STAMP_TOKEN)
+            .filter_map(|attr| attr.values.get(0));
+        for token in tokens {
+            let token: ContentInfo = token.decode_as()?;
+            let token_data: SignedData = token.content.decode_as()?;
+            sets.push(token_data.certificates);
+        }
+    }
+
+    let mut certificates: Vec<X509> = Vec::new();
+    for choice in sets.into_iter().flatten().flat_map(|set| set.0.into_vec()) {
+        if let CertificateChoices::Certificate(certificate) = choice {
+            let der = certificate.to_der()?;
+            if !certificates.iter().any(|existing| existing.to_der().ok().as_deref() == Some(der.as_slice())) {
+                certificates.push(X509::from_der(&der)?);
+            }
+        }
+    }
+
+    Ok(certificates)
+}
+
+async fn fetch_ocsp_response(client: &reqwest::Client, certificate: &X509, issuer: &X509) -> Result<Option<Vec<u8>>> {
+    let url = match certificate.ocsp_responders()?.iter().next() {
+        Some(url) => url.to_string(),
+        None => return Ok(None),
+    };
+
+    let request_der = {
+        let mut request = OcspRequest::new()?;
+        request.add_id(OcspCertId::from_cert(MessageDigest::sha1(), certificate, issuer)?)?;
+        request.to_der()?
+    };
+
+    let response = client
+        .post(&url)
+        .header("Content-Type", "application/ocsp-request")
+        .body(request_der)
+        .send()
+        .await?
+        .bytes()
+        .await?;
+
+    let status = OcspResponse::from_der(&response)?.status();
+    if status != OcspResponseStatus::SUCCESSFUL {
+        return Err(anyhow!("OCSP responder {} answered with status {:?}", url, status));
+    }
+
+    Ok(Some(response.to_vec()))
+}
+
+fn crl_urls(certificate: &X509) -> Vec<String> {
+    let mut urls = Vec::new();
+    if let Some(points) = certificate.crl_distribution_points() {
+        for point in points.iter() {
+            if let Some(names) = point.distpoint().and_then(|name| name.fullname()) {
+                urls.extend(names.iter()
+                    .filter_map(|name| name.uri())
+                    .filter(|uri| uri.starts_with("http://") || uri.starts_with("https://"))
+                    .map(str::to_string));
+            }
+        }
+    }
+    urls
+}
+
+async fn fetch_crl(client: &reqwest::Client, url: &str) -> Result<Vec<u8>> {
+    let body = client.get(url).send().await?.error_for_status()?.bytes().await?;
+    let crl = X509Crl::from_der(&body).or_else(|_| X509Crl::from_pem(&body))?;
+    Ok(crl.to_der()?)
+}
+
+/// Add (or extend) the catalog's `/DSS` dictionary in a new revision
+fn add_dss(pdf_bytes: Vec<u8>, certificates: &[Vec<u8>], ocsps: &[Vec<u8>], crls: &[Vec<u8>]) -> Result<Vec<u8>> {
+    let previous = Document::load_mem(&pdf_bytes).context("Failed to load signed PDF")?;
+    let catalog_id = previous.trailer.get(b"Root").and_then(Object::as_reference)
+        .context("PDF has no catalog")?;
+
+    // Keep validation data from earlier revisions
+    let existing_dss = previous.catalog().ok()
+        .and_then(|catalog| catalog.get(b"DSS").ok())
+        .and_then(|dss| previous.dereference(dss).ok())
+        .and_then(|(_, dss)| dss.as_dict().ok())
+        .cloned()
+        .unwrap_or_default();
+    let existing_entries = |key: &[u8]| -> Vec<Object> {
+        existing_dss.get(key).ok()
+            .and_then(|entries| previous.dereference(entries).ok())
+            .and_then(|(_, entries)| entries.as_array().ok())
+            .cloned()
+            .unwrap_or_default()
+    };
+    let mut certs = existing_entries(b"Certs");
+    let mut ocsp_refs = existing_entries(b"OCSPs");
+    let mut crl_refs = existing_entries(b"CRLs");
+
+    let mut document = IncrementalDocument::create_from(pdf_bytes, previous.clone());
+    let mut add_streams = |target: &mut Vec<Object>, items: &[Vec<u8>]| {
+        for item in items {
+            let id = document.new_document.add_object(Stream::new(Dictionary::new(), item.clone()));
+            target.push(Object::Reference(id));
+        }
+    };
+    add_streams(&mut certs, certificates);
+    add_streams(&mut ocsp_refs, ocsps);
+    add_streams(&mut crl_refs, crls);
+
+    let mut dss = existing_dss.clone();
+    dss.set("Certs", Object::Array(certs));
+    if !ocsp_refs.is_empty() {
+        dss.set("OCSPs", Object::Array(ocsp_refs));
+    }
+    if !crl_refs.is_empty() {
+        dss.set("CRLs", Object::Array(crl_refs));
+    }
+    let dss_id = document.new_document.add_object(dss);
+
+    document.opt_clone_object_to_new_document(catalog_id)?;
+    document.new_document.get_dictionary_mut(catalog_id)?.set("DSS", Object::Reference(dss_id));
+
+    let mut output = Vec::new();
+    document.save_to(&mut output)?;
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::digital_signature::{generate_root_ca, generate_rsa_keypair, CAConfig};
+    use crate::services::pdf_fonts::FONT_NOTO_SANS;
+    use crate::services::timestamp::LocalTsa;
+    use openssl::pkcs7::{Pkcs7, Pkcs7Flags};
+    use openssl::stack::Stack;
+    use openssl::x509::store::X509StoreBuilder;
+
+    fn build_pdf() -> Vec<u8> {
+        let mut doc = Document::with_version("1.5");
+        let pages_id = doc.new_object_id();
+        let content = Content {
+            operations: vec![
+                Operation::new("BT", vec![]),
+                Operation::new("Tj", vec![Object::string_literal("Contract")]),
+                Operation::new("ET", vec![]),
+            ],
+        };
+        let content_id = doc.add_object(Stream::new(Dictionary::new(), content.encode().unwrap()));
+        let page_id = doc.add_object(dictionary! {
+            "Type" => "Page",
+            "Parent" => pages_id,
+            "MediaBox" => vec![0.into(), 0.into(), 595.into(), 842.into()],
+            "Contents" => content_id,
+        });
+        doc.objects.insert(pages_id, Object::Dictionary(dictionary! {
+            "Type" => "Pages",
+            "Kids" => vec![page_id.into()],
+            "Count" => 1,
+        }));
+        let catalog_id = doc.add_object(dictionary! { "Type" => "Catalog", "Pages" => pages_id });
+        doc.trailer.set("Root", catalog_id);
+
+        let mut output = Vec::new();
+        doc.save_to(&mut output).unwrap();
+        output
+    }
+
+    /// Check every signature's ByteRange digest and return their SubFilters
+    fn verify_all_signatures(pdf: &[u8]) -> Vec<String> {
+        let doc = Document::load_mem(pdf).unwrap();
+        let mut sub_filters = Vec::new();
+
+        for object in doc.objects.values() {
+            let Ok(dict) = object.as_dict() else { continue };
+            if dict.get(b"Type").and_then(Object::as_name).ok() != Some(b"Sig".as_slice()) {
+                continue;
+            }
+
+            let range: Vec<usize> = dict.get(b"ByteRange").unwrap().as_array().unwrap()
+                .iter().map(|v| v.as_i64().unwrap() as usize).collect();
+            let mut content = pdf[range[0]..range[0] + range[1]].to_vec();
+            content.extend_from_slice(&pdf[range[2]..range[2] + range[3]]);
+
+            let contents = dict.get(b"Contents").unwrap().as_str().unwrap();
+            let mut reader = SliceReader::new(contents).unwrap();
+            let der = ContentInfo::decode(&mut reader).unwrap().to_der().unwrap();
+            let store = X509StoreBuilder::new().unwrap().build();
+            Pkcs7::from_der(&der).unwrap()
+                .verify(&Stack::new().unwrap(), &store, Some(&content), None, Pkcs7Flags::NOVERIFY)
+                .unwrap();
+
+            sub_filters.push(String::from_utf8(dict.get(b"SubFilter").unwrap().as_name().unwrap().to_vec()).unwrap());
+        }
+
+        sub_filters.sort();
+        sub_filters
+    }
+
+    fn identity() -> (X509, PKey<Private>) {
+        let key = generate_rsa_keypair().unwrap();
+        let cert = generate_root_ca(&key, &CAConfig::default()).unwrap();
+        (cert, key)
+    }
+
+    fn metadata() -> SignatureMetadata<'static> {
+        SignatureMetadata {
+            field_name: "Signature",
+            name: "Jane Signer",
+            reason: "Approval",
+            location: "Test",
+            contact_info: None,
+            appearance: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pades_signature_is_appended_incrementally() {
+        let original = build_pdf();
+        let (cert, key) = identity();
+        let signer = SigningIdentity { certificate: &cert, key: &key, chain: &[] };
+
+        let signed = sign_pdf(&original, &signer, &metadata(), SignatureProfile::PadesBB, None).await.unwrap();
+
+        assert!(signed.starts_with(&original));
+        assert_eq!(verify_all_signatures(&signed), vec!["ETSI.CAdES.detached"]);
+    }
+
+    #[tokio::test]
+    async fn test_second_signature_keeps_first_valid() {
+        let (cert, key) = identity();
+        let signer = SigningIdentity { certificate: &cert, key: &key, chain: &[] };
+
+        let first = sign_pdf(&build_pdf(), &signer, &metadata(), SignatureProfile::Pkcs7Detached, None).await.unwrap();
+        let second = sign_pdf(&first, &signer, &metadata(), SignatureProfile::PadesBB, None).await.unwrap();
+
+        // The first revision is untouched, so its ByteRange still covers exactly that revision
+        assert!(second.starts_with(&first));
+        assert_eq!(verify_all_signatures(&second), vec!["ETSI.CAdES.detached", "adbe.pkcs7.detached"]);
+        let doc = Document::load_mem(&second).unwrap();
+        let names: Vec<Vec<u8>> = doc.objects.values()
+            .filter_map(|o| o.as_dict().ok())
+            .filter_map(|d| d.get(b"T").and_then(Object::as_str).ok().map(|t| t.to_vec()))
+            .collect();
+        assert!(names.contains(&b"Signature".to_vec()));
+        assert!(names.contains(&b"Signature2".to_vec()));
+    }
+
+    #[tokio::test]
+    async fn test_b_lt_adds_dss_with_signer_and_tsa_certificates() {
+        let (cert, key) = identity();
+        let signer = SigningIdentity { certificate: &cert, key: &key, chain: &[] };
+        let tsa = TimestampAuthority::Local(LocalTsa::generate().unwrap());
+
+        let signed = sign_pdf(&build_pdf(), &signer, &metadata(), SignatureProfile::PadesBLT, Some(&tsa)).await.unwrap();
+
+        let doc = Document::load_mem(&signed).unwrap();
+        let dss = doc.catalog().unwrap().get(b"DSS").unwrap();
+        let (_, dss) = doc.dereference(dss).unwrap();
+        let certs = dss.as_dict().unwrap().get(b"Certs").unwrap().as_array().unwrap();
+        assert_eq!(certs.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_signed_revision_tracks_incremental_updates() {
+        let (cert, key) = identity();
+        let signer = SigningIdentity { certificate: &cert, key: &key, chain: &[] };
+        let original = build_pdf();
+
+        let first = sign_pdf(&original, &signer, &metadata(), SignatureProfile::PadesBB, None).await.unwrap();
+        let second = sign_pdf(&first, &signer, &metadata(), SignatureProfile::PadesBB, None).await.unwrap();
+
+        assert_eq!(revision_count(&second), 3);
+        assert_eq!(signed_revision(&second, first.len()), Some(2));
+        assert_eq!(signed_revision(&second, second.len()), Some(3));
+        assert_eq!(signed_revision(&second, original.len() - 10), None);
+    }
+
+    #[tokio::test]
+    async fn test_visible_widget_gets_rect_and_appearance() {
+        let (cert, key) = identity();
+        let signer = SigningIdentity { certificate: &cert, key: &key, chain: &[] };
+        let appearance = SignatureAppearance {
+            page_index: 0,
+            rect: [100.0, 600.0, 300.0, 680.0],
+            image: Some(image::RgbaImage::from_pixel(40, 20, image::Rgba([0, 0, 255, 128]))),
+            lines: vec!["Nguyễn Thị Lan".to_string(), "ID: 1234".to_string()],
+            font: FONT_NOTO_SANS.to_string(),
+        };
+        let metadata = SignatureMetadata { appearance: Some(&appearance), ..metadata() };
+
+        let signed = sign_pdf(&build_pdf(), &signer, &metadata, SignatureProfile::PadesBB, None).await.unwrap();
+
+        assert_eq!(verify_all_signatures(&signed), vec!["ETSI.CAdES.detached"]);
+        let doc = Document::load_mem(&signed).unwrap();
+        let widget = doc.objects.values()
+            .filter_map(|o| o.as_dict().ok())
+            .find(|d| d.get(b"FT").and_then(Object::as_name).ok() == Some(b"Sig".as_slice()))
+            .unwrap();
+        let rect: Vec<f32> = widget.get(b"Rect").unwrap().as_array().unwrap()
+            .iter().map(|v| v.as_float().unwrap()).collect();
+        assert_eq!(rect, vec![100.0, 600.0, 300.0, 680.0]);
+
+        let stream_id = widget.get(b"AP").unwrap().as_dict().unwrap().get(b"N").unwrap().as_reference().unwrap();
+        let stream = doc.get_object(stream_id).unwrap().as_stream().unwrap();
+        let resources = stream.dict.get(b"Resources").unwrap().as_dict().unwrap();
+        assert!(resources.has(b"XObject"));
+        let font_id = resources.get(b"Font").unwrap().as_dict().unwrap().get(b"LmsF1").unwrap().as_reference().unwrap();
+        let font = doc.get_dictionary(font_id).unwrap();
+        assert_eq!(font.get(b"Subtype").unwrap().as_name().unwrap(), b"Type0");
+        assert!(font.has(b"ToUnicode"));
+        let content = String::from_utf8_lossy(&stream.content).to_string();
+        assert!(content.contains("/LmsF1 8 Tf"));
+        assert!(content.contains("/Im1 Do"));
+    }
+
+    #[tokio::test]
+    async fn test_b_t_requires_timestamp_authority() {
+        let (cert, key) = identity();
+        let signer = SigningIdentity { certificate: &cert, key: &key, chain: &[] };
+        assert!(sign_pdf(&build_pdf(), &signer, &metadata(), SignatureProfile::PadesBT, None).await.is_err());
+    }
+}
@@ -0,0 +1,713 @@
+use anyhow::{anyhow, Context, Result};
+use lopdf::content::Operation;
+use lopdf::{dictionary, Dictionary, Document, Object, ObjectId, Stream, StringFormat};
+use sqlx::{PgPool, Row};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+use ttf_parser::{name_id, Face, GlyphId, RawFace, Tag};
+
+pub const FONT_NOTO_SANS: &str = "noto-sans";
+pub const FONT_NOTO_SANS_BOLD: &str = "noto-sans-bold";
+
+/// Built-in keys: Noto Sans from `FONTS_PATH` or the distribution package
+/// when installed, otherwise the DejaVu Sans compiled into the binary, so the
+/// default key always resolves to a real face
+const BUILTIN_FONTS: &[(&str, &str, &[u8])] = &[
+    (FONT_NOTO_SANS, "NotoSans-Regular.ttf", include_bytes!("../../assets/fonts/DejaVuSans.ttf")),
+    (FONT_NOTO_SANS_BOLD, "NotoSans-Bold.ttf", include_bytes!("../../assets/fonts/DejaVuSans-Bold.ttf")),
+];
+
+const SYSTEM_NOTO_PATH: &str = "/usr/share/fonts/truetype/noto";
+
+/// Distribution font packages probed last, for scripts no configured font covers
+const SYSTEM_FONTS: &[&str] = &[
+    "/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf",
+    "/usr/share/fonts/TTF/DejaVuSans.ttf",
+];
+
+/// Tables copied into a subset; everything else (cmap, GSUB, name, ...) is not
+/// needed by a CID-keyed font that addresses glyphs directly
+const KEPT_TABLES: &[&[u8; 4]] = &[b"OS/2", b"cvt ", b"fpgm", b"glyf", b"head", b"hhea", b"hmtx", b"loca", b"maxp", b"prep"];
+
+/// A TrueType face loaded from disk or compiled into the binary
+pub struct FontFace {
+    pub key: String,
+    data: Cow<'static, [u8]>,
+    index: u32,
+    postscript_name: String,
+}
+
+impl FontFace {
+    /// Load `path`, where `path#N` selects face `N` of a collection
+    fn load(key: &str, path: &Path) -> Result<Self> {
+        let (path, index) = match path.to_str().and_then(|p| p.rsplit_once('#')) {
+            Some((file, index)) => (PathBuf::from(file), index.parse().context("Invalid face index")?),
+            None => (path.to_path_buf(), 0),
+        };
+        let data = std::fs::read(&path).with_context(|| format!("Failed to read font {}", path.display()))?;
+        Self::parse(key, data.into(), index).with_context(|| format!("Failed to load font {}", path.display()))
+    }
+
+    fn parse(key: &str, data: Cow<'static, [u8]>, index: u32) -> Result<Self> {
+        let face = Face::parse(&data, index).map_err(|e| anyhow!("Failed to parse font: {}", e))?;
+        if face.raw_face().table(Tag::from_bytes(b"glyf")).is_none() {
+            return Err(anyhow!("No TrueType outlines; CFF fonts are not supported"));
+        }
+
+        let postscript_name = face.names().into_iter()
+            .filter(|name| name.name_id == name_id::POST_SCRIPT_NAME)
+            .find_map(|name| name.to_string())
+            .map(|name| name.chars().filter(|c| c.is_ascii_alphanumeric() || *c == '-').collect::<String>())
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| key.to_string());
+
+        Ok(Self { key: key.to_string(), data, index, postscript_name })
+    }
+
+    fn face(&self) -> Face<'_> {
+        Face::parse(&self.data, self.index).expect("font was validated when loaded")
+    }
+}
+
+struct FontCatalog {
+    builtin: Vec<Arc<FontFace>>,
+    fallbacks: Vec<Arc<FontFace>>,
+    system: Vec<Arc<FontFace>>,
+}
+
+fn key_for(path: &str) -> String {
+    Path::new(path.split('#').next().unwrap_or(path))
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_lowercase())
+        .unwrap_or_else(|| path.to_string())
+}
+
+/// Loaded once per process. `PDF_FALLBACK_FONTS` is a comma separated list of
+/// extra TrueType files (e.g. Noto Sans Thai, Noto Sans Arabic) tried in order
+/// for characters the account's font lacks.
+fn catalog() -> &'static FontCatalog {
+    static CATALOG: OnceLock<FontCatalog> = OnceLock::new();
+    CATALOG.get_or_init(|| {
+        let fonts_path = PathBuf::from(std::env::var("FONTS_PATH").unwrap_or_else(|_| ".".to_string()));
+        let load = |key: &str, path: PathBuf| match FontFace::load(key, &path) {
+            Ok(font) => Some(Arc::new(font)),
+            Err(e) => {
+                eprintln!("Warning: skipping font '{}': {:#}", key, e);
+                None
+            }
+        };
+
+        let builtin = BUILTIN_FONTS.iter()
+            .filter_map(|(key, file, bundled)| {
+                [fonts_path.join(file), Path::new(SYSTEM_NOTO_PATH).join(file)].into_iter()
+                    .filter(|path| path.exists())
+                    .find_map(|path| load(key, path))
+                    .or_else(|| match FontFace::parse(key, Cow::Borrowed(*bundled), 0) {
+                        Ok(font) => Some(Arc::new(font)),
+                        Err(e) => {
+                            eprintln!("Warning: skipping bundled font '{}': {:#}", key, e);
+                            None
+                        }
+                    })
+            })
+            .collect();
+        let fallbacks = std::env::var("PDF_FALLBACK_FONTS").unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .filter_map(|entry| load(&key_for(entry), fonts_path.join(entry)))
+            .collect();
+        let system = SYSTEM_FONTS.iter()
+            .filter(|path| Path::new(path).exists())
+            .filter_map(|path| load(&key_for(path), PathBuf::from(path)))
+            .collect();
+
+        FontCatalog { builtin, fallbacks, system }
+    })
+}
+
+/// Keys an account may pick as its field font
+pub fn available_fonts() -> Vec<String> {
+    let catalog = catalog();
+    catalog.builtin.iter().chain(&catalog.fallbacks).chain(&catalog.system).map(|font| font.key.clone()).collect()
+}
+
+/// The account's font first, then the configured fallbacks, the remaining
+/// built-in fonts and finally system fonts. Unknown keys start at the fallbacks.
+fn font_chain(primary: &str) -> Vec<Arc<FontFace>> {
+    let catalog = catalog();
+    let mut chain: Vec<Arc<FontFace>> = Vec::new();
+    let all = || catalog.builtin.iter().chain(&catalog.fallbacks).chain(&catalog.system);
+    if let Some(font) = all().find(|font| font.key == primary) {
+        chain.push(font.clone());
+    }
+    for font in catalog.fallbacks.iter().chain(&catalog.builtin).chain(&catalog.system) {
+        if !chain.iter().any(|f| Arc::ptr_eq(f, font)) {
+            chain.push(font.clone());
+        }
+    }
+    chain
+}
+
+/// Load the account's field font; accounts without settings use Noto Sans
+pub async fn load_field_font(pool: &PgPool, user_id: i64, account_id: Option<i64>) -> Result<String, sqlx::Error> {
+    let row = sqlx::query(
+        "SELECT field_font FROM pdf_signature_settings WHERE user_id = $1 OR account_id = $2 LIMIT 1"
+    )
+    .bind(user_id)
+    .bind(account_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(row
+        .map(|row| row.get::<String, _>("field_font"))
+        .unwrap_or_else(|| FONT_NOTO_SANS.to_string()))
+}
+
+/// Fonts used while drawing text into one document.
+///
+/// Text is shaped per character against the font chain, shown with glyph ids
+/// through `Identity-H`, and [`DocumentFonts::finish`] embeds a subset of each
+/// font with the glyphs that were actually drawn plus a ToUnicode map so the
+/// text stays searchable and copyable. Should no font load at all, text is
+/// drawn in the standard Helvetica instead, limited to WinAnsi characters.
+pub struct DocumentFonts {
+    chain: Vec<Arc<FontFace>>,
+    /// Glyph id to the text it was drawn for, per chain entry
+    used: Vec<BTreeMap<u16, String>>,
+    object_ids: Vec<Option<ObjectId>>,
+}
+
+impl DocumentFonts {
+    pub fn new(primary: &str) -> Self {
+        let chain = font_chain(primary);
+        if chain.is_empty() {
+            eprintln!("Warning: no usable fonts found; falling back to Helvetica");
+        }
+        // The Helvetica fallback takes the single slot of an empty chain
+        let count = chain.len().max(1);
+        Self { chain, used: vec![BTreeMap::new(); count], object_ids: vec![None; count] }
+    }
+
+    /// WinAnsi code for `ch` in the Helvetica fallback; Latin-1 maps directly
+    fn win_ansi(ch: char) -> u8 {
+        match ch as u32 {
+            code @ (0x20..=0x7E | 0xA0..=0xFF) => code as u8,
+            _ => b'?',
+        }
+    }
+
+    fn resource_name(index: usize) -> Vec<u8> {
+        format!("LmsF{}", index + 1).into_bytes()
+    }
+
+    /// Split `text` into runs of (chain index, glyph id, character)
+    fn shape(&self, text: &str) -> Vec<(usize, Vec<(u16, char)>)> {
+        let faces: Vec<Face<'_>> = self.chain.iter().map(|font| font.face()).collect();
+        let mut runs: Vec<(usize, Vec<(u16, char)>)> = Vec::new();
+        for ch in text.chars() {
+            let ch = if ch.is_control() { ' ' } else { ch };
+            let (index, glyph) = faces.iter()
+                .enumerate()
+                .find_map(|(index, face)| face.glyph_index(ch).filter(|g| g.0 != 0).map(|g| (index, g.0)))
+                .unwrap_or((0, 0));
+            match runs.last_mut() {
+                Some((run_index, glyphs)) if *run_index == index => glyphs.push((glyph, ch)),
+                _ => runs.push((index, vec![(glyph, ch)])),
+            }
+        }
+        runs
+    }
+
+    /// Advance width of `text` in points
+    pub fn text_width(&self, text: &str, font_size: f64) -> f64 {
+        if self.chain.is_empty() {
+            // Average Helvetica advance; close enough for fitting text into a field
+            return text.chars().count() as f64 * font_size * 0.55;
+        }
+        self.shape(text).iter()
+            .map(|(index, glyphs)| {
+                let face = self.chain[*index].face();
+                let units: f64 = glyphs.iter()
+                    .map(|(glyph, _)| face.glyph_hor_advance(GlyphId(*glyph)).unwrap_or(0) as f64)
+                    .sum();
+                units * font_size / face.units_per_em() as f64
+            })
+            .sum()
+    }
+
+    /// `Tf`/`Tj` pairs that show `text` at the current text position; must be
+    /// placed inside `BT`/`ET` on a page or form registered with these fonts
+    pub fn text_operations(&mut self, text: &str, font_size: f64) -> Vec<Operation> {
+        if self.chain.is_empty() {
+            let bytes: Vec<u8> = text.chars().map(|ch| Self::win_ansi(if ch.is_control() { ' ' } else { ch })).collect();
+            for &code in &bytes {
+                self.used[0].entry(code as u16).or_insert_with(|| (code as char).to_string());
+            }
+            return vec![
+                Operation::new("Tf", vec![Object::Name(Self::resource_name(0)), Object::Real(font_size as f32)]),
+                Operation::new("Tj", vec![Object::String(bytes, StringFormat::Literal)]),
+            ];
+        }
+        let mut operations = Vec::new();
+        for (index, glyphs) in self.shape(text) {
+            let mut bytes = Vec::with_capacity(glyphs.len() * 2);
+            for (glyph, ch) in glyphs {
+                bytes.extend_from_slice(&glyph.to_be_bytes());
+                self.used[index].entry(glyph).or_insert_with(|| ch.to_string());
+            }
+            operations.push(Operation::new("Tf", vec![
+                Object::Name(Self::resource_name(index)),
+                Object::Real(font_size as f32),
+            ]));
+            operations.push(Operation::new("Tj", vec![Object::String(bytes, StringFormat::Hexadecimal)]));
+        }
+        operations
+    }
+
+    /// Font resource entries for every font drawn with so far
+    pub fn resources(&mut self, doc: &mut Document) -> Dictionary {
+        let mut fonts = Dictionary::new();
+        for index in 0..self.used.len() {
+            if self.used[index].is_empty() {
+                continue;
+            }
+            let id = *self.object_ids[index].get_or_insert_with(|| doc.new_object_id());
+            fonts.set(Self::resource_name(index), Object::Reference(id));
+        }
+        fonts
+    }
+
+    /// Add the fonts to the page's `/Resources /Font`, wherever it lives
+    pub fn register_on_page(&mut self, doc: &mut Document, page_id: ObjectId) -> Result<()> {
+        let fonts = self.resources(doc);
+
+        let resources_ref = match doc.get_dictionary(page_id)?.get(b"Resources") {
+            Ok(Object::Reference(id)) => Some(*id),
+            _ => None,
+        };
+        let resources = match resources_ref {
+            Some(id) => doc.get_dictionary_mut(id)?,
+            None => {
+                let page = doc.get_dictionary_mut(page_id)?;
+                if !page.has(b"Resources") {
+                    page.set("Resources", Dictionary::new());
+                }
+                page.get_mut(b"Resources")?.as_dict_mut()?
+            }
+        };
+
+        let font_ref = match resources.get(b"Font") {
+            Ok(Object::Reference(id)) => Some(*id),
+            _ => None,
+        };
+        let font_dict = match font_ref {
+            Some(id) => doc.get_dictionary_mut(id)?,
+            None => {
+                if !resources.has(b"Font") {
+                    resources.set("Font", Dictionary::new());
+                }
+                resources.get_mut(b"Font")?.as_dict_mut()?
+            }
+        };
+        for (name, value) in fonts.iter() {
+            font_dict.set(name.clone(), value.clone());
+        }
+        Ok(())
+    }
+
+    /// Write the subsetted fonts into the objects reserved by [`Self::resources`]
+    pub fn finish(self, doc: &mut Document) -> Result<()> {
+        if self.chain.is_empty() {
+            if let Some(font_id) = self.object_ids[0] {
+                doc.objects.insert(font_id, Object::Dictionary(dictionary! {
+                    "Type" => "Font",
+                    "Subtype" => "Type1",
+                    "BaseFont" => "Helvetica",
+                    "Encoding" => "WinAnsiEncoding",
+                }));
+            }
+            return Ok(());
+        }
+        for (index, font) in self.chain.iter().enumerate() {
+            let Some(font_id) = self.object_ids[index] else { continue };
+            let font_dict = embed_font(doc, font, &self.used[index])?;
+            doc.objects.insert(font_id, Object::Dictionary(font_dict));
+        }
+        Ok(())
+    }
+}
+
+/// Type0 font over a CIDFontType2 subset; CIDs equal glyph ids of the original font
+fn embed_font(doc: &mut Document, font: &FontFace, used: &BTreeMap<u16, String>) -> Result<Dictionary> {
+    let face = font.face();
+    let scale = 1000.0 / face.units_per_em() as f64;
+    let scaled = |value: i16| Object::Integer((value as f64 * scale).round() as i64);
+
+    let glyphs: BTreeSet<u16> = used.keys().copied().collect();
+    let subset = subset_truetype(&font.data, font.index, &glyphs)?;
+    let mut font_file = Stream::new(dictionary! { "Length1" => subset.len() as i64 }, subset);
+    font_file.compress()?;
+    let font_file_id = doc.add_object(font_file);
+
+    let base_font = format!("{}+{}", subset_tag(&font.key, &glyphs), font.postscript_name);
+    let bbox = face.global_bounding_box();
+    let mut flags = 4; // symbolic: glyphs are addressed by id, not a standard encoding
+    if face.is_monospaced() {
+        flags |= 1;
+    }
+    if face.is_italic() {
+        flags |= 64;
+    }
+    let descriptor_id = doc.add_object(dictionary! {
+        "Type" => "FontDescriptor",
+        "FontName" => Object::Name(base_font.clone().into_bytes()),
+        "Flags" => flags,
+        "FontBBox" => vec![scaled(bbox.x_min), scaled(bbox.y_min), scaled(bbox.x_max), scaled(bbox.y_max)],
+        "ItalicAngle" => Object::Real(face.italic_angle()),
+        "Ascent" => scaled(face.ascender()),
+        "Descent" => scaled(face.descender()),
+        "CapHeight" => scaled(face.capital_height().unwrap_or(face.ascender())),
+        "StemV" => if face.is_bold() { 120 } else { 80 },
+        "FontFile2" => font_file_id,
+    });
+
+    let mut widths = Vec::with_capacity(used.len() * 2);
+    for glyph in used.keys() {
+        let advance = face.glyph_hor_advance(GlyphId(*glyph)).unwrap_or(0);
+        widths.push(Object::Integer(*glyph as i64));
+        widths.push(Object::Array(vec![Object::Integer((advance as f64 * scale).round() as i64)]));
+    }
+    let cid_font_id = doc.add_object(dictionary! {
+        "Type" => "Font",
+        "Subtype" => "CIDFontType2",
+        "BaseFont" => Object::Name(base_font.clone().into_bytes()),
+        "CIDSystemInfo" => dictionary! {
+            "Registry" => Object::string_literal("Adobe"),
+            "Ordering" => Object::string_literal("Identity"),
+            "Supplement" => 0,
+        },
+        "FontDescriptor" => descriptor_id,
+        "CIDToGIDMap" => "Identity",
+        "W" => widths,
+    });
+
+    let to_unicode_id = doc.add_object(Stream::new(Dictionary::new(), to_unicode_cmap(used).into_bytes()));
+
+    Ok(dictionary! {
+        "Type" => "Font",
+        "Subtype" => "Type0",
+        "BaseFont" => Object::Name(base_font.into_bytes()),
+        "Encoding" => "Identity-H",
+        "DescendantFonts" => vec![Object::Reference(cid_font_id)],
+        "ToUnicode" => to_unicode_id,
+    })
+}
+
+/// Six uppercase letters identifying the subset, stable for the same glyph set
+fn subset_tag(key: &str, glyphs: &BTreeSet<u16>) -> String {
+    let mut hash: u32 = 0x811c9dc5;
+    for byte in key.bytes().chain(glyphs.iter().flat_map(|g| g.to_be_bytes())) {
+        hash = (hash ^ byte as u32).wrapping_mul(0x01000193);
+    }
+    (0..6).map(|i| (b'A' + ((hash >> (i * 5)) % 26) as u8) as char).collect()
+}
+
+fn to_unicode_cmap(used: &BTreeMap<u16, String>) -> String {
+    let mut cmap = String::from(
+        "/CIDInit /ProcSet findresource begin\n\
+         12 dict begin\n\
+         begincmap\n\
+         /CIDSystemInfo << /Registry (Adobe) /Ordering (UCS) /Supplement 0 >> def\n\
+         /CMapName /Adobe-Identity-UCS def\n\
+         /CMapType 2 def\n\
+         1 begincodespacerange\n\
+         <0000> <FFFF>\n\
+         endcodespacerange\n",
+    );
+    let entries: Vec<_> = used.iter().collect();
+    // At most 100 mappings per bfchar block
+    for chunk in entries.chunks(100) {
+        cmap.push_str(&format!("{} beginbfchar\n", chunk.len()));
+        for (glyph, text) in chunk {
+            let utf16: String = text.encode_utf16().map(|unit| format!("{:04X}", unit)).collect();
+            cmap.push_str(&format!("<{:04X}> <{}>\n", glyph, utf16));
+        }
+        cmap.push_str("endbfchar\n");
+    }
+    cmap.push_str(
+        "endcmap\n\
+         CMapName currentdict /CMap defineresource pop\n\
+         end\n\
+         end\n",
+    );
+    cmap
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    data.get(offset..offset + 2).map(|b| u16::from_be_bytes([b[0], b[1]]))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    data.get(offset..offset + 4).map(|b| u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+fn table_checksum(data: &[u8]) -> u32 {
+    data.chunks(4).fold(0u32, |sum, chunk| {
+        let mut word = [0u8; 4];
+        word[..chunk.len()].copy_from_slice(chunk);
+        sum.wrapping_add(u32::from_be_bytes(word))
+    })
+}
+
+/// Subset a TrueType font down to `glyphs` (plus `.notdef` and composite
+/// components) without renumbering, so glyph ids stay valid as CIDs.
+/// Dropped glyphs keep an empty `loca` entry and the glyph count is cut after
+/// the highest glyph kept.
+pub fn subset_truetype(data: &[u8], index: u32, glyphs: &BTreeSet<u16>) -> Result<Vec<u8>> {
+    let raw = RawFace::parse(data, index).map_err(|e| anyhow!("Failed to parse font: {}", e))?;
+    let table = |tag: &[u8; 4]| raw.table(Tag::from_bytes(tag));
+    let missing = |tag: &str| anyhow!("Font has no {} table", tag);
+
+    let head = table(b"head").ok_or_else(|| missing("head"))?;
+    let hhea = table(b"hhea").ok_or_else(|| missing("hhea"))?;
+    let maxp = table(b"maxp").ok_or_else(|| missing("maxp"))?;
+    let hmtx = table(b"hmtx").ok_or_else(|| missing("hmtx"))?;
+    let loca = table(b"loca").ok_or_else(|| missing("loca"))?;
+    let glyf = table(b"glyf").ok_or_else(|| missing("glyf"))?;
+
+    let glyph_count = read_u16(maxp, 4).ok_or_else(|| missing("maxp"))?;
+    let long_loca = read_u16(head, 50).ok_or_else(|| missing("head"))? == 1;
+    let glyph_data = |glyph: u16| -> &[u8] {
+        let (start, end) = if long_loca {
+            (read_u32(loca, glyph as usize * 4), read_u32(loca, glyph as usize * 4 + 4))
+        } else {
+            (
+                read_u16(loca, glyph as usize * 2).map(|v| v as u32 * 2),
+                read_u16(loca, glyph as usize * 2 + 2).map(|v| v as u32 * 2),
+            )
+        };
+        match (start, end) {
+            (Some(start), Some(end)) if start < end => glyf.get(start as usize..end as usize).unwrap_or(&[]),
+            _ => &[],
+        }
+    };
+
+    // Composite glyphs reference their components by id
+    let mut kept: BTreeSet<u16> = BTreeSet::new();
+    let mut pending: Vec<u16> = std::iter::once(0).chain(glyphs.iter().copied().filter(|g| *g < glyph_count)).collect();
+    while let Some(glyph) = pending.pop() {
+        if !kept.insert(glyph) {
+            continue;
+        }
+        let data = glyph_data(glyph);
+        if data.len() < 10 || (read_u16(data, 0).unwrap_or(0) as i16) >= 0 {
+            continue;
+        }
+        let mut offset = 10;
+        while let (Some(flags), Some(component)) = (read_u16(data, offset), read_u16(data, offset + 2)) {
+            if component < glyph_count && !kept.contains(&component) {
+                pending.push(component);
+            }
+            offset += 4 + if flags & 0x0001 != 0 { 4 } else { 2 };
+            offset += if flags & 0x0008 != 0 {
+                2
+            } else if flags & 0x0040 != 0 {
+                4
+            } else if flags & 0x0080 != 0 {
+                8
+            } else {
+                0
+            };
+            if flags & 0x0020 == 0 {
+                break;
+            }
+        }
+    }
+
+    let new_count = kept.last().copied().unwrap_or(0) + 1;
+    let mut new_glyf = Vec::new();
+    let mut new_loca = Vec::with_capacity((new_count as usize + 1) * 4);
+    for glyph in 0..new_count {
+        new_loca.extend_from_slice(&(new_glyf.len() as u32).to_be_bytes());
+        if kept.contains(&glyph) {
+            new_glyf.extend_from_slice(glyph_data(glyph));
+            new_glyf.resize(new_glyf.len().next_multiple_of(4), 0);
+        }
+    }
+    new_loca.extend_from_slice(&(new_glyf.len() as u32).to_be_bytes());
+
+    // hmtx: long metrics up to numberOfHMetrics, then left side bearings only
+    let metrics_count = read_u16(hhea, 34).ok_or_else(|| missing("hhea"))?;
+    let new_metrics_count = metrics_count.min(new_count);
+    let mut new_hmtx = hmtx.get(..new_metrics_count as usize * 4).ok_or_else(|| missing("hmtx"))?.to_vec();
+    if new_count > metrics_count {
+        let start = metrics_count as usize * 4;
+        let end = start + (new_count - metrics_count) as usize * 2;
+        new_hmtx.extend_from_slice(hmtx.get(start..end).ok_or_else(|| missing("hmtx"))?);
+    }
+
+    let mut new_head = head.to_vec();
+    new_head[8..12].copy_from_slice(&[0; 4]);
+    new_head[50..52].copy_from_slice(&1u16.to_be_bytes());
+    let mut new_hhea = hhea.to_vec();
+    new_hhea[34..36].copy_from_slice(&new_metrics_count.to_be_bytes());
+    let mut new_maxp = maxp.to_vec();
+    new_maxp[4..6].copy_from_slice(&new_count.to_be_bytes());
+
+    let tables: Vec<(&[u8; 4], Vec<u8>)> = KEPT_TABLES.iter()
+        .filter_map(|tag| {
+            let data = match *tag {
+                b"glyf" => new_glyf.clone(),
+                b"loca" => new_loca.clone(),
+                b"hmtx" => new_hmtx.clone(),
+                b"head" => new_head.clone(),
+                b"hhea" => new_hhea.clone(),
+                b"maxp" => new_maxp.clone(),
+                _ => table(tag)?.to_vec(),
+            };
+            Some((*tag, data))
+        })
+        .collect();
+
+    let table_count = tables.len() as u16;
+    let entry_selector = 15 - table_count.leading_zeros() as u16;
+    let search_range = (1u16 << entry_selector) * 16;
+    let mut output = Vec::new();
+    output.extend_from_slice(&0x0001_0000u32.to_be_bytes());
+    output.extend_from_slice(&table_count.to_be_bytes());
+    output.extend_from_slice(&search_range.to_be_bytes());
+    output.extend_from_slice(&entry_selector.to_be_bytes());
+    output.extend_from_slice(&(table_count * 16 - search_range).to_be_bytes());
+
+    let mut offset = 12 + tables.len() * 16;
+    let mut head_offset = 0;
+    for (tag, data) in &tables {
+        if *tag == b"head" {
+            head_offset = offset;
+        }
+        output.extend_from_slice(*tag);
+        output.extend_from_slice(&table_checksum(data).to_be_bytes());
+        output.extend_from_slice(&(offset as u32).to_be_bytes());
+        output.extend_from_slice(&(data.len() as u32).to_be_bytes());
+        offset += data.len().next_multiple_of(4);
+    }
+    for (_, data) in &tables {
+        output.extend_from_slice(data);
+        output.resize(output.len().next_multiple_of(4), 0);
+    }
+
+    let adjustment = 0xB1B0_AFBAu32.wrapping_sub(table_checksum(&output));
+    output[head_offset + 8..head_offset + 12].copy_from_slice(&adjustment.to_be_bytes());
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The font compiled in for the default key
+    fn test_font() -> FontFace {
+        FontFace::parse(FONT_NOTO_SANS, Cow::Borrowed(BUILTIN_FONTS[0].2), 0).unwrap()
+    }
+
+    fn fonts() -> DocumentFonts {
+        let font = Arc::new(test_font());
+        DocumentFonts { chain: vec![font], used: vec![BTreeMap::new()], object_ids: vec![None] }
+    }
+
+    #[test]
+    fn test_subset_keeps_glyph_ids() {
+        let font = test_font();
+        let face = font.face();
+        let glyphs: BTreeSet<u16> = "Nguyễn Văn Ánh".chars().filter_map(|c| face.glyph_index(c)).map(|g| g.0).collect();
+
+        let subset = subset_truetype(&font.data, 0, &glyphs).unwrap();
+        assert!(subset.len() < font.data.len() / 4);
+
+        let subset_face = Face::parse(&subset, 0).unwrap();
+        for glyph in &glyphs {
+            assert_eq!(subset_face.glyph_hor_advance(GlyphId(*glyph)), face.glyph_hor_advance(GlyphId(*glyph)));
+            assert_eq!(subset_face.glyph_bounding_box(GlyphId(*glyph)), face.glyph_bounding_box(GlyphId(*glyph)));
+        }
+        assert_eq!(table_checksum(&subset), 0xB1B0_AFBA);
+    }
+
+    #[test]
+    fn test_text_is_shown_with_glyph_ids_and_mapped_back() {
+        let mut fonts = fonts();
+        let operations = fonts.text_operations("Hà Nội", 12.0);
+        assert_eq!(operations.len(), 2);
+        assert_eq!(operations[0].operator, "Tf");
+        let Object::String(bytes, StringFormat::Hexadecimal) = &operations[1].operands[0] else {
+            panic!("expected a hex string");
+        };
+        assert_eq!(bytes.len(), "Hà Nội".chars().count() * 2);
+
+        let mut doc = Document::with_version("1.7");
+        let page_id = doc.add_object(dictionary! { "Type" => "Page" });
+        fonts.register_on_page(&mut doc, page_id).unwrap();
+        fonts.finish(&mut doc).unwrap();
+
+        let font_ref = doc.get_dictionary(page_id).unwrap()
+            .get(b"Resources").unwrap().as_dict().unwrap()
+            .get(b"Font").unwrap().as_dict().unwrap()
+            .get(b"LmsF1").unwrap().as_reference().unwrap();
+        let font = doc.get_dictionary(font_ref).unwrap();
+        let cmap_id = font.get(b"ToUnicode").unwrap().as_reference().unwrap();
+        let cmap = String::from_utf8(doc.get_object(cmap_id).unwrap().as_stream().unwrap().content.clone()).unwrap();
+        let face = test_font();
+        let glyph = face.face().glyph_index('ộ').unwrap().0;
+        assert!(cmap.contains(&format!("<{:04X}> <1ED9>", glyph)));
+    }
+
+    #[test]
+    fn test_missing_characters_fall_through_the_chain() {
+        let regular = Arc::new(test_font());
+        // A subset has no cmap, so it stands in for a primary font without Latin glyphs
+        let primary = Arc::new(FontFace {
+            key: "empty".to_string(),
+            data: subset_truetype(&regular.data, 0, &BTreeSet::new()).unwrap().into(),
+            index: 0,
+            postscript_name: "Empty".to_string(),
+        });
+        let fonts = DocumentFonts {
+            chain: vec![primary, regular.clone()],
+            used: vec![BTreeMap::new(); 2],
+            object_ids: vec![None; 2],
+        };
+
+        let a = regular.face().glyph_index('A').unwrap().0;
+        // DejaVu Sans has no CJK glyphs either, so those end up as .notdef of the primary font
+        assert_eq!(fonts.shape("AA漢"), vec![(1, vec![(a, 'A'), (a, 'A')]), (0, vec![(0, '漢')])]);
+        assert!(fonts.text_width("AA", 10.0) > fonts.text_width("A", 10.0));
+    }
+
+    #[test]
+    fn test_bundled_fonts_always_resolve() {
+        let keys = available_fonts();
+        assert!(keys.iter().any(|key| key == FONT_NOTO_SANS));
+        assert!(keys.iter().any(|key| key == FONT_NOTO_SANS_BOLD));
+        assert_eq!(font_chain(FONT_NOTO_SANS)[0].key, FONT_NOTO_SANS);
+    }
+
+    #[test]
+    fn test_empty_chain_falls_back_to_helvetica() {
+        let mut fonts = DocumentFonts { chain: Vec::new(), used: vec![BTreeMap::new()], object_ids: vec![None] };
+        let operations = fonts.text_operations("Café 漢", 10.0);
+        assert_eq!(operations[1].operands[0], Object::String(b"Caf\xe9 ?".to_vec(), StringFormat::Literal));
+        assert!(fonts.text_width("Café", 10.0) > 0.0);
+
+        let mut doc = Document::with_version("1.7");
+        let page_id = doc.add_object(dictionary! { "Type" => "Page" });
+        fonts.register_on_page(&mut doc, page_id).unwrap();
+        fonts.finish(&mut doc).unwrap();
+        let font_ref = doc.get_dictionary(page_id).unwrap()
+            .get(b"Resources").unwrap().as_dict().unwrap()
+            .get(b"Font").unwrap().as_dict().unwrap()
+            .get(b"LmsF1").unwrap().as_reference().unwrap();
+        assert_eq!(doc.get_dictionary(font_ref).unwrap().get(b"BaseFont").unwrap().as_name().unwrap(), b"Helvetica");
+    }
+}
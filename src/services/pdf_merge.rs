@@ -0,0 +1,228 @@
+use lopdf::{Dictionary, Document, Object, ObjectId};
+
+/// Page attributes that may be inherited from an ancestor `Pages` node.
+/// They are copied onto each page before the original page trees are dropped.
+const INHERITABLE_PAGE_KEYS: [&[u8]; 4] = [b"Resources", b"MediaBox", b"CropBox", b"Rotate"];
+
+/// Guard against malformed page trees with `Parent` cycles
+const MAX_PAGE_TREE_DEPTH: usize = 64;
+
+/// Count the pages of a PDF document
+pub fn count_pages(pdf_bytes: &[u8]) -> Result<usize, Box<dyn std::error::Error>> {
+    let doc = Document::load_mem(pdf_bytes)?;
+    Ok(doc.get_pages().len())
+}
+
+/// Merge multiple PDFs into one, preserving page order.
+///
+/// Every document is renumbered into a shared object id space, its pages are
+/// re-parented under a single new page tree and its form fields are combined
+/// into one AcroForm. Fonts, resources and annotations travel with the pages.
+pub fn merge_pdfs(pdf_bytes_list: Vec<Vec<u8>>) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    if pdf_bytes_list.is_empty() {
+        return Err("No PDFs to merge".into());
+    }
+
+    if pdf_bytes_list.len() == 1 {
+        return Ok(pdf_bytes_list.into_iter().next().unwrap_or_default());
+    }
+
+    let mut merged = Document::with_version("1.5");
+    let mut max_id = 1;
+    let mut page_ids: Vec<ObjectId> = Vec::new();
+    let mut form_fields: Vec<Object> = Vec::new();
+    let mut form_defaults: Option<Dictionary> = None;
+
+    for pdf_bytes in &pdf_bytes_list {
+        let mut doc = Document::load_mem(pdf_bytes)?;
+        doc.renumber_objects_with(max_id);
+        max_id = doc.max_id + 1;
+
+        // get_pages is keyed by page number, so this keeps the reading order
+        let pages: Vec<ObjectId> = doc.get_pages().into_values().collect();
+        for page_id in &pages {
+            let inherited = collect_inherited_attributes(&doc, *page_id);
+            if let Ok(page) = doc.get_dictionary_mut(*page_id) {
+                for (key, value) in inherited {
+                    if !page.has(&key) {
+                        page.set(key, value);
+                    }
+                }
+            }
+        }
+
+        if let Some(acro_form) = get_acro_form(&doc) {
+            if let Ok(fields) = acro_form.get(b"Fields").and_then(|f| doc.dereference(f)).and_then(|(_, f)| f.as_array()) {
+                form_fields.extend(fields.iter().cloned());
+            }
+            if form_defaults.is_none() {
+                let mut defaults = acro_form.clone();
+                defaults.remove(b"Fields");
+                form_defaults = Some(defaults);
+            }
+        }
+
+        page_ids.extend(pages);
+
+        for (object_id, object) in doc.objects {
+            match object.type_name().unwrap_or("") {
+                // Rebuilt below; outlines point into the old page trees
+                "Catalog" | "Pages" | "Outlines" | "Outline" => {}
+                _ => {
+                    merged.objects.insert(object_id, object);
+                }
+            }
+        }
+    }
+
+    merged.max_id = max_id;
+    let pages_id = merged.new_object_id();
+
+    for page_id in &page_ids {
+        if let Ok(page) = merged.get_dictionary_mut(*page_id) {
+            page.set("Parent", Object::Reference(pages_id));
+        }
+    }
+
+    let mut pages = Dictionary::new();
+    pages.set("Type", Object::Name(b"Pages".to_vec()));
+    pages.set("Count", Object::Integer(page_ids.len() as i64));
+    pages.set("Kids", Object::Array(page_ids.iter().map(|id| Object::Reference(*id)).collect()));
+    merged.objects.insert(pages_id, Object::Dictionary(pages));
+
+    let mut catalog = Dictionary::new();
+    catalog.set("Type", Object::Name(b"Catalog".to_vec()));
+    catalog.set("Pages", Object::Reference(pages_id));
+    if !form_fields.is_empty() {
+        let mut acro_form = form_defaults.unwrap_or_default();
+        acro_form.set("Fields", Object::Array(form_fields));
+        catalog.set("AcroForm", Object::Dictionary(acro_form));
+    }
+    let catalog_id = merged.add_object(Object::Dictionary(catalog));
+    merged.trailer.set("Root", Object::Reference(catalog_id));
+
+    merged.renumber_objects();
+    merged.compress();
+
+    let mut output = Vec::new();
+    merged.save_to(&mut output)?;
+    Ok(output)
+}
+
+/// Walk up the page tree and collect inheritable attributes the page does not override
+fn collect_inherited_attributes(doc: &Document, page_id: ObjectId) -> Vec<(Vec<u8>, Object)> {
+    let mut inherited: Vec<(Vec<u8>, Object)> = Vec::new();
+    let mut parent = doc.get_dictionary(page_id).ok()
+        .and_then(|page| page.get(b"Parent").and_then(Object::as_reference).ok());
+
+    let mut depth = 0;
+    while let Some(parent_id) = parent {
+        depth += 1;
+        if depth > MAX_PAGE_TREE_DEPTH {
+            break;
+        }
+
+        let node = match doc.get_dictionary(parent_id) {
+            Ok(node) => node,
+            Err(_) => break,
+        };
+
+        for key in INHERITABLE_PAGE_KEYS {
+            if inherited.iter().any(|(k, _)| k.as_slice() == key) {
+                continue;
+            }
+            if let Ok(value) = node.get(key) {
+                inherited.push((key.to_vec(), value.clone()));
+            }
+        }
+
+        parent = node.get(b"Parent").and_then(Object::as_reference).ok();
+    }
+
+    inherited
+}
+
+fn get_acro_form(doc: &Document) -> Option<&Dictionary> {
+    let catalog = doc.catalog().ok()?;
+    let acro_form = catalog.get(b"AcroForm").ok()?;
+    doc.dereference(acro_form).ok()?.1.as_dict().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lopdf::content::{Content, Operation};
+    use lopdf::{dictionary, Stream};
+
+    /// Build a PDF whose MediaBox and Resources live on the Pages node
+    fn build_pdf(page_count: usize) -> Vec<u8> {
+        let mut doc = Document::with_version("1.5");
+        let pages_id = doc.new_object_id();
+        let font_id = doc.add_object(dictionary! {
+            "Type" => "Font",
+            "Subtype" => "Type1",
+            "BaseFont" => "Helvetica",
+        });
+
+        let mut kids = Vec::new();
+        for i in 0..page_count {
+            let content = Content {
+                operations: vec![
+                    Operation::new("BT", vec![]),
+                    Operation::new("Tf", vec!["F1".into(), 12.into()]),
+                    Operation::new("Tj", vec![Object::string_literal(format!("Page {}", i + 1))]),
+                    Operation::new("ET", vec![]),
+                ],
+            };
+            let content_id = doc.add_object(Stream::new(Dictionary::new(), content.encode().unwrap()));
+            let page_id = doc.add_object(dictionary! {
+                "Type" => "Page",
+                "Parent" => pages_id,
+                "Contents" => content_id,
+            });
+            kids.push(Object::Reference(page_id));
+        }
+
+        doc.objects.insert(pages_id, Object::Dictionary(dictionary! {
+            "Type" => "Pages",
+            "Kids" => kids,
+            "Count" => page_count as i64,
+            "MediaBox" => vec![0.into(), 0.into(), 595.into(), 842.into()],
+            "Resources" => dictionary! { "Font" => dictionary! { "F1" => font_id } },
+        }));
+        let catalog_id = doc.add_object(dictionary! {
+            "Type" => "Catalog",
+            "Pages" => pages_id,
+        });
+        doc.trailer.set("Root", catalog_id);
+
+        let mut output = Vec::new();
+        doc.save_to(&mut output).unwrap();
+        output
+    }
+
+    #[test]
+    fn test_merge_pdfs_combines_pages_in_order() {
+        let merged = merge_pdfs(vec![build_pdf(2), build_pdf(3)]).unwrap();
+        assert_eq!(count_pages(&merged).unwrap(), 5);
+
+        let doc = Document::load_mem(&merged).unwrap();
+        let pages_id = doc.catalog().unwrap().get(b"Pages").unwrap().as_reference().unwrap();
+        for (_, page_id) in doc.get_pages() {
+            let page = doc.get_dictionary(page_id).unwrap();
+            assert_eq!(page.get(b"Parent").unwrap().as_reference().unwrap(), pages_id);
+            assert!(page.has(b"MediaBox"));
+            assert!(page.has(b"Resources"));
+        }
+
+        let texts: Vec<String> = doc.get_pages().keys().map(|n| doc.extract_text(&[*n]).unwrap()).collect();
+        assert!(texts[0].contains("Page 1"));
+        assert!(texts[2].contains("Page 1"));
+        assert!(texts[4].contains("Page 3"));
+    }
+
+    #[test]
+    fn test_merge_pdfs_rejects_empty_input() {
+        assert!(merge_pdfs(vec![]).is_err());
+    }
+}
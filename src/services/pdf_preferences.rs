@@ -33,13 +33,14 @@ pub async fn get_user_pdf_settings(
     }
 }
 
-/// Generate download filename based on user settings
+/// Generate download filename based on user settings, in the account's locale
 pub fn generate_download_filename(
     filename_format: &str,
     document_name: &str,
     submission_status: &str,
     submitter_emails: Vec<String>,
     completed_at: Option<NaiveDateTime>,
+    locale: &str,
 ) -> String {
     apply_filename_format(
         filename_format,
@@ -47,9 +48,20 @@ pub fn generate_download_filename(
         submission_status,
         submitter_emails,
         completed_at,
+        locale,
     )
 }
 
+/// `Content-Disposition` value for a download name: control characters are dropped,
+/// `filename` gets an ASCII fallback and `filename*` the UTF-8 name (RFC 6266 / 5987)
+pub fn content_disposition(disposition: &str, filename: &str) -> String {
+    let filename: String = filename.chars().filter(|c| !c.is_control()).collect();
+    let ascii: String = filename.chars()
+        .map(|c| if c.is_ascii() && c != '"' && c != '\\' { c } else { '_' })
+        .collect();
+    format!("{}; filename=\"{}\"; filename*=UTF-8''{}", disposition, ascii, urlencoding::encode(&filename))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -63,6 +75,7 @@ mod tests {
             "signed",
             vec![],
             None,
+            "en",
         );
         assert_eq!(result, "Contract - Signed.pdf");
     }
@@ -75,7 +88,20 @@ mod tests {
             "signed",
             vec!["test@example.com".to_string()],
             None,
+            "en",
         );
         assert_eq!(result, "Contract - test@example.com.pdf");
     }
+
+    #[test]
+    fn test_content_disposition() {
+        assert_eq!(
+            content_disposition("attachment", "Contrat - Signé.pdf"),
+            "attachment; filename=\"Contrat - Sign_.pdf\"; filename*=UTF-8''Contrat%20-%20Sign%C3%A9.pdf"
+        );
+        assert_eq!(
+            content_disposition("attachment", "a\r\n\"b\".pdf"),
+            "attachment; filename=\"a_b_.pdf\"; filename*=UTF-8''a%22b%22.pdf"
+        );
+    }
 }
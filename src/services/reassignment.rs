@@ -0,0 +1,43 @@
+use crate::services::field_validation::is_email;
+
+/// Statuses of submitters that have not acted yet and can still be handed over
+pub const REASSIGNABLE_STATUSES: [&str; 4] = ["pending", "sent", "viewed", "waiting"];
+
+/// Check that a submitter can be handed over to `email`.
+///
+/// `envelope_emails` holds the emails of every submitter of the submission,
+/// this one included; the new signer must not already be one of them.
+pub fn validate_reassignment(status: &str, name: &str, email: &str, envelope_emails: &[&str]) -> Result<(), String> {
+    if !REASSIGNABLE_STATUSES.contains(&status) {
+        return Err(format!("Only submitters who have not signed yet can be reassigned; this one is {}", status));
+    }
+    if name.trim().is_empty() {
+        return Err("Name is required".to_string());
+    }
+    if !is_email(email.trim()) {
+        return Err(format!("Invalid email '{}'", email));
+    }
+    if envelope_emails.iter().any(|existing| existing.trim().eq_ignore_ascii_case(email.trim())) {
+        return Err(format!("{} is already a signer of this submission", email.trim()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_reassignment() {
+        let envelope = ["jane@example.com", "boss@example.com"];
+        assert!(validate_reassignment("pending", "John", "john@example.com", &envelope).is_ok());
+        assert!(validate_reassignment("waiting", "John", " john@example.com ", &envelope).is_ok());
+
+        assert!(validate_reassignment("signed", "John", "john@example.com", &envelope).unwrap_err().contains("signed"));
+        assert!(validate_reassignment("declined", "John", "john@example.com", &envelope).is_err());
+        assert!(validate_reassignment("voided", "John", "john@example.com", &envelope).is_err());
+        assert!(validate_reassignment("pending", " ", "john@example.com", &envelope).unwrap_err().contains("Name"));
+        assert!(validate_reassignment("pending", "John", "john", &envelope).unwrap_err().contains("Invalid email"));
+        assert!(validate_reassignment("viewed", "Jane", "Jane@Example.com", &envelope).unwrap_err().contains("already a signer"));
+    }
+}
@@ -1,251 +1,289 @@
 use std::sync::Arc;
-use tokio::sync::Mutex;
-use tokio::time::{sleep, Duration};
-use chrono::Utc;
+use chrono::{DateTime, Duration, Utc};
 
 use crate::database::connection::DbPool;
-use crate::database::queries::{SubmitterQueries, EmailTemplateQueries};
-use crate::services::email::EmailService;
+use crate::database::models::DbSubmitter;
+use crate::database::queries::{SubmitterQueries, SubmissionQueries, EmailTemplateQueries, TemplateQueries};
+use crate::models::submitter::ReminderConfig;
+use crate::services::email::{EmailContext, EmailService};
+use crate::services::expiry;
+use crate::services::jobs::{self, JobHandler, JobOutcome};
 
-use crate::common::utils::replace_template_variables;
+use crate::services::email_branding::EmailBranding;
+use crate::services::email_template_engine;
 
+/// Most reminders a submitter gets
+pub const MAX_REMINDERS: i32 = 3;
+
+/// When reminder number `reminder_count + 1` is due. Reminder hours count from the
+/// invitation, and a reminder never follows the previous one sooner than the gap
+/// between their configured hours.
+pub fn next_reminder_at(
+    config: &ReminderConfig,
+    reminder_count: i32,
+    invited_at: DateTime<Utc>,
+    last_reminder_sent_at: Option<DateTime<Utc>>,
+) -> Option<DateTime<Utc>> {
+    let hours = [config.first_reminder_hours, config.second_reminder_hours, config.third_reminder_hours];
+    let index = usize::try_from(reminder_count).ok()?;
+    let due_hours = *hours.get(index)?;
+
+    let mut due = invited_at + Duration::hours(due_hours as i64);
+    if let (Some(last_sent), Some(previous_hours)) = (last_reminder_sent_at, index.checked_sub(1).map(|i| hours[i])) {
+        due = due.max(last_sent + Duration::hours((due_hours - previous_hours).max(0) as i64));
+    }
+    Some(due)
+}
+
+/// Idempotency key of a reminder job. A new invitation (reassignment) or a new
+/// expiry date (extension) starts a fresh series of reminders.
+pub fn reminder_job_key(submitter_id: i64, reminder_number: i32, invited_at: DateTime<Utc>, expires_at: Option<DateTime<Utc>>) -> String {
+    format!(
+        "reminder:{}:{}:{}:{}",
+        submitter_id,
+        reminder_number,
+        invited_at.timestamp(),
+        expires_at.map(|at| at.timestamp()).unwrap_or(0)
+    )
+}
+
+// 'waiting' submitters have not been invited yet
+fn is_awaiting_signature(submitter: &DbSubmitter) -> bool {
+    matches!(submitter.status.as_str(), "pending" | "sent" | "viewed")
+}
+
+fn reminder_config(submitter: &DbSubmitter) -> Option<ReminderConfig> {
+    let config = submitter.reminder_config.as_ref()?;
+    match serde_json::from_value::<ReminderConfig>(config.clone()) {
+        Ok(config) => Some(config),
+        Err(e) => {
+            eprintln!("Failed to parse reminder config for submitter {}: {}", submitter.id, e);
+            None
+        }
+    }
+}
+
+/// Queue the next reminder of a submitter if their reminder settings call for one.
+/// Errors are logged; scheduling never fails the request that invited the submitter.
+pub async fn schedule_next_reminder(pool: &DbPool, submitter_id: i64) {
+    let submitter = match SubmitterQueries::get_submitter_by_id(pool, submitter_id).await {
+        Ok(Some(submitter)) => submitter,
+        Ok(None) => return,
+        Err(e) => {
+            eprintln!("❌ Failed to load submitter {} to schedule a reminder: {}", submitter_id, e);
+            return;
+        }
+    };
+    if !is_awaiting_signature(&submitter) || submitter.reminder_count >= MAX_REMINDERS {
+        return;
+    }
+    let Some(config) = reminder_config(&submitter) else {
+        return;
+    };
+
+    let invited_at = submitter.invited_at.unwrap_or(submitter.created_at);
+    let Some(due) = next_reminder_at(&config, submitter.reminder_count, invited_at, submitter.last_reminder_sent_at) else {
+        return;
+    };
+    let expires_at = match submitter.submission_id {
+        Some(submission_id) => SubmissionQueries::get_submission_by_id(pool, submission_id).await
+            .ok()
+            .flatten()
+            .and_then(|submission| submission.expires_at),
+        None => None,
+    };
+
+    let reminder_number = submitter.reminder_count + 1;
+    let key = reminder_job_key(submitter.id, reminder_number, invited_at, expires_at);
+    let payload = serde_json::json!({ "submitter_id": submitter.id, "reminder_number": reminder_number });
+    if let Err(e) = jobs::enqueue(pool, jobs::JOB_SEND_REMINDER, payload, Some(due), Some(&key)).await {
+        eprintln!("❌ Failed to schedule reminder #{} for submitter {}: {}", reminder_number, submitter.id, e);
+    }
+}
+
+/// Sends signing reminders. Each `send_reminder` job sends one reminder and
+/// queues the next, so nothing has to poll the submitters table.
 #[derive(Clone)]
 pub struct ReminderQueue {
-    db_pool: Arc<Mutex<DbPool>>,
     email_service: Arc<EmailService>,
     base_url: String,
 }
 
 impl ReminderQueue {
-    pub fn new(db_pool: Arc<Mutex<DbPool>>, email_service: EmailService, base_url: String) -> Self {
+    pub fn new(email_service: EmailService, base_url: String) -> Self {
         Self {
-            db_pool,
             email_service: Arc::new(email_service),
             base_url,
         }
     }
 
-    /// Main background task that continuously checks and sends reminders
-    pub async fn start_processing(&self) {
-        println!("🔔 Starting reminder queue processor...");
-        
-        // For testing: run once immediately
-        if let Err(e) = self.process_pending_reminders().await {
-            eprintln!("❌ Error processing reminders: {}", e);
+    /// Job handler for `send_reminder`
+    pub fn handler(&self) -> JobHandler {
+        let queue = self.clone();
+        Arc::new(move |pool, payload| {
+            let queue = queue.clone();
+            Box::pin(async move { queue.run_job(&pool, payload).await })
+        })
+    }
+
+    async fn run_job(&self, pool: &DbPool, payload: serde_json::Value) -> Result<JobOutcome, String> {
+        let submitter_id = payload.get("submitter_id").and_then(|v| v.as_i64())
+            .ok_or("Reminder job has no submitter_id")?;
+        let reminder_number = payload.get("reminder_number").and_then(|v| v.as_i64())
+            .ok_or("Reminder job has no reminder_number")? as i32;
+
+        let submitter = match SubmitterQueries::get_submitter_by_id(pool, submitter_id).await {
+            Ok(Some(submitter)) => submitter,
+            Ok(None) => return Ok(JobOutcome::Done),
+            Err(e) => return Err(format!("Failed to load submitter {}: {}", submitter_id, e)),
+        };
+
+        // Signed, declined, reassigned or already reminded since the job was queued
+        if !is_awaiting_signature(&submitter) || submitter.reminder_count + 1 != reminder_number {
+            return Ok(JobOutcome::Done);
         }
-        
-        loop {
-            if let Err(e) = self.process_pending_reminders().await {
-                eprintln!("❌ Error processing reminders: {}", e);
-            }
-            
-            // Check every 5 seconds for testing
-            sleep(Duration::from_secs(5)).await;
+        // Extending an expired submission schedules its reminders again
+        match expiry::closed_link_reason(pool, &submitter).await {
+            Ok(None) => {}
+            Ok(Some(_)) => return Ok(JobOutcome::Done),
+            Err(e) => return Err(format!("Failed to check submission of submitter {}: {}", submitter.id, e)),
         }
+        let Some(config) = reminder_config(&submitter) else {
+            return Ok(JobOutcome::Done);
+        };
+        let invited_at = submitter.invited_at.unwrap_or(submitter.created_at);
+        let Some(due) = next_reminder_at(&config, submitter.reminder_count, invited_at, submitter.last_reminder_sent_at) else {
+            return Ok(JobOutcome::Done);
+        };
+        if due > Utc::now() {
+            return Ok(JobOutcome::RunAt(due));
+        }
+
+        self.send_reminder(pool, &submitter, reminder_number).await?;
+        SubmitterQueries::update_reminder_sent(pool, submitter.id).await
+            .map_err(|e| format!("Failed to update reminder count for submitter {}: {}", submitter.id, e))?;
+        println!("✅ Reminder #{} sent to submitter {}", reminder_number, submitter.id);
+        record_reminder_event(pool, &submitter, reminder_number).await;
+
+        schedule_next_reminder(pool, submitter.id).await;
+        Ok(JobOutcome::Done)
     }
 
-    /// Process all pending reminders
-    pub async fn process_pending_reminders(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        let submitters = {
-            let db_guard = self.db_pool.lock().await;
-            SubmitterQueries::get_pending_reminders(&*db_guard).await?
+    async fn send_reminder(&self, pool: &DbPool, submitter: &DbSubmitter, reminder_number: i32) -> Result<(), String> {
+        let template_name = match TemplateQueries::get_template_by_id(pool, submitter.template_id).await {
+            Ok(Some(template)) => template.name,
+            _ => format!("Document #{}", submitter.template_id),
         };
-        
-        if submitters.is_empty() {
-            return Ok(());
-        }
-        for submitter in submitters {
-            // Parse reminder config
-            let reminder_config = match submitter.reminder_config.as_ref() {
-                Some(config_json) => {
-                    match serde_json::from_value::<crate::models::submitter::ReminderConfig>(config_json.clone()) {
-                        Ok(config) => config,
-                        Err(e) => {
-                            eprintln!("Failed to parse reminder config for submitter {}: {}", submitter.id, e);
-                            continue;
-                        }
-                    }
-                },
-                None => continue, // No reminder config, skip
-            };
-
-            // Calculate time since creation
-            let now = Utc::now();
-            let hours_since_created = (now - submitter.created_at).num_hours();
-            
-            // println!("🔍 Checking submitter {}: reminder_count={}, hours_since_created={}, first={}, second={}, third={}",
-            //     submitter.id, submitter.reminder_count, hours_since_created,
-            //     reminder_config.first_reminder_hours, reminder_config.second_reminder_hours, reminder_config.third_reminder_hours);
-            
-            // Determine which reminder to send based on time elapsed
-            let reminder_to_send = if submitter.reminder_count == 0 {
-                // First reminder
-                if hours_since_created >= reminder_config.first_reminder_hours as i64 {
-                    Some(1)
-                } else {
-                    None
-                }
-            } else if submitter.reminder_count == 1 {
-                // Second reminder
-                if hours_since_created >= reminder_config.second_reminder_hours as i64 {
-                    Some(2)
-                } else {
-                    None
-                }
-            } else if submitter.reminder_count == 2 {
-                // Third reminder
-                if hours_since_created >= reminder_config.third_reminder_hours as i64 {
-                    Some(3)
-                } else {
-                    None
-                }
-            } else {
-                None // Already sent all reminders
-            };
-
-            if let Some(reminder_number) = reminder_to_send {
-                println!("🎯 FOUND ELIGIBLE SUBMITTER: reminder_number={}, submitter_id={}", reminder_number, submitter.id);
-                
-                // Check if we should send this reminder (not sent too recently)
-                if let Some(last_sent) = submitter.last_reminder_sent_at {
-                    let hours_since_last = (now - last_sent).num_hours();
-                    println!("⏰ Last reminder sent {} hours ago", hours_since_last);
-                    
-                    // Calculate minimum gap required between reminders
-                    let required_gap_hours = match reminder_number {
-                        1 => 0, // First reminder has no gap requirement
-                        2 => reminder_config.second_reminder_hours - reminder_config.first_reminder_hours,
-                        3 => reminder_config.third_reminder_hours - reminder_config.second_reminder_hours,
-                        _ => continue,
-                    };
-                    
-                    if hours_since_last < required_gap_hours as i64 {
-                        println!("⏰ Skipping reminder - need {} hours gap, only {} hours passed", required_gap_hours, hours_since_last);
-                        continue;
-                    }
-                } else {
-                    println!("⏰ No previous reminder sent - proceeding");
-                }
-
-                println!("🔍 About to query template for ID: {}", submitter.template_id);
-                // Get the actual template name - now included in the submitter data
-                let template_name = submitter.template_name.clone().unwrap_or_else(|| format!("Document #{}", submitter.template_id));
-                println!("✅ Using template name from submitter data: '{}'", template_name);
-
-                let signature_link = format!("{}/templates/{}/edit", self.base_url, submitter.token);
-
-                println!("📧 Sending reminder #{} to {} with template name: '{}' and link: {}", 
-                    reminder_number, submitter.email, template_name, signature_link);
-
-                println!("🚀 About to call email_service.send_signature_reminder");
-                
-                // Try to get user's default reminder template
-                let pool = self.db_pool.lock().await;
-                let email_template_result = EmailTemplateQueries::get_default_template_by_type(
-                    &pool, submitter.user_id, "reminder"
-                ).await;
-                
-                match email_template_result {
-                    Ok(Some(email_template)) => {
-                        // Use custom email template
-                        let reminder_number_str = reminder_number.to_string();
-                        
-                        let mut variables = std::collections::HashMap::new();
-                        variables.insert("submitter.name", submitter.name.as_str());
-                        variables.insert("template.name", &template_name);
-                        variables.insert("submitter.link", &signature_link);
-                        variables.insert("account.name", "DocuSeal Pro");
-                        variables.insert("reminder.number", &reminder_number_str);
-
-                        let subject = replace_template_variables(&email_template.subject, &variables);
-                        let body = replace_template_variables(&email_template.body, &variables);
-
-                        // Generate attachments if needed
-                        let mut document_path = None;
-
-                        if email_template.attach_documents {
-                            // Get template to access documents
-                            if let Ok(Some(db_template)) = crate::database::queries::TemplateQueries::get_template_by_id(&pool, submitter.template_id).await {
-                                if let Ok(storage_service) = crate::services::storage::StorageService::new().await {
-                                    if let Some(documents) = &db_template.documents {
-                                        if let Ok(docs) = serde_json::from_value::<Vec<crate::models::template::Document>>(documents.clone()) {
-                                            if let Some(first_doc) = docs.first() {
-                                                if let Ok(pdf_bytes) = storage_service.download_file(&first_doc.url).await {
-                                                    let temp_file = std::env::temp_dir().join(format!("original_document_{}.pdf", submitter.template_id));
-                                                    if let Ok(_) = tokio::fs::write(&temp_file, pdf_bytes).await {
-                                                        document_path = Some(temp_file.to_string_lossy().to_string());
-                                                    }
-                                                }
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                        }
-
-                        match self.email_service.send_template_email(
-                            &submitter.email,
-                            &submitter.name,
-                            &subject,
-                            &body,
-                            &email_template.body_format,
-                            email_template.attach_documents,
-                            email_template.attach_audit_log,
-                            document_path.as_deref(),
-                            None, // No audit log for reminder
-                        ).await {
-                            Ok(_) => {
-                                println!("✅ Template reminder #{} sent successfully to submitter {}", reminder_number, submitter.id);
-                                
-                                // Update reminder count in database
-                                if let Err(e) = SubmitterQueries::update_reminder_sent(&pool, submitter.id).await {
-                                    eprintln!("❌ Failed to update reminder count for submitter {}: {:?}", submitter.id, e);
-                                } else {
-                                    println!("✅ Updated reminder count to {} for submitter {}", reminder_number, submitter.id);
-                                }
-                            },
-                            Err(e) => {
-                                eprintln!("❌ Failed to send template reminder email to {}: {}", submitter.email, e);
-                            }
-                        }
-
-                        // Clean up temporary file
-                        if let Some(path) = document_path {
-                            let _ = tokio::fs::remove_file(path).await;
-                        }
-                    },
-                    _ => {
-                        // Fall back to default hardcoded reminder email
-                        match self.email_service.send_signature_reminder(
-                            &submitter.email,
-                            &submitter.name,
-                            &template_name,
-                            &signature_link,
-                            reminder_number,
-                        ).await {
-                            Ok(_) => {
-                                println!("✅ Email service returned OK");
-                                println!("✅ Reminder #{} sent successfully to submitter {}", reminder_number, submitter.id);
-
-                                // Update reminder count in database
-                                if let Err(e) = SubmitterQueries::update_reminder_sent(&pool, submitter.id).await {
-                                    eprintln!("❌ Failed to update reminder count for submitter {}: {:?}", submitter.id, e);
-                                } else {
-                                    println!("✅ Updated reminder count to {} for submitter {}", reminder_number, submitter.id);
-                                }
-                            },
-                            Err(e) => {
-                                eprintln!("❌ Failed to send reminder email to {}: {}", submitter.email, e);
-                            }
-                        }
-                    }
-                }
-                
-                // Small delay between emails to avoid overwhelming SMTP server
-                sleep(Duration::from_millis(500)).await;
+        let signature_link = format!("{}/templates/{}/edit", self.base_url, submitter.token);
+        let branding = EmailBranding::for_user(pool, submitter.user_id).await.for_recipient(submitter);
+        let email_service = self.email_service.as_ref().clone().with_context(EmailContext {
+            user_id: Some(submitter.user_id),
+            submitter_id: Some(submitter.id),
+            kind: Some("reminder".to_string()),
+        }).with_branding(&branding);
+
+        println!("📧 Sending reminder #{} to {} with template name: '{}' and link: {}",
+            reminder_number, submitter.email, template_name, signature_link);
+
+        // Try to get user's default reminder template
+        let email_template = match EmailTemplateQueries::get_default_template_by_type(pool, submitter.user_id, "reminder").await {
+            Ok(Some(email_template)) => email_template,
+            _ => {
+                // Fall back to default hardcoded reminder email
+                return email_service.send_signature_reminder(
+                    &submitter.email,
+                    &submitter.name,
+                    &template_name,
+                    &signature_link,
+                    reminder_number,
+                ).await.map_err(|e| format!("Failed to send reminder email to {}: {}", submitter.email, e));
             }
+        };
+
+        let mut context = branding.context();
+        email_template_engine::insert_submitter_variables(pool, &mut context, submitter, &template_name).await;
+        context
+            .insert("submitter.link", signature_link.as_str())
+            .insert("reminder.number", reminder_number);
+
+        let (subject, body) = branding.render(&email_template, &context)?;
+
+        // Generate attachments if needed
+        let mut document_path = None;
+        if email_template.attach_documents {
+            document_path = download_original_document(pool, submitter.template_id).await;
+        }
+
+        let result = email_service.send_template_email(
+            &submitter.email,
+            &submitter.name,
+            &subject,
+            &body,
+            &email_template.body_format,
+            email_template.attach_documents,
+            email_template.attach_audit_log,
+            document_path.as_deref(),
+            None, // No audit log for reminder
+        ).await;
+
+        // Clean up temporary file
+        if let Some(path) = document_path {
+            let _ = tokio::fs::remove_file(path).await;
         }
+        result.map_err(|e| format!("Failed to send template reminder email to {}: {}", submitter.email, e))
+    }
+}
+
+// First document of the template, written to a temporary file for attaching
+async fn download_original_document(pool: &DbPool, template_id: i64) -> Option<String> {
+    let db_template = TemplateQueries::get_template_by_id(pool, template_id).await.ok()??;
+    let storage_service = crate::services::storage::StorageService::new().await.ok()?;
+    let docs = serde_json::from_value::<Vec<crate::models::template::Document>>(db_template.documents?).ok()?;
+    let pdf_bytes = storage_service.download_file(&docs.first()?.url).await.ok()?;
+
+    let temp_file = std::env::temp_dir().join(format!("original_document_{}.pdf", template_id));
+    tokio::fs::write(&temp_file, pdf_bytes).await.ok()?;
+    Some(temp_file.to_string_lossy().to_string())
+}
+
+async fn record_reminder_event(pool: &DbPool, submitter: &DbSubmitter, reminder_number: i32) {
+    crate::services::audit::record_event(
+        pool,
+        submitter,
+        crate::services::audit::EVENT_REMINDED,
+        "System",
+        format!("Reminder #{} sent to {}", reminder_number, submitter.email),
+        crate::services::audit::AuditContext::system(),
+        Some(serde_json::json!({ "reminder_number": reminder_number })),
+    ).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_next_reminder_at() {
+        let config = ReminderConfig { first_reminder_hours: 24, second_reminder_hours: 72, third_reminder_hours: 168 };
+        let invited = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+
+        assert_eq!(next_reminder_at(&config, 0, invited, None), Some(invited + Duration::hours(24)));
+        assert_eq!(next_reminder_at(&config, 1, invited, Some(invited + Duration::hours(24))), Some(invited + Duration::hours(72)));
+        // A late first reminder pushes the second one back by the configured gap
+        let late = invited + Duration::hours(60);
+        assert_eq!(next_reminder_at(&config, 1, invited, Some(late)), Some(late + Duration::hours(48)));
+        assert_eq!(next_reminder_at(&config, 3, invited, Some(late)), None);
+        assert_eq!(next_reminder_at(&config, -1, invited, None), None);
+    }
 
-        Ok(())
+    #[test]
+    fn test_reminder_job_key() {
+        let invited = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(reminder_job_key(7, 1, invited, None), "reminder:7:1:1735689600:0");
+        assert_ne!(
+            reminder_job_key(7, 1, invited, Some(invited + Duration::days(7))),
+            reminder_job_key(7, 1, invited, Some(invited + Duration::days(14)))
+        );
     }
 }
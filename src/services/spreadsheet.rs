@@ -0,0 +1,335 @@
+use std::collections::HashMap;
+use std::io::{Cursor, Read};
+
+use xmlparser::{ElementEnd, Token, Tokenizer};
+
+/// Columns of an Excel worksheet, `A` to `XFD`
+const MAX_COLUMNS: usize = 16_384;
+
+/// Cells kept from one worksheet, counting the blanks that pad sparse rows
+const MAX_CELLS: usize = 1_000_000;
+
+/// Largest XML part read out of an XLSX archive, after decompression
+const MAX_ENTRY_SIZE: u64 = 64 * 1024 * 1024;
+
+/// Read the rows of an uploaded CSV or XLSX file (first worksheet).
+///
+/// XLSX is detected by its zip signature or file extension; anything else is
+/// read as UTF-8 CSV with `,` or `;` as separator. Blank rows are dropped.
+pub fn read_rows(filename: Option<&str>, data: &[u8]) -> Result<Vec<Vec<String>>, String> {
+    let is_xlsx = data.starts_with(b"PK\x03\x04")
+        || filename.map(|f| f.to_lowercase().ends_with(".xlsx")).unwrap_or(false);
+    let rows = if is_xlsx { read_xlsx(data)? } else { read_csv(data)? };
+    Ok(rows.into_iter()
+        .filter(|row| row.iter().any(|cell| !cell.trim().is_empty()))
+        .collect())
+}
+
+fn read_csv(data: &[u8]) -> Result<Vec<Vec<String>>, String> {
+    let text = std::str::from_utf8(data).map_err(|_| "CSV file must be UTF-8 encoded".to_string())?;
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+
+    // Spreadsheet exports in many locales use ';' because ',' is the decimal separator
+    let header = text.lines().next().unwrap_or("");
+    let delimiter = if header.matches(';').count() > header.matches(',').count() { ';' } else { ',' };
+
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut cell = String::new();
+    let mut in_quotes = false;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    cell.push('"');
+                }
+                '"' => in_quotes = false,
+                _ => cell.push(c),
+            }
+            continue;
+        }
+        match c {
+            '"' if cell.is_empty() => in_quotes = true,
+            c if c == delimiter => row.push(std::mem::take(&mut cell)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' | '\r' => {
+                row.push(std::mem::take(&mut cell));
+                rows.push(std::mem::take(&mut row));
+            }
+            _ => cell.push(c),
+        }
+    }
+    if in_quotes {
+        return Err("CSV file has an unterminated quoted value".to_string());
+    }
+    if !cell.is_empty() || !row.is_empty() {
+        row.push(cell);
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+fn unescape_xml(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let Some(end) = rest.find(';') else { break };
+        let entity = &rest[1..end];
+        let decoded = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ if entity.starts_with("#x") => u32::from_str_radix(&entity[2..], 16).ok().and_then(char::from_u32),
+            _ if entity.starts_with('#') => entity[1..].parse().ok().and_then(char::from_u32),
+            _ => None,
+        };
+        match decoded {
+            Some(c) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+fn zip_entry(archive: &mut zip::ZipArchive<Cursor<&[u8]>>, name: &str) -> Result<Option<String>, String> {
+    let mut entry = match archive.by_name(name) {
+        Ok(entry) => entry,
+        Err(zip::result::ZipError::FileNotFound) => return Ok(None),
+        Err(e) => return Err(format!("Invalid XLSX file: {}", e)),
+    };
+    // The declared size can lie, so the limit is applied to what is actually inflated
+    let mut text = String::new();
+    (&mut entry).take(MAX_ENTRY_SIZE + 1).read_to_string(&mut text).map_err(|e| format!("Invalid XLSX file: {}", e))?;
+    if text.len() as u64 > MAX_ENTRY_SIZE {
+        return Err(format!("XLSX file is too large: {} exceeds {} MB", name, MAX_ENTRY_SIZE / 1024 / 1024));
+    }
+    Ok(Some(text))
+}
+
+/// Path of the first worksheet, following workbook.xml and its relationships
+fn first_sheet_path(archive: &mut zip::ZipArchive<Cursor<&[u8]>>) -> Result<String, String> {
+    let fallback = "xl/worksheets/sheet1.xml".to_string();
+    let (Some(workbook), Some(rels)) = (zip_entry(archive, "xl/workbook.xml")?, zip_entry(archive, "xl/_rels/workbook.xml.rels")?) else {
+        return Ok(fallback);
+    };
+
+    let mut sheet_rel = None;
+    let mut in_sheet = false;
+    for token in Tokenizer::from(workbook.as_str()).flatten() {
+        match token {
+            Token::ElementStart { local, .. } => in_sheet = local.as_str() == "sheet",
+            Token::Attribute { prefix, local, value, .. } if in_sheet && prefix.as_str() == "r" && local.as_str() == "id" => {
+                sheet_rel = Some(value.as_str().to_string());
+                break;
+            }
+            _ => {}
+        }
+    }
+    let Some(sheet_rel) = sheet_rel else { return Ok(fallback) };
+
+    let mut id = None;
+    let mut target = None;
+    for token in Tokenizer::from(rels.as_str()).flatten() {
+        match token {
+            Token::ElementStart { .. } => {
+                id = None;
+                target = None;
+            }
+            Token::Attribute { local, value, .. } => match local.as_str() {
+                "Id" => id = Some(value.as_str().to_string()),
+                "Target" => target = Some(value.as_str().to_string()),
+                _ => {}
+            },
+            Token::ElementEnd { .. } if id.as_deref() == Some(sheet_rel.as_str()) => {
+                if let Some(target) = target.take() {
+                    return Ok(match target.strip_prefix('/') {
+                        Some(absolute) => absolute.to_string(),
+                        None => format!("xl/{}", target),
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(fallback)
+}
+
+fn shared_strings(xml: &str) -> Vec<String> {
+    let mut strings = Vec::new();
+    let mut current = String::new();
+    let mut in_text = false;
+    for token in Tokenizer::from(xml).flatten() {
+        match token {
+            Token::ElementStart { local, .. } => in_text = local.as_str() == "t",
+            Token::ElementEnd { end: ElementEnd::Close(_, local), .. } => match local.as_str() {
+                "si" => strings.push(std::mem::take(&mut current)),
+                "t" => in_text = false,
+                _ => {}
+            },
+            Token::Text { text } if in_text => current.push_str(&unescape_xml(text.as_str())),
+            Token::Cdata { text, .. } if in_text => current.push_str(text.as_str()),
+            _ => {}
+        }
+    }
+    strings
+}
+
+/// Zero-based column index of a cell reference such as `AB12`; `None` past `XFD`
+fn column_index(reference: &str) -> Option<usize> {
+    let letters: String = reference.chars().take_while(|c| c.is_ascii_alphabetic()).collect();
+    if letters.is_empty() || letters.len() > 3 {
+        return None;
+    }
+    let column = letters.to_ascii_uppercase().bytes().fold(0, |acc, b| acc * 26 + (b - b'A' + 1) as usize);
+    (column <= MAX_COLUMNS).then(|| column - 1)
+}
+
+fn read_xlsx(data: &[u8]) -> Result<Vec<Vec<String>>, String> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(data)).map_err(|e| format!("Invalid XLSX file: {}", e))?;
+    let strings = zip_entry(&mut archive, "xl/sharedStrings.xml")?
+        .map(|xml| shared_strings(&xml))
+        .unwrap_or_default();
+    let sheet_path = first_sheet_path(&mut archive)?;
+    let sheet = zip_entry(&mut archive, &sheet_path)?
+        .ok_or_else(|| "XLSX file has no worksheet".to_string())?;
+
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    let mut cells = 0;
+    let mut row: HashMap<usize, String> = HashMap::new();
+    let mut cell_column = 0;
+    let mut next_column = 0;
+    let mut cell_type = String::new();
+    let mut value = String::new();
+    let mut element = String::new();
+    for token in Tokenizer::from(sheet.as_str()) {
+        let token = token.map_err(|e| format!("Invalid XLSX worksheet: {}", e))?;
+        match token {
+            Token::ElementStart { local, .. } => {
+                element = local.as_str().to_string();
+                if element == "c" {
+                    cell_column = next_column;
+                    cell_type.clear();
+                    value.clear();
+                }
+            }
+            Token::Attribute { local, value: attr, .. } if element == "c" => match local.as_str() {
+                "r" => cell_column = column_index(attr.as_str())
+                    .ok_or_else(|| format!("Invalid XLSX cell reference '{}'", attr.as_str()))?,
+                "t" => cell_type = attr.as_str().to_string(),
+                _ => {}
+            },
+            Token::Text { text } if element == "v" || element == "t" => value.push_str(&unescape_xml(text.as_str())),
+            Token::ElementEnd { end: ElementEnd::Close(_, local), .. } => {
+                match local.as_str() {
+                    "c" => {
+                        if cell_column >= MAX_COLUMNS {
+                            return Err(format!("XLSX worksheet has more than {} columns", MAX_COLUMNS));
+                        }
+                        let cell = match cell_type.as_str() {
+                            "s" => value.trim().parse::<usize>().ok().and_then(|i| strings.get(i).cloned()).unwrap_or_default(),
+                            "b" => if value.trim() == "1" { "TRUE".to_string() } else { "FALSE".to_string() },
+                            _ => value.clone(),
+                        };
+                        row.insert(cell_column, cell);
+                        next_column = cell_column + 1;
+                    }
+                    "row" => {
+                        let width = row.keys().max().map(|max| max + 1).unwrap_or(0);
+                        cells += width;
+                        if cells > MAX_CELLS {
+                            return Err(format!("XLSX worksheet is too large; at most {} cells are read", MAX_CELLS));
+                        }
+                        rows.push((0..width).map(|i| row.remove(&i).unwrap_or_default()).collect());
+                        row.clear();
+                        next_column = 0;
+                    }
+                    _ => {}
+                }
+                element.clear();
+            }
+            Token::ElementEnd { end: ElementEnd::Empty, .. } => {
+                if element == "c" {
+                    next_column = cell_column + 1;
+                }
+                element.clear();
+            }
+            _ => {}
+        }
+    }
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn test_csv_quotes_and_separators() {
+        let csv = "\u{feff}Name,Email,Note\r\n\"Doe, Jane\",jane@example.com,\"said \"\"hi\"\"\nthen left\"\r\n\r\nBob,bob@example.com,\n";
+        let rows = read_rows(Some("people.csv"), csv.as_bytes()).unwrap();
+        assert_eq!(rows, vec![
+            vec!["Name", "Email", "Note"],
+            vec!["Doe, Jane", "jane@example.com", "said \"hi\"\nthen left"],
+            vec!["Bob", "bob@example.com", ""],
+        ]);
+
+        let semicolons = read_rows(None, "Name;Price\nJane;1,5".as_bytes()).unwrap();
+        assert_eq!(semicolons[1], vec!["Jane", "1,5"]);
+        assert!(read_rows(None, b"a,\"b").is_err());
+    }
+
+    #[test]
+    fn test_xlsx_shared_and_inline_strings() {
+        let mut buffer = Cursor::new(Vec::new());
+        {
+            let mut zip = zip::ZipWriter::new(&mut buffer);
+            let options = zip::write::FileOptions::default();
+            let files = [
+                ("xl/workbook.xml", r#"<workbook xmlns:r="urn:r"><sheets><sheet name="People" sheetId="1" r:id="rId1"/></sheets></workbook>"#),
+                ("xl/_rels/workbook.xml.rels", r#"<Relationships><Relationship Id="rId1" Target="worksheets/people.xml"/></Relationships>"#),
+                ("xl/sharedStrings.xml", r#"<sst><si><t>Name</t></si><si><t>Email</t></si><si><r><t>Tom </t></r><r><t>&amp; Co</t></r></si></sst>"#),
+                ("xl/worksheets/people.xml", concat!(
+                    r#"<worksheet><sheetData>"#,
+                    r#"<row r="1"><c r="A1" t="s"><v>0</v></c><c r="B1" t="s"><v>1</v></c><c r="D1" t="inlineStr"><is><t>Qty</t></is></c></row>"#,
+                    r#"<row r="2"><c r="A2" t="s"><v>2</v></c><c r="B2" t="str"><v>tom@example.com</v></c><c r="D2"><v>3</v></c></row>"#,
+                    r#"</sheetData></worksheet>"#,
+                )),
+            ];
+            for (name, content) in files {
+                zip.start_file(name, options).unwrap();
+                zip.write_all(content.as_bytes()).unwrap();
+            }
+            zip.finish().unwrap();
+        }
+
+        let rows = read_rows(Some("people.xlsx"), buffer.get_ref()).unwrap();
+        assert_eq!(rows, vec![
+            vec!["Name", "Email", "", "Qty"],
+            vec!["Tom & Co", "tom@example.com", "", "3"],
+        ]);
+    }
+
+    #[test]
+    fn test_xlsx_column_references_are_bounded() {
+        assert_eq!(column_index("A1"), Some(0));
+        assert_eq!(column_index("ab12"), Some(27));
+        assert_eq!(column_index("XFD1"), Some(MAX_COLUMNS - 1));
+        assert_eq!(column_index("XFE1"), None);
+        assert_eq!(column_index("ZZZZZZZZZZZZZZZZ1"), None);
+        assert_eq!(column_index("12"), None);
+    }
+}
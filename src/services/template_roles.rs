@@ -0,0 +1,99 @@
+use std::collections::HashSet;
+
+use crate::database::models::DbTemplateRole;
+use crate::models::submitter::CreateSubmitterRequest;
+
+/// Role of each submitter of a new submission.
+///
+/// Submitters naming a role (by `role_id` or `role`) get that role; the others
+/// take the template's unclaimed roles in order. Submitters left over once every
+/// role is taken get none and can only fill fields without a role.
+pub fn assign_roles(roles: &[DbTemplateRole], submitters: &[CreateSubmitterRequest]) -> Result<Vec<Option<i64>>, String> {
+    let mut assigned = Vec::with_capacity(submitters.len());
+    let mut claimed = HashSet::new();
+    for submitter in submitters {
+        let role = match (submitter.role_id, submitter.role.as_deref()) {
+            (Some(id), _) => Some(roles.iter().find(|r| r.id == id)
+                .ok_or_else(|| format!("Role {} does not exist in this template", id))?),
+            (None, Some(name)) => Some(roles.iter().find(|r| r.name == name)
+                .ok_or_else(|| format!("Role '{}' does not exist in this template", name))?),
+            (None, None) => None,
+        };
+        if let Some(role) = role {
+            if !claimed.insert(role.id) {
+                return Err(format!("Role '{}' is assigned to more than one submitter", role.name));
+            }
+        }
+        assigned.push(role.map(|r| r.id));
+    }
+
+    let mut unclaimed = roles.iter().filter(|r| !claimed.contains(&r.id));
+    for role in assigned.iter_mut().filter(|role| role.is_none()) {
+        *role = unclaimed.next().map(|r| r.id);
+    }
+    Ok(assigned)
+}
+
+/// Whether a submitter signing as `submitter_role` may fill a field assigned to `field_role`
+pub fn can_fill(field_role: Option<i64>, submitter_role: Option<i64>) -> bool {
+    match field_role {
+        Some(role) => submitter_role == Some(role),
+        None => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn role(id: i64, name: &str) -> DbTemplateRole {
+        DbTemplateRole {
+            id,
+            template_id: 1,
+            name: name.to_string(),
+            display_order: id as i32,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn submitter(role_id: Option<i64>, role: Option<&str>) -> CreateSubmitterRequest {
+        CreateSubmitterRequest {
+            name: "Jane".to_string(),
+            email: "jane@example.com".to_string(),
+            order: None,
+            role_id,
+            role: role.map(str::to_string),
+            locale: None,
+            reminder_config: None,
+            values: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_assign_roles() {
+        let roles = vec![role(1, "Buyer"), role(2, "Seller")];
+
+        let implicit = assign_roles(&roles, &[submitter(None, None), submitter(None, None), submitter(None, None)]).unwrap();
+        assert_eq!(implicit, vec![Some(1), Some(2), None]);
+
+        let explicit = assign_roles(&roles, &[submitter(None, None), submitter(None, Some("Buyer"))]).unwrap();
+        assert_eq!(explicit, vec![Some(2), Some(1)]);
+        assert_eq!(assign_roles(&roles, &[submitter(Some(2), None)]).unwrap(), vec![Some(2)]);
+
+        assert!(assign_roles(&roles, &[submitter(None, Some("Witness"))]).is_err());
+        assert!(assign_roles(&roles, &[submitter(Some(9), None)]).is_err());
+        assert!(assign_roles(&roles, &[submitter(Some(1), None), submitter(None, Some("Buyer"))]).is_err());
+        assert_eq!(assign_roles(&[], &[submitter(None, None)]).unwrap(), vec![None]);
+    }
+
+    #[test]
+    fn test_can_fill() {
+        assert!(can_fill(None, None));
+        assert!(can_fill(None, Some(1)));
+        assert!(can_fill(Some(1), Some(1)));
+        assert!(!can_fill(Some(1), Some(2)));
+        assert!(!can_fill(Some(1), None));
+    }
+}
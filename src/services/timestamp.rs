@@ -0,0 +1,508 @@
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use cms::cert::CertificateChoices;
+use cms::content_info::ContentInfo;
+use cms::signed_data::{SignedData, SignerIdentifier, SignerInfo};
+use der::asn1::{BitString, Int, ObjectIdentifier, OctetString, SetOfVec};
+use der::{Any, Decode, Encode, Reader, Sequence, SliceReader, Tag};
+use openssl::asn1::Asn1Time;
+use openssl::bn::BigNum;
+use openssl::hash::{hash, MessageDigest};
+use openssl::nid::Nid;
+use openssl::pkey::{PKey, Private};
+use openssl::sign::Verifier;
+use openssl::x509::extension::ExtendedKeyUsage;
+use openssl::x509::{X509Builder, X509NameBuilder, X509};
+use spki::AlgorithmIdentifierOwned;
+use sqlx::{PgPool, Row};
+use x509_cert::ext::Extensions;
+use x509_cert::Certificate;
+
+use crate::services::cms_builder::{
+    attribute, build_signed_data, digest_for, sha256_algorithm,
+    ID_AA_TIME_STAMP_TOKEN, ID_MESSAGE_DIGEST, ID_SIGNED_DATA,
+};
+
+const ID_CT_TST_INFO: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.9.16.1.4");
+
+/// Placeholder policy reported by the local stand-in authority
+const LOCAL_TSA_POLICY: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.3.4.1");
+
+/// `tsa_url` value selecting the in-process stand-in instead of a real authority.
+/// Its timestamps are signed by a throwaway certificate, so it only works in tests.
+pub const LOCAL_TSA_URL: &str = "local";
+
+const REQUEST_TIMEOUT_SECS: u64 = 15;
+
+// RFC 3161 structures
+
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+struct MessageImprint {
+    hash_algorithm: AlgorithmIdentifierOwned,
+    hashed_message: OctetString,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+struct TimeStampReq {
+    version: u8,
+    message_imprint: MessageImprint,
+    req_policy: Option<ObjectIdentifier>,
+    nonce: Option<Int>,
+    #[asn1(default = "Default::default")]
+    cert_req: bool,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+struct PkiStatusInfo {
+    status: u8,
+    status_string: Option<Vec<String>>,
+    fail_info: Option<BitString>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+struct TimeStampResp {
+    status: PkiStatusInfo,
+    time_stamp_token: Option<ContentInfo>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+struct Accuracy {
+    seconds: Option<Int>,
+    #[asn1(context_specific = "0", tag_mode = "IMPLICIT", optional = "true")]
+    millis: Option<Int>,
+    #[asn1(context_specific = "1", tag_mode = "IMPLICIT", optional = "true")]
+    micros: Option<Int>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+struct TstInfo {
+    version: u8,
+    policy: ObjectIdentifier,
+    message_imprint: MessageImprint,
+    serial_number: Int,
+    // Kept raw: der's GeneralizedTime rejects the fractional seconds many authorities send
+    gen_time: Any,
+    accuracy: Option<Accuracy>,
+    #[asn1(default = "Default::default")]
+    ordering: bool,
+    nonce: Option<Int>,
+    #[asn1(context_specific = "0", tag_mode = "EXPLICIT", optional = "true")]
+    tsa: Option<Any>,
+    #[asn1(context_specific = "1", tag_mode = "IMPLICIT", optional = "true")]
+    extensions: Option<Extensions>,
+}
+
+/// Timestamp found on a signature during verification
+#[derive(Debug, Clone)]
+pub struct SignatureTimestamp {
+    pub time: DateTime<Utc>,
+    pub serial_number: String,
+    /// DER of the certificate that signed the token, when the authority included it
+    pub tsa_certificate: Option<Vec<u8>>,
+    /// Token covers this signature and its own signature checks out
+    pub is_valid: bool,
+}
+
+/// Source of RFC 3161 timestamp tokens
+pub enum TimestampAuthority {
+    /// HTTP endpoint accepting `application/timestamp-query` requests
+    Remote { url: String },
+    /// In-process authority for tests (`tsa_url = 'local'`)
+    Local(LocalTsa),
+}
+
+impl TimestampAuthority {
+    /// Request a token over a SHA-256 digest, checking it answers this exact request
+    pub async fn request_token(&self, digest: &[u8]) -> Result<Vec<u8>> {
+        let nonce = random_int()?;
+        let request = TimeStampReq {
+            version: 1,
+            message_imprint: MessageImprint {
+                hash_algorithm: sha256_algorithm(),
+                hashed_message: OctetString::new(digest.to_vec())?,
+            },
+            req_policy: None,
+            nonce: Some(nonce.clone()),
+            cert_req: true,
+        };
+        let request_der = request.to_der()?;
+
+        let response_der = match self {
+            Self::Remote { url } => post_request(url, request_der).await?,
+            Self::Local(tsa) => tsa.respond(&request_der)?,
+        };
+
+        let response = TimeStampResp::from_der(&response_der).context("Malformed timestamp response")?;
+        // 0 = granted, 1 = granted with modifications
+        if response.status.status > 1 {
+            let reason = response.status.status_string.unwrap_or_default().join("; ");
+            return Err(anyhow!("Timestamp request rejected with status {}: {}", response.status.status, reason));
+        }
+
+        let token = response.time_stamp_token.ok_or_else(|| anyhow!("Timestamp response contains no token"))?;
+        let (_, _, tst_info) = decode_token(&token)?;
+        if tst_info.message_imprint != request.message_imprint {
+            return Err(anyhow!("Timestamp token does not cover the requested digest"));
+        }
+        if tst_info.nonce != Some(nonce) {
+            return Err(anyhow!("Timestamp token nonce does not match the request"));
+        }
+
+        Ok(token.to_der()?)
+    }
+}
+
+// The URL comes from account settings, so it gets the same checks as a webhook: a public
+// address, connected to as resolved, no redirects, and no details of the response echoed back
+async fn post_request(url: &str, body: Vec<u8>) -> Result<Vec<u8>> {
+    let (host, address) = crate::services::webhooks::resolve_public_url(url, "Timestamp authority").await
+        .map_err(|e| anyhow!(e))?;
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(REQUEST_TIMEOUT_SECS))
+        .redirect(reqwest::redirect::Policy::none())
+        .resolve(&host, address)
+        .build()?;
+
+    let response = client
+        .post(url)
+        .header("Content-Type", "application/timestamp-query")
+        .header("Accept", "application/timestamp-reply")
+        .body(body)
+        .send()
+        .await
+        .map_err(|_| anyhow!("Failed to reach timestamp authority {}", host))?;
+
+    if !response.status().is_success() {
+        return Err(anyhow!("Timestamp authority {} did not return a timestamp", host));
+    }
+
+    Ok(response.bytes().await?.to_vec())
+}
+
+/// Stand-in timestamp authority with a throwaway self-signed certificate
+pub struct LocalTsa {
+    certificate: X509,
+    key: PKey<Private>,
+}
+
+impl LocalTsa {
+    pub fn generate() -> Result<Self> {
+        let key = crate::services::digital_signature::generate_rsa_keypair()?;
+
+        let mut name_builder = X509NameBuilder::new()?;
+        name_builder.append_entry_by_nid(Nid::ORGANIZATIONNAME, "Letmesign LLC")?;
+        name_builder.append_entry_by_nid(Nid::COMMONNAME, "Letmesign Local TSA")?;
+        let name = name_builder.build();
+
+        let mut builder = X509Builder::new()?;
+        builder.set_version(2)?;
+        let serial = BigNum::from_u32(1)?.to_asn1_integer()?;
+        builder.set_serial_number(&serial)?;
+        builder.set_subject_name(&name)?;
+        builder.set_issuer_name(&name)?;
+        let not_before = Asn1Time::days_from_now(0)?;
+        let not_after = Asn1Time::days_from_now(365)?;
+        builder.set_not_before(&not_before)?;
+        builder.set_not_after(&not_after)?;
+        builder.set_pubkey(&key)?;
+        builder.append_extension(ExtendedKeyUsage::new().critical().time_stamping().build()?)?;
+        builder.sign(&key, MessageDigest::sha256())?;
+
+        Ok(Self { certificate: builder.build(), key })
+    }
+
+    /// Answer a DER `TimeStampReq` with a DER `TimeStampResp`
+    pub fn respond(&self, request_der: &[u8]) -> Result<Vec<u8>> {
+        let request = TimeStampReq::from_der(request_der).context("Malformed timestamp request")?;
+
+        let tst_info = TstInfo {
+            version: 1,
+            policy: request.req_policy.unwrap_or(LOCAL_TSA_POLICY),
+            message_imprint: request.message_imprint,
+            serial_number: random_int()?,
+            gen_time: Any::new(Tag::GeneralizedTime, Utc::now().format("%Y%m%d%H%M%SZ").to_string().into_bytes())?,
+            accuracy: None,
+            ordering: false,
+            nonce: request.nonce,
+            tsa: None,
+            extensions: None,
+        };
+
+        let response = TimeStampResp {
+            status: PkiStatusInfo { status: 0, status_string: None, fail_info: None },
+            time_stamp_token: Some(self.sign_tst_info(&tst_info.to_der()?, request.cert_req)?),
+        };
+
+        Ok(response.to_der()?)
+    }
+
+    fn sign_tst_info(&self, tst_der: &[u8], include_certificate: bool) -> Result<ContentInfo> {
+        let token = build_signed_data(ID_CT_TST_INFO, tst_der, true, &self.certificate, &self.key, &[])?;
+        if include_certificate {
+            return Ok(token);
+        }
+
+        // RFC 3161: no certificates unless the request asked for them
+        let mut signed_data: SignedData = token.content.decode_as()?;
+        signed_data.certificates = None;
+        Ok(ContentInfo { content_type: ID_SIGNED_DATA, content: Any::encode_from(&signed_data)? })
+    }
+}
+
+/// Add an RFC 3161 timestamp token to every signer of a DER PKCS#7 signature.
+///
+/// The token covers the SHA-256 of the signature value and is stored as the
+/// `id-aa-timeStampToken` unsigned attribute, so the signed content and the
+/// existing signature bytes stay untouched.
+pub async fn timestamp_pkcs7_signature(pkcs7_der: &[u8], tsa: &TimestampAuthority) -> Result<Vec<u8>> {
+    let content_info = ContentInfo::from_der(pkcs7_der).context("Malformed PKCS#7 signature")?;
+    if content_info.content_type != ID_SIGNED_DATA {
+        return Err(anyhow!("PKCS#7 signature is not SignedData"));
+    }
+
+    // Keep every field but signerInfos as raw bytes so certificates are not re-sorted
+    let mut fields: Vec<Any> = Vec::new();
+    let mut reader = SliceReader::new(content_info.content.value())?;
+    while !reader.is_finished() {
+        fields.push(Any::decode(&mut reader)?);
+    }
+    let signer_infos_field = fields.pop().ok_or_else(|| anyhow!("PKCS#7 signature has no signers"))?;
+    let mut signer_infos = signer_infos_field.decode_as::<SetOfVec<SignerInfo>>()?.into_vec();
+
+    for signer_info in signer_infos.iter_mut() {
+        let digest = hash(MessageDigest::sha256(), signer_info.signature.as_bytes())?;
+        let token_der = tsa.request_token(&digest).await?;
+
+        let mut unsigned_attrs = signer_info.unsigned_attrs.take().map(SetOfVec::into_vec).unwrap_or_default();
+        unsigned_attrs.retain(|attr| attr.oid != ID_AA_TIME_STAMP_TOKEN);
+        unsigned_attrs.push(attribute(ID_AA_TIME_STAMP_TOKEN, Any::from_der(&token_der)?)?);
+        signer_info.unsigned_attrs = Some(SetOfVec::try_from(unsigned_attrs)?);
+    }
+    fields.push(Any::encode_from(&SetOfVec::try_from(signer_infos)?)?);
+
+    let mut body = Vec::new();
+    for field in &fields {
+        field.encode_to_vec(&mut body)?;
+    }
+
+    let timestamped = ContentInfo {
+        content_type: content_info.content_type,
+        content: Any::new(Tag::Sequence, body)?,
+    };
+    Ok(timestamped.to_der()?)
+}
+
+/// Read and check the timestamp token of the first signer of a PKCS#7 signature.
+/// Trailing padding after the DER structure (as in PDF `/Contents`) is ignored.
+pub fn extract_signature_timestamp(pkcs7_bytes: &[u8]) -> Option<SignatureTimestamp> {
+    let mut reader = SliceReader::new(pkcs7_bytes).ok()?;
+    let content_info = ContentInfo::decode(&mut reader).ok()?;
+    let signed_data: SignedData = content_info.content.decode_as().ok()?;
+    let signer_info = signed_data.signer_infos.0.get(0)?;
+
+    let token_attr = signer_info.unsigned_attrs.as_ref()?
+        .iter()
+        .find(|attr| attr.oid == ID_AA_TIME_STAMP_TOKEN)?;
+    let token: ContentInfo = token_attr.values.get(0)?.decode_as().ok()?;
+    let (token_data, tst_der, tst_info) = decode_token(&token).ok()?;
+
+    let time = parse_generalized_time(tst_info.gen_time.value())?;
+    let imprint_valid = digest_for(&tst_info.message_imprint.hash_algorithm.oid)
+        .and_then(|md| hash(md, signer_info.signature.as_bytes()).ok())
+        .map(|digest| digest.as_ref() == tst_info.message_imprint.hashed_message.as_bytes())
+        .unwrap_or(false);
+
+    let tsa_certificate = find_signer_certificate(&token_data);
+    let signature_valid = tsa_certificate.as_ref()
+        .and_then(|cert| verify_token_signature(&token_data, &tst_der, cert))
+        .unwrap_or(false);
+
+    Some(SignatureTimestamp {
+        time,
+        serial_number: hex::encode(tst_info.serial_number.as_bytes()),
+        tsa_certificate: tsa_certificate.and_then(|cert| cert.to_der().ok()),
+        is_valid: imprint_valid && signature_valid,
+    })
+}
+
+/// Load the account's timestamp authority from `pdf_signature_settings`, if enabled
+pub async fn load_timestamp_authority(
+    pool: &PgPool,
+    user_id: i64,
+    account_id: Option<i64>,
+) -> Result<Option<TimestampAuthority>> {
+    let row = sqlx::query(
+        "SELECT tsa_enabled, tsa_url FROM pdf_signature_settings WHERE user_id = $1 OR account_id = $2 LIMIT 1"
+    )
+    .bind(user_id)
+    .bind(account_id)
+    .fetch_optional(pool)
+    .await?;
+
+    let url = row
+        .filter(|row| row.get::<bool, _>("tsa_enabled"))
+        .and_then(|row| row.get::<Option<String>, _>("tsa_url"))
+        .filter(|url| !url.trim().is_empty());
+
+    match url {
+        Some(url) if url == LOCAL_TSA_URL && cfg!(test) => Ok(Some(TimestampAuthority::Local(LocalTsa::generate()?))),
+        Some(url) if url == LOCAL_TSA_URL => Err(anyhow!("The local timestamp authority is only available in tests; configure a real one")),
+        Some(url) => Ok(Some(TimestampAuthority::Remote { url })),
+        None => Ok(None),
+    }
+}
+
+/// Split a token into its SignedData, the raw TSTInfo bytes and the decoded TSTInfo
+fn decode_token(token: &ContentInfo) -> Result<(SignedData, Vec<u8>, TstInfo)> {
+    if token.content_type != ID_SIGNED_DATA {
+        return Err(anyhow!("Timestamp token is not SignedData"));
+    }
+
+    let signed_data: SignedData = token.content.decode_as()?;
+    if signed_data.encap_content_info.econtent_type != ID_CT_TST_INFO {
+        return Err(anyhow!("Timestamp token does not contain TSTInfo"));
+    }
+
+    let tst_der = signed_data.encap_content_info.econtent.as_ref()
+        .ok_or_else(|| anyhow!("Timestamp token has no content"))?
+        .decode_as::<OctetString>()?
+        .into_bytes();
+    let tst_info = TstInfo::from_der(&tst_der).context("Malformed TSTInfo")?;
+
+    Ok((signed_data, tst_der, tst_info))
+}
+
+fn find_signer_certificate(signed_data: &SignedData) -> Option<Certificate> {
+    let signer_info = signed_data.signer_infos.0.get(0)?;
+    let certificates: Vec<&Certificate> = signed_data.certificates.as_ref()?.0
+        .iter()
+        .filter_map(|choice| match choice {
+            CertificateChoices::Certificate(cert) => Some(cert),
+            _ => None,
+        })
+        .collect();
+
+    match &signer_info.sid {
+        SignerIdentifier::IssuerAndSerialNumber(id) => certificates.into_iter()
+            .find(|cert| cert.tbs_certificate.issuer == id.issuer && cert.tbs_certificate.serial_number == id.serial_number)
+            .cloned(),
+        SignerIdentifier::SubjectKeyIdentifier(_) => certificates.first().map(|cert| (*cert).clone()),
+    }
+}
+
+/// Check the token's message digest attribute and its signature over the signed attributes
+fn verify_token_signature(signed_data: &SignedData, tst_der: &[u8], certificate: &Certificate) -> Option<bool> {
+    let signer_info = signed_data.signer_infos.0.get(0)?;
+    let md = digest_for(&signer_info.digest_alg.oid)?;
+    let signed_attrs = signer_info.signed_attrs.as_ref()?;
+
+    let message_digest: OctetString = signed_attrs.iter()
+        .find(|attr| attr.oid == ID_MESSAGE_DIGEST)?
+        .values.get(0)?
+        .decode_as().ok()?;
+    if hash(md, tst_der).ok()?.as_ref() != message_digest.as_bytes() {
+        return Some(false);
+    }
+
+    let public_key = X509::from_der(&certificate.to_der().ok()?).ok()?.public_key().ok()?;
+    let mut verifier = Verifier::new(md, &public_key).ok()?;
+    verifier.update(&signed_attrs.to_der().ok()?).ok()?;
+    verifier.verify(signer_info.signature.as_bytes()).ok()
+}
+
+/// Random positive 64-bit INTEGER for nonces and serial numbers
+fn random_int() -> Result<Int> {
+    let mut bytes = [0u8; 8];
+    openssl::rand::rand_bytes(&mut bytes)?;
+    // Non-zero leading byte below 0x80 keeps the encoding positive and minimal
+    bytes[0] = bytes[0] % 0x7f + 1;
+    Ok(Int::new(&bytes)?)
+}
+
+/// Parse `YYYYMMDDHHMMSS[.fff]Z`
+fn parse_generalized_time(value: &[u8]) -> Option<DateTime<Utc>> {
+    let text = std::str::from_utf8(value).ok()?.strip_suffix('Z')?;
+    NaiveDateTime::parse_from_str(text, "%Y%m%d%H%M%S%.f")
+        .ok()
+        .map(|dt| dt.and_utc())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::digital_signature::{generate_root_ca, generate_rsa_keypair, CAConfig};
+    use openssl::pkcs7::{Pkcs7, Pkcs7Flags};
+    use openssl::stack::Stack;
+    use cms::signed_data::SignerInfos;
+    use openssl::x509::store::X509StoreBuilder;
+
+    fn sign_detached(content: &[u8]) -> Vec<u8> {
+        let key = generate_rsa_keypair().unwrap();
+        let cert = generate_root_ca(&key, &CAConfig::default()).unwrap();
+        Pkcs7::sign(&cert, &key, &Stack::new().unwrap(), content, Pkcs7Flags::DETACHED | Pkcs7Flags::BINARY)
+            .unwrap()
+            .to_der()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_local_tsa_timestamp_round_trip() {
+        let content = b"%PDF-1.5 signed byte ranges";
+        let signature = sign_detached(content);
+        let tsa = LocalTsa::generate().unwrap();
+        let tsa_cert_der = tsa.certificate.to_der().unwrap();
+
+        let timestamped = timestamp_pkcs7_signature(&signature, &TimestampAuthority::Local(tsa)).await.unwrap();
+
+        // The original signature still verifies over the same content
+        let pkcs7 = Pkcs7::from_der(&timestamped).unwrap();
+        let store = X509StoreBuilder::new().unwrap().build();
+        pkcs7.verify(&Stack::new().unwrap(), &store, Some(content), None, Pkcs7Flags::NOVERIFY).unwrap();
+
+        // PDF /Contents placeholders are zero padded
+        let mut padded = timestamped.clone();
+        padded.extend_from_slice(&[0u8; 64]);
+        let timestamp = extract_signature_timestamp(&padded).unwrap();
+        assert!(timestamp.is_valid);
+        assert_eq!(timestamp.tsa_certificate, Some(tsa_cert_der));
+        assert!((Utc::now() - timestamp.time).num_seconds().abs() < 60);
+    }
+
+    #[tokio::test]
+    async fn test_timestamp_does_not_transfer_to_other_signature() {
+        let tsa = TimestampAuthority::Local(LocalTsa::generate().unwrap());
+        let timestamped = timestamp_pkcs7_signature(&sign_detached(b"first"), &tsa).await.unwrap();
+
+        // Graft the first signer's token onto a second signature
+        let first: SignedData = ContentInfo::from_der(&timestamped).unwrap().content.decode_as().unwrap();
+        let token_attrs = first.signer_infos.0.get(0).unwrap().unsigned_attrs.clone();
+        let second_info = ContentInfo::from_der(&sign_detached(b"second")).unwrap();
+        let mut second: SignedData = second_info.content.decode_as().unwrap();
+        let mut signers = second.signer_infos.0.into_vec();
+        signers[0].unsigned_attrs = token_attrs;
+        second.signer_infos = SignerInfos(SetOfVec::try_from(signers).unwrap());
+        let grafted = ContentInfo { content_type: ID_SIGNED_DATA, content: Any::encode_from(&second).unwrap() };
+
+        let timestamp = extract_signature_timestamp(&grafted.to_der().unwrap()).unwrap();
+        assert!(!timestamp.is_valid);
+    }
+
+    #[tokio::test]
+    async fn test_remote_authority_must_be_public() {
+        for url in ["http://127.0.0.1:8080/tsa", "http://169.254.169.254/latest", "file:///etc/passwd"] {
+            let tsa = TimestampAuthority::Remote { url: url.to_string() };
+            assert!(tsa.request_token(&[0u8; 32]).await.is_err(), "{}", url);
+        }
+        let error = TimestampAuthority::Remote { url: "http://10.0.0.1/tsa".to_string() }.request_token(&[0u8; 32]).await.unwrap_err();
+        assert!(error.to_string().contains("not a public address"));
+    }
+
+    #[test]
+    fn test_parse_generalized_time_with_fraction() {
+        let plain = parse_generalized_time(b"20250101120000Z").unwrap();
+        let fractional = parse_generalized_time(b"20250101120000.125Z").unwrap();
+        assert_eq!(plain.to_rfc3339(), "2025-01-01T12:00:00+00:00");
+        assert_eq!((fractional - plain).num_milliseconds(), 125);
+        assert!(parse_generalized_time(b"20250101120000").is_none());
+    }
+}
@@ -0,0 +1,71 @@
+use chrono::FixedOffset;
+
+/// UTC offset of an account timezone setting, given either as an IANA name or as the
+/// short names the settings page offers ("Berlin", "Eastern", ...). Offsets are fixed
+/// (no daylight saving), matching SignatureRenderer.tsx; unset or unknown timezones
+/// fall back to GMT+7.
+pub fn utc_offset(timezone: Option<&str>) -> FixedOffset {
+    let timezone_str = timezone.unwrap_or("Asia/Ho_Chi_Minh");
+
+    // Map common timezone names to IANA identifiers (matching SignatureRenderer)
+    let timezone_mapped = match timezone_str {
+        "Midway Island" => "Pacific/Midway",
+        "Hawaii" => "Pacific/Honolulu",
+        "Alaska" => "America/Anchorage",
+        "Pacific" => "America/Los_Angeles",
+        "Mountain" => "America/Denver",
+        "Central" => "America/Chicago",
+        "Eastern" => "America/New_York",
+        "Atlantic" => "America/Halifax",
+        "Newfoundland" => "America/St_Johns",
+        "London" => "Europe/London",
+        "Berlin" => "Europe/Berlin",
+        "Paris" => "Europe/Paris",
+        "Rome" => "Europe/Rome",
+        "Moscow" => "Europe/Moscow",
+        "Tokyo" => "Asia/Tokyo",
+        "Shanghai" => "Asia/Shanghai",
+        "Hong Kong" => "Asia/Hong_Kong",
+        "Singapore" => "Asia/Singapore",
+        "Sydney" => "Australia/Sydney",
+        "UTC" => "UTC",
+        _ => timezone_str,
+    };
+
+    // Parse timezone offset (simplified approach for common timezones)
+    let timezone_offset_hours = match timezone_mapped {
+        "Asia/Ho_Chi_Minh" => 7,
+        "Pacific/Midway" => -11,
+        "Pacific/Honolulu" => -10,
+        "America/Anchorage" => -9,
+        "America/Los_Angeles" => -8,
+        "America/Denver" => -7,
+        "America/Chicago" => -6,
+        "America/New_York" => -5,
+        "America/Halifax" => -4,
+        "Europe/London" => 0,
+        "Europe/Berlin" | "Europe/Paris" | "Europe/Rome" => 1,
+        "Europe/Moscow" => 3,
+        "Asia/Tokyo" => 9,
+        "Asia/Shanghai" | "Asia/Hong_Kong" | "Asia/Singapore" => 8,
+        "Australia/Sydney" => 10,
+        "UTC" => 0,
+        _ => 7, // Default to GMT+7
+    };
+
+
+    FixedOffset::east_opt(timezone_offset_hours * 3600).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_utc_offset() {
+        assert_eq!(utc_offset(Some("UTC")).local_minus_utc(), 0);
+        assert_eq!(utc_offset(Some("Berlin")).local_minus_utc(), 3600);
+        assert_eq!(utc_offset(Some("America/New_York")).local_minus_utc(), -5 * 3600);
+        assert_eq!(utc_offset(None).local_minus_utc(), 7 * 3600);
+    }
+}
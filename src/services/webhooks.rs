@@ -0,0 +1,364 @@
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tokio::time::{sleep, Duration};
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::database::connection::DbPool;
+use crate::database::models::{DbSubmission, DbSubmitter, DbWebhookDelivery, DbWebhookEndpoint};
+use crate::database::queries::{UserQueries, WebhookQueries};
+
+pub const EVENT_SUBMISSION_CREATED: &str = "submission.created";
+pub const EVENT_SUBMISSION_VIEWED: &str = "submission.viewed";
+pub const EVENT_SUBMISSION_SIGNED: &str = "submission.signed";
+pub const EVENT_SUBMISSION_DECLINED: &str = "submission.declined";
+pub const EVENT_SUBMISSION_COMPLETED: &str = "submission.completed";
+pub const EVENT_SUBMISSION_EXPIRED: &str = "submission.expired";
+pub const EVENT_SUBMISSION_VOIDED: &str = "submission.voided";
+
+pub const SUPPORTED_EVENTS: [&str; 7] = [
+    EVENT_SUBMISSION_CREATED,
+    EVENT_SUBMISSION_VIEWED,
+    EVENT_SUBMISSION_SIGNED,
+    EVENT_SUBMISSION_DECLINED,
+    EVENT_SUBMISSION_COMPLETED,
+    EVENT_SUBMISSION_EXPIRED,
+    EVENT_SUBMISSION_VOIDED,
+];
+
+/// Attempts before a delivery is marked failed
+pub const MAX_ATTEMPTS: i32 = 8;
+
+const BASE_RETRY_SECS: i64 = 30;
+const MAX_RETRY_SECS: i64 = 6 * 60 * 60;
+const REQUEST_TIMEOUT_SECS: u64 = 15;
+/// Outlives one request with its DNS lookup, so a delivery is never attempted twice at once
+const DELIVERY_LEASE_SECS: i64 = REQUEST_TIMEOUT_SECS as i64 * 4;
+const DELIVERIES_PER_PASS: usize = 50;
+const MAX_LOGGED_RESPONSE_BYTES: usize = 4096;
+
+/// Whether webhooks may be sent to an address. Only public unicast addresses are
+/// allowed, so an endpoint cannot reach this server, the private network or a
+/// cloud metadata service.
+pub fn is_public_address(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [a, b, ..] = ip.octets();
+            !(ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+                || ip.is_multicast()
+                || ip.is_documentation()
+                || a == 0
+                || (a == 100 && (64..128).contains(&b)) // shared address space (RFC 6598)
+                || a >= 240)
+        }
+        IpAddr::V6(ip) => {
+            if let Some(mapped) = ip.to_ipv4_mapped() {
+                return is_public_address(IpAddr::V4(mapped));
+            }
+            let first = ip.segments()[0];
+            !(ip.is_loopback()
+                || ip.is_unspecified()
+                || ip.is_multicast()
+                || (first & 0xfe00) == 0xfc00 // unique local
+                || (first & 0xffc0) == 0xfe80) // link-local
+        }
+    }
+}
+
+/// Check a webhook URL and resolve its host. Every address the host resolves to must
+/// be public; the returned one is what deliveries connect to, so a DNS answer that
+/// changes after the check cannot point the request elsewhere.
+pub async fn resolve_target(url: &str) -> Result<(String, SocketAddr), String> {
+    resolve_public_url(url, "Webhook").await
+}
+
+/// Same check for any URL the server calls on a user's behalf; `label` names it in errors
+pub async fn resolve_public_url(url: &str, label: &str) -> Result<(String, SocketAddr), String> {
+    let parsed = reqwest::Url::parse(url).map_err(|e| format!("{} URL is invalid: {}", label, e))?;
+    if parsed.scheme() != "https" && parsed.scheme() != "http" {
+        return Err(format!("{} URL must start with http:// or https://", label));
+    }
+    let host = parsed.host_str().ok_or_else(|| format!("{} URL must have a host", label))?.to_string();
+    let port = parsed.port_or_known_default().unwrap_or(443);
+
+    let lookup_host = host.trim_start_matches('[').trim_end_matches(']');
+    // Bounded, so the lookup fits inside a delivery's lease
+    let lookup = tokio::time::timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS), tokio::net::lookup_host((lookup_host, port))).await
+        .map_err(|_| format!("Timed out resolving {} host '{}'", label.to_lowercase(), host))?;
+    let addresses: Vec<SocketAddr> = lookup
+        .map_err(|e| format!("Could not resolve {} host '{}': {}", label.to_lowercase(), host, e))?
+        .collect();
+    if addresses.is_empty() {
+        return Err(format!("Could not resolve {} host '{}'", label.to_lowercase(), host));
+    }
+    if let Some(address) = addresses.iter().find(|a| !is_public_address(a.ip())) {
+        return Err(format!("{} host '{}' resolves to {}, which is not a public address", label, host, address.ip()));
+    }
+    Ok((host, addresses[0]))
+}
+
+/// Signature header value in the same `t=...,v1=...` format Stripe uses:
+/// hex HMAC-SHA256 of `"{timestamp}.{body}"` keyed with the endpoint secret
+pub fn sign_payload(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(format!("{}.", timestamp).as_bytes());
+    mac.update(body);
+    format!("t={},v1={}", timestamp, hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Delay before retrying after the given (1-based) failed attempt: 30s, 1m, 2m, ... capped at 6h
+pub fn retry_delay_secs(attempt: i32) -> i64 {
+    let exponent = (attempt.max(1) - 1).min(20) as u32;
+    (BASE_RETRY_SECS * 2_i64.pow(exponent)).min(MAX_RETRY_SECS)
+}
+
+/// Generate a new endpoint signing secret
+pub fn generate_secret() -> String {
+    format!("whsec_{}", crate::common::utils::generate_api_key())
+}
+
+fn submitter_data(submitter: &DbSubmitter) -> serde_json::Value {
+    serde_json::json!({
+        "id": submitter.id,
+        "name": submitter.name,
+        "email": submitter.email,
+        "status": submitter.status,
+        "signing_order": submitter.signing_order,
+        "signed_at": submitter.signed_at,
+        "decline_reason": submitter.decline_reason
+    })
+}
+
+/// Event data for submitter-level events (viewed, signed, declined)
+pub fn submitter_event_data(submitter: &DbSubmitter) -> serde_json::Value {
+    serde_json::json!({
+        "submission_id": submitter.submission_id,
+        "template_id": submitter.template_id,
+        "submitter": submitter_data(submitter)
+    })
+}
+
+/// Event data for envelope-level events (created, completed, expired, voided)
+pub fn submission_event_data(submission: &DbSubmission, submitters: &[DbSubmitter]) -> serde_json::Value {
+    serde_json::json!({
+        "submission_id": submission.id,
+        "template_id": submission.template_id,
+        "name": submission.name,
+        "status": submission.status,
+        "signing_mode": submission.signing_mode,
+        "expires_at": submission.expires_at,
+        "completed_at": submission.completed_at,
+        "void_reason": submission.void_reason,
+        "submitters": submitters.iter().map(submitter_data).collect::<Vec<_>>()
+    })
+}
+
+/// Queue an event for every endpoint of the owner's account subscribed to it.
+/// Errors are logged; firing webhooks never fails the request that triggered them.
+pub async fn dispatch_event(pool: &DbPool, owner_user_id: i64, event_type: &str, data: serde_json::Value) {
+    let account_id = match UserQueries::get_user_by_id(pool, owner_user_id).await {
+        Ok(Some(user)) => user.account_id,
+        Ok(None) => return,
+        Err(e) => {
+            eprintln!("❌ Failed to load webhook owner {}: {}", owner_user_id, e);
+            return;
+        }
+    };
+
+    let endpoints = match WebhookQueries::get_subscribed_endpoints(pool, owner_user_id, account_id, event_type).await {
+        Ok(endpoints) => endpoints,
+        Err(e) => {
+            eprintln!("❌ Failed to load webhook endpoints for '{}': {}", event_type, e);
+            return;
+        }
+    };
+
+    for endpoint in endpoints {
+        let payload = serde_json::json!({
+            "event_type": event_type,
+            "timestamp": Utc::now().to_rfc3339(),
+            "data": data
+        });
+        if let Err(e) = WebhookQueries::create_delivery(pool, endpoint.id, event_type, &payload).await {
+            eprintln!("❌ Failed to queue '{}' webhook for endpoint {}: {}", event_type, endpoint.id, e);
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct WebhookQueue {
+    db_pool: Arc<Mutex<DbPool>>,
+}
+
+impl WebhookQueue {
+    pub fn new(db_pool: Arc<Mutex<DbPool>>) -> Self {
+        Self { db_pool }
+    }
+
+    // Client that connects to `host` at the address it was checked to resolve to.
+    // Redirects are not followed; they could lead to an address that was never checked.
+    fn client_for(host: &str, address: SocketAddr) -> reqwest::Result<reqwest::Client> {
+        reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(REQUEST_TIMEOUT_SECS))
+            .redirect(reqwest::redirect::Policy::none())
+            .resolve(host, address)
+            .build()
+    }
+
+    /// Background task that delivers due webhooks
+    pub async fn start_processing(&self) {
+        println!("🪝 Starting webhook delivery queue...");
+
+        loop {
+            if let Err(e) = self.process_due_deliveries().await {
+                eprintln!("❌ Error processing webhook deliveries: {}", e);
+            }
+
+            sleep(Duration::from_secs(5)).await;
+        }
+    }
+
+    /// Deliver every webhook whose next attempt is due
+    pub async fn process_due_deliveries(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let pool = self.db_pool.lock().await.clone();
+
+        // Claimed one at a time, so each lease only has to outlive a single request
+        for _ in 0..DELIVERIES_PER_PASS {
+            let Some(delivery) = WebhookQueries::claim_due_deliveries(&pool, 1, DELIVERY_LEASE_SECS).await?.pop() else {
+                break;
+            };
+            match WebhookQueries::get_endpoint_by_id(&pool, delivery.endpoint_id).await? {
+                Some(endpoint) => self.deliver(&pool, &endpoint, &delivery).await?,
+                None => continue, // Endpoint deleted; its deliveries are removed with it
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn deliver(&self, pool: &DbPool, endpoint: &DbWebhookEndpoint, delivery: &DbWebhookDelivery) -> Result<(), sqlx::Error> {
+        let attempt = delivery.attempts + 1;
+        let body = delivery.payload.to_string();
+        let signature = sign_payload(&endpoint.secret, Utc::now().timestamp(), body.as_bytes());
+
+        let started = std::time::Instant::now();
+        // Checked on every attempt: the host may have been pointed somewhere private since it was saved
+        let result = match resolve_target(&endpoint.url).await {
+            Ok((host, address)) => match Self::client_for(&host, address) {
+                Ok(client) => client
+                    .post(&endpoint.url)
+                    .header("Content-Type", "application/json")
+                    .header("User-Agent", "Letmesign-Webhooks/1.0")
+                    .header("X-Letmesign-Event", &delivery.event_type)
+                    .header("X-Letmesign-Delivery", delivery.id.to_string())
+                    .header("X-Letmesign-Signature", signature)
+                    .body(body)
+                    .send()
+                    .await
+                    .map_err(|e| e.to_string()),
+                Err(e) => Err(e.to_string()),
+            },
+            Err(e) => Err(e),
+        };
+        let duration_ms = started.elapsed().as_millis() as i64;
+
+        let (response_status, response_body, error) = match result {
+            Ok(response) => {
+                let status = response.status().as_u16() as i32;
+                let mut text = response.text().await.unwrap_or_default();
+                if text.len() > MAX_LOGGED_RESPONSE_BYTES {
+                    let mut cut = MAX_LOGGED_RESPONSE_BYTES;
+                    while !text.is_char_boundary(cut) {
+                        cut -= 1;
+                    }
+                    text.truncate(cut);
+                }
+                (Some(status), Some(text), None)
+            }
+            Err(e) => (None, None, Some(e)),
+        };
+
+        let succeeded = matches!(response_status, Some(status) if (200..300).contains(&status));
+        let (status, next_attempt_at) = if succeeded {
+            println!("✅ Webhook delivery {} ({}) succeeded on attempt {}", delivery.id, delivery.event_type, attempt);
+            ("succeeded", None)
+        } else if attempt >= MAX_ATTEMPTS {
+            eprintln!("❌ Webhook delivery {} failed permanently after {} attempts", delivery.id, attempt);
+            ("failed", None)
+        } else {
+            let delay = retry_delay_secs(attempt);
+            eprintln!("⚠️  Webhook delivery {} attempt {} failed, retrying in {}s", delivery.id, attempt, delay);
+            ("pending", Some(Utc::now() + chrono::Duration::seconds(delay)))
+        };
+
+        let recorded = WebhookQueries::record_attempt(
+            pool,
+            delivery.id,
+            delivery.next_attempt_at,
+            attempt,
+            response_status,
+            response_body.as_deref(),
+            error.as_deref(),
+            duration_ms,
+            status,
+            next_attempt_at,
+        ).await?;
+        if !recorded {
+            eprintln!("⚠️  Webhook delivery {} outlived its lease; the result of attempt {} was dropped", delivery.id, attempt);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_payload_matches_stripe_scheme() {
+        let body = br#"{"event_type":"submission.completed"}"#;
+        let header = sign_payload("whsec_test", 1700000000, body);
+
+        let mut mac = Hmac::<Sha256>::new_from_slice(b"whsec_test").unwrap();
+        mac.update(b"1700000000.");
+        mac.update(body);
+        let expected = hex::encode(mac.finalize().into_bytes());
+
+        assert_eq!(header, format!("t=1700000000,v1={}", expected));
+    }
+
+    #[test]
+    fn test_retry_delay_backs_off_exponentially() {
+        assert_eq!(retry_delay_secs(1), 30);
+        assert_eq!(retry_delay_secs(2), 60);
+        assert_eq!(retry_delay_secs(5), 480);
+        assert_eq!(retry_delay_secs(30), MAX_RETRY_SECS);
+    }
+
+    #[test]
+    fn test_is_public_address() {
+        for blocked in ["127.0.0.1", "10.1.2.3", "172.16.0.1", "192.168.1.1", "169.254.169.254", "0.0.0.0", "100.64.0.1", "::1", "fd00::1", "fe80::1", "::ffff:127.0.0.1"] {
+            assert!(!is_public_address(blocked.parse().unwrap()), "{}", blocked);
+        }
+        for allowed in ["93.184.216.34", "8.8.8.8", "2606:4700:4700::1111"] {
+            assert!(is_public_address(allowed.parse().unwrap()), "{}", allowed);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_resolve_target_rejects_private_hosts() {
+        assert!(resolve_target("ftp://example.com/hook").await.is_err());
+        assert!(resolve_target("http://127.0.0.1:8080/hook").await.unwrap_err().contains("not a public address"));
+        assert!(resolve_target("http://169.254.169.254/latest/meta-data").await.is_err());
+        assert!(resolve_target("https://[::1]/hook").await.is_err());
+        let (host, address) = resolve_target("https://93.184.216.34/hook").await.unwrap();
+        assert_eq!(host, "93.184.216.34");
+        assert_eq!(address.port(), 443);
+    }
+}